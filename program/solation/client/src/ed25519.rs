@@ -0,0 +1,50 @@
+//! Ed25519Program instruction assembly for quote verification.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use solation::utils::ED25519_PROGRAM_ID;
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+const PUBKEY_SERIALIZED_SIZE: usize = 32;
+const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+
+/// Instruction index meaning "this instruction"; the program rejects offsets
+/// that point anywhere else
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Ed25519Program instruction verifying one signature over `message`, laid out
+/// as `verify_ed25519_signature` expects: header, offsets, key, signature, message
+pub fn ed25519_verify_instruction(
+    signing_key: &Pubkey,
+    signature: &[u8; 64],
+    message: &[u8],
+) -> Instruction {
+    let public_key_offset = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+    let signature_offset = public_key_offset + PUBKEY_SERIALIZED_SIZE;
+    let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+
+    let mut data = Vec::with_capacity(message_data_offset + message.len());
+    data.push(1); // num_signatures
+    data.push(0); // padding
+    for field in [
+        signature_offset as u16,
+        CURRENT_INSTRUCTION,
+        public_key_offset as u16,
+        CURRENT_INSTRUCTION,
+        message_data_offset as u16,
+        message.len() as u16,
+        CURRENT_INSTRUCTION,
+    ] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(signing_key.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ED25519_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
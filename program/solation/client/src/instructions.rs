@@ -0,0 +1,788 @@
+//! Typed builders for the instructions MMs, users and keepers send most.
+//! Accounts and arguments go through the program's Anchor types, so a layout
+//! change in the program is a compile error here rather than silent drift.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use solation::instructions::{
+    PostQuoteParams, StandingQuoteParams, SubmitIntentParams, TakePostedQuoteParams, TakeQuoteParams,
+};
+use solation::state::{Intent, Position, PostedQuote, StandingQuote, StrategyType};
+use solation::constants::MAX_BASKET_COMPONENTS;
+use solation::utils::{CHAINLINK_STORE_PROGRAM_ID, JUPITER_PROGRAM_ID};
+
+use crate::ed25519::ed25519_verify_instruction;
+use crate::mm::SignedQuote;
+use crate::pda;
+use crate::quote::Quote;
+use crate::resale::SignedAsk;
+
+/// How the user satisfies the asset's access gate
+#[derive(Clone, Copy, Debug, Default)]
+pub enum AssetAccess {
+    #[default]
+    Open,
+    /// KYC pass token account held by the user
+    PassToken(Pubkey),
+    /// UserCredential PDA from the asset's configured issuer
+    Credential(Pubkey),
+}
+
+/// Oracle feeds to pass wherever a price is read; supply the ones the asset is configured for
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OracleFeeds {
+    pub price_update: Option<Pubkey>,
+    pub switchboard_feed: Option<Pubkey>,
+    pub chainlink_feed: Option<Pubkey>,
+    /// Stake pool or rate feed, for positions collateralized in the asset's LST
+    pub lst_rate: Option<Pubkey>,
+    /// Pyth update for the quote currency, for assets that check its peg
+    pub quote_price_update: Option<Pubkey>,
+    /// Stake pool or rate feed, for assets priced at fair value
+    pub fair_value_rate: Option<Pubkey>,
+    /// Pyth updates for an index asset's basket, one per component in basket
+    /// order; sent as remaining accounts where the index level is read
+    pub basket_updates: [Option<Pubkey>; MAX_BASKET_COMPONENTS],
+}
+
+impl OracleFeeds {
+    fn accounts(&self) -> solation::accounts::OracleAccounts {
+        solation::accounts::OracleAccounts {
+            price_update: self.price_update,
+            switchboard_feed: self.switchboard_feed,
+            chainlink_feed: self.chainlink_feed,
+            chainlink_program: self.chainlink_feed.map(|_| CHAINLINK_STORE_PROGRAM_ID),
+            lst_rate: self.lst_rate,
+            fair_value_rate: self.fair_value_rate,
+        }
+    }
+
+    fn basket_metas(&self) -> impl Iterator<Item = AccountMeta> + '_ {
+        self.basket_updates
+            .iter()
+            .flatten()
+            .map(|update| AccountMeta::new_readonly(*update, false))
+    }
+}
+
+/// Jupiter route for a user's payout swap, as returned by the swap API
+#[derive(Clone, Debug)]
+pub struct PayoutSwapRoute {
+    /// Route instruction data
+    pub data: Vec<u8>,
+    /// Route accounts, in order; the position PDA is the transfer authority
+    pub accounts: Vec<AccountMeta>,
+    /// User's account for the output mint (or their beneficiary's)
+    pub swap_destination: Pubkey,
+}
+
+/// A user's submission of an MM-signed quote
+#[derive(Clone, Copy, Debug)]
+pub struct SubmitIntentRequest {
+    pub user: Pubkey,
+    /// MM registry owner the quote came from
+    pub market_maker: Pubkey,
+    /// MM's registered Ed25519 signing key
+    pub mm_signing_key: Pubkey,
+    pub mm_signature: [u8; 64],
+    pub intent_id: u64,
+    pub quote: Quote,
+    /// Mint escrowed: the quote mint for puts, the underlying or its LST for calls
+    pub collateral_mint: Pubkey,
+    /// Spot feeds the strike is bounded against, plus the LST rate account
+    /// when escrowing the asset's LST
+    pub oracle: OracleFeeds,
+    /// Source of the escrow; None escrows native SOL for wrapped-SOL collateral
+    pub user_token_account: Option<Pubkey>,
+    pub token_program: Pubkey,
+    pub access: AssetAccess,
+    /// Reject the quote if the MM's reputation score is below this
+    pub min_mm_reputation: Option<u32>,
+    /// Reject the quote if the MM's fill rate (0-100) is below this
+    pub min_mm_fill_rate: Option<u8>,
+    /// Size the intent in quote-currency notional instead of taking the
+    /// quote's full contract size; sent as submit_notional_intent
+    pub notional: Option<u64>,
+}
+
+/// Ed25519 verification followed by submit_intent (or submit_notional_intent
+/// when the request is sized in notional), in transaction order
+pub fn submit_intent(request: &SubmitIntentRequest) -> [Instruction; 2] {
+    const ED25519_INSTRUCTION_INDEX: u8 = 0;
+
+    let quote = &request.quote;
+    let verify_ix = ed25519_verify_instruction(
+        &request.mm_signing_key,
+        &request.mm_signature,
+        &quote.message(),
+    );
+
+    let intent = pda::intent(&request.user, request.intent_id).0;
+    let (access_pass, user_credential) = match request.access {
+        AssetAccess::Open => (None, None),
+        AssetAccess::PassToken(pass) => (Some(pass), None),
+        AssetAccess::Credential(credential) => (None, Some(credential)),
+    };
+    let accounts = solation::accounts::SubmitIntent {
+        user: request.user,
+        global_state: pda::global_state().0,
+        mm_registry: pda::mm_registry(&request.market_maker).0,
+        blocklist: pda::blocklist().0,
+        nonce_tracker: pda::nonce_tracker(&request.market_maker).0,
+        asset_config: pda::asset_config(&quote.asset_mint).0,
+        intent,
+        user_escrow: pda::user_escrow(&intent).0,
+        user_stats: pda::user_stats(&request.user).0,
+        user_token_account: request.user_token_account,
+        escrow_mint: request.collateral_mint,
+        oracle: request.oracle.accounts(),
+        access_pass,
+        user_credential,
+        composer_allowlist: None,
+        instructions_sysvar: sysvar::instructions::ID,
+        token_program: request.token_program,
+        system_program: system_program::ID,
+    };
+    let params = SubmitIntentParams {
+        min_mm_reputation: request.min_mm_reputation,
+        min_mm_fill_rate: request.min_mm_fill_rate,
+        ..quote.submit_params(
+            request.intent_id,
+            request.mm_signature,
+            ED25519_INSTRUCTION_INDEX,
+        )
+    };
+    let data = match request.notional {
+        Some(notional) => solation::instruction::SubmitNotionalIntent { params, notional }.data(),
+        None => solation::instruction::SubmitIntent { params }.data(),
+    };
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(request.oracle.basket_metas());
+
+    [
+        verify_ix,
+        Instruction {
+            program_id: solation::ID,
+            accounts: metas,
+            data,
+        },
+    ]
+}
+
+/// Ed25519 verification followed by post_quote, in transaction order.
+/// `poster` pays the rent and gets it back when the quote is closed.
+pub fn post_quote(
+    poster: &Pubkey,
+    market_maker: &Pubkey,
+    signed: &SignedQuote,
+) -> [Instruction; 2] {
+    const ED25519_INSTRUCTION_INDEX: u8 = 0;
+
+    let quote = &signed.quote;
+    let accounts = solation::accounts::PostQuote {
+        poster: *poster,
+        global_state: pda::global_state().0,
+        mm_registry: pda::mm_registry(market_maker).0,
+        nonce_tracker: pda::nonce_tracker(market_maker).0,
+        asset_config: pda::asset_config(&quote.asset_mint).0,
+        posted_quote: pda::posted_quote(market_maker, quote.quote_nonce).0,
+        instructions_sysvar: sysvar::instructions::ID,
+        system_program: system_program::ID,
+    };
+    let args = solation::instruction::PostQuote {
+        params: PostQuoteParams {
+            asset_mint: quote.asset_mint,
+            quote_mint: quote.quote_mint,
+            strategy: quote.strategy,
+            strike_price: quote.strike_price,
+            premium_per_contract: quote.premium_per_contract,
+            contract_size: quote.contract_size,
+            quote_expiry: quote.quote_expiry,
+            quote_nonce: quote.quote_nonce,
+            implied_volatility_bps: quote.implied_volatility_bps,
+            mm_signature: signed.signature,
+            ed25519_instruction_index: ED25519_INSTRUCTION_INDEX,
+        },
+    };
+
+    [
+        signed.verify_instruction(),
+        Instruction {
+            program_id: solation::ID,
+            accounts: accounts.to_account_metas(None),
+            data: args.data(),
+        },
+    ]
+}
+
+/// A user's take of part of a posted quote
+#[derive(Clone, Copy, Debug)]
+pub struct TakePostedQuoteRequest {
+    pub user: Pubkey,
+    pub intent_id: u64,
+    pub contract_size: u64,
+    /// Mint escrowed: the quote mint for puts, the underlying or its LST for calls
+    pub collateral_mint: Pubkey,
+    /// Spot feeds the strike is bounded against, plus the LST rate account
+    /// when escrowing the asset's LST
+    pub oracle: OracleFeeds,
+    /// Source of the escrow; None escrows native SOL for wrapped-SOL collateral
+    pub user_token_account: Option<Pubkey>,
+    pub token_program: Pubkey,
+    pub access: AssetAccess,
+    /// Reject the quote if the MM's reputation score is below this
+    pub min_mm_reputation: Option<u32>,
+    /// Reject the quote if the MM's fill rate (0-100) is below this
+    pub min_mm_fill_rate: Option<u8>,
+}
+
+/// User submits an intent against `quote`, the fetched PostedQuote account;
+/// no Ed25519 instruction is needed
+pub fn submit_intent_from_quote(
+    request: &TakePostedQuoteRequest,
+    quote: &PostedQuote,
+) -> Instruction {
+    let intent = pda::intent(&request.user, request.intent_id).0;
+    let (access_pass, user_credential) = match request.access {
+        AssetAccess::Open => (None, None),
+        AssetAccess::PassToken(pass) => (Some(pass), None),
+        AssetAccess::Credential(credential) => (None, Some(credential)),
+    };
+    let accounts = solation::accounts::SubmitIntentFromQuote {
+        user: request.user,
+        global_state: pda::global_state().0,
+        posted_quote: pda::posted_quote(&quote.market_maker, quote.quote_nonce).0,
+        mm_registry: pda::mm_registry(&quote.market_maker).0,
+        blocklist: pda::blocklist().0,
+        asset_config: pda::asset_config(&quote.asset_mint).0,
+        intent,
+        user_escrow: pda::user_escrow(&intent).0,
+        user_stats: pda::user_stats(&request.user).0,
+        user_token_account: request.user_token_account,
+        escrow_mint: request.collateral_mint,
+        oracle: request.oracle.accounts(),
+        access_pass,
+        user_credential,
+        composer_allowlist: None,
+        instructions_sysvar: sysvar::instructions::ID,
+        token_program: request.token_program,
+        system_program: system_program::ID,
+    };
+    let args = solation::instruction::SubmitIntentFromQuote {
+        params: TakePostedQuoteParams {
+            intent_id: request.intent_id,
+            contract_size: request.contract_size,
+            min_mm_reputation: request.min_mm_reputation,
+            min_mm_fill_rate: request.min_mm_fill_rate,
+        },
+    };
+
+    Instruction {
+        program_id: solation::ID,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+/// MM (owner or signing key) withdraws a posted quote, or anyone closes one
+/// that is spent or expired; rent returns to the poster
+pub fn close_posted_quote(caller: &Pubkey, quote: &PostedQuote) -> Instruction {
+    let accounts = solation::accounts::ClosePostedQuote {
+        caller: *caller,
+        mm_registry: pda::mm_registry(&quote.market_maker).0,
+        posted_quote: pda::posted_quote(&quote.market_maker, quote.quote_nonce).0,
+        poster: quote.poster,
+    };
+
+    Instruction {
+        program_id: solation::ID,
+        accounts: accounts.to_account_metas(None),
+        data: solation::instruction::ClosePostedQuote {}.data(),
+    }
+}
+
+/// MM creates or replaces its standing quote `params.quote_id`
+pub fn set_standing_quote(market_maker: &Pubkey, params: StandingQuoteParams) -> Instruction {
+    let accounts = solation::accounts::SetStandingQuote {
+        owner: *market_maker,
+        mm_registry: pda::mm_registry(market_maker).0,
+        asset_config: pda::asset_config(&params.asset_mint).0,
+        standing_quote: pda::standing_quote(market_maker, params.quote_id).0,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: solation::ID,
+        accounts: accounts.to_account_metas(None),
+        data: solation::instruction::SetStandingQuote { params }.data(),
+    }
+}
+
+/// A user's take of one strike on a standing quote
+#[derive(Clone, Copy, Debug)]
+pub struct TakeQuoteRequest {
+    pub user: Pubkey,
+    pub params: TakeQuoteParams,
+    /// Mint escrowed: the quote mint for puts, the underlying or its LST for calls
+    pub collateral_mint: Pubkey,
+    /// Spot feeds the strike is bounded against, plus the LST rate account
+    /// when escrowing the asset's LST
+    pub oracle: OracleFeeds,
+    /// Source of the collateral; None escrows native SOL for wrapped-SOL collateral
+    pub user_token_account: Option<Pubkey>,
+    /// User's quote-mint account receiving the premium
+    pub user_premium_account: Pubkey,
+    pub token_program: Pubkey,
+    pub access: AssetAccess,
+    /// Series the position joins (`pda::option_series`), if any
+    pub option_series: Option<Pubkey>,
+}
+
+/// User takes `quote`, the fetched StandingQuote account; the premium comes
+/// out of the MM's vault (`pda::mm_vault`) and the position opens at once
+pub fn take_quote(request: &TakeQuoteRequest, quote: &StandingQuote) -> Instruction {
+    let mm_registry = pda::mm_registry(&quote.market_maker).0;
+    let position = pda::position(&request.user, request.params.position_id).0;
+    let (access_pass, user_credential) = match request.access {
+        AssetAccess::Open => (None, None),
+        AssetAccess::PassToken(pass) => (Some(pass), None),
+        AssetAccess::Credential(credential) => (None, Some(credential)),
+    };
+    let accounts = solation::accounts::TakeQuote {
+        user: request.user,
+        global_state: pda::global_state().0,
+        standing_quote: pda::standing_quote(&quote.market_maker, quote.quote_id).0,
+        mm_registry,
+        blocklist: pda::blocklist().0,
+        asset_config: pda::asset_config(&quote.asset_mint).0,
+        position,
+        position_user_vault: pda::position_user_vault(&position).0,
+        user_stats: pda::user_stats(&request.user).0,
+        user_token_account: request.user_token_account,
+        escrow_mint: request.collateral_mint,
+        mm_vault: pda::mm_vault(&quote.market_maker, &quote.quote_mint).0,
+        user_premium_account: request.user_premium_account,
+        quote_mint: quote.quote_mint,
+        oracle: request.oracle.accounts(),
+        access_pass,
+        user_credential,
+        composer_allowlist: None,
+        instructions_sysvar: sysvar::instructions::ID,
+        option_series: request.option_series,
+        token_program: request.token_program,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: solation::ID,
+        accounts: accounts.to_account_metas(None),
+        data: solation::instruction::TakeQuote { params: request.params }.data(),
+    }
+}
+
+/// MM (or its fill operator) fills `intent`, paying the premium into the
+/// user's ATA (created if missing). `mm_token_account` is the signer's own
+/// account or the MM vault (`pda::mm_vault`). `oracle` is only read when the
+/// asset sets a fill-time price band; pass the default otherwise.
+/// `option_series` enrolls the position in that series (`pda::option_series`);
+/// `installments` takes the premium installment plan the user offered.
+/// `underlying_premium` pays the premium in that much of the underlying
+/// instead, into the user's ATA; `mm_token_account` must then hold the
+/// underlying and `oracle` is always read.
+#[allow(clippy::too_many_arguments)]
+pub fn fill_intent(
+    market_maker: &Pubkey,
+    intent: &Intent,
+    mm_token_account: &Pubkey,
+    oracle: &OracleFeeds,
+    option_series: Option<Pubkey>,
+    installments: bool,
+    underlying_premium: Option<u64>,
+    token_program: &Pubkey,
+) -> Instruction {
+    let intent_key = pda::intent(&intent.user, intent.intent_id).0;
+    let position = pda::position(&intent.user, intent.intent_id).0;
+    let accounts = solation::accounts::FillIntent {
+        market_maker: *market_maker,
+        global_state: pda::global_state().0,
+        intent: intent_key,
+        asset_config: pda::asset_config(&intent.asset_mint).0,
+        mm_registry: pda::mm_registry(&intent.market_maker).0,
+        blocklist: pda::blocklist().0,
+        user_stats: pda::user_stats(&intent.user).0,
+        user_escrow: pda::user_escrow(&intent_key).0,
+        user_wallet: intent.user,
+        user_token_account: get_associated_token_address_with_program_id(
+            &intent.user,
+            &intent.quote_mint,
+            token_program,
+        ),
+        mm_token_account: *mm_token_account,
+        position,
+        quote_mint: intent.quote_mint,
+        underlying_mint: underlying_premium.map(|_| intent.asset_mint),
+        user_underlying_account: underlying_premium.map(|_| {
+            get_associated_token_address_with_program_id(
+                &intent.user,
+                &intent.asset_mint,
+                token_program,
+            )
+        }),
+        oracle: oracle.accounts(),
+        composer_allowlist: None,
+        instructions_sysvar: sysvar::instructions::ID,
+        option_series,
+        premium_schedule: installments.then(|| pda::premium_schedule(&position).0),
+        hedge_adapter_allowlist: None,
+        hedge_adapter: None,
+        token_program: *token_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: solation::ID,
+        accounts: accounts.to_account_metas(None),
+        data: match underlying_premium {
+            Some(underlying_premium) => {
+                solation::instruction::FillIntentInUnderlying { underlying_premium }.data()
+            }
+            None => solation::instruction::FillIntent {}.data(),
+        },
+    }
+}
+
+/// MM pays the next premium installment on `position` from its quote-mint
+/// ATA into the holder's
+pub fn pay_installment(
+    payer: &Pubkey,
+    position: &Position,
+    token_program: &Pubkey,
+) -> Instruction {
+    let position_key = pda::position(&position.user, position.position_id).0;
+    let accounts = solation::accounts::PayInstallment {
+        payer: *payer,
+        position: position_key,
+        premium_schedule: pda::premium_schedule(&position_key).0,
+        payer_token_account: get_associated_token_address_with_program_id(
+            payer,
+            &position.quote_mint,
+            token_program,
+        ),
+        holder_token_account: get_associated_token_address_with_program_id(
+            &position.holder,
+            &position.quote_mint,
+            token_program,
+        ),
+        quote_mint: position.quote_mint,
+        token_program: *token_program,
+    };
+
+    Instruction {
+        program_id: solation::ID,
+        accounts: accounts.to_account_metas(None),
+        data: solation::instruction::PayInstallment {}.data(),
+    }
+}
+
+/// User cancels their unfilled intent; None returns wrapped SOL as native SOL.
+/// `escrow_mint` is the mint the intent escrowed (the underlying or its LST
+/// for covered calls).
+pub fn cancel_intent(
+    intent: &Intent,
+    escrow_mint: &Pubkey,
+    user_token_account: Option<Pubkey>,
+    token_program: &Pubkey,
+) -> Instruction {
+    let intent_key = pda::intent(&intent.user, intent.intent_id).0;
+    let accounts = solation::accounts::CancelIntent {
+        user: intent.user,
+        global_state: pda::global_state().0,
+        intent: intent_key,
+        user_stats: pda::user_stats(&intent.user).0,
+        user_escrow: pda::user_escrow(&intent_key).0,
+        user_token_account,
+        escrow_mint: *escrow_mint,
+        token_program: *token_program,
+    };
+
+    Instruction {
+        program_id: solation::ID,
+        accounts: accounts.to_account_metas(None),
+        data: solation::instruction::CancelIntent {}.data(),
+    }
+}
+
+/// Keeper closes an intent past its fill deadline, returning the escrow and
+/// collecting the expiry tip (the tip vault must have been initialized).
+/// `registered_keeper` passes the caller's Keeper account, needed while
+/// cranks are reserved for keepers.
+pub fn expire_intent(
+    caller: &Pubkey,
+    registered_keeper: bool,
+    intent: &Intent,
+    escrow_mint: &Pubkey,
+    user_token_account: Option<Pubkey>,
+    token_program: &Pubkey,
+) -> Instruction {
+    let intent_key = pda::intent(&intent.user, intent.intent_id).0;
+    let accounts = solation::accounts::ExpireIntent {
+        caller: *caller,
+        global_state: pda::global_state().0,
+        intent: intent_key,
+        user_stats: pda::user_stats(&intent.user).0,
+        mm_registry: pda::mm_registry(&intent.market_maker).0,
+        user_escrow: pda::user_escrow(&intent_key).0,
+        user_token_account,
+        user_wallet: intent.user,
+        escrow_mint: *escrow_mint,
+        tip_vault: Some(pda::tip_vault().0),
+        keeper: registered_keeper.then(|| pda::keeper(caller).0),
+        token_program: *token_program,
+    };
+
+    Instruction {
+        program_id: solation::ID,
+        accounts: accounts.to_account_metas(None),
+        data: solation::instruction::ExpireIntent {}.data(),
+    }
+}
+
+/// Buyer takes `signed`, an ask from `position`'s holder, paying from their
+/// quote-mint ATA into the holder's. Returns the Ed25519 verify instruction
+/// followed by buy_position.
+pub fn buy_position(
+    buyer: &Pubkey,
+    position: &Position,
+    signed: &SignedAsk,
+    token_program: &Pubkey,
+) -> [Instruction; 2] {
+    const ED25519_INSTRUCTION_INDEX: u8 = 0;
+
+    let accounts = solation::accounts::BuyPosition {
+        buyer: *buyer,
+        global_state: pda::global_state().0,
+        position: signed.ask.position,
+        blocklist: pda::blocklist().0,
+        buyer_token_account: get_associated_token_address_with_program_id(
+            buyer,
+            &position.quote_mint,
+            token_program,
+        ),
+        seller_token_account: get_associated_token_address_with_program_id(
+            &position.holder,
+            &position.quote_mint,
+            token_program,
+        ),
+        quote_mint: position.quote_mint,
+        instructions_sysvar: sysvar::instructions::ID,
+        token_program: *token_program,
+    };
+    let args = solation::instruction::BuyPosition {
+        params: signed.buy_params(ED25519_INSTRUCTION_INDEX),
+    };
+
+    [
+        signed.verify_instruction(),
+        Instruction {
+            program_id: solation::ID,
+            accounts: accounts.to_account_metas(None),
+            data: args.data(),
+        },
+    ]
+}
+
+/// Deposit `amount` into `asset_mint`'s `strategy` vault from the depositor's
+/// ATA of the deposit mint (the underlying for covered calls, the quote for
+/// cash-secured puts), receiving shares in their share ATA. `oracle` is read
+/// only while the vault holds a balance outside its deposit mint.
+pub fn deposit_to_vault(
+    depositor: &Pubkey,
+    asset_mint: &Pubkey,
+    quote_mint: &Pubkey,
+    strategy: StrategyType,
+    amount: u64,
+    oracle: &OracleFeeds,
+    token_program: &Pubkey,
+) -> Instruction {
+    let vault = pda::option_vault(asset_mint, strategy).0;
+    let share_mint = pda::vault_share_mint(&vault).0;
+    let deposit_mint = match strategy {
+        StrategyType::CoveredCall => asset_mint,
+        StrategyType::CashSecuredPut => quote_mint,
+    };
+    let ata = |owner: &Pubkey, mint: &Pubkey| {
+        get_associated_token_address_with_program_id(owner, mint, token_program)
+    };
+    let accounts = solation::accounts::DepositToVault {
+        depositor: *depositor,
+        vault,
+        asset_config: pda::asset_config(asset_mint).0,
+        blocklist: pda::blocklist().0,
+        oracle: oracle.accounts(),
+        deposit_mint: *deposit_mint,
+        share_mint,
+        vault_asset_account: ata(&vault, asset_mint),
+        vault_quote_account: ata(&vault, quote_mint),
+        depositor_token_account: ata(depositor, deposit_mint),
+        depositor_share_account: ata(depositor, &share_mint),
+        token_program: *token_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: solation::ID,
+        accounts: accounts.to_account_metas(None),
+        data: solation::instruction::DepositToVault { amount }.data(),
+    }
+}
+
+/// Redeem `shares` of `asset_mint`'s `strategy` vault into the withdrawer's
+/// underlying and quote ATAs
+pub fn withdraw_from_vault(
+    withdrawer: &Pubkey,
+    asset_mint: &Pubkey,
+    quote_mint: &Pubkey,
+    strategy: StrategyType,
+    shares: u64,
+    token_program: &Pubkey,
+) -> Instruction {
+    let vault = pda::option_vault(asset_mint, strategy).0;
+    let share_mint = pda::vault_share_mint(&vault).0;
+    let ata = |owner: &Pubkey, mint: &Pubkey| {
+        get_associated_token_address_with_program_id(owner, mint, token_program)
+    };
+    let accounts = solation::accounts::WithdrawFromVault {
+        withdrawer: *withdrawer,
+        vault,
+        asset_mint: *asset_mint,
+        quote_mint: *quote_mint,
+        share_mint,
+        vault_asset_account: ata(&vault, asset_mint),
+        vault_quote_account: ata(&vault, quote_mint),
+        withdrawer_share_account: ata(withdrawer, &share_mint),
+        withdrawer_asset_account: ata(withdrawer, asset_mint),
+        withdrawer_quote_account: ata(withdrawer, quote_mint),
+        token_program: *token_program,
+    };
+
+    Instruction {
+        program_id: solation::ID,
+        accounts: accounts.to_account_metas(None),
+        data: solation::instruction::WithdrawFromVault { shares }.data(),
+    }
+}
+
+/// Deposit `amount` of `quote_mint` into its liquidity vault from the LP's
+/// ATA, receiving shares in their share ATA
+pub fn deposit_liquidity(
+    lp: &Pubkey,
+    quote_mint: &Pubkey,
+    amount: u64,
+    token_program: &Pubkey,
+) -> Instruction {
+    let vault = pda::liquidity_vault(quote_mint).0;
+    let share_mint = pda::lp_share_mint(&vault).0;
+    let ata = |owner: &Pubkey, mint: &Pubkey| {
+        get_associated_token_address_with_program_id(owner, mint, token_program)
+    };
+    let accounts = solation::accounts::DepositLiquidity {
+        lp: *lp,
+        vault,
+        blocklist: pda::blocklist().0,
+        quote_mint: *quote_mint,
+        share_mint,
+        vault_quote_account: ata(&vault, quote_mint),
+        lp_quote_account: ata(lp, quote_mint),
+        lp_share_account: ata(lp, &share_mint),
+        token_program: *token_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: solation::ID,
+        accounts: accounts.to_account_metas(None),
+        data: solation::instruction::DepositLiquidity { amount }.data(),
+    }
+}
+
+/// Keeper settles an expired position at the oracle price. Payouts go to the
+/// payout owners' ATAs for `vault_mint`, created at the settler's expense;
+/// `swap` routes the user's share through their requested payout swap;
+/// `registered_keeper` passes the settler's Keeper account.
+pub fn settle_position(
+    settler: &Pubkey,
+    registered_keeper: bool,
+    position: &Position,
+    vault_mint: &Pubkey,
+    oracle: &OracleFeeds,
+    token_program: &Pubkey,
+    swap: Option<&PayoutSwapRoute>,
+) -> Instruction {
+    let position_key = pda::position(&position.user, position.position_id).0;
+    let user_payout_owner = position.user_payout_owner();
+    let mm_payout_owner = position.mm_payout_owner();
+    let user_underlying_destination = position.user_payout_in_underlying.then(|| {
+        get_associated_token_address_with_program_id(
+            &user_payout_owner,
+            &position.asset_mint,
+            token_program,
+        )
+    });
+    let accounts = solation::accounts::SettlePosition {
+        settler: *settler,
+        keeper: registered_keeper.then(|| pda::keeper(settler).0),
+        global_state: pda::global_state().0,
+        position: position_key,
+        asset_config: pda::asset_config(&position.asset_mint).0,
+        mm_registry: pda::mm_registry(&position.market_maker).0,
+        user_stats: pda::user_stats(&position.user).0,
+        position_user_vault: position.user_vault,
+        vault_mint: *vault_mint,
+        position_mm_vault: position.mm_vault_locked,
+        position_authority: position_key,
+        user_payout_owner,
+        user_destination: get_associated_token_address_with_program_id(
+            &user_payout_owner,
+            vault_mint,
+            token_program,
+        ),
+        user_underlying_destination,
+        user_wallet: None,
+        mm_payout_owner,
+        mm_destination: get_associated_token_address_with_program_id(
+            &mm_payout_owner,
+            vault_mint,
+            token_program,
+        ),
+        payout_swap: swap.map(|_| pda::payout_swap(&position_key).0),
+        swap_destination: swap.map(|route| route.swap_destination),
+        jupiter_program: swap.map(|_| JUPITER_PROGRAM_ID),
+        oracle: oracle.accounts(),
+        quote_price_update: oracle.quote_price_update,
+        blocklist: pda::blocklist().0,
+        option_series: position.in_series().then_some(position.series),
+        token_program: *token_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+    };
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(oracle.basket_metas());
+    if let Some(route) = swap {
+        metas.extend(route.accounts.iter().cloned());
+    }
+    let args = solation::instruction::SettlePosition {
+        swap_route: swap.map(|route| route.data.clone()),
+    };
+
+    Instruction {
+        program_id: solation::ID,
+        accounts: metas,
+        data: args.data(),
+    }
+}
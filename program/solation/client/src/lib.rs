@@ -0,0 +1,20 @@
+//! Off-chain client for the Solation program.
+//!
+//! Instruction builders wrap the program's own Anchor account and argument
+//! types, and quote messages come from the program's
+//! `construct_quote_message`, so MMs and keepers never hand-roll byte layouts.
+
+pub mod ed25519;
+pub mod instructions;
+pub mod mm;
+pub mod pda;
+pub mod quote;
+pub mod resale;
+
+pub use ed25519::*;
+pub use instructions::*;
+pub use mm::*;
+pub use quote::*;
+pub use resale::*;
+
+pub use solation::ID as PROGRAM_ID;
@@ -0,0 +1,127 @@
+//! Quote signing and nonce allocation for MM backends.
+
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::{borsh, AnchorDeserialize, AnchorSerialize, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use solana_signer::{Signer, SignerError};
+use solation::instructions::SubmitIntentParams;
+use solation::state::NonceTracker;
+
+use crate::ed25519::ed25519_verify_instruction;
+use crate::quote::Quote;
+
+/// A quote signed by the MM's registered signing key, ready to hand to a user
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SignedQuote {
+    pub quote: Quote,
+    pub signing_key: Pubkey,
+    pub signature: [u8; 64],
+}
+
+impl SignedQuote {
+    /// Ed25519Program instruction the user places before submit_intent
+    pub fn verify_instruction(&self) -> Instruction {
+        ed25519_verify_instruction(&self.signing_key, &self.signature, &self.quote.message())
+    }
+
+    /// submit_intent arguments, with the Ed25519 instruction at `ed25519_instruction_index`
+    pub fn submit_params(
+        &self,
+        intent_id: u64,
+        ed25519_instruction_index: u8,
+    ) -> SubmitIntentParams {
+        self.quote
+            .submit_params(intent_id, self.signature, ed25519_instruction_index)
+    }
+}
+
+/// Sign `quote` with the MM's signing key (a keypair, or a remote/HSM signer)
+pub fn sign_quote<S: Signer + ?Sized>(
+    signer: &S,
+    quote: Quote,
+) -> Result<SignedQuote, SignerError> {
+    let signature = signer.try_sign_message(&quote.message())?;
+    Ok(SignedQuote {
+        quote,
+        signing_key: signer.try_pubkey()?,
+        signature: signature.into(),
+    })
+}
+
+/// Hands out quote nonces that the on-chain NonceTracker will accept.
+///
+/// The tracker remembers a 256-nonce window starting at `base_nonce`; nonces
+/// below it count as used, and submitting one past the window slides it
+/// forward, burning every older nonce. The allocator therefore hands out the
+/// lowest nonces that are free both on-chain and among quotes still in
+/// flight, so outstanding quotes are not invalidated by newer ones.
+#[derive(Clone)]
+pub struct NonceAllocator {
+    tracker: NonceTracker,
+    reserved: BTreeSet<u64>,
+}
+
+impl NonceAllocator {
+    pub fn new(tracker: NonceTracker) -> Self {
+        Self {
+            tracker,
+            reserved: BTreeSet::new(),
+        }
+    }
+
+    /// Refresh from a freshly fetched tracker, dropping reservations the
+    /// program has since consumed or slid past
+    pub fn sync(&mut self, tracker: NonceTracker) {
+        self.tracker = tracker;
+        let tracker = &self.tracker;
+        self.reserved.retain(|nonce| !tracker.is_used(*nonce));
+    }
+
+    /// Reserve the next usable nonce for a new quote
+    pub fn allocate(&mut self) -> u64 {
+        let base = self.tracker.base_nonce;
+        let window_end = base.saturating_add(NonceTracker::BITMAP_SIZE as u64);
+        let nonce = (base..window_end)
+            .find(|nonce| !self.tracker.is_used(*nonce) && !self.reserved.contains(nonce))
+            // Window exhausted: the next nonce past everything outstanding
+            .unwrap_or_else(|| {
+                self.reserved
+                    .last()
+                    .map_or(window_end, |last| last.saturating_add(1).max(window_end))
+            });
+        self.reserved.insert(nonce);
+        nonce
+    }
+
+    /// Reserve a nonce for a quote expiring at `quote_expiry`. A tracker on
+    /// time-bounded nonces only accepts one from the quote's expiry bucket;
+    /// `None` once that bucket's sequence space is spent.
+    pub fn allocate_for_expiry(&mut self, quote_expiry: i64) -> Option<u64> {
+        if !self.tracker.is_time_bounded() {
+            return Some(self.allocate());
+        }
+        let start = self.tracker.bucket_start(quote_expiry);
+        let end = start.saturating_add(1 << NonceTracker::BUCKET_SEQUENCE_BITS);
+        let nonce = (start..end)
+            .find(|nonce| !self.tracker.is_used(*nonce) && !self.reserved.contains(nonce))?;
+        self.reserved.insert(nonce);
+        Some(nonce)
+    }
+
+    /// Return the nonce of a quote that expired unsubmitted. Only safe once
+    /// `quote_expiry` has passed, since the old signature stays valid until then.
+    pub fn release(&mut self, nonce: u64) {
+        self.reserved.remove(&nonce);
+    }
+
+    /// Whether a submitted quote with `nonce` would still be accepted
+    pub fn is_available(&self, nonce: u64) -> bool {
+        !self.tracker.is_used(nonce)
+    }
+
+    /// Nonces handed out and not yet seen on-chain
+    pub fn outstanding(&self) -> impl Iterator<Item = u64> + '_ {
+        self.reserved.iter().copied()
+    }
+}
@@ -0,0 +1,141 @@
+//! PDA derivation for Solation accounts. Each helper returns (address, bump).
+
+use anchor_lang::prelude::Pubkey;
+use solation::constants::*;
+use solation::state::StrategyType;
+
+fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &solation::ID)
+}
+
+pub fn global_state() -> (Pubkey, u8) {
+    find(&[GLOBAL_STATE_SEED])
+}
+
+pub fn asset_config(asset_mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[ASSET_CONFIG_SEED, asset_mint.as_ref()])
+}
+
+pub fn mm_registry(market_maker: &Pubkey) -> (Pubkey, u8) {
+    find(&[MM_REGISTRY_SEED, market_maker.as_ref()])
+}
+
+/// MM's per-mint float, held by its registry PDA
+pub fn mm_vault(market_maker: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    let mm_registry = mm_registry(market_maker).0;
+    find(&[MM_VAULT_SEED, mm_registry.as_ref(), mint.as_ref()])
+}
+
+pub fn nonce_tracker(market_maker: &Pubkey) -> (Pubkey, u8) {
+    find(&[NONCE_TRACKER_SEED, market_maker.as_ref()])
+}
+
+pub fn posted_quote(market_maker: &Pubkey, quote_nonce: u64) -> (Pubkey, u8) {
+    find(&[POSTED_QUOTE_SEED, market_maker.as_ref(), &quote_nonce.to_le_bytes()])
+}
+
+pub fn standing_quote(market_maker: &Pubkey, quote_id: u64) -> (Pubkey, u8) {
+    find(&[STANDING_QUOTE_SEED, market_maker.as_ref(), &quote_id.to_le_bytes()])
+}
+
+/// Series for one asset, style, strike and calendar expiry
+pub fn option_series(
+    asset_mint: &Pubkey,
+    strategy: StrategyType,
+    strike_price: u64,
+    expiry_timestamp: i64,
+) -> (Pubkey, u8) {
+    find(&[
+        OPTION_SERIES_SEED,
+        asset_mint.as_ref(),
+        &[strategy as u8],
+        &strike_price.to_le_bytes(),
+        &expiry_timestamp.to_le_bytes(),
+    ])
+}
+
+/// Pooled covered-call or cash-secured-put vault for an asset; it is the
+/// `user` of its rounds' intents and positions, keyed by epoch
+pub fn option_vault(asset_mint: &Pubkey, strategy: StrategyType) -> (Pubkey, u8) {
+    find(&[OPTION_VAULT_SEED, asset_mint.as_ref(), &[strategy as u8]])
+}
+
+pub fn vault_share_mint(vault: &Pubkey) -> (Pubkey, u8) {
+    find(&[VAULT_SHARE_MINT_SEED, vault.as_ref()])
+}
+
+/// Passive LP vault for a quote mint
+pub fn liquidity_vault(quote_mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[LIQUIDITY_VAULT_SEED, quote_mint.as_ref()])
+}
+
+pub fn lp_share_mint(vault: &Pubkey) -> (Pubkey, u8) {
+    find(&[LP_SHARE_MINT_SEED, vault.as_ref()])
+}
+
+/// An MM's credit line on a liquidity vault
+pub fn lp_backing(vault: &Pubkey, market_maker: &Pubkey) -> (Pubkey, u8) {
+    find(&[LP_BACKING_SEED, vault.as_ref(), market_maker.as_ref()])
+}
+
+pub fn lp_withdrawal(vault: &Pubkey, lp: &Pubkey) -> (Pubkey, u8) {
+    find(&[LP_WITHDRAWAL_SEED, vault.as_ref(), lp.as_ref()])
+}
+
+pub fn intent(user: &Pubkey, intent_id: u64) -> (Pubkey, u8) {
+    find(&[INTENT_SEED, user.as_ref(), &intent_id.to_le_bytes()])
+}
+
+pub fn user_escrow(intent: &Pubkey) -> (Pubkey, u8) {
+    find(&[USER_ESCROW_SEED, intent.as_ref()])
+}
+
+/// Positions share the intent's ID; the position PDA also signs for its vaults
+pub fn position(user: &Pubkey, position_id: u64) -> (Pubkey, u8) {
+    find(&[POSITION_SEED, user.as_ref(), &position_id.to_le_bytes()])
+}
+
+pub fn position_user_vault(position: &Pubkey) -> (Pubkey, u8) {
+    find(&[POSITION_USER_VAULT_SEED, position.as_ref()])
+}
+
+/// Premium installment plan offered for `position`
+pub fn premium_schedule(position: &Pubkey) -> (Pubkey, u8) {
+    find(&[PREMIUM_SCHEDULE_SEED, position.as_ref()])
+}
+
+pub fn user_stats(user: &Pubkey) -> (Pubkey, u8) {
+    find(&[USER_STATS_SEED, user.as_ref()])
+}
+
+pub fn roll_preference(user: &Pubkey, asset_mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[ROLL_PREFERENCE_SEED, user.as_ref(), asset_mint.as_ref()])
+}
+
+pub fn payout_swap(position: &Pubkey) -> (Pubkey, u8) {
+    find(&[PAYOUT_SWAP_SEED, position.as_ref()])
+}
+
+pub fn blocklist() -> (Pubkey, u8) {
+    find(&[BLOCKLIST_SEED])
+}
+
+pub fn composer_allowlist() -> (Pubkey, u8) {
+    find(&[COMPOSER_ALLOWLIST_SEED])
+}
+
+pub fn user_credential(issuer: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    find(&[USER_CREDENTIAL_SEED, issuer.as_ref(), user.as_ref()])
+}
+
+pub fn insurance_fund(quote_mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[INSURANCE_FUND_SEED, quote_mint.as_ref()])
+}
+
+pub fn tip_vault() -> (Pubkey, u8) {
+    find(&[TIP_VAULT_SEED])
+}
+
+pub fn keeper(keeper: &Pubkey) -> (Pubkey, u8) {
+    find(&[KEEPER_SEED, keeper.as_ref()])
+}
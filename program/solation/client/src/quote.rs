@@ -0,0 +1,63 @@
+//! MM quotes and the message they sign.
+
+use anchor_lang::prelude::*;
+use solation::instructions::SubmitIntentParams;
+use solation::state::StrategyType;
+use solation::utils::{construct_quote_message, QUOTE_MESSAGE_LEN};
+
+/// Terms of an MM quote, as signed off-chain and submitted by the user
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct Quote {
+    pub asset_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub strategy: StrategyType,
+    pub strike_price: u64,
+    pub premium_per_contract: u64,
+    pub contract_size: u64,
+    pub quote_expiry: i64,
+    pub quote_nonce: u64,
+    /// Implied volatility the quote was priced at, in basis points
+    pub implied_volatility_bps: u32,
+}
+
+impl Quote {
+    /// Bytes the MM signs with its registered signing key
+    pub fn message(&self) -> [u8; QUOTE_MESSAGE_LEN] {
+        construct_quote_message(
+            &self.asset_mint,
+            &self.quote_mint,
+            self.strategy,
+            self.strike_price,
+            self.premium_per_contract,
+            self.contract_size,
+            self.quote_expiry,
+            self.quote_nonce,
+            self.implied_volatility_bps,
+        )
+    }
+
+    /// submit_intent arguments for this quote
+    pub fn submit_params(
+        &self,
+        intent_id: u64,
+        mm_signature: [u8; 64],
+        ed25519_instruction_index: u8,
+    ) -> SubmitIntentParams {
+        SubmitIntentParams {
+            intent_id,
+            asset_mint: self.asset_mint,
+            quote_mint: self.quote_mint,
+            strategy: self.strategy,
+            strike_price: self.strike_price,
+            premium_per_contract: self.premium_per_contract,
+            contract_size: self.contract_size,
+            quote_expiry: self.quote_expiry,
+            quote_nonce: self.quote_nonce,
+            implied_volatility_bps: self.implied_volatility_bps,
+            mm_signature,
+            ed25519_instruction_index,
+            min_mm_reputation: None,
+            min_mm_fill_rate: None,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Resale asks a position holder signs for buyers to take on-chain.
+
+use anchor_lang::prelude::{borsh, AnchorDeserialize, AnchorSerialize, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use solana_signer::{Signer, SignerError};
+use solation::instructions::BuyPositionParams;
+use solation::utils::{construct_ask_message, ASK_MESSAGE_LEN};
+
+use crate::ed25519::ed25519_verify_instruction;
+
+/// Terms of a resale ask, as signed off-chain by the position's holder
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct Ask {
+    pub position: Pubkey,
+    /// Asking price in the position's quote mint
+    pub price: u64,
+    pub ask_expiry: i64,
+    /// The position's current `resale_nonce`
+    pub resale_nonce: u64,
+}
+
+impl Ask {
+    /// Bytes the holder signs with their wallet key
+    pub fn message(&self) -> [u8; ASK_MESSAGE_LEN] {
+        construct_ask_message(&self.position, self.price, self.ask_expiry, self.resale_nonce)
+    }
+}
+
+/// An ask signed by the position's holder, ready to hand to a buyer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SignedAsk {
+    pub ask: Ask,
+    pub holder: Pubkey,
+    pub signature: [u8; 64],
+}
+
+impl SignedAsk {
+    /// Ed25519Program instruction the buyer places before buy_position
+    pub fn verify_instruction(&self) -> Instruction {
+        ed25519_verify_instruction(&self.holder, &self.signature, &self.ask.message())
+    }
+
+    /// buy_position arguments, with the Ed25519 instruction at `ed25519_instruction_index`
+    pub fn buy_params(&self, ed25519_instruction_index: u8) -> BuyPositionParams {
+        BuyPositionParams {
+            price: self.ask.price,
+            ask_expiry: self.ask.ask_expiry,
+            resale_nonce: self.ask.resale_nonce,
+            holder_signature: self.signature,
+            ed25519_instruction_index,
+        }
+    }
+}
+
+/// Sign `ask` with the holder's key
+pub fn sign_ask<S: Signer + ?Sized>(signer: &S, ask: Ask) -> Result<SignedAsk, SignerError> {
+    let signature = signer.try_sign_message(&ask.message())?;
+    Ok(SignedAsk {
+        ask,
+        holder: signer.try_pubkey()?,
+        signature: signature.into(),
+    })
+}
@@ -15,15 +15,121 @@ pub const INTENT_SEED: &[u8] = b"intent";
 pub const MM_REGISTRY_SEED: &[u8] = b"mm_registry";
 pub const NONCE_TRACKER_SEED: &[u8] = b"nonce_tracker";
 pub const USER_ESCROW_SEED: &[u8] = b"user_escrow";
+pub const ROLL_PREFERENCE_SEED: &[u8] = b"roll_preference";
+pub const SETTLEMENT_OBSERVATIONS_SEED: &[u8] = b"settlement_observations";
+pub const PENDING_CHANGE_SEED: &[u8] = b"pending_change";
+pub const DISPUTE_RECORD_SEED: &[u8] = b"dispute_record";
+pub const RESOLUTION_VOTE_SEED: &[u8] = b"resolution_vote";
+pub const BLOCKLIST_SEED: &[u8] = b"blocklist";
+pub const USER_CREDENTIAL_SEED: &[u8] = b"user_credential";
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund";
+pub const INSURANCE_VAULT_SEED: &[u8] = b"insurance_vault";
+pub const DEFAULT_HAIRCUT_SEED: &[u8] = b"default_haircut";
+pub const USER_STATS_SEED: &[u8] = b"user_stats";
+pub const POSITION_ARCHIVE_SEED: &[u8] = b"position_archive";
+pub const PROTOCOL_LOOKUP_TABLE_SEED: &[u8] = b"protocol_lookup_table";
+pub const COMPOSER_ALLOWLIST_SEED: &[u8] = b"composer_allowlist";
+pub const HEDGE_ADAPTER_ALLOWLIST_SEED: &[u8] = b"hedge_adapter_allowlist";
+pub const PAYOUT_SWAP_SEED: &[u8] = b"payout_swap";
+pub const FILL_ESCROW_SEED: &[u8] = b"fill_escrow";
+pub const PREMIUM_ESCROW_SEED: &[u8] = b"premium_escrow";
+pub const TIP_VAULT_SEED: &[u8] = b"tip_vault";
+pub const KEEPER_SEED: &[u8] = b"keeper";
+pub const MM_EXPOSURE_SEED: &[u8] = b"mm_exposure";
+pub const RISK_CONFIG_SEED: &[u8] = b"risk_config";
+pub const POSTED_QUOTE_SEED: &[u8] = b"posted_quote";
+pub const STANDING_QUOTE_SEED: &[u8] = b"standing_quote";
+pub const SETTLEMENT_ATTESTATION_SEED: &[u8] = b"settlement_attestation";
+pub const WORMHOLE_EMITTER_SEED: &[u8] = b"emitter";
+pub const WORMHOLE_MESSAGE_SEED: &[u8] = b"wormhole_message";
+pub const OPTION_SERIES_SEED: &[u8] = b"option_series";
+pub const PREMIUM_SCHEDULE_SEED: &[u8] = b"premium_schedule";
+pub const OPTION_VAULT_SEED: &[u8] = b"option_vault";
+pub const VAULT_SHARE_MINT_SEED: &[u8] = b"option_vault_shares";
+pub const LIQUIDITY_VAULT_SEED: &[u8] = b"liquidity_vault";
+pub const LP_SHARE_MINT_SEED: &[u8] = b"lp_shares";
+pub const LP_BACKING_SEED: &[u8] = b"lp_backing";
+pub const LP_WITHDRAWAL_SEED: &[u8] = b"lp_withdrawal";
+pub const VAULT_WITHDRAWAL_SEED: &[u8] = b"vault_withdrawal";
+
+// Instruction families gated by GlobalState.enabled_operations
+pub const OPERATION_SETTLEMENT: u8 = 1 << 0;
+pub const OPERATION_FILLS: u8 = 1 << 1;
+pub const OPERATION_NEW_INTENTS: u8 = 1 << 2;
+pub const OPERATION_ALL: u8 = OPERATION_SETTLEMENT | OPERATION_FILLS | OPERATION_NEW_INTENTS;
+
+// Order in which resume_protocol re-enables families after a shutdown
+pub const RESUME_STAGES: [u8; 3] = [OPERATION_SETTLEMENT, OPERATION_FILLS, OPERATION_NEW_INTENTS];
 
 // MM Confirmation Window (seconds)
 pub const MM_CONFIRMATION_WINDOW: i64 = 30;
 
-// Intent fill timeout (seconds) - same as confirmation window
-pub const INTENT_FILL_TIMEOUT: i64 = 30;
+// Intent fill timeout (seconds); the default matches the confirmation window
+// and the authority can tune it within these bounds
+pub const DEFAULT_INTENT_FILL_TIMEOUT: i64 = 30;
+pub const MIN_INTENT_FILL_TIMEOUT: i64 = 10;
+pub const MAX_INTENT_FILL_TIMEOUT: i64 = 10 * 60; // 10 minutes
+
+// Time-bounded quote nonces: bounds on the bucket width an MM can choose (seconds)
+pub const MIN_NONCE_BUCKET_SECONDS: u32 = 60;
+pub const MAX_NONCE_BUCKET_SECONDS: u32 = 24 * 60 * 60; // 24 hours
+
+// Longest cooldown the authority can impose between one party's dispute flags
+pub const MAX_DISPUTE_FLAG_COOLDOWN: i64 = 7 * 24 * 60 * 60; // 7 days
+
+// Treasury's share of a bond forfeited on a frivolous dispute; the
+// counterparty receives the rest
+pub const FRIVOLOUS_BOND_TREASURY_BPS: u64 = 5000; // 50%
+
+// Tip paid from the tip vault to whoever expires a stale intent (lamports)
+pub const EXPIRE_INTENT_TIP_LAMPORTS: u64 = 50_000;
+
+// How long a posted quote can be taken after it is posted (seconds)
+pub const POSTED_QUOTE_LIFETIME: i64 = 2 * 60; // 2 minutes
+
+// Standing quotes: strikes per grid and the longest an update keeps a quote up
+pub const MAX_STANDING_QUOTE_LEVELS: usize = 16;
+pub const MAX_STANDING_QUOTE_LIFETIME: i64 = 24 * 60 * 60; // 24 hours
+
+// Option series expiry calendar: weekly, Fridays 08:00 UTC (the epoch was a Thursday)
+pub const SERIES_EXPIRY_INTERVAL: i64 = 7 * 24 * 60 * 60;
+pub const SERIES_EXPIRY_OFFSET: i64 = 32 * 60 * 60;
+
+// Premium installment plans: installment count, shortest interval and the
+// grace after a due date before the holder may cut unpaid coverage
+pub const MAX_PREMIUM_INSTALLMENTS: u8 = 12;
+pub const MIN_INSTALLMENT_INTERVAL: i64 = 24 * 60 * 60; // 1 day
+pub const PREMIUM_INSTALLMENT_GRACE: i64 = 6 * 60 * 60; // 6 hours
+
+// Time an MM has to lock collateral after escrowing a fill's premium (seconds)
+pub const ESCROWED_FILL_WINDOW: i64 = 60;
+
+// Upper bound on the post-snapshot settlement dispute window (seconds)
+pub const MAX_SETTLEMENT_DISPUTE_WINDOW: i64 = 7 * 24 * 60 * 60; // 7 days
+
+// Timelock on parameter changes (seconds)
+pub const DEFAULT_TIMELOCK_DELAY: i64 = 48 * 60 * 60; // 48 hours
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60; // 30 days
+
+// Upper bound on how long cranks stay reserved for registered keepers (seconds)
+pub const MAX_KEEPER_GRACE_PERIOD: i64 = 24 * 60 * 60; // 24 hours
+
+// Delay past expiry before the authority may settle at a manual price
+pub const ADMIN_SETTLEMENT_DELAY: i64 = 24 * 60 * 60; // 24 hours
+
+// Default auto-exercise threshold: ITM by less than this settles OTM
+pub const DEFAULT_MIN_ITM_BPS: u16 = 10; // 0.1%
 
-// Pyth parameters
+// Pyth parameters (defaults for new assets, tunable via update_asset)
 pub const PYTH_STALENESS_THRESHOLD: u64 = 60; // 60 seconds
+pub const DEFAULT_MAX_CONFIDENCE_BPS: u16 = 200; // 2% of price
+pub const MAX_PYTH_STALENESS_THRESHOLD: u64 = 60 * 60; // 1 hour
+pub const DEFAULT_PRICE_EXPONENT: i32 = -8; // Pyth USD feeds
+pub const SWITCHBOARD_PRECISION: u32 = 18; // Decimals in Switchboard pull feed results
+
+// TWAP settlement parameters
+pub const MAX_TWAP_WINDOW_SECONDS: i64 = 60 * 60; // 1 hour before expiry
+pub const MAX_SETTLEMENT_OBSERVATIONS: usize = 12;
 
 // Quote parameters
 pub const MAX_STRIKES_PER_QUOTE: usize = 10;
@@ -34,3 +140,65 @@ pub const BASIS_POINTS_DIVISOR: u64 = 10000;
 // Dispute reason max length
 pub const MAX_DISPUTE_REASON_LEN: usize = 200;
 
+// Arbitration committee size
+pub const MAX_ARBITERS: usize = 5;
+
+// Dispute evidence limits
+pub const MAX_EVIDENCE_URI_LEN: usize = 128;
+pub const MAX_EVIDENCE_PER_PARTY: usize = 4;
+pub const MAX_EVIDENCE_ENTRIES: usize = 2 * MAX_EVIDENCE_PER_PARTY;
+
+
+// Compliance blocklist capacity (fits a single CPI account allocation)
+pub const MAX_BLOCKLIST_ENTRIES: usize = 256;
+
+// Programs allowed to submit or fill intents via CPI
+pub const MAX_COMPOSERS: usize = 32;
+
+// Perp venue adapters an MM may hedge a fill through
+pub const MAX_HEDGE_ADAPTERS: usize = 8;
+
+// Floor on the portfolio margin stress move, so the risk admin cannot
+// stress less than a ±20% spot move
+pub const MIN_PORTFOLIO_STRESS_BPS: u16 = 2_000;
+
+// Cap on the slippage a user may accept on a settlement payout swap
+pub const MAX_PAYOUT_SWAP_SLIPPAGE_BPS: u16 = 500; // 5%
+
+// Largest shortfall a user may accept on premium paid in the underlying,
+// against its value at oracle spot (bps)
+pub const MAX_UNDERLYING_PREMIUM_SLIPPAGE_BPS: u16 = 500; // 5%
+
+// Alternative stablecoin quote mints an asset can list besides its primary one
+pub const MAX_ALT_QUOTE_MINTS: usize = 4;
+
+// Components of an index asset's basket, and the fixed-point scale of their
+// weights (units of a component per unit of the index)
+pub const MAX_BASKET_COMPONENTS: usize = 8;
+pub const BASKET_WEIGHT_SCALE: u64 = 1_000_000;
+
+// Protocol address lookup table: addresses per extension (keeps the
+// transaction under the size limit) and the table's hard capacity
+pub const MAX_LOOKUP_TABLE_EXTENSION: usize = 20;
+pub const MAX_LOOKUP_TABLE_ADDRESSES: usize = 256;
+
+// Every StrategyType bit; new assets list all strategies
+pub const ALL_STRATEGIES: u8 = 0b11;
+
+// Passive LP vault: cap on the premium share charged to MMs, and the
+// shortest withdrawal epoch the authority may set
+pub const MAX_LP_PREMIUM_SHARE_BPS: u16 = 5_000; // 50%
+pub const MIN_LP_EPOCH_DURATION: i64 = 86400; // 1 day
+
+// Pooled vault fees, charged to the treasury in shares at each epoch roll:
+// caps on the annual management fee and the performance fee, and the fixed
+// point scale share prices (and high-water marks) are kept in
+pub const MAX_VAULT_MANAGEMENT_FEE_BPS: u16 = 500; // 5% a year
+pub const MAX_VAULT_PERFORMANCE_FEE_BPS: u16 = 3_000; // 30%
+pub const SHARE_PRICE_SCALE: u64 = 1_000_000_000;
+pub const SECONDS_PER_YEAR: i64 = 365 * 86400;
+
+// Option vault deposit/withdraw window after each round: default and bounds
+pub const DEFAULT_VAULT_WINDOW: i64 = 86400; // 1 day
+pub const MIN_VAULT_WINDOW: i64 = 3600; // 1 hour
+pub const MAX_VAULT_WINDOW: i64 = 7 * 86400; // 7 days
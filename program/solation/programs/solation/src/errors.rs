@@ -120,5 +120,448 @@ pub enum ErrorCode {
 
     #[msg("Invalid vault address")]
     InvalidVault,
-}
 
+    #[msg("Auto-roll is not enabled for this position")]
+    AutoRollDisabled,
+
+    #[msg("New quote does not satisfy the auto-roll constraints")]
+    RollConstraintViolated,
+
+    #[msg("Settlement payout is insufficient to fund the rolled intent")]
+    InsufficientRollCollateral,
+
+    #[msg("Invalid trigger prices")]
+    InvalidTriggerPrice,
+
+    #[msg("Trigger condition not met")]
+    TriggerNotHit,
+
+    #[msg("Market maker does not offer early unwinds")]
+    UnwindNotOffered,
+
+    #[msg("Position has already expired")]
+    PositionExpired,
+
+    #[msg("Invalid or mismatched batch accounts")]
+    InvalidBatchAccounts,
+
+    #[msg("Settlement price has not been snapshotted")]
+    SettlementNotSnapshotted,
+
+    #[msg("Invalid settlement dispute window")]
+    InvalidDisputeWindow,
+
+    #[msg("Settlement dispute window is still open")]
+    DisputeWindowOpen,
+
+    #[msg("Settlement dispute window has closed")]
+    DisputeWindowClosed,
+
+    #[msg("Settlement is disputed")]
+    SettlementDisputed,
+
+    #[msg("One-shot settlement is disabled while a dispute window is configured")]
+    SettlementRequiresSnapshot,
+
+    #[msg("Invalid settlement price")]
+    InvalidSettlementPrice,
+
+    #[msg("Invalid TWAP configuration")]
+    InvalidTwapConfig,
+
+    #[msg("Observation is outside the TWAP window")]
+    ObservationOutsideWindow,
+
+    #[msg("Observation is too close to the previous one")]
+    ObservationTooFrequent,
+
+    #[msg("Not enough observations for a TWAP settlement")]
+    InsufficientObservations,
+
+    #[msg("Pyth price update is not fully verified")]
+    InsufficientVerificationLevel,
+
+    #[msg("Oracle price must be positive")]
+    InvalidOraclePrice,
+
+    #[msg("Pyth confidence interval is too wide")]
+    PriceConfidenceTooWide,
+
+    #[msg("Invalid oracle configuration")]
+    InvalidOracleConfig,
+
+    #[msg("Oracle account does not match the asset configuration")]
+    InvalidOracleAccount,
+
+    #[msg("Oracle account for the asset's source was not provided")]
+    MissingOracleAccount,
+
+    #[msg("Admin settlement delay has not elapsed")]
+    AdminSettlementTooEarly,
+
+    #[msg("ITM physical settlement requires MM delivery")]
+    PhysicalDeliveryRequired,
+
+    #[msg("Position does not require physical delivery")]
+    PhysicalDeliveryNotRequired,
+
+    #[msg("Underlying payout is only available on covered calls escrowed in the underlying")]
+    UnderlyingPayoutUnavailable,
+
+    #[msg("User elected an underlying payout but no underlying destination was provided")]
+    UnderlyingDestinationRequired,
+
+    #[msg("Protocol is not paused")]
+    ProtocolNotPaused,
+
+    #[msg("Unpausing must go through resume_protocol")]
+    StagedResumeRequired,
+
+    #[msg("Timelock delay has not elapsed for this change")]
+    TimelockNotElapsed,
+
+    #[msg("Invalid timelock delay")]
+    InvalidTimelockDelay,
+
+    #[msg("Asset config does not match the queued change")]
+    AssetConfigMismatch,
+
+    #[msg("Asset is paused")]
+    AssetPaused,
+
+    #[msg("Asset has been delisted")]
+    AssetDelisted,
+
+    #[msg("Asset must be delisted with no open positions")]
+    AssetStillActive,
+
+    #[msg("Fill would exceed the open interest cap")]
+    OpenInterestCapExceeded,
+
+    #[msg("Intent is not disputed")]
+    IntentNotDisputed,
+
+    #[msg("Evidence URI too long")]
+    EvidenceUriTooLong,
+
+    #[msg("Evidence limit reached for this party")]
+    EvidenceLimitReached,
+
+    #[msg("Invalid arbitration committee")]
+    InvalidArbitrationCommittee,
+
+    #[msg("Signer is not an arbiter")]
+    NotAnArbiter,
+
+    #[msg("Arbitration committee mode is not enabled")]
+    CommitteeModeDisabled,
+
+    #[msg("Arbiter has already voted on this resolution")]
+    AlreadyVoted,
+
+    #[msg("Vote does not match the pending resolution proposal")]
+    ResolutionProposalMismatch,
+
+    #[msg("Resolution has already been executed")]
+    ResolutionAlreadyExecuted,
+
+    #[msg("Resolution lacks committee approval")]
+    CommitteeApprovalRequired,
+
+    #[msg("Intent's escrow is not held by the treasury")]
+    NotTreasuryHeld,
+
+    #[msg("Distribution exceeds the treasury-held amount")]
+    DistributionExceedsHeld,
+
+    #[msg("Address is on the compliance blocklist")]
+    AddressBlocked,
+
+    #[msg("Blocklist is full")]
+    BlocklistFull,
+
+    #[msg("No withheld payout to claim")]
+    NoWithheldPayout,
+
+    #[msg("Permissioned asset requires a pass token or issuer")]
+    InvalidAccessGate,
+
+    #[msg("User does not meet the asset's access requirement")]
+    AccessDenied,
+
+    #[msg("Invalid credential expiry")]
+    InvalidCredentialExpiry,
+
+    #[msg("Insurance fund balance is insufficient")]
+    InsufficientInsuranceFund,
+
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+
+    #[msg("Haircut has already been finalized")]
+    HaircutFinalized,
+
+    #[msg("Haircut has not been finalized")]
+    HaircutNotFinalized,
+
+    #[msg("Position's default claim is already registered or paid")]
+    DefaultClaimAlreadyRegistered,
+
+    #[msg("Position has no registered default claim")]
+    NoDefaultClaim,
+
+    #[msg("Position still has an open settlement, withheld payout or default claim")]
+    PositionNotArchivable,
+
+    #[msg("Position archive tree is full")]
+    ArchiveTreeFull,
+
+    #[msg("Invalid archive tree parameters")]
+    InvalidArchiveTree,
+
+    #[msg("Lookup table address does not match the authority and slot")]
+    InvalidLookupTable,
+
+    #[msg("Too many addresses for the lookup table")]
+    LookupTableFull,
+
+    #[msg("Token mint does not match")]
+    InvalidMint,
+
+    #[msg("Mint uses a Token-2022 extension that is not supported")]
+    UnsupportedMintExtension,
+
+    #[msg("Mint charges transfer fees and the asset does not allow them")]
+    TransferFeeMintRejected,
+
+    #[msg("A token account is required unless the mint is wrapped SOL")]
+    TokenAccountRequired,
+
+    #[msg("Calling program is not an allowlisted composer")]
+    ComposerNotAllowed,
+
+    #[msg("Composer allowlist is full")]
+    ComposerAllowlistFull,
+
+    #[msg("Escrow mint is not accepted collateral for this strategy")]
+    InvalidCollateralMint,
+
+    #[msg("Invalid LST collateral configuration")]
+    InvalidLstConfig,
+
+    #[msg("Payout swap must target the position's other leg within the slippage cap")]
+    InvalidPayoutSwap,
+
+    #[msg("Swap route spent more than the user's payout or touched other funds")]
+    InvalidSwapRoute,
+
+    #[msg("Swap output is below the user's slippage bound")]
+    SwapSlippageExceeded,
+
+    #[msg("Position has already been opened")]
+    PositionAlreadyOpen,
+
+    #[msg("Intent has no escrowed fill in progress")]
+    FillNotInProgress,
+
+    #[msg("Escrowed fill can only be unwound by the MM until its window closes")]
+    EscrowedFillWindowOpen,
+
+    #[msg("MM collateral stays locked until the position is settled")]
+    MmCollateralLocked,
+
+    #[msg("Only registered keepers may crank this until its grace period passes")]
+    KeeperRequired,
+
+    #[msg("Keeper grace period out of range")]
+    InvalidKeeperGracePeriod,
+
+    #[msg("MM's reputation or fill rate is below the required floor")]
+    MMBelowQualityFloor,
+
+    #[msg("Stress move is below the portfolio margin floor or above 100%")]
+    InvalidStressMove,
+
+    #[msg("Portfolio margin requires every open position in the MM's book, once each")]
+    MarginBookIncomplete,
+
+    #[msg("Spot moved beyond the asset's fill band since the intent was submitted")]
+    FillPriceMoved,
+
+    #[msg("Intent notional exceeds the protocol or asset per-intent cap")]
+    IntentNotionalCapExceeded,
+
+    #[msg("Intent fill timeout out of range")]
+    InvalidFillTimeout,
+
+    #[msg("Posted quote has fewer contracts left than requested")]
+    PostedQuoteExhausted,
+
+    #[msg("Posted quote can only be withdrawn by its MM while it is live")]
+    PostedQuoteLive,
+
+    #[msg("Quote grid is empty, too long or repeats a strike")]
+    InvalidQuoteLevels,
+
+    #[msg("Strike is not on the standing quote's grid")]
+    StrikeNotQuoted,
+
+    #[msg("Quoted premium is below the taker's minimum")]
+    PremiumBelowMinimum,
+
+    #[msg("Standing quote has fewer contracts left than requested")]
+    StandingQuoteExhausted,
+
+    #[msg("Series expiry is not on the expiry calendar or has passed")]
+    InvalidSeriesExpiry,
+
+    #[msg("Series strike is not on the asset's strike grid")]
+    InvalidSeriesStrike,
+
+    #[msg("Option series account is missing or does not match the position")]
+    SeriesMismatch,
+
+    #[msg("Resale ask has expired")]
+    AskExpired,
+
+    #[msg("Position has been resold; the original user's roll preference no longer applies")]
+    PositionResold,
+
+    #[msg("Installment plan count or interval out of range, or it runs past expiry")]
+    InvalidInstallmentPlan,
+
+    #[msg("Premium installment plan is not running")]
+    InstallmentPlanNotRunning,
+
+    #[msg("No premium installment is overdue")]
+    InstallmentNotOverdue,
+
+    #[msg("Vault round is active; deposits, withdrawals and new intents wait for it to finish")]
+    VaultRoundActive,
+
+    #[msg("Vault round has not finished")]
+    VaultRoundNotFinished,
+
+    #[msg("Vault's outstanding shares are worth nothing; it takes no new deposits")]
+    VaultNavDepleted,
+
+    #[msg("Exceeds the liquidity vault's utilization cap or idle balance")]
+    LiquidityUtilized,
+
+    #[msg("Draw would exceed the market maker's LP credit line")]
+    CreditLimitExceeded,
+
+    #[msg("Liquidity vault premium share, utilization cap or epoch out of range")]
+    InvalidLiquidityVaultConfig,
+
+    #[msg("Withdrawal epoch has not ended")]
+    LpEpochNotEnded,
+
+    #[msg("Vault management or performance fee above its cap")]
+    InvalidVaultFees,
+
+    #[msg("Vault deposit and withdrawal window is closed")]
+    VaultWindowClosed,
+
+    #[msg("Vault deposit and withdrawal window is still open")]
+    VaultWindowOpen,
+
+    #[msg("Deposit would take the vault past its deposit cap")]
+    DepositCapExceeded,
+
+    #[msg("Claimable queued withdrawals must be paid before the next round")]
+    WithdrawalsPending,
+
+    #[msg("Vault window length out of range")]
+    InvalidVaultWindow,
+
+    #[msg("Strategy is not allowed on this asset")]
+    StrategyNotAllowed,
+
+    #[msg("Strategy whitelist must allow at least one known strategy")]
+    InvalidStrategyMask,
+
+    #[msg("Minimum contract size is above the maximum")]
+    InvalidContractSizeBounds,
+
+    #[msg("Intent premium is below the protocol or asset premium floor")]
+    PremiumBelowFloor,
+
+    #[msg("Nonce bucket width is out of bounds")]
+    InvalidNonceBucket,
+
+    #[msg("Quote nonce is not in its expiry's time bucket")]
+    NonceOutsideBucket,
+
+    #[msg("Notional buys more contracts than the quote covers")]
+    NotionalExceedsQuote,
+
+    #[msg("Vault holds nothing to sweep")]
+    NoDustToSweep,
+
+    #[msg("User has too many pending intents")]
+    TooManyPendingIntents,
+
+    #[msg("Protocol is at its limit on pending intents")]
+    ProtocolSaturated,
+
+    #[msg("Dispute flag cooldown is out of bounds")]
+    InvalidDisputeCooldown,
+
+    #[msg("Party flagged a dispute too recently")]
+    DisputeCooldownActive,
+
+    #[msg("Frivolous ruling requires the dispute record, counterparty and treasury")]
+    DisputeBondAccountsRequired,
+
+    #[msg("Dispute has not been resolved")]
+    DisputeUnresolved,
+
+    #[msg("No dispute bond to reclaim")]
+    NoDisputeBond,
+
+    #[msg("Premium cannot be paid in the underlying for this intent")]
+    InvalidUnderlyingPremium,
+
+    #[msg("Underlying premium is worth less than the user accepts")]
+    UnderlyingPremiumShortfall,
+
+    #[msg("Quote peg check needs a deviation threshold between 1 and 10000 bps")]
+    InvalidQuotePegConfig,
+
+    #[msg("Settlement halted after the quote currency depegged")]
+    QuoteDepegged,
+
+    #[msg("Alternative quote mint is invalid or not listed")]
+    InvalidAltQuote,
+
+    #[msg("Asset already lists the maximum number of alternative quotes")]
+    AltQuotesFull,
+
+    #[msg("Position has not settled")]
+    PositionNotSettled,
+
+    #[msg("Invalid Wormhole account")]
+    InvalidWormholeAccount,
+
+    #[msg("Program is not an allowlisted hedge adapter")]
+    HedgeAdapterNotAllowed,
+
+    #[msg("Hedge adapter allowlist is full")]
+    HedgeAdapterAllowlistFull,
+
+    #[msg("Invalid hedge size or adapter accounts")]
+    InvalidHedge,
+
+    #[msg("Fair-value pricing requires an exchange rate account")]
+    InvalidPriceComposition,
+
+    #[msg("Invalid basket: weights and feeds must be set and distinct, settlement cash-only")]
+    InvalidBasket,
+
+    #[msg("Basket assets are priced from one price update per component")]
+    BasketPriceUpdatesRequired,
+
+    #[msg("Off-chain assets have no mint and list cash-settled puts only")]
+    InvalidOffchainAsset,
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::ErrorCode;
+use crate::utils::require_supported_mint;
 
 // Initialize global state
 #[derive(Accounts)]
@@ -31,11 +33,33 @@ pub fn handle_initialize_global_state(
     let global_state = &mut ctx.accounts.global_state;
 
     global_state.authority = ctx.accounts.authority.key();
+    global_state.pauser = ctx.accounts.authority.key();
+    global_state.dispute_resolver = ctx.accounts.authority.key();
+    global_state.arbiters = Vec::new();
+    global_state.arbitration_threshold = 0;
     global_state.treasury = ctx.accounts.treasury.key();
     global_state.protocol_fee_bps = protocol_fee_bps;
     global_state.paused = false;
     global_state.total_volume = 0;
     global_state.total_positions = 0;
+    global_state.settlement_dispute_window = 0;
+    global_state.enabled_operations = OPERATION_ALL;
+    global_state.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+    global_state.pending_change_count = 0;
+    global_state.total_open_notional = 0;
+    global_state.max_open_notional = 0;
+    global_state.max_intent_notional = 0;
+    global_state.intent_fill_timeout = DEFAULT_INTENT_FILL_TIMEOUT;
+    global_state.min_premium = 0;
+    global_state.max_pending_intents = 0;
+    global_state.total_pending_intents = 0;
+    global_state.max_total_pending_intents = 0;
+    global_state.dispute_flag_cooldown = 0;
+    global_state.dispute_bond = 0;
+    global_state.insurance_fee_share_bps = 0;
+    global_state.keeper_grace_period = 0;
+    global_state.min_mm_reputation = 0;
+    global_state.min_mm_fill_rate = 0;
     global_state.bump = ctx.bumps.global_state;
 
     msg!("Global state initialized with authority: {}", global_state.authority);
@@ -57,29 +81,96 @@ pub struct UpdateGlobalState<'info> {
     pub authority: Signer<'info>,
 }
 
+/// Parameters for updating global state (None = leave unchanged)
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct UpdateGlobalStateParams {
+    pub new_authority: Option<Pubkey>,
+    pub new_pauser: Option<Pubkey>,
+    pub new_dispute_resolver: Option<Pubkey>,
+    /// Pausing halts everything; unpausing goes through resume_protocol
+    pub paused: Option<bool>,
+    pub settlement_dispute_window: Option<i64>,
+    /// Protocol-wide open notional cap (0 = uncapped)
+    pub max_open_notional: Option<u64>,
+    pub max_intent_notional: Option<u64>,
+    pub intent_fill_timeout: Option<i64>,
+    pub min_premium: Option<u64>,
+    /// Pending intents per user (0 = uncapped)
+    pub max_pending_intents: Option<u32>,
+    pub max_total_pending_intents: Option<u64>,
+    pub dispute_flag_cooldown: Option<i64>,
+}
+
 pub fn handle_update_global_state(
     ctx: Context<UpdateGlobalState>,
-    new_authority: Option<Pubkey>,
-    new_treasury: Option<Pubkey>,
-    new_fee_bps: Option<u16>,
-    paused: Option<bool>,
-) -> Result<()> {
+    params: UpdateGlobalStateParams) -> Result<()> {
     let global_state = &mut ctx.accounts.global_state;
 
-    if let Some(auth) = new_authority {
+    if let Some(auth) = params.new_authority {
         global_state.authority = auth;
     }
 
-    if let Some(treasury) = new_treasury {
-        global_state.treasury = treasury;
+    if let Some(pauser) = params.new_pauser {
+        global_state.pauser = pauser;
+    }
+
+    if let Some(resolver) = params.new_dispute_resolver {
+        global_state.dispute_resolver = resolver;
+    }
+
+    // Pausing halts everything; unpausing is staged through resume_protocol
+    if let Some(pause) = params.paused {
+        if pause {
+            global_state.halt();
+        } else {
+            require!(!global_state.paused, ErrorCode::StagedResumeRequired);
+        }
+    }
+
+    if let Some(window) = params.settlement_dispute_window {
+        require!(
+            (0..=MAX_SETTLEMENT_DISPUTE_WINDOW).contains(&window),
+            ErrorCode::InvalidDisputeWindow
+        );
+        global_state.settlement_dispute_window = window;
+    }
+
+    // Lowering the cap below current open interest only blocks new fills
+    if let Some(cap) = params.max_open_notional {
+        global_state.max_open_notional = cap;
+    }
+
+    if let Some(cap) = params.max_intent_notional {
+        global_state.max_intent_notional = cap;
+    }
+
+    // Applies to intents submitted from now on; existing deadlines stand
+    if let Some(timeout) = params.intent_fill_timeout {
+        require!(
+            (MIN_INTENT_FILL_TIMEOUT..=MAX_INTENT_FILL_TIMEOUT).contains(&timeout),
+            ErrorCode::InvalidFillTimeout
+        );
+        global_state.intent_fill_timeout = timeout;
+    }
+
+    if let Some(floor) = params.min_premium {
+        global_state.min_premium = floor;
+    }
+
+    if let Some(cap) = params.max_pending_intents {
+        global_state.max_pending_intents = cap;
     }
 
-    if let Some(fee) = new_fee_bps {
-        global_state.protocol_fee_bps = fee;
+    if let Some(ceiling) = params.max_total_pending_intents {
+        global_state.max_total_pending_intents = ceiling;
     }
 
-    if let Some(pause) = paused {
-        global_state.paused = pause;
+    if let Some(cooldown) = params.dispute_flag_cooldown {
+        require!(
+            (0..=MAX_DISPUTE_FLAG_COOLDOWN).contains(&cooldown),
+            ErrorCode::InvalidDisputeCooldown
+        );
+        global_state.dispute_flag_cooldown = cooldown;
     }
 
     msg!("Global state updated");
@@ -89,7 +180,7 @@ pub fn handle_update_global_state(
 
 // Add asset configuration
 #[derive(Accounts)]
-#[instruction(asset_mint: Pubkey)]
+#[instruction(asset_mint: Pubkey, quote_mint: Pubkey)]
 pub struct AddAsset<'info> {
     #[account(
         seeds = [GLOBAL_STATE_SEED],
@@ -100,19 +191,32 @@ pub struct AddAsset<'info> {
 
     #[account(
         init,
-        payer = authority,
+        payer = payer,
         space = AssetConfig::LEN,
         seeds = [ASSET_CONFIG_SEED, asset_mint.as_ref()],
         bump
     )]
     pub asset_config: Account<'info, AssetConfig>,
 
-    #[account(mut)]
+    /// Underlying mint, checked for unsupported Token-2022 extensions; omitted
+    /// for an off-chain asset
+    #[account(address = asset_mint @ ErrorCode::InvalidMint)]
+    pub asset_mint_account: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Quote mint, read for its decimals
+    #[account(address = quote_mint @ ErrorCode::InvalidQuoteParameters)]
+    pub quote_mint_account: InterfaceAccount<'info, Mint>,
+
     pub authority: Signer<'info>,
 
+    /// Funds the AssetConfig rent, so a multisig authority never pays
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handle_add_asset(
     ctx: Context<AddAsset>,
     asset_mint: Pubkey,
@@ -123,6 +227,8 @@ pub fn handle_add_asset(
     min_expiry_seconds: i64,
     max_expiry_seconds: i64,
     decimals: u8,
+    allow_transfer_fee: bool,
+    asset_class: AssetClass,
 ) -> Result<()> {
     require!(
         min_strike_percentage < max_strike_percentage,
@@ -134,17 +240,72 @@ pub fn handle_add_asset(
         ErrorCode::InvalidExpiryRange
     );
 
+    // Token-2022 mints are listed only with the benign extension set, plus
+    // transfer fees when the asset opts into fee-aware accounting. An
+    // off-chain asset has no mint, only a market key
+    match (asset_class, ctx.accounts.asset_mint_account.as_ref()) {
+        (AssetClass::Token, Some(asset_mint_account)) => {
+            require_supported_mint(asset_mint_account, allow_transfer_fee)?
+        }
+        (AssetClass::OffChain, None) => {}
+        _ => return err!(ErrorCode::InvalidOffchainAsset),
+    }
+    require_supported_mint(&ctx.accounts.quote_mint_account, allow_transfer_fee)?;
+
     let asset_config = &mut ctx.accounts.asset_config;
 
     asset_config.asset_mint = asset_mint;
     asset_config.quote_mint = quote_mint;
     asset_config.pyth_feed_id = pyth_feed_id;
     asset_config.enabled = true;
+    asset_config.paused = false;
+    asset_config.delisted = false;
+    asset_config.open_positions = 0;
+    asset_config.open_notional = 0;
+    asset_config.max_open_notional = 0;
+    asset_config.max_intent_notional = 0;
     asset_config.min_strike_percentage = min_strike_percentage;
     asset_config.max_strike_percentage = max_strike_percentage;
     asset_config.min_expiry_seconds = min_expiry_seconds;
     asset_config.max_expiry_seconds = max_expiry_seconds;
     asset_config.decimals = decimals;
+    asset_config.quote_decimals = ctx.accounts.quote_mint_account.decimals;
+    asset_config.twap_window_seconds = 0;
+    asset_config.min_twap_observations = 0;
+    asset_config.max_confidence_bps = DEFAULT_MAX_CONFIDENCE_BPS;
+    asset_config.max_price_age_seconds = PYTH_STALENESS_THRESHOLD;
+    asset_config.oracle_source = OracleSource::Pyth;
+    asset_config.settlement_kind = SettlementKind::Cash;
+    asset_config.min_itm_bps = DEFAULT_MIN_ITM_BPS;
+    asset_config.switchboard_feed = Pubkey::default();
+    asset_config.chainlink_feed = Pubkey::default();
+    asset_config.price_exponent = DEFAULT_PRICE_EXPONENT;
+    asset_config.access_gate = AccessGate::Open;
+    asset_config.access_gate_key = Pubkey::default();
+    asset_config.allow_transfer_fee = allow_transfer_fee;
+    asset_config.lst_mint = Pubkey::default();
+    asset_config.lst_rate_source = LstRateSource::StakePool;
+    asset_config.lst_rate_account = Pubkey::default();
+    asset_config.max_fill_price_move_bps = 0;
+    asset_config.series_strike_step = 0;
+    asset_config.allowed_strategies = match asset_class {
+        AssetClass::Token => ALL_STRATEGIES,
+        AssetClass::OffChain => StrategyType::CashSecuredPut.mask(),
+    };
+    asset_config.min_contract_size = 0;
+    asset_config.max_contract_size = 0;
+    asset_config.min_premium = 0;
+    asset_config.lot_size = 0;
+    asset_config.underlying_premium = false;
+    asset_config.quote_pyth_feed_id = [0u8; 32];
+    asset_config.max_quote_depeg_bps = 0;
+    asset_config.quote_depegged = false;
+    asset_config.alt_quotes = [AltQuote::default(); MAX_ALT_QUOTE_MINTS];
+    asset_config.price_composition = PriceComposition::Direct;
+    asset_config.fair_value_rate_source = LstRateSource::StakePool;
+    asset_config.fair_value_rate_account = Pubkey::default();
+    asset_config.basket = [BasketComponent::default(); MAX_BASKET_COMPONENTS];
+    asset_config.asset_class = asset_class;
     asset_config.bump = ctx.bumps.asset_config;
 
     msg!("Asset added: {}", asset_mint);
@@ -172,37 +333,489 @@ pub struct UpdateAsset<'info> {
     pub authority: Signer<'info>,
 }
 
+/// Parameters for updating an asset (None = leave unchanged)
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct UpdateAssetParams {
+    pub enabled: Option<bool>,
+    pub twap_window_seconds: Option<i64>,
+    pub min_twap_observations: Option<u8>,
+    pub max_confidence_bps: Option<u16>,
+    pub max_price_age_seconds: Option<u64>,
+    pub oracle_source: Option<OracleSource>,
+    pub settlement_kind: Option<SettlementKind>,
+    pub min_itm_bps: Option<u16>,
+    pub switchboard_feed: Option<Pubkey>,
+    pub chainlink_feed: Option<Pubkey>,
+    pub price_exponent: Option<i32>,
+    pub max_open_notional: Option<u64>,
+    pub access_gate: Option<AccessGate>,
+    pub access_gate_key: Option<Pubkey>,
+    /// LST accepted as covered-call collateral (default key = none); must
+    /// share the underlying's decimals
+    pub lst_mint: Option<Pubkey>,
+    pub lst_rate_source: Option<LstRateSource>,
+    pub lst_rate_account: Option<Pubkey>,
+    pub max_fill_price_move_bps: Option<u16>,
+    pub series_strike_step: Option<u64>,
+    /// Strategy whitelist, one bit per StrategyType; open intents and
+    /// positions are unaffected
+    pub allowed_strategies: Option<u8>,
+    /// Whether MMs may pay premium in the underlying at oracle spot
+    pub underlying_premium: Option<bool>,
+    /// Pyth USD feed for the quote currency's peg check (zero = unchecked)
+    pub quote_pyth_feed_id: Option<[u8; 32]>,
+    /// Quote deviation from $1 that halts settlement, in bps
+    pub max_quote_depeg_bps: Option<u16>,
+    /// Clear (false) a settlement halt after a quote depeg
+    pub quote_depegged: Option<bool>,
+    /// Price a yield-bearing asset at fair value: the oracle feeds then quote
+    /// its base asset, scaled by the rate account's exchange rate
+    pub price_composition: Option<PriceComposition>,
+    pub fair_value_rate_source: Option<LstRateSource>,
+    pub fair_value_rate_account: Option<Pubkey>,
+}
+
 pub fn handle_update_asset(
     ctx: Context<UpdateAsset>,
-    enabled: Option<bool>,
-    min_strike_percentage: Option<u16>,
-    max_strike_percentage: Option<u16>,
-    min_expiry_seconds: Option<i64>,
-    max_expiry_seconds: Option<i64>,
+    params: UpdateAssetParams,
 ) -> Result<()> {
     let asset_config = &mut ctx.accounts.asset_config;
 
-    if let Some(e) = enabled {
+    if let Some(e) = params.enabled {
+        require!(!(e && asset_config.delisted), ErrorCode::AssetDelisted);
         asset_config.enabled = e;
     }
 
-    if let Some(min) = min_strike_percentage {
-        asset_config.min_strike_percentage = min;
+    if let Some(window) = params.twap_window_seconds {
+        require!(
+            (0..=MAX_TWAP_WINDOW_SECONDS).contains(&window),
+            ErrorCode::InvalidTwapConfig
+        );
+        asset_config.twap_window_seconds = window;
+    }
+
+    if let Some(min_obs) = params.min_twap_observations {
+        require!(
+            min_obs as usize <= MAX_SETTLEMENT_OBSERVATIONS,
+            ErrorCode::InvalidTwapConfig
+        );
+        asset_config.min_twap_observations = min_obs;
+    }
+
+    require!(
+        !asset_config.uses_twap() || asset_config.min_twap_observations > 0,
+        ErrorCode::InvalidTwapConfig
+    );
+
+    if let Some(max_conf) = params.max_confidence_bps {
+        require!(
+            max_conf > 0 && max_conf as u64 <= BASIS_POINTS_DIVISOR,
+            ErrorCode::InvalidOracleConfig
+        );
+        asset_config.max_confidence_bps = max_conf;
+    }
+
+    if let Some(max_age) = params.max_price_age_seconds {
+        require!(
+            max_age > 0 && max_age <= MAX_PYTH_STALENESS_THRESHOLD,
+            ErrorCode::InvalidOracleConfig
+        );
+        asset_config.max_price_age_seconds = max_age;
+    }
+
+    if let Some(feed) = params.switchboard_feed {
+        asset_config.switchboard_feed = feed;
+    }
+
+    if let Some(feed) = params.chainlink_feed {
+        asset_config.chainlink_feed = feed;
+    }
+
+    if let Some(source) = params.oracle_source {
+        asset_config.oracle_source = source;
+    }
+
+    if let Some(kind) = params.settlement_kind {
+        asset_config.settlement_kind = kind;
+    }
+
+    if let Some(min_itm) = params.min_itm_bps {
+        require!(
+            (min_itm as u64) < BASIS_POINTS_DIVISOR,
+            ErrorCode::InvalidPercentage
+        );
+        asset_config.min_itm_bps = min_itm;
     }
 
-    if let Some(max) = max_strike_percentage {
-        asset_config.max_strike_percentage = max;
+    if let Some(expo) = params.price_exponent {
+        require!(
+            (-(SWITCHBOARD_PRECISION as i32)..=0).contains(&expo),
+            ErrorCode::InvalidOracleConfig
+        );
+        asset_config.price_exponent = expo;
     }
 
-    if let Some(min) = min_expiry_seconds {
-        asset_config.min_expiry_seconds = min;
+    if let Some(cap) = params.max_open_notional {
+        asset_config.max_open_notional = cap;
     }
 
-    if let Some(max) = max_expiry_seconds {
-        asset_config.max_expiry_seconds = max;
+    if let Some(gate) = params.access_gate {
+        asset_config.access_gate = gate;
     }
 
+    if let Some(key) = params.access_gate_key {
+        asset_config.access_gate_key = key;
+    }
+
+    require!(
+        asset_config.has_valid_access_gate(),
+        ErrorCode::InvalidAccessGate
+    );
+
+    if let Some(mint) = params.lst_mint {
+        asset_config.lst_mint = mint;
+    }
+
+    if let Some(source) = params.lst_rate_source {
+        asset_config.lst_rate_source = source;
+    }
+
+    if let Some(account) = params.lst_rate_account {
+        asset_config.lst_rate_account = account;
+    }
+
+    if let Some(max_move) = params.max_fill_price_move_bps {
+        require!(
+            max_move as u64 <= BASIS_POINTS_DIVISOR,
+            ErrorCode::InvalidPercentage
+        );
+        asset_config.max_fill_price_move_bps = max_move;
+    }
+
+    // Existing series keep their strikes; the step only gates new listings
+    if let Some(step) = params.series_strike_step {
+        asset_config.series_strike_step = step;
+    }
+
+    if let Some(mask) = params.allowed_strategies {
+        require!(
+            mask != 0 && mask & !ALL_STRATEGIES == 0,
+            ErrorCode::InvalidStrategyMask
+        );
+        asset_config.allowed_strategies = mask;
+    }
+
+    if let Some(underlying_premium) = params.underlying_premium {
+        asset_config.underlying_premium = underlying_premium;
+    }
+
+    if let Some(feed_id) = params.quote_pyth_feed_id {
+        asset_config.quote_pyth_feed_id = feed_id;
+    }
+
+    if let Some(max_depeg_bps) = params.max_quote_depeg_bps {
+        asset_config.max_quote_depeg_bps = max_depeg_bps;
+    }
+
+    if let Some(depegged) = params.quote_depegged {
+        asset_config.quote_depegged = depegged;
+    }
+
+    if let Some(composition) = params.price_composition {
+        asset_config.price_composition = composition;
+    }
+
+    if let Some(source) = params.fair_value_rate_source {
+        asset_config.fair_value_rate_source = source;
+    }
+
+    if let Some(account) = params.fair_value_rate_account {
+        asset_config.fair_value_rate_account = account;
+    }
+
+    require!(
+        !asset_config.has_fair_value_price()
+            || asset_config.fair_value_rate_account != Pubkey::default(),
+        ErrorCode::InvalidPriceComposition
+    );
+
+    require!(
+        !asset_config.has_basket() || basket_compatible(asset_config),
+        ErrorCode::InvalidBasket
+    );
+
+    require!(
+        !asset_config.has_quote_peg_check()
+            || (1..=BASIS_POINTS_DIVISOR).contains(&(asset_config.max_quote_depeg_bps as u64)),
+        ErrorCode::InvalidQuotePegConfig
+    );
+
+    require!(
+        !asset_config.has_lst_collateral()
+            || (asset_config.lst_rate_account != Pubkey::default()
+                && asset_config.lst_mint != asset_config.asset_mint),
+        ErrorCode::InvalidLstConfig
+    );
+
+    require!(
+        asset_config.has_feed_for(asset_config.oracle_source),
+        ErrorCode::InvalidOracleConfig
+    );
+
+    require!(
+        asset_config.has_valid_asset_class(),
+        ErrorCode::InvalidOffchainAsset
+    );
+
     msg!("Asset updated: {}", asset_config.asset_mint);
 
     Ok(())
 }
+
+// List an alternative stablecoin quote for an asset, priced in USD by its own
+// Pyth feed; a zero feed ID delists it. Open positions already quoted in a
+// delisted stablecoin settle at the unrestated USD price.
+#[derive(Accounts)]
+pub struct SetAltQuote<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, asset_config.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Stablecoin to list; shares the primary quote's decimals so strike and
+    /// escrow math carry over unchanged
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handle_set_alt_quote(ctx: Context<SetAltQuote>, pyth_feed_id: [u8; 32]) -> Result<()> {
+    let mint = ctx.accounts.quote_mint.key();
+    let asset_config = &mut ctx.accounts.asset_config;
+    require!(
+        mint != asset_config.quote_mint && mint != asset_config.asset_mint,
+        ErrorCode::InvalidAltQuote
+    );
+
+    let existing = asset_config
+        .alt_quotes
+        .iter()
+        .position(|quote| !quote.is_empty() && quote.mint == mint);
+    if pyth_feed_id == [0u8; 32] {
+        let index = existing.ok_or(ErrorCode::InvalidAltQuote)?;
+        asset_config.alt_quotes[index] = AltQuote::default();
+        msg!("Alt quote removed: {}", mint);
+        return Ok(());
+    }
+
+    require_supported_mint(&ctx.accounts.quote_mint, asset_config.allow_transfer_fee)?;
+    require!(
+        ctx.accounts.quote_mint.decimals == asset_config.quote_decimals,
+        ErrorCode::InvalidAltQuote
+    );
+    let index = existing
+        .or_else(|| asset_config.alt_quotes.iter().position(AltQuote::is_empty))
+        .ok_or(ErrorCode::AltQuotesFull)?;
+    asset_config.alt_quotes[index] = AltQuote { mint, pyth_feed_id };
+
+    msg!("Alt quote set: {}", mint);
+
+    Ok(())
+}
+
+// Make an asset an index over a weighted basket of Pyth feeds, or (with no
+// components) return it to its single feed. Index assets settle in cash only.
+#[derive(Accounts)]
+pub struct SetBasket<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, asset_config.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handle_set_basket(ctx: Context<SetBasket>, components: Vec<BasketComponent>) -> Result<()> {
+    let asset_config = &mut ctx.accounts.asset_config;
+    require!(
+        components.len() <= MAX_BASKET_COMPONENTS,
+        ErrorCode::InvalidBasket
+    );
+    for (i, component) in components.iter().enumerate() {
+        require!(
+            component.weight > 0
+                && component.pyth_feed_id != [0u8; 32]
+                && components[..i]
+                    .iter()
+                    .all(|other| other.pyth_feed_id != component.pyth_feed_id),
+            ErrorCode::InvalidBasket
+        );
+    }
+
+    let mut basket = [BasketComponent::default(); MAX_BASKET_COMPONENTS];
+    basket[..components.len()].copy_from_slice(&components);
+    asset_config.basket = basket;
+    require!(
+        !asset_config.has_basket() || basket_compatible(asset_config),
+        ErrorCode::InvalidBasket
+    );
+
+    msg!(
+        "Basket set for {}: {} components",
+        asset_config.asset_mint,
+        components.len()
+    );
+
+    Ok(())
+}
+
+/// An index is cash-settled and priced from its basket alone
+fn basket_compatible(asset_config: &AssetConfig) -> bool {
+    asset_config.settlement_kind == SettlementKind::Cash && !asset_config.has_fair_value_price()
+}
+
+// Pause or unpause a single asset (pauser or authority)
+#[derive(Accounts)]
+pub struct SetAssetPaused<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = signer.key() == global_state.pauser
+            || signer.key() == global_state.authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, asset_config.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_set_asset_paused(ctx: Context<SetAssetPaused>, paused: bool) -> Result<()> {
+    let asset_config = &mut ctx.accounts.asset_config;
+    asset_config.paused = paused;
+
+    msg!("Asset {} paused: {}", asset_config.asset_mint, paused);
+
+    Ok(())
+}
+
+// Delist an asset: new intents are blocked, pending intents can still be
+// filled and open positions settle as normal
+#[derive(Accounts)]
+pub struct DelistAsset<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, asset_config.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.delisted @ ErrorCode::AssetDelisted
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handle_delist_asset(ctx: Context<DelistAsset>) -> Result<()> {
+    let asset_config = &mut ctx.accounts.asset_config;
+    asset_config.delisted = true;
+    asset_config.enabled = false;
+
+    msg!("Asset delisted: {} ({} open positions winding down)",
+         asset_config.asset_mint, asset_config.open_positions);
+
+    Ok(())
+}
+
+// Close a delisted asset's config once all its positions have settled
+#[derive(Accounts)]
+pub struct CloseAssetConfig<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, asset_config.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = asset_config.delisted && asset_config.open_positions == 0 @ ErrorCode::AssetStillActive,
+        close = authority
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handle_close_asset_config(ctx: Context<CloseAssetConfig>) -> Result<()> {
+    msg!("Asset config closed: {}", ctx.accounts.asset_config.asset_mint);
+
+    Ok(())
+}
+
+// Create the SOL pool that tips callers of expire_intent
+#[derive(Accounts)]
+pub struct InitializeTipVault<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = TipVault::LEN,
+        seeds = [TIP_VAULT_SEED],
+        bump
+    )]
+    pub tip_vault: Account<'info, TipVault>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays rent for the tip vault
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_tip_vault(ctx: Context<InitializeTipVault>) -> Result<()> {
+    let tip_vault = &mut ctx.accounts.tip_vault;
+    tip_vault.total_tipped = 0;
+    tip_vault.bump = ctx.bumps.tip_vault;
+
+    Ok(())
+}
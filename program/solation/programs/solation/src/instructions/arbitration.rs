@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+// ===== Events =====
+
+#[event]
+pub struct ArbitrationCommitteeUpdated {
+    pub arbiters: Vec<Pubkey>,
+    pub threshold: u8,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct ResolutionVoteCast {
+    pub intent_id: u64,
+    pub arbiter: Pubkey,
+    pub proposal: ResolutionProposal,
+    pub approvals: u8,
+    pub threshold: u8,
+}
+
+// ===== Set Arbitration Committee =====
+// A threshold of 0 turns committee mode off (single-key resolution)
+
+#[derive(Accounts)]
+pub struct SetArbitrationCommittee<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handle_set_arbitration_committee(
+    ctx: Context<SetArbitrationCommittee>,
+    arbiters: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(
+        arbiters.len() <= MAX_ARBITERS && threshold as usize <= arbiters.len(),
+        ErrorCode::InvalidArbitrationCommittee
+    );
+    let distinct = arbiters
+        .iter()
+        .enumerate()
+        .all(|(i, arbiter)| !arbiters[..i].contains(arbiter));
+    require!(distinct, ErrorCode::InvalidArbitrationCommittee);
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.arbiters = arbiters.clone();
+    global_state.arbitration_threshold = threshold;
+
+    emit!(ArbitrationCommitteeUpdated {
+        arbiters,
+        threshold,
+        updated_by: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+// ===== Vote Resolution =====
+// Arbiter approves a resolution for a disputed intent. The first vote sets
+// the proposal; later votes must match it.
+
+#[derive(Accounts)]
+pub struct VoteResolution<'info> {
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.committee_mode() @ ErrorCode::CommitteeModeDisabled,
+        constraint = global_state.is_arbiter(arbiter.key()) @ ErrorCode::NotAnArbiter
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        constraint = intent.can_be_resolved() @ ErrorCode::IntentNotResolvable
+    )]
+    pub intent: Account<'info, Intent>,
+
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        space = ResolutionVote::LEN,
+        seeds = [RESOLUTION_VOTE_SEED, intent.key().as_ref()],
+        bump
+    )]
+    pub resolution_vote: Account<'info, ResolutionVote>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_vote_resolution(
+    ctx: Context<VoteResolution>,
+    proposal: ResolutionProposal,
+) -> Result<()> {
+    let arbiter = ctx.accounts.arbiter.key();
+    let resolution_vote = &mut ctx.accounts.resolution_vote;
+
+    if resolution_vote.voters.is_empty() {
+        resolution_vote.intent = ctx.accounts.intent.key();
+        resolution_vote.proposal = proposal;
+        resolution_vote.executed = false;
+        resolution_vote.bump = ctx.bumps.resolution_vote;
+    }
+
+    require!(!resolution_vote.executed, ErrorCode::ResolutionAlreadyExecuted);
+    require!(
+        resolution_vote.proposal == proposal,
+        ErrorCode::ResolutionProposalMismatch
+    );
+    require!(
+        !resolution_vote.voters.contains(&arbiter),
+        ErrorCode::AlreadyVoted
+    );
+    resolution_vote.voters.push(arbiter);
+
+    let global_state = &ctx.accounts.global_state;
+    emit!(ResolutionVoteCast {
+        intent_id: ctx.accounts.intent.intent_id,
+        arbiter,
+        proposal,
+        approvals: resolution_vote.approvals(global_state) as u8,
+        threshold: global_state.arbitration_threshold,
+    });
+
+    Ok(())
+}
+
+/// In committee mode, an owner override only runs with a matching proposal
+/// approved by enough current arbiters; the vote is then spent.
+pub(crate) fn require_committee_approval(
+    global_state: &GlobalState,
+    resolution_vote: Option<&mut Account<ResolutionVote>>,
+    intent: Pubkey,
+    proposal: ResolutionProposal,
+) -> Result<()> {
+    if !global_state.committee_mode() {
+        return Ok(());
+    }
+
+    let resolution_vote = resolution_vote.ok_or(ErrorCode::CommitteeApprovalRequired)?;
+    require_keys_eq!(
+        resolution_vote.intent,
+        intent,
+        ErrorCode::CommitteeApprovalRequired
+    );
+    require!(!resolution_vote.executed, ErrorCode::ResolutionAlreadyExecuted);
+    require!(
+        resolution_vote.proposal == proposal,
+        ErrorCode::ResolutionProposalMismatch
+    );
+    require!(
+        resolution_vote.approvals(global_state) >= global_state.arbitration_threshold as usize,
+        ErrorCode::CommitteeApprovalRequired
+    );
+
+    resolution_vote.executed = true;
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::{
+    append_leaf, init_empty_merkle_tree, wrap_application_data, ACCOUNT_COMPRESSION_PROGRAM_ID,
+    NOOP_PROGRAM_ID,
+};
+
+/// Depth limits of the SPL concurrent Merkle tree
+const MIN_ARCHIVE_TREE_DEPTH: u32 = 3;
+const MAX_ARCHIVE_TREE_DEPTH: u32 = 30;
+
+// ===== Events =====
+
+#[event]
+pub struct PositionArchived {
+    pub position: Pubkey,
+    pub position_id: u64,
+    pub merkle_tree: Pubkey,
+    pub leaf_index: u64,
+    pub leaf: [u8; 32],
+}
+
+// ===== Initialize Position Archive =====
+// Authority binds a pre-allocated concurrent Merkle tree (owned by the
+// compression program) to the archive PDA, which becomes its tree authority
+
+#[derive(Accounts)]
+pub struct InitializePositionArchive<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PositionArchive::LEN,
+        seeds = [POSITION_ARCHIVE_SEED],
+        bump
+    )]
+    pub position_archive: Account<'info, PositionArchive>,
+
+    /// CHECK: Empty tree account, validated by the compression program
+    #[account(mut, owner = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub noop_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_position_archive(
+    ctx: Context<InitializePositionArchive>,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<()> {
+    require!(
+        (MIN_ARCHIVE_TREE_DEPTH..=MAX_ARCHIVE_TREE_DEPTH).contains(&max_depth),
+        ErrorCode::InvalidArchiveTree
+    );
+
+    let bump = ctx.bumps.position_archive;
+    let signer_seeds: &[&[&[u8]]] = &[&[POSITION_ARCHIVE_SEED, &[bump]]];
+    init_empty_merkle_tree(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &ctx.accounts.position_archive.to_account_info(),
+        &ctx.accounts.noop_program.to_account_info(),
+        max_depth,
+        max_buffer_size,
+        signer_seeds,
+    )?;
+
+    let position_archive = &mut ctx.accounts.position_archive;
+    position_archive.merkle_tree = ctx.accounts.merkle_tree.key();
+    position_archive.max_depth = max_depth;
+    position_archive.leaf_count = 0;
+    position_archive.bump = bump;
+
+    msg!(
+        "Position archive initialized: tree {}, depth {}",
+        position_archive.merkle_tree,
+        max_depth
+    );
+
+    Ok(())
+}
+
+// ===== Archive Position =====
+// Permissionless: commits a settled position's final record to the archive
+// tree and closes the account, returning its rent to the market maker
+
+#[derive(Accounts)]
+pub struct ArchivePosition<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_ARCHIVE_SEED],
+        bump = position_archive.bump,
+        constraint = position_archive.has_capacity() @ ErrorCode::ArchiveTreeFull
+    )]
+    pub position_archive: Account<'info, PositionArchive>,
+
+    /// CHECK: Tree bound to the archive
+    #[account(mut, address = position_archive.merkle_tree @ ErrorCode::InvalidArchiveTree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, position.user.as_ref(), &position.position_id.to_le_bytes()],
+        bump = position.bump,
+        constraint = position.is_archivable() @ ErrorCode::PositionNotArchivable,
+        close = rent_recipient
+    )]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: Market maker who funded the position account
+    #[account(mut, address = position.market_maker @ ErrorCode::Unauthorized)]
+    pub rent_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub noop_program: UncheckedAccount<'info>,
+}
+
+pub fn handle_archive_position(ctx: Context<ArchivePosition>) -> Result<()> {
+    let position = &ctx.accounts.position;
+    let record =
+        ArchivedPositionRecord::new(position.key(), position, Clock::get()?.unix_timestamp);
+    let leaf = record.leaf()?;
+
+    // Full record goes into the transaction log; only its hash is stored
+    wrap_application_data(
+        &ctx.accounts.noop_program.to_account_info(),
+        record.try_to_vec()?,
+    )?;
+
+    let bump = ctx.accounts.position_archive.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[POSITION_ARCHIVE_SEED, &[bump]]];
+    append_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &ctx.accounts.position_archive.to_account_info(),
+        &ctx.accounts.noop_program.to_account_info(),
+        leaf,
+        signer_seeds,
+    )?;
+
+    let position_archive = &mut ctx.accounts.position_archive;
+    let leaf_index = position_archive.leaf_count;
+    position_archive.leaf_count = leaf_index
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(PositionArchived {
+        position: record.position,
+        position_id: record.position_id,
+        merkle_tree: position_archive.merkle_tree,
+        leaf_index,
+        leaf,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,518 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::instructions::compliance::require_asset_access;
+use crate::instructions::keeper::{active_keeper, require_keeper};
+use crate::instructions::intent::{
+    calculate_escrow_amount, emit_intent_created, populate_intent, require_intent_notional,
+    require_listed_terms, require_mm_quality, verify_signed_quote, SubmitIntentParams,
+};
+use crate::instructions::settlement::{
+    calculate_settlement, check_quote_peg, get_asset_price, price_in_quote,
+    record_position_settled, transfer_from_position_vault, QuoteCheck,
+};
+use crate::state::*;
+use crate::utils::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+// ===== Events =====
+
+#[event]
+pub struct PositionRolled {
+    pub position_id: u64,
+    pub new_intent_id: u64,
+    pub user: Pubkey,
+    pub keeper: Pubkey,
+    pub settlement_price: u64,
+    pub rolled_amount: u64,
+}
+
+// ===== Create Roll Preference =====
+
+/// Constraints a rolled quote must satisfy
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct RollPreferenceParams {
+    pub strategy: StrategyType,
+    pub max_strike_distance_bps: u16,
+    pub min_premium_per_contract: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_mint: Pubkey)]
+pub struct CreateRollPreference<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        space = RollPreference::LEN,
+        seeds = [ROLL_PREFERENCE_SEED, user.key().as_ref(), asset_mint.as_ref()],
+        bump
+    )]
+    pub roll_preference: Account<'info, RollPreference>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_roll_preference(
+    ctx: Context<CreateRollPreference>,
+    asset_mint: Pubkey,
+    params: RollPreferenceParams,
+) -> Result<()> {
+    require!(
+        params.max_strike_distance_bps as u64 <= BASIS_POINTS_DIVISOR,
+        ErrorCode::InvalidPercentage
+    );
+
+    let roll_preference = &mut ctx.accounts.roll_preference;
+    roll_preference.user = ctx.accounts.user.key();
+    roll_preference.asset_mint = asset_mint;
+    roll_preference.strategy = params.strategy;
+    roll_preference.enabled = true;
+    roll_preference.max_strike_distance_bps = params.max_strike_distance_bps;
+    roll_preference.min_premium_per_contract = params.min_premium_per_contract;
+    roll_preference.total_rolls = 0;
+    roll_preference.bump = ctx.bumps.roll_preference;
+
+    Ok(())
+}
+
+// ===== Update Roll Preference =====
+
+#[derive(Accounts)]
+pub struct UpdateRollPreference<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ROLL_PREFERENCE_SEED, user.key().as_ref(), roll_preference.asset_mint.as_ref()],
+        bump = roll_preference.bump,
+        constraint = roll_preference.user == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub roll_preference: Account<'info, RollPreference>,
+}
+
+pub fn handle_update_roll_preference(
+    ctx: Context<UpdateRollPreference>,
+    enabled: Option<bool>,
+    params: Option<RollPreferenceParams>,
+) -> Result<()> {
+    let roll_preference = &mut ctx.accounts.roll_preference;
+
+    if let Some(e) = enabled {
+        roll_preference.enabled = e;
+    }
+
+    if let Some(p) = params {
+        require!(
+            p.max_strike_distance_bps as u64 <= BASIS_POINTS_DIVISOR,
+            ErrorCode::InvalidPercentage
+        );
+        roll_preference.strategy = p.strategy;
+        roll_preference.max_strike_distance_bps = p.max_strike_distance_bps;
+        roll_preference.min_premium_per_contract = p.min_premium_per_contract;
+    }
+
+    Ok(())
+}
+
+// ===== Roll Position =====
+// Settle an expired position and re-open the user's collateral into a new
+// intent against a fresh MM quote, without the user signing.
+
+#[derive(Accounts)]
+#[instruction(params: SubmitIntentParams)]
+pub struct RollPosition<'info> {
+    /// Keeper cranking the roll (pays rent for the new intent)
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// Caller's keeper registration; required while cranks are reserved for keepers
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, keeper.key().as_ref()],
+        bump = registered_keeper.bump
+    )]
+    pub registered_keeper: Option<Account<'info, Keeper>>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_SETTLEMENT | OPERATION_NEW_INTENTS) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive,
+        constraint = !position.is_resold() @ ErrorCode::PositionResold
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused,
+        constraint = !asset_config.quote_depegged @ ErrorCode::QuoteDepegged
+    )]
+    pub asset_config: Box<Account<'info, AssetConfig>>,
+
+    #[account(
+        mut,
+        seeds = [ROLL_PREFERENCE_SEED, position.user.as_ref(), position.asset_mint.as_ref()],
+        bump = roll_preference.bump,
+        constraint = roll_preference.enabled @ ErrorCode::AutoRollDisabled
+    )]
+    pub roll_preference: Box<Account<'info, RollPreference>>,
+
+    /// Position user's lifetime stats
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, position.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    /// Registry of the rolled position's MM (for stats tracking)
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, position.market_maker.as_ref()],
+        bump = position_mm_registry.bump
+    )]
+    pub position_mm_registry: Box<Account<'info, MMRegistry>>,
+
+    /// Position's user vault (user's locked collateral)
+    #[account(
+        mut,
+        constraint = position_user_vault.key() == position.user_vault @ ErrorCode::InvalidVault
+    )]
+    pub position_user_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Mint of the position vault, which the rolled intent escrows in turn
+    #[account(
+        address = position_user_vault.mint @ ErrorCode::InvalidMint,
+        constraint = asset_config.is_collateral_mint(params.strategy, params.quote_mint, vault_mint.key())
+            @ ErrorCode::InvalidCollateralMint
+    )]
+    pub vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA authority for position vaults
+    #[account(
+        seeds = [POSITION_SEED, position.user.as_ref(), &position.position_id.to_le_bytes()],
+        bump = position.bump
+    )]
+    pub position_authority: AccountInfo<'info>,
+
+    /// User's destination for any payout not rolled into the new intent
+    #[account(
+        mut,
+        constraint = user_destination.owner == position.user_payout_owner()
+    )]
+    pub user_destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// MM's destination token account
+    #[account(
+        mut,
+        constraint = mm_destination.owner == position.mm_payout_owner()
+    )]
+    pub mm_destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Oracle feeds for the asset's configured source
+    pub oracle: OracleAccounts<'info>,
+
+    /// Pyth update for the quote currency, required when the asset checks
+    /// its quote peg
+    pub quote_price_update: Option<Account<'info, PriceUpdateV2>>,
+
+    /// Registry of the MM quoting the new intent
+    #[account(
+        seeds = [MM_REGISTRY_SEED, mm_registry.owner.as_ref()],
+        bump = mm_registry.bump,
+        constraint = mm_registry.active @ ErrorCode::MMNotActive
+    )]
+    pub mm_registry: Box<Account<'info, MMRegistry>>,
+
+    /// Compliance blocklist, covering both the rolled position and the quoting MM
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.blocks_either_side(&position) @ ErrorCode::AddressBlocked,
+        constraint = !blocklist.is_blocked(mm_registry.owner) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Box<Account<'info, Blocklist>>,
+
+    /// Nonce tracker for the quoting MM
+    #[account(
+        mut,
+        seeds = [NONCE_TRACKER_SEED, mm_registry.owner.as_ref()],
+        bump = nonce_tracker.load()?.bump
+    )]
+    pub nonce_tracker: AccountLoader<'info, NonceTracker>,
+
+    /// The rolled intent, owned by the position's user
+    #[account(
+        init,
+        payer = keeper,
+        space = Intent::LEN,
+        seeds = [INTENT_SEED, position.user.as_ref(), &params.intent_id.to_le_bytes()],
+        bump
+    )]
+    pub intent: Box<Account<'info, Intent>>,
+
+    /// Escrow for the rolled intent, in the old position's collateral mint
+    #[account(
+        init,
+        payer = keeper,
+        token::mint = vault_mint,
+        token::authority = intent,
+        seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
+        bump
+    )]
+    pub user_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// User's KYC pass token account, for assets gated on a pass mint
+    pub access_pass: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// User's credential, for assets gated on a credential issuer
+    pub user_credential: Option<Box<Account<'info, UserCredential>>>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// Series the position belongs to; required when it joined one
+    #[account(mut)]
+    pub option_series: Option<Account<'info, OptionSeries>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle_roll_position(ctx: Context<RollPosition>, params: SubmitIntentParams) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // With a dispute window or TWAP pricing, settlement must go through snapshot/distribute
+    require!(
+        ctx.accounts.global_state.settlement_dispute_window == 0
+            && !ctx.accounts.asset_config.uses_twap(),
+        ErrorCode::SettlementRequiresSnapshot
+    );
+
+    // 1. Position must be expired
+    require!(
+        clock.unix_timestamp >= ctx.accounts.position.expiry_timestamp,
+        ErrorCode::PositionNotExpired
+    );
+    require_keeper(
+        &ctx.accounts.global_state,
+        ctx.accounts.registered_keeper.as_ref(),
+        ctx.accounts.position.expiry_timestamp,
+        clock.unix_timestamp,
+    )?;
+
+    // A depeg halts rolls like any settlement. The roll opens accounts, so it
+    // cannot commit the halt itself and fails instead; settle_position or
+    // snapshot_settlement_price records it
+    let quote_price = match check_quote_peg(
+        &mut ctx.accounts.asset_config,
+        ctx.accounts.position.quote_mint,
+        ctx.accounts.quote_price_update.as_ref(),
+        ctx.accounts.keeper.key(),
+        &clock,
+    )? {
+        QuoteCheck::Settle(quote_price) => quote_price,
+        QuoteCheck::Halted => return err!(ErrorCode::QuoteDepegged),
+    };
+
+    // 2. Settle at the oracle price (Switchboard fallback, or an index's
+    // component updates in remaining_accounts), restated in the position's
+    // quote; the rolled strike is bounded against the same price
+    let settlement_price = get_asset_price(
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        &ctx.accounts.asset_config,
+        &clock,
+    )?;
+    let settlement_price = price_in_quote(settlement_price, quote_price)?;
+
+    // An LST vault is split (and the rolled escrow sized) at the current rate
+    let terms = settlement_terms_for_vault(
+        &ctx.accounts.asset_config,
+        ctx.accounts.vault_mint.key(),
+        ctx.accounts.oracle.lst_rate.as_ref(),
+        &clock,
+    )?;
+    let position = &ctx.accounts.position;
+    let (user_amount, mm_amount, status) = calculate_settlement(
+        position.strategy,
+        terms,
+        settlement_price,
+        position.strike_price,
+        position.contract_size,
+        ctx.accounts.position_user_vault.amount,
+    )?;
+    require!(
+        !(ctx.accounts.asset_config.settlement_kind == SettlementKind::Physical
+            && status == PositionStatus::SettledITM),
+        ErrorCode::PhysicalDeliveryRequired
+    );
+
+    // 3. New quote must match the standing preference, and keep the
+    // position's strategy so its collateral can be re-escrowed as is
+    let roll_preference = &ctx.accounts.roll_preference;
+    require!(
+        params.asset_mint == position.asset_mint
+            && params.quote_mint == position.quote_mint
+            && params.strategy == position.strategy
+            && params.strategy == roll_preference.strategy,
+        ErrorCode::RollConstraintViolated
+    );
+    require!(
+        roll_preference.strike_within_bounds(params.strike_price, settlement_price),
+        ErrorCode::RollConstraintViolated
+    );
+    require!(
+        params.premium_per_contract >= roll_preference.min_premium_per_contract,
+        ErrorCode::RollConstraintViolated
+    );
+
+    // The rolled quote is held to the asset's listing like a fresh one
+    require_listed_terms(
+        &ctx.accounts.asset_config,
+        &params,
+        settlement_price,
+        clock.unix_timestamp,
+    )?;
+    require_intent_notional(
+        &ctx.accounts.global_state,
+        &ctx.accounts.asset_config,
+        &params,
+    )?;
+
+    // Rolls are held to the protocol's MM quality floor too
+    require_mm_quality(&ctx.accounts.global_state, &ctx.accounts.mm_registry, &params)?;
+
+    // The rolled intent must still pass the asset's access gate
+    require_asset_access(
+        &ctx.accounts.asset_config,
+        position.user,
+        ctx.accounts.access_pass.as_deref(),
+        ctx.accounts.user_credential.as_deref(),
+        clock.unix_timestamp,
+    )?;
+
+    verify_signed_quote(
+        &params,
+        &ctx.accounts.mm_registry,
+        &mut *ctx.accounts.nonce_tracker.load_mut()?,
+        &ctx.accounts.instructions_sysvar,
+        clock.unix_timestamp,
+    )?;
+
+    // 4. Rolled escrow comes out of the user's settlement payout
+    let escrow_amount = calculate_escrow_amount(
+        params.strategy,
+        params.strike_price,
+        params.contract_size,
+        &terms,
+    )?;
+    // Any mint transfer fee on funding the new escrow comes out of the user's share
+    let escrow_funding = gross_up_for_fee(&ctx.accounts.vault_mint, escrow_amount)?;
+    require!(escrow_funding <= user_amount, ErrorCode::InsufficientRollCollateral);
+    let user_remainder = user_amount - escrow_funding;
+
+    // 5. Distribute: MM share, rolled escrow, leftover to user
+    let position = &ctx.accounts.position;
+    let vault_amount = ctx.accounts.position_user_vault.amount;
+    transfer_from_position_vault(
+        position,
+        &ctx.accounts.position_user_vault,
+        &ctx.accounts.vault_mint,
+        &ctx.accounts.mm_destination,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_program,
+        mm_amount,
+    )?;
+    transfer_from_position_vault(
+        position,
+        &ctx.accounts.position_user_vault,
+        &ctx.accounts.vault_mint,
+        &ctx.accounts.user_escrow,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_program,
+        escrow_funding,
+    )?;
+    transfer_from_position_vault(
+        position,
+        &ctx.accounts.position_user_vault,
+        &ctx.accounts.vault_mint,
+        &ctx.accounts.user_destination,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_program,
+        user_remainder,
+    )?;
+
+    // 6. Close out the old position. The rolled escrow is still the user's:
+    // it counts as paid out of the old position
+    let position = &mut ctx.accounts.position;
+    position.settlement_price = Some(settlement_price);
+    position.status = status;
+    record_position_settled(
+        position,
+        vault_amount,
+        user_amount,
+        mm_amount,
+        ctx.accounts.keeper.key(),
+        &mut ctx.accounts.asset_config,
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.position_mm_registry,
+        ctx.accounts.option_series.as_mut(),
+    )?;
+    ctx.accounts
+        .user_stats
+        .record_intent_submitted(ctx.accounts.global_state.max_pending_intents)?;
+    ctx.accounts.global_state.record_intent_opened()?;
+
+    // 7. Open the rolled intent on the user's behalf
+    let intent = &mut ctx.accounts.intent;
+    populate_intent(
+        intent,
+        &params,
+        position.user,
+        ctx.accounts.mm_registry.owner,
+        ctx.accounts.user_escrow.key(),
+        escrow_amount,
+        ctx.accounts.global_state.intent_fill_timeout,
+        clock.unix_timestamp,
+    );
+    intent.reference_price = settlement_price;
+    intent.bump = ctx.bumps.intent;
+
+    let roll_preference = &mut ctx.accounts.roll_preference;
+    roll_preference.total_rolls = roll_preference.total_rolls.saturating_add(1);
+    if let Some(keeper) = active_keeper(&mut ctx.accounts.registered_keeper) {
+        keeper.record_settlements(1, clock.unix_timestamp);
+    }
+
+    emit_intent_created(intent)?;
+
+    emit!(PositionRolled {
+        position_id: position.position_id,
+        new_intent_id: intent.intent_id,
+        user: position.user,
+        keeper: ctx.accounts.keeper.key(),
+        settlement_price,
+        rolled_amount: escrow_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,334 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::instructions::settlement::transfer_from_position_vault;
+use crate::state::*;
+
+// ===== Events =====
+
+#[event]
+pub struct BlocklistUpdated {
+    pub address: Pubkey,
+    pub blocked: bool,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct PayoutWithheld {
+    pub position_id: u64,
+    pub party: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct WithheldPayoutClaimed {
+    pub position_id: u64,
+    pub party: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CredentialIssued {
+    pub issuer: Pubkey,
+    pub user: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct CredentialRevoked {
+    pub issuer: Pubkey,
+    pub user: Pubkey,
+}
+
+// ===== Initialize Blocklist =====
+
+#[derive(Accounts)]
+pub struct InitializeBlocklist<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Blocklist::LEN,
+        seeds = [BLOCKLIST_SEED],
+        bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_blocklist(ctx: Context<InitializeBlocklist>) -> Result<()> {
+    let blocklist = &mut ctx.accounts.blocklist;
+    blocklist.addresses = Vec::new();
+    blocklist.bump = ctx.bumps.blocklist;
+
+    Ok(())
+}
+
+// ===== Update Blocklist =====
+
+#[derive(Accounts)]
+pub struct UpdateBlocklist<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+}
+
+pub fn handle_update_blocklist(
+    ctx: Context<UpdateBlocklist>,
+    address: Pubkey,
+    blocked: bool,
+) -> Result<()> {
+    let blocklist = &mut ctx.accounts.blocklist;
+
+    if blocked {
+        if !blocklist.is_blocked(address) {
+            require!(
+                blocklist.addresses.len() < MAX_BLOCKLIST_ENTRIES,
+                ErrorCode::BlocklistFull
+            );
+            blocklist.addresses.push(address);
+        }
+    } else {
+        blocklist.addresses.retain(|key| *key != address);
+    }
+
+    emit!(BlocklistUpdated {
+        address,
+        blocked,
+        updated_by: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+// ===== Claim Withheld Payout =====
+// A party whose settlement payout was withheld claims it once compliance
+// review has cleared them (and their beneficiary) from the blocklist
+
+#[derive(Accounts)]
+pub struct ClaimWithheldPayout<'info> {
+    pub claimant: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_SETTLEMENT) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(mut)]
+    pub position: Account<'info, Position>,
+
+    /// Position's user vault, where withheld payouts are left
+    #[account(
+        mut,
+        constraint = position_user_vault.key() == position.user_vault @ ErrorCode::InvalidVault
+    )]
+    pub position_user_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the position vault
+    #[account(address = position_user_vault.mint @ ErrorCode::InvalidMint)]
+    pub vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA authority for position vaults
+    #[account(
+        seeds = [POSITION_SEED, position.user.as_ref(), &position.position_id.to_le_bytes()],
+        bump = position.bump
+    )]
+    pub position_authority: AccountInfo<'info>,
+
+    /// Claimant's payout account (or their beneficiary's)
+    #[account(
+        mut,
+        constraint = destination.mint == position_user_vault.mint @ ErrorCode::InvalidVault
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_claim_withheld_payout(ctx: Context<ClaimWithheldPayout>) -> Result<()> {
+    let claimant = ctx.accounts.claimant.key();
+    let blocklist = &ctx.accounts.blocklist;
+    let position = &mut ctx.accounts.position;
+
+    // The user side takes precedence when one key is on both sides
+    let user_side = claimant == position.holder && position.user_payout_withheld > 0;
+    let (amount, payout_owner, still_blocked) = if user_side {
+        (
+            position.user_payout_withheld,
+            position.user_payout_owner(),
+            blocklist.blocks_user_side(position),
+        )
+    } else if claimant == position.market_maker {
+        (
+            position.mm_payout_withheld,
+            position.mm_payout_owner(),
+            blocklist.blocks_mm_side(position),
+        )
+    } else {
+        return err!(ErrorCode::NoWithheldPayout);
+    };
+
+    require!(amount > 0, ErrorCode::NoWithheldPayout);
+    require!(!still_blocked, ErrorCode::AddressBlocked);
+    require!(
+        ctx.accounts.destination.owner == payout_owner,
+        ErrorCode::InvalidVault
+    );
+
+    transfer_from_position_vault(
+        position,
+        &ctx.accounts.position_user_vault,
+        &ctx.accounts.vault_mint,
+        &ctx.accounts.destination,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    if user_side {
+        position.user_payout_withheld = 0;
+    } else {
+        position.mm_payout_withheld = 0;
+    }
+
+    emit!(WithheldPayoutClaimed {
+        position_id: position.position_id,
+        party: claimant,
+        destination: ctx.accounts.destination.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+// ===== Issue Credential =====
+// Any key can attest credentials under its own issuer seed; an asset only
+// accepts the issuer named in its access gate
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct IssueCredential<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = UserCredential::LEN,
+        seeds = [USER_CREDENTIAL_SEED, issuer.key().as_ref(), user.as_ref()],
+        bump
+    )]
+    pub user_credential: Account<'info, UserCredential>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_issue_credential(
+    ctx: Context<IssueCredential>,
+    user: Pubkey,
+    expires_at: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        expires_at == 0 || expires_at > clock.unix_timestamp,
+        ErrorCode::InvalidCredentialExpiry
+    );
+
+    // Re-issuing renews the expiry
+    let user_credential = &mut ctx.accounts.user_credential;
+    user_credential.issuer = ctx.accounts.issuer.key();
+    user_credential.user = user;
+    user_credential.issued_at = clock.unix_timestamp;
+    user_credential.expires_at = expires_at;
+    user_credential.bump = ctx.bumps.user_credential;
+
+    emit!(CredentialIssued {
+        issuer: user_credential.issuer,
+        user,
+        expires_at,
+    });
+
+    Ok(())
+}
+
+// ===== Revoke Credential =====
+
+#[derive(Accounts)]
+pub struct RevokeCredential<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = issuer,
+        seeds = [USER_CREDENTIAL_SEED, issuer.key().as_ref(), user_credential.user.as_ref()],
+        bump = user_credential.bump
+    )]
+    pub user_credential: Account<'info, UserCredential>,
+}
+
+pub fn handle_revoke_credential(ctx: Context<RevokeCredential>) -> Result<()> {
+    emit!(CredentialRevoked {
+        issuer: ctx.accounts.issuer.key(),
+        user: ctx.accounts.user_credential.user,
+    });
+
+    Ok(())
+}
+
+/// Check `user` against the asset's access gate. Permissioned assets need
+/// either a pass token account or a credential from the configured issuer.
+pub(crate) fn require_asset_access(
+    asset_config: &AssetConfig,
+    user: Pubkey,
+    access_pass: Option<&InterfaceAccount<TokenAccount>>,
+    user_credential: Option<&Account<UserCredential>>,
+    now: i64,
+) -> Result<()> {
+    let granted = match asset_config.access_gate {
+        AccessGate::Open => true,
+        AccessGate::PassToken => access_pass.is_some_and(|pass| {
+            pass.owner == user && pass.mint == asset_config.access_gate_key && pass.amount > 0
+        }),
+        AccessGate::Credential => user_credential.is_some_and(|credential| {
+            credential.user == user
+                && credential.issuer == asset_config.access_gate_key
+                && credential.is_valid_at(now)
+        }),
+    };
+    require!(granted, ErrorCode::AccessDenied);
+    Ok(())
+}
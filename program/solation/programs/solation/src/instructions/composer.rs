@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+// ===== Events =====
+
+#[event]
+pub struct ComposerAllowlistUpdated {
+    pub program_id: Pubkey,
+    pub allowed: bool,
+    pub updated_by: Pubkey,
+}
+
+// ===== Initialize Composer Allowlist =====
+
+#[derive(Accounts)]
+pub struct InitializeComposerAllowlist<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ComposerAllowlist::LEN,
+        seeds = [COMPOSER_ALLOWLIST_SEED],
+        bump
+    )]
+    pub composer_allowlist: Account<'info, ComposerAllowlist>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_composer_allowlist(
+    ctx: Context<InitializeComposerAllowlist>,
+) -> Result<()> {
+    let composer_allowlist = &mut ctx.accounts.composer_allowlist;
+    composer_allowlist.programs = Vec::new();
+    composer_allowlist.bump = ctx.bumps.composer_allowlist;
+
+    Ok(())
+}
+
+// ===== Update Composer Allowlist =====
+
+#[derive(Accounts)]
+pub struct UpdateComposerAllowlist<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [COMPOSER_ALLOWLIST_SEED],
+        bump = composer_allowlist.bump
+    )]
+    pub composer_allowlist: Account<'info, ComposerAllowlist>,
+}
+
+pub fn handle_update_composer_allowlist(
+    ctx: Context<UpdateComposerAllowlist>,
+    program_id: Pubkey,
+    allowed: bool,
+) -> Result<()> {
+    let composer_allowlist = &mut ctx.accounts.composer_allowlist;
+
+    if allowed {
+        if !composer_allowlist.is_allowed(program_id) {
+            require!(
+                composer_allowlist.programs.len() < MAX_COMPOSERS,
+                ErrorCode::ComposerAllowlistFull
+            );
+            composer_allowlist.programs.push(program_id);
+        }
+    } else {
+        composer_allowlist.programs.retain(|key| *key != program_id);
+    }
+
+    emit!(ComposerAllowlistUpdated {
+        program_id,
+        allowed,
+        updated_by: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
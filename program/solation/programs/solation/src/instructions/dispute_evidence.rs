@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+// ===== Events =====
+
+#[event]
+pub struct DisputeEvidenceSubmitted {
+    pub intent_id: u64,
+    pub submitted_by: Pubkey,
+    pub entry_index: u8,
+    pub content_hash: [u8; 32],
+    pub uri: String,
+}
+
+#[event]
+pub struct DisputeBondReclaimed {
+    pub intent_id: u64,
+    pub flagged_by: Pubkey,
+    pub amount: u64,
+}
+
+// ===== Submit Dispute Evidence =====
+// User or MM appends an evidence reference while the intent is still disputed
+
+#[derive(Accounts)]
+pub struct SubmitDisputeEvidence<'info> {
+    pub party: Signer<'info>,
+
+    #[account(
+        constraint = intent.is_disputed() @ ErrorCode::IntentNotDisputed
+    )]
+    pub intent: Account<'info, Intent>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_RECORD_SEED, intent.key().as_ref()],
+        bump = dispute_record.bump,
+        constraint = dispute_record.is_party(party.key()) @ ErrorCode::UnauthorizedDispute
+    )]
+    pub dispute_record: Account<'info, DisputeRecord>,
+}
+
+pub fn handle_submit_dispute_evidence(
+    ctx: Context<SubmitDisputeEvidence>,
+    content_hash: [u8; 32],
+    uri: String,
+) -> Result<()> {
+    require!(
+        uri.len() <= MAX_EVIDENCE_URI_LEN,
+        ErrorCode::EvidenceUriTooLong
+    );
+
+    let party = ctx.accounts.party.key();
+    let dispute_record = &mut ctx.accounts.dispute_record;
+    require!(
+        dispute_record.can_submit(party),
+        ErrorCode::EvidenceLimitReached
+    );
+
+    let entry_index = dispute_record.entries.len() as u8;
+    dispute_record.entries.push(EvidenceEntry {
+        submitted_by: party,
+        content_hash,
+        uri: uri.clone(),
+        submitted_at: Clock::get()?.unix_timestamp,
+    });
+
+    emit!(DisputeEvidenceSubmitted {
+        intent_id: ctx.accounts.intent.intent_id,
+        submitted_by: party,
+        entry_index,
+        content_hash,
+        uri,
+    });
+
+    Ok(())
+}
+
+// ===== Reclaim Dispute Bond =====
+// Once the dispute is resolved, the flagger takes back a bond the resolver
+// did not forfeit
+
+#[derive(Accounts)]
+pub struct ReclaimDisputeBond<'info> {
+    #[account(mut)]
+    pub flagger: Signer<'info>,
+
+    #[account(
+        constraint = intent.resolution.is_some() @ ErrorCode::DisputeUnresolved
+    )]
+    pub intent: Account<'info, Intent>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_RECORD_SEED, intent.key().as_ref()],
+        bump = dispute_record.bump,
+        constraint = dispute_record.flagged_by == flagger.key() @ ErrorCode::Unauthorized
+    )]
+    pub dispute_record: Account<'info, DisputeRecord>,
+}
+
+pub fn handle_reclaim_dispute_bond(ctx: Context<ReclaimDisputeBond>) -> Result<()> {
+    let dispute_record = &mut ctx.accounts.dispute_record;
+    let amount = dispute_record.bond;
+    require!(amount > 0, ErrorCode::NoDisputeBond);
+
+    dispute_record.bond = 0;
+    dispute_record.to_account_info().sub_lamports(amount)?;
+    ctx.accounts.flagger.add_lamports(amount)?;
+
+    emit!(DisputeBondReclaimed {
+        intent_id: ctx.accounts.intent.intent_id,
+        flagged_by: ctx.accounts.flagger.key(),
+        amount,
+    });
+
+    Ok(())
+}
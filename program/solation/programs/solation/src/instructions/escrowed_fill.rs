@@ -0,0 +1,1224 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::instructions::intent::{
+    hand_escrow_to_position, open_position, require_fill_price_band, IntentFilled,
+};
+use crate::instructions::settlement::{
+    get_asset_price, physical_delivery_amount, split_basket_updates, transfer_from_position_vault,
+};
+use crate::state::*;
+use crate::utils::*;
+
+// ===== Events =====
+
+#[event]
+pub struct EscrowedFillStarted {
+    pub intent_id: u64,
+    pub market_maker: Pubkey,
+    pub premium: u64,
+    pub collateral_mint: Pubkey,
+    pub collateral_amount: u64,
+    pub complete_by: i64,
+}
+
+#[event]
+pub struct EscrowedFillUnwound {
+    pub intent_id: u64,
+    pub market_maker: Pubkey,
+    pub premium_returned: u64,
+    pub timed_out: bool,
+}
+
+#[event]
+pub struct MmCollateralReleased {
+    pub position_id: u64,
+    pub market_maker: Pubkey,
+    pub amount: u64,
+    /// Left in the MM vault for the rest of a portfolio-margined book
+    pub held_back: u64,
+}
+
+// ===== Begin Escrowed Fill =====
+// Optional alternative to fill_intent: the MM's premium lands in a program
+// escrow first, and the position only opens once the MM has also locked its
+// collateral (complete_escrowed_fill). Until then the intent cannot be filled
+// or cancelled; unwind_escrowed_fill backs the first leg out.
+
+#[derive(Accounts)]
+pub struct BeginEscrowedFill<'info> {
+    #[account(mut)]
+    pub market_maker: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_FILLS) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        constraint = intent.is_pending() @ ErrorCode::IntentNotPending,
+        constraint = intent.market_maker == market_maker.key() @ ErrorCode::UnauthorizedFill
+    )]
+    pub intent: Account<'info, Intent>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, intent.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    #[account(
+        seeds = [MM_REGISTRY_SEED, market_maker.key().as_ref()],
+        bump = mm_registry.bump,
+        constraint = mm_registry.active @ ErrorCode::MMNotActive
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    /// Compliance blocklist
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.is_blocked(market_maker.key()) @ ErrorCode::AddressBlocked,
+        constraint = !blocklist.is_blocked(intent.user) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        init,
+        payer = market_maker,
+        space = FillEscrow::LEN,
+        seeds = [FILL_ESCROW_SEED, intent.key().as_ref()],
+        bump
+    )]
+    pub fill_escrow: Account<'info, FillEscrow>,
+
+    /// Holds the premium until the fill completes or unwinds
+    #[account(
+        init,
+        payer = market_maker,
+        token::mint = quote_mint,
+        token::authority = fill_escrow,
+        seeds = [PREMIUM_ESCROW_SEED, intent.key().as_ref()],
+        bump
+    )]
+    pub premium_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// MM's token account to pay premium from
+    #[account(
+        mut,
+        constraint = mm_token_account.owner == market_maker.key()
+    )]
+    pub mm_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Quote mint of the premium
+    #[account(address = intent.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Portfolio margin parameters; pass with `oracle` and `mm_exposure` for
+    /// a whitelisted MM to lock only stress-scenario collateral
+    #[account(seeds = [RISK_CONFIG_SEED], bump = risk_config.bump)]
+    pub risk_config: Option<Account<'info, RiskConfig>>,
+
+    /// MM's open book on this asset; its positions and their MM vaults
+    /// follow in `remaining_accounts`, after an index asset's component price
+    /// updates (see PORTFOLIO_MARGIN_ACCOUNTS_PER_POSITION)
+    #[account(
+        mut,
+        seeds = [MM_EXPOSURE_SEED, market_maker.key().as_ref(), intent.asset_mint.as_ref()],
+        bump = mm_exposure.bump
+    )]
+    pub mm_exposure: Option<Box<Account<'info, MMExposure>>>,
+
+    /// Spot feeds for the fill-time price band and the portfolio margin
+    /// stress scenario; only read when either applies
+    pub oracle: OracleAccounts<'info>,
+
+    /// Composer allowlist, required when filled via CPI
+    #[account(seeds = [COMPOSER_ALLOWLIST_SEED], bump = composer_allowlist.bump)]
+    pub composer_allowlist: Option<Account<'info, ComposerAllowlist>>,
+
+    /// Instructions sysvar, to identify a calling program
+    /// CHECK: This is the instructions sysvar
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_begin_escrowed_fill<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BeginEscrowedFill<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require_allowed_caller(
+        &ctx.accounts.instructions_sysvar,
+        ctx.accounts.composer_allowlist.as_deref(),
+    )?;
+    let intent = &ctx.accounts.intent;
+    require!(
+        clock.unix_timestamp <= intent.fill_deadline,
+        ErrorCode::IntentExpired
+    );
+    // An index asset's component updates lead remaining_accounts, ahead of
+    // the portfolio margin book
+    let (basket_updates, book_accounts) =
+        split_basket_updates(ctx.remaining_accounts, &ctx.accounts.asset_config)?;
+    require_fill_price_band(
+        &ctx.accounts.asset_config,
+        intent,
+        &ctx.accounts.oracle,
+        basket_updates,
+        &clock,
+    )?;
+    ctx.accounts.asset_config.require_contract_size(intent.contract_size)?;
+
+    // The deposit covers any mint transfer fee twice, into the escrow and on
+    // to the user, so the user still receives the full premium
+    let premium = intent.calculate_total_premium()?;
+    let premium_released = gross_up_for_fee(&ctx.accounts.quote_mint, premium)?;
+    let deposit = gross_up_for_fee(&ctx.accounts.quote_mint, premium_released)?;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.mm_token_account.to_account_info(),
+        mint: ctx.accounts.quote_mint.to_account_info(),
+        to: ctx.accounts.premium_escrow.to_account_info(),
+        authority: ctx.accounts.market_maker.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    token_interface::transfer_checked(
+        CpiContext::new(cpi_program, cpi_accounts),
+        deposit,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+
+    // Physically settled assets lock the MM's delivery leg up front; cash
+    // settlement is funded from the user vault, so no collateral is needed
+    let asset_config = &ctx.accounts.asset_config;
+    let mut portfolio_margined = false;
+    let (collateral_mint, collateral_amount) =
+        if asset_config.settlement_kind == SettlementKind::Physical {
+            let mint = match intent.strategy {
+                StrategyType::CoveredCall => intent.quote_mint,
+                StrategyType::CashSecuredPut => intent.asset_mint,
+            };
+            let mut amount = physical_delivery_amount(
+                intent.strategy,
+                intent.strike_price,
+                intent.contract_size,
+                &asset_config.settlement_terms(),
+            )?;
+            // Portfolio margin: lock only what the MM's whole open book on
+            // this asset, this fill included, stands to deliver across the
+            // stress scenario beyond the collateral it already has locked.
+            // Without the book (a first fill) the gross requirement stands.
+            if let (Some(risk_config), Some(mm_exposure)) =
+                (ctx.accounts.risk_config.as_ref(), ctx.accounts.mm_exposure.as_ref())
+            {
+                if ctx.accounts.mm_registry.portfolio_margin {
+                    let spot_price = get_asset_price(
+                        &ctx.accounts.oracle,
+                        basket_updates,
+                        asset_config,
+                        &clock,
+                    )?;
+                    let scenario = risk_config.stressed_range(spot_price);
+                    let fill_itm = stressed_itm(intent.strategy, intent.strike_price, scenario);
+                    let fill_loss = if fill_itm { amount } else { 0 };
+                    let (book_loss, book_locked) = stressed_book(
+                        book_accounts,
+                        mm_exposure,
+                        intent.strategy,
+                        scenario,
+                        &asset_config.settlement_terms(),
+                    )?;
+                    let gross = amount;
+                    amount = fill_loss
+                        .checked_add(book_loss)
+                        .ok_or(ErrorCode::MathOverflow)?
+                        .saturating_sub(book_locked)
+                        .min(amount);
+                    portfolio_margined = amount < gross;
+                }
+            }
+            (mint, amount)
+        } else {
+            (intent.quote_mint, 0)
+        };
+    // The book now relies on collateral pooled across its positions, so
+    // releasing any of it must leave the rest covered
+    if portfolio_margined {
+        if let Some(mm_exposure) = ctx.accounts.mm_exposure.as_mut() {
+            mm_exposure.portfolio_margined = true;
+        }
+    }
+
+    let complete_by = clock
+        .unix_timestamp
+        .checked_add(ESCROWED_FILL_WINDOW)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let fill_escrow = &mut ctx.accounts.fill_escrow;
+    fill_escrow.intent = intent.key();
+    fill_escrow.market_maker = ctx.accounts.market_maker.key();
+    fill_escrow.premium_amount = premium;
+    fill_escrow.collateral_mint = collateral_mint;
+    fill_escrow.collateral_amount = collateral_amount;
+    fill_escrow.complete_by = complete_by;
+    fill_escrow.bump = ctx.bumps.fill_escrow;
+
+    let intent = &mut ctx.accounts.intent;
+    intent.status = IntentStatus::FillInProgress;
+
+    emit!(EscrowedFillStarted {
+        intent_id: intent.intent_id,
+        market_maker: ctx.accounts.market_maker.key(),
+        premium,
+        collateral_mint,
+        collateral_amount,
+        complete_by,
+    });
+
+    Ok(())
+}
+
+/// Accounts per open position in a portfolio-margin `remaining_accounts`:
+/// position, position_mm_vault
+pub const PORTFOLIO_MARGIN_ACCOUNTS_PER_POSITION: usize = 2;
+
+/// A `strategy` position struck at `strike_price` finishes in the money
+/// somewhere in the stress scenario's (low, high) spot range
+fn stressed_itm(strategy: StrategyType, strike_price: u64, (low, high): (u64, u64)) -> bool {
+    match strategy {
+        StrategyType::CoveredCall => high > strike_price,
+        StrategyType::CashSecuredPut => low < strike_price,
+    }
+}
+
+/// Stressed delivery owed by the MM's open `strategy` positions, and the
+/// `strategy` collateral still locked against them, over `book`: every
+/// position counted in `mm_exposure` (in ascending key order), each followed
+/// by its MM vault. A settled position's unreleased collateral still counts
+/// as locked.
+fn stressed_book<'info>(
+    book: &'info [AccountInfo<'info>],
+    mm_exposure: &MMExposure,
+    strategy: StrategyType,
+    scenario: (u64, u64),
+    terms: &SettlementTerms,
+) -> Result<(u64, u64)> {
+    require!(
+        book.len() as u64
+            == mm_exposure
+                .locked_positions
+                .saturating_mul(PORTFOLIO_MARGIN_ACCOUNTS_PER_POSITION as u64),
+        ErrorCode::MarginBookIncomplete
+    );
+
+    let mut loss: u64 = 0;
+    let mut locked: u64 = 0;
+    let mut previous = None;
+    for group in book.chunks_exact(PORTFOLIO_MARGIN_ACCOUNTS_PER_POSITION) {
+        let [position_info, vault_info] = group else {
+            return err!(ErrorCode::MarginBookIncomplete);
+        };
+        // Ascending keys rule out passing one position twice
+        require!(
+            previous.is_none_or(|previous| previous < position_info.key),
+            ErrorCode::MarginBookIncomplete
+        );
+        previous = Some(position_info.key);
+
+        let position: Account<'info, Position> = Account::try_from(position_info)?;
+        require!(
+            position.market_maker == mm_exposure.market_maker
+                && position.asset_mint == mm_exposure.asset_mint
+                && position.has_locked_mm_collateral()
+                && vault_info.key() == position.mm_vault_locked,
+            ErrorCode::MarginBookIncomplete
+        );
+        if position.strategy != strategy {
+            continue;
+        }
+
+        let vault: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(vault_info)?;
+        locked = locked.checked_add(vault.amount).ok_or(ErrorCode::MathOverflow)?;
+        if !position.is_terminal()
+            && stressed_itm(position.strategy, position.strike_price, scenario)
+        {
+            let delivery = physical_delivery_amount(
+                position.strategy,
+                position.strike_price,
+                position.contract_size,
+                terms,
+            )?;
+            loss = loss.checked_add(delivery).ok_or(ErrorCode::MathOverflow)?;
+        }
+    }
+
+    Ok((loss, locked))
+}
+
+// ===== Complete Escrowed Fill =====
+// MM locks its collateral in the position MM vault; the escrowed premium is
+// released to the user and the position opens in the same instruction.
+
+#[derive(Accounts)]
+pub struct CompleteEscrowedFill<'info> {
+    #[account(mut)]
+    pub market_maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_FILLS) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        constraint = intent.status == IntentStatus::FillInProgress @ ErrorCode::FillNotInProgress,
+        constraint = intent.market_maker == market_maker.key() @ ErrorCode::UnauthorizedFill
+    )]
+    pub intent: Box<Account<'info, Intent>>,
+
+    #[account(
+        mut,
+        seeds = [FILL_ESCROW_SEED, intent.key().as_ref()],
+        bump = fill_escrow.bump,
+        has_one = intent,
+        close = market_maker
+    )]
+    pub fill_escrow: Box<Account<'info, FillEscrow>>,
+
+    #[account(
+        mut,
+        seeds = [PREMIUM_ESCROW_SEED, intent.key().as_ref()],
+        bump
+    )]
+    pub premium_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, intent.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused
+    )]
+    pub asset_config: Box<Account<'info, AssetConfig>>,
+
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, market_maker.key().as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Box<Account<'info, MMRegistry>>,
+
+    /// MM's locked collateral on this asset, created on its first escrowed fill
+    #[account(
+        init_if_needed,
+        payer = market_maker,
+        space = MMExposure::LEN,
+        seeds = [MM_EXPOSURE_SEED, market_maker.key().as_ref(), intent.asset_mint.as_ref()],
+        bump
+    )]
+    pub mm_exposure: Box<Account<'info, MMExposure>>,
+
+    /// Intent user's lifetime stats
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, intent.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    /// CHECK: Intent user, owner of the premium ATA
+    #[account(address = intent.user @ ErrorCode::Unauthorized)]
+    pub user_wallet: UncheckedAccount<'info>,
+
+    /// User's ATA to receive premium, created by the MM if missing
+    #[account(
+        init_if_needed,
+        payer = market_maker,
+        associated_token::mint = quote_mint,
+        associated_token::authority = user_wallet,
+        associated_token::token_program = token_program
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = market_maker,
+        space = Position::LEN,
+        seeds = [POSITION_SEED, intent.user.as_ref(), &intent.intent_id.to_le_bytes()],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// User's escrow, taken over by the position as its user vault
+    #[account(
+        mut,
+        seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
+        bump
+    )]
+    pub user_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Position MM vault holding the locked collateral
+    #[account(
+        init,
+        payer = market_maker,
+        token::mint = collateral_mint,
+        token::authority = position,
+        seeds = [POSITION_MM_VAULT_SEED, position.key().as_ref()],
+        bump
+    )]
+    pub position_mm_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// MM's account funding the collateral (its own, its MM vault, or a
+    /// liquidity vault it is backed by); required when any is owed
+    #[account(
+        mut,
+        constraint = mm_collateral_source.owner == market_maker.key()
+            || mm_collateral_source.owner == mm_registry.key()
+            || liquidity_vault
+                .as_ref()
+                .is_some_and(|vault| mm_collateral_source.owner == vault.key()) @ ErrorCode::Unauthorized,
+        constraint = mm_collateral_source.mint == collateral_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub mm_collateral_source: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Liquidity vault to draw the collateral from
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_VAULT_SEED, liquidity_vault.quote_mint.as_ref()],
+        bump = liquidity_vault.bump,
+        constraint = liquidity_vault.quote_mint == collateral_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub liquidity_vault: Option<Box<Account<'info, LiquidityVault>>>,
+
+    /// MM's credit line on the liquidity vault
+    #[account(
+        mut,
+        seeds = [LP_BACKING_SEED, lp_backing.vault.as_ref(), market_maker.key().as_ref()],
+        bump = lp_backing.bump
+    )]
+    pub lp_backing: Option<Box<Account<'info, LpBacking>>>,
+
+    #[account(address = fill_escrow.collateral_mint @ ErrorCode::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Quote mint of the premium
+    #[account(address = intent.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_complete_escrowed_fill(ctx: Context<CompleteEscrowedFill>) -> Result<()> {
+    let clock = Clock::get()?;
+    let fill_escrow = &ctx.accounts.fill_escrow;
+
+    // 1. Lock MM collateral (the MM covers any transfer fee)
+    let mut lp_draw = None;
+    if fill_escrow.collateral_amount > 0 {
+        let source = ctx
+            .accounts
+            .mm_collateral_source
+            .as_ref()
+            .ok_or(ErrorCode::TokenAccountRequired)?;
+        let amount = gross_up_for_fee(&ctx.accounts.collateral_mint, fill_escrow.collateral_amount)?;
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        if source.owner == ctx.accounts.mm_registry.key() {
+            // Drawn from the MM vault: the registry PDA signs
+            let market_maker = ctx.accounts.market_maker.key();
+            let seeds = &[
+                MM_REGISTRY_SEED,
+                market_maker.as_ref(),
+                &[ctx.accounts.mm_registry.bump],
+            ];
+            let cpi_accounts = TransferChecked {
+                from: source.to_account_info(),
+                mint: ctx.accounts.collateral_mint.to_account_info(),
+                to: ctx.accounts.position_mm_vault.to_account_info(),
+                authority: ctx.accounts.mm_registry.to_account_info(),
+            };
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(cpi_program, cpi_accounts, &[&seeds[..]]),
+                amount,
+                ctx.accounts.collateral_mint.decimals,
+            )?;
+        } else if let Some(vault) = ctx
+            .accounts
+            .liquidity_vault
+            .as_ref()
+            .filter(|vault| source.owner == vault.key())
+        {
+            // Drawn from LP liquidity: the liquidity vault PDA signs
+            let seeds = &[LIQUIDITY_VAULT_SEED, vault.quote_mint.as_ref(), &[vault.bump]];
+            let cpi_accounts = TransferChecked {
+                from: source.to_account_info(),
+                mint: ctx.accounts.collateral_mint.to_account_info(),
+                to: ctx.accounts.position_mm_vault.to_account_info(),
+                authority: vault.to_account_info(),
+            };
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(cpi_program, cpi_accounts, &[&seeds[..]]),
+                amount,
+                ctx.accounts.collateral_mint.decimals,
+            )?;
+            lp_draw = Some((amount, source.amount));
+        } else {
+            let cpi_accounts = TransferChecked {
+                from: source.to_account_info(),
+                mint: ctx.accounts.collateral_mint.to_account_info(),
+                to: ctx.accounts.position_mm_vault.to_account_info(),
+                authority: ctx.accounts.market_maker.to_account_info(),
+            };
+            token_interface::transfer_checked(
+                CpiContext::new(cpi_program, cpi_accounts),
+                amount,
+                ctx.accounts.collateral_mint.decimals,
+            )?;
+        }
+    }
+
+    // Count what actually landed in the vault as locked
+    ctx.accounts.position_mm_vault.reload()?;
+    let mm_exposure = &mut ctx.accounts.mm_exposure;
+    if mm_exposure.market_maker == Pubkey::default() {
+        mm_exposure.market_maker = ctx.accounts.market_maker.key();
+        mm_exposure.asset_mint = ctx.accounts.intent.asset_mint;
+        mm_exposure.bump = ctx.bumps.mm_exposure;
+    }
+    mm_exposure.record_lock(
+        ctx.accounts.collateral_mint.key(),
+        ctx.accounts.position_mm_vault.amount,
+    )?;
+
+    // 2. Release the escrowed premium to the user and close the escrow
+    let intent_key = ctx.accounts.intent.key();
+    let seeds = &[FILL_ESCROW_SEED, intent_key.as_ref(), &[fill_escrow.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.premium_escrow.to_account_info(),
+        mint: ctx.accounts.quote_mint.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.fill_escrow.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+        ctx.accounts.premium_escrow.amount,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+    close_escrow_account(
+        &ctx.accounts.premium_escrow,
+        &ctx.accounts.market_maker.to_account_info(),
+        &ctx.accounts.fill_escrow.to_account_info(),
+        &ctx.accounts.token_program,
+        signer_seeds,
+    )?;
+
+    // LP-backed collateral goes on the MM's credit line, charged the vault's
+    // share of the premium
+    let premium = ctx.accounts.fill_escrow.premium_amount;
+    let mut collateral_lender = Pubkey::default();
+    if let Some((amount, idle_balance)) = lp_draw {
+        let vault = ctx
+            .accounts
+            .liquidity_vault
+            .as_mut()
+            .ok_or(ErrorCode::InvalidVault)?;
+        let lp_backing = ctx
+            .accounts
+            .lp_backing
+            .as_mut()
+            .ok_or(ErrorCode::CreditLimitExceeded)?;
+        require_keys_eq!(lp_backing.vault, vault.key(), ErrorCode::CreditLimitExceeded);
+        let fee = apply_bps(premium, vault.premium_share_bps as u64)?;
+        lp_backing.record_draw(amount, fee)?;
+        vault.record_draw(amount, fee, idle_balance)?;
+        collateral_lender = lp_backing.key();
+    }
+
+    // 3. Open the position against the locked collateral, handing it the
+    // user escrow as its user vault
+    let intent = &ctx.accounts.intent;
+    hand_escrow_to_position(
+        intent,
+        &ctx.accounts.user_escrow,
+        ctx.accounts.position.key(),
+        &ctx.accounts.token_program,
+    )?;
+    let notional = ctx
+        .accounts
+        .asset_config
+        .notional_value(intent.contract_size, intent.strike_price)?;
+    let position = &mut ctx.accounts.position;
+    open_position(
+        position,
+        &intent.position_terms(),
+        notional,
+        premium,
+        ctx.accounts.position_mm_vault.key(),
+        ctx.bumps.position_mm_vault,
+        clock.unix_timestamp,
+    );
+    position.collateral_lender = collateral_lender;
+    position.bump = ctx.bumps.position;
+
+    ctx.accounts.asset_config.record_position_opened(notional)?;
+    ctx.accounts.global_state.record_notional_opened(notional)?;
+    ctx.accounts
+        .mm_registry
+        .record_fill(intent.contract_size, clock.unix_timestamp);
+    ctx.accounts.user_stats.record_fill(premium);
+    ctx.accounts.user_stats.release_pending_intent();
+    ctx.accounts.global_state.record_intent_closed();
+
+    let intent = &mut ctx.accounts.intent;
+    intent.status = IntentStatus::Filled;
+
+    emit!(IntentFilled {
+        intent_id: intent.intent_id,
+        position_id: intent.intent_id,
+        market_maker: ctx.accounts.market_maker.key(),
+        user: intent.user,
+        implied_volatility_bps: intent.implied_volatility_bps,
+    });
+
+    Ok(())
+}
+
+// ===== Unwind Escrowed Fill =====
+// Returns the escrowed premium to the MM and puts the intent back to pending.
+// The MM may back out at any time; anyone may unwind once the window closes,
+// which counts against the MM like an expired intent.
+
+#[derive(Accounts)]
+pub struct UnwindEscrowedFill<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = intent.status == IntentStatus::FillInProgress @ ErrorCode::FillNotInProgress
+    )]
+    pub intent: Account<'info, Intent>,
+
+    #[account(
+        mut,
+        seeds = [FILL_ESCROW_SEED, intent.key().as_ref()],
+        bump = fill_escrow.bump,
+        has_one = intent,
+        close = mm_wallet
+    )]
+    pub fill_escrow: Account<'info, FillEscrow>,
+
+    #[account(
+        mut,
+        seeds = [PREMIUM_ESCROW_SEED, intent.key().as_ref()],
+        bump
+    )]
+    pub premium_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, fill_escrow.market_maker.as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    /// CHECK: MM that began the fill, refunded the escrow rent
+    #[account(mut, address = fill_escrow.market_maker @ ErrorCode::Unauthorized)]
+    pub mm_wallet: UncheckedAccount<'info>,
+
+    /// MM's token account receiving the premium back
+    #[account(
+        mut,
+        constraint = mm_token_account.owner == fill_escrow.market_maker @ ErrorCode::Unauthorized
+    )]
+    pub mm_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Quote mint of the premium
+    #[account(address = intent.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_unwind_escrowed_fill(ctx: Context<UnwindEscrowedFill>) -> Result<()> {
+    let clock = Clock::get()?;
+    let fill_escrow = &ctx.accounts.fill_escrow;
+    let timed_out = clock.unix_timestamp > fill_escrow.complete_by;
+    require!(
+        timed_out || ctx.accounts.caller.key() == fill_escrow.market_maker,
+        ErrorCode::EscrowedFillWindowOpen
+    );
+
+    let intent_key = ctx.accounts.intent.key();
+    let seeds = &[FILL_ESCROW_SEED, intent_key.as_ref(), &[fill_escrow.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let premium_returned = ctx.accounts.premium_escrow.amount;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.premium_escrow.to_account_info(),
+        mint: ctx.accounts.quote_mint.to_account_info(),
+        to: ctx.accounts.mm_token_account.to_account_info(),
+        authority: ctx.accounts.fill_escrow.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+        premium_returned,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+    close_escrow_account(
+        &ctx.accounts.premium_escrow,
+        &ctx.accounts.mm_wallet.to_account_info(),
+        &ctx.accounts.fill_escrow.to_account_info(),
+        &ctx.accounts.token_program,
+        signer_seeds,
+    )?;
+
+    if timed_out && ctx.accounts.caller.key() != ctx.accounts.fill_escrow.market_maker {
+        ctx.accounts.mm_registry.record_expire();
+    }
+
+    // Back to pending: the MM may fill again before the deadline, or the
+    // user may cancel or expire the intent as usual
+    let intent = &mut ctx.accounts.intent;
+    intent.status = IntentStatus::Pending;
+
+    emit!(EscrowedFillUnwound {
+        intent_id: intent.intent_id,
+        market_maker: ctx.accounts.fill_escrow.market_maker,
+        premium_returned,
+        timed_out,
+    });
+
+    Ok(())
+}
+
+// ===== Release MM Collateral =====
+// Once a position from an escrowed fill is settled, whatever is left in its
+// MM vault (all of it unless delivered physically) goes back to the MM, or
+// to the liquidity vault it was drawn from. On a portfolio-margined book the
+// rest of the book is re-stressed first, and what it still needs stays
+// locked until a later release.
+
+#[derive(Accounts)]
+pub struct ReleaseMmCollateral<'info> {
+    #[account(mut)]
+    pub market_maker: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = position.market_maker == market_maker.key() @ ErrorCode::Unauthorized,
+        constraint = position.has_locked_mm_collateral() @ ErrorCode::InvalidVault,
+        constraint = position.is_terminal() @ ErrorCode::MmCollateralLocked
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_MM_VAULT_SEED, position.key().as_ref()],
+        bump = position.mm_vault_bump
+    )]
+    pub position_mm_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = position_mm_vault.mint @ ErrorCode::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// MM's book on this asset; when portfolio-margined, its positions and
+    /// their MM vaults follow in `remaining_accounts`, after an index asset's
+    /// component price updates (see PORTFOLIO_MARGIN_ACCOUNTS_PER_POSITION)
+    #[account(
+        mut,
+        seeds = [MM_EXPOSURE_SEED, position.market_maker.as_ref(), position.asset_mint.as_ref()],
+        bump = mm_exposure.bump
+    )]
+    pub mm_exposure: Account<'info, MMExposure>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Box<Account<'info, AssetConfig>>,
+
+    /// Portfolio margin parameters; required when the book is portfolio-margined
+    #[account(seeds = [RISK_CONFIG_SEED], bump = risk_config.bump)]
+    pub risk_config: Option<Account<'info, RiskConfig>>,
+
+    /// Spot feeds for the stress scenario; only read when the book is
+    /// portfolio-margined
+    pub oracle: OracleAccounts<'info>,
+
+    /// CHECK: PDA authority for position vaults
+    #[account(
+        seeds = [POSITION_SEED, position.user.as_ref(), &position.position_id.to_le_bytes()],
+        bump = position.bump
+    )]
+    pub position_authority: AccountInfo<'info>,
+
+    /// MM's destination for the collateral (or their beneficiary's); the
+    /// liquidity vault's ATA when LP-backed
+    #[account(
+        mut,
+        constraint = mm_destination.owner == position.mm_payout_owner()
+            || position.is_lp_backed() @ ErrorCode::Unauthorized
+    )]
+    pub mm_destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Liquidity vault the collateral was drawn from
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_VAULT_SEED, liquidity_vault.quote_mint.as_ref()],
+        bump = liquidity_vault.bump
+    )]
+    pub liquidity_vault: Option<Account<'info, LiquidityVault>>,
+
+    /// MM's credit line the collateral was drawn on
+    #[account(
+        mut,
+        address = position.collateral_lender @ ErrorCode::InvalidVault
+    )]
+    pub lp_backing: Option<Account<'info, LpBacking>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_release_mm_collateral<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReleaseMmCollateral<'info>>,
+) -> Result<()> {
+    let position = &ctx.accounts.position;
+    let vault_amount = ctx.accounts.position_mm_vault.amount;
+
+    // A portfolio-margined book counted this vault toward the collateral of
+    // its other positions: keep back whatever their stressed delivery still
+    // needs beyond the rest of the book's locked collateral
+    let held_back = if ctx.accounts.mm_exposure.portfolio_margined {
+        let risk_config = ctx
+            .accounts
+            .risk_config
+            .as_ref()
+            .ok_or(ErrorCode::MarginBookIncomplete)?;
+        let asset_config = &ctx.accounts.asset_config;
+        let (basket_updates, book_accounts) =
+            split_basket_updates(ctx.remaining_accounts, asset_config)?;
+        let spot_price = get_asset_price(
+            &ctx.accounts.oracle,
+            basket_updates,
+            asset_config,
+            &Clock::get()?,
+        )?;
+        let (book_loss, book_locked) = stressed_book(
+            book_accounts,
+            &ctx.accounts.mm_exposure,
+            position.strategy,
+            risk_config.stressed_range(spot_price),
+            &asset_config.settlement_terms(),
+        )?;
+        book_loss
+            .saturating_sub(book_locked.saturating_sub(vault_amount))
+            .min(vault_amount)
+    } else {
+        0
+    };
+    let amount = vault_amount - held_back;
+
+    // LP-backed collateral returns to the vault and pays down the draw
+    if position.is_lp_backed() {
+        let (Some(vault), Some(lp_backing)) = (
+            ctx.accounts.liquidity_vault.as_mut(),
+            ctx.accounts.lp_backing.as_mut(),
+        ) else {
+            return err!(ErrorCode::InvalidVault);
+        };
+        require_keys_eq!(lp_backing.vault, vault.key(), ErrorCode::InvalidVault);
+        require_keys_eq!(
+            ctx.accounts.mm_destination.owner,
+            vault.key(),
+            ErrorCode::Unauthorized
+        );
+        let repaid = lp_backing.record_return(amount);
+        vault.record_repayment(repaid);
+    }
+    transfer_from_position_vault(
+        position,
+        &ctx.accounts.position_mm_vault,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.mm_destination,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    if held_back > 0 {
+        ctx.accounts
+            .mm_exposure
+            .record_unlock(ctx.accounts.collateral_mint.key(), amount);
+        emit!(MmCollateralReleased {
+            position_id: position.position_id,
+            market_maker: position.market_maker,
+            amount,
+            held_back,
+        });
+        return Ok(());
+    }
+
+    let position_id_bytes = position.position_id.to_le_bytes();
+    let seeds = &[
+        POSITION_SEED,
+        position.user.as_ref(),
+        &position_id_bytes,
+        &[position.bump],
+    ];
+    close_escrow_account(
+        &ctx.accounts.position_mm_vault,
+        &ctx.accounts.market_maker.to_account_info(),
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_program,
+        &[&seeds[..]],
+    )?;
+
+    ctx.accounts
+        .mm_exposure
+        .record_release(ctx.accounts.collateral_mint.key(), amount);
+
+    let position = &mut ctx.accounts.position;
+    position.mm_vault_locked = Pubkey::default();
+    position.mm_vault_bump = 0;
+    position.collateral_lender = Pubkey::default();
+
+    emit!(MmCollateralReleased {
+        position_id: position.position_id,
+        market_maker: position.market_maker,
+        amount,
+        held_back,
+    });
+
+    Ok(())
+}
+
+/// Close an emptied program-owned token account, refunding its rent
+fn close_escrow_account<'info>(
+    account: &InterfaceAccount<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: account.to_account_info(),
+            destination: destination.clone(),
+            authority: authority.clone(),
+        },
+        signer_seeds,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token;
+
+    const MARKET_MAKER: Pubkey = Pubkey::new_from_array([1; 32]);
+    const ASSET_MINT: Pubkey = Pubkey::new_from_array([2; 32]);
+    const QUOTE_MINT: Pubkey = Pubkey::new_from_array([3; 32]);
+
+    // Stress range around a $100 spot, 1e-6 prices
+    const SCENARIO: (u64, u64) = (90_000_000, 110_000_000);
+
+    // SOL-like asset (9 decimals) delivered against USDC (6 decimals)
+    fn physical_terms() -> SettlementTerms {
+        SettlementTerms {
+            kind: SettlementKind::Physical,
+            min_itm_bps: 0,
+            asset_decimals: 9,
+            quote_decimals: 6,
+            price_exponent: -6,
+            collateral_rate: None,
+        }
+    }
+
+    fn exposure(locked_positions: u64) -> MMExposure {
+        MMExposure {
+            market_maker: MARKET_MAKER,
+            asset_mint: ASSET_MINT,
+            locked_quote: 0,
+            locked_underlying: 0,
+            locked_positions,
+            portfolio_margined: true,
+            bump: 255,
+        }
+    }
+
+    // One contract of the MM's, with a funded MM vault
+    fn position(strategy: StrategyType, strike_price: u64, status: PositionStatus) -> Position {
+        let mut position =
+            Position::try_deserialize_unchecked(&mut &[0u8; Position::LEN][..]).unwrap();
+        position.market_maker = MARKET_MAKER;
+        position.asset_mint = ASSET_MINT;
+        position.quote_mint = QUOTE_MINT;
+        position.strategy = strategy;
+        position.strike_price = strike_price;
+        position.contract_size = 1_000_000_000;
+        position.status = status;
+        position.mm_vault_bump = 255;
+        position
+    }
+
+    fn account_info(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> AccountInfo<'static> {
+        AccountInfo::new(
+            Box::leak(Box::new(key)),
+            false,
+            false,
+            Box::leak(Box::new(0)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            false,
+            0,
+        )
+    }
+
+    // A position at `key` followed by its MM vault holding `locked`
+    fn entry(key: u8, mut position: Position, locked: u64) -> [AccountInfo<'static>; 2] {
+        let vault_key = Pubkey::new_unique();
+        position.mm_vault_locked = vault_key;
+        let mut position_data = Vec::new();
+        position.try_serialize(&mut position_data).unwrap();
+
+        let vault = spl_token::state::Account {
+            mint: QUOTE_MINT,
+            owner: Pubkey::new_unique(),
+            amount: locked,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut vault_data = vec![0u8; spl_token::state::Account::LEN];
+        vault.pack_into_slice(&mut vault_data);
+
+        [
+            account_info(Pubkey::new_from_array([key; 32]), crate::ID, position_data),
+            account_info(vault_key, spl_token::ID, vault_data),
+        ]
+    }
+
+    fn book(entries: Vec<[AccountInfo<'static>; 2]>) -> &'static [AccountInfo<'static>] {
+        Box::leak(entries.into_iter().flatten().collect::<Vec<_>>().into_boxed_slice())
+    }
+
+    fn call(strike_price: u64, status: PositionStatus) -> Position {
+        position(StrategyType::CoveredCall, strike_price, status)
+    }
+
+    fn delivery(strike_price: u64) -> u64 {
+        physical_delivery_amount(
+            StrategyType::CoveredCall,
+            strike_price,
+            1_000_000_000,
+            &physical_terms(),
+        )
+        .unwrap()
+    }
+
+    // Stress the MM's covered calls over `book`, of `locked_positions` positions
+    fn stress_calls(
+        book: &'static [AccountInfo<'static>],
+        locked_positions: u64,
+    ) -> Result<(u64, u64)> {
+        stressed_book(
+            book,
+            &exposure(locked_positions),
+            StrategyType::CoveredCall,
+            SCENARIO,
+            &physical_terms(),
+        )
+    }
+
+    #[test]
+    fn test_stressed_book_counts_itm_delivery_and_locked() {
+        let book = book(vec![
+            entry(10, call(105_000_000, PositionStatus::Active), 40_000_000),
+            entry(11, call(120_000_000, PositionStatus::Active), 30_000_000),
+        ]);
+        let (loss, locked) = stress_calls(book, 2).unwrap();
+        // Only the 105 strike finishes in the money within the scenario
+        assert_eq!(loss, delivery(105_000_000));
+        assert_eq!(locked, 70_000_000);
+    }
+
+    #[test]
+    fn test_stressed_book_requires_every_position() {
+        let book = book(vec![entry(10, call(105_000_000, PositionStatus::Active), 40_000_000)]);
+        assert!(stress_calls(book, 2).is_err());
+        assert!(stress_calls(&book[..1], 1).is_err());
+        assert!(stress_calls(book, 1).is_ok());
+    }
+
+    #[test]
+    fn test_stressed_book_rejects_repeated_or_unsorted_positions() {
+        // The same position passed twice
+        let [position_info, vault_info] =
+            entry(10, call(105_000_000, PositionStatus::Active), 40_000_000);
+        let repeated = book(vec![
+            [position_info.clone(), vault_info.clone()],
+            [position_info, vault_info],
+        ]);
+        assert!(stress_calls(repeated, 2).is_err());
+
+        let unsorted = book(vec![
+            entry(11, call(105_000_000, PositionStatus::Active), 40_000_000),
+            entry(10, call(110_000_000, PositionStatus::Active), 40_000_000),
+        ]);
+        assert!(stress_calls(unsorted, 2).is_err());
+    }
+
+    #[test]
+    fn test_stressed_book_rejects_foreign_positions_and_vaults() {
+        let mut foreign = call(105_000_000, PositionStatus::Active);
+        foreign.market_maker = Pubkey::new_unique();
+        assert!(stress_calls(book(vec![entry(10, foreign, 40_000_000)]), 1).is_err());
+
+        // A vault other than the position's own
+        let [position_info, _] = entry(10, call(105_000_000, PositionStatus::Active), 0);
+        let [_, other_vault] = entry(11, call(105_000_000, PositionStatus::Active), 40_000_000);
+        assert!(stress_calls(book(vec![[position_info, other_vault]]), 1).is_err());
+    }
+
+    #[test]
+    fn test_stressed_book_skips_other_strategies() {
+        let put = position(StrategyType::CashSecuredPut, 95_000_000, PositionStatus::Active);
+        let book = book(vec![
+            entry(10, call(105_000_000, PositionStatus::Active), 40_000_000),
+            entry(11, put, 1_000_000_000),
+        ]);
+        let (loss, locked) = stress_calls(book, 2).unwrap();
+        assert_eq!(loss, delivery(105_000_000));
+        assert_eq!(locked, 40_000_000);
+    }
+
+    #[test]
+    fn test_stressed_book_counts_settled_collateral_as_locked_only() {
+        let book = book(vec![
+            entry(10, call(105_000_000, PositionStatus::SettledOTM), 60_000_000),
+            entry(11, call(100_000_000, PositionStatus::Active), 50_000_000),
+        ]);
+        let (loss, locked) = stress_calls(book, 2).unwrap();
+        // The settled position owes nothing more, but its unreleased
+        // collateral still backs the active one
+        assert_eq!(loss, delivery(100_000_000));
+        assert_eq!(locked, 110_000_000);
+    }
+}
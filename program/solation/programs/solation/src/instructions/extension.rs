@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::gross_up_for_fee;
+
+// ===== Events =====
+
+#[event]
+pub struct PositionExtended {
+    pub position_id: u64,
+    pub previous_expiry: i64,
+    pub new_expiry: i64,
+    pub additional_premium: u64,
+}
+
+// ===== Extend Expiry =====
+// User and MM co-sign to push expiry out; MM pays extra premium,
+// collateral stays locked in the position vault.
+
+#[derive(Accounts)]
+pub struct ExtendExpiry<'info> {
+    pub user: Signer<'info>,
+
+    pub market_maker: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive,
+        constraint = position.holder == user.key() @ ErrorCode::Unauthorized,
+        constraint = position.market_maker == market_maker.key() @ ErrorCode::Unauthorized
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// User's token account to receive the additional premium
+    #[account(
+        mut,
+        constraint = user_token_account.owner == position.holder
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// MM's token account to pay the additional premium from
+    #[account(
+        mut,
+        constraint = mm_token_account.owner == market_maker.key()
+    )]
+    pub mm_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Quote mint of the transferred tokens
+    #[account(address = position.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_extend_expiry(
+    ctx: Context<ExtendExpiry>,
+    new_expiry_timestamp: i64,
+    additional_premium: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let position = &ctx.accounts.position;
+    let previous_expiry = position.expiry_timestamp;
+
+    // Only live positions can be extended, and only forward
+    require!(clock.unix_timestamp < previous_expiry, ErrorCode::PositionExpired);
+    require!(
+        new_expiry_timestamp > previous_expiry,
+        ErrorCode::InvalidExpiryRange
+    );
+
+    // Total tenor must stay within the asset's configured window
+    require!(
+        new_expiry_timestamp - position.created_at <= ctx.accounts.asset_config.max_expiry_seconds,
+        ErrorCode::InvalidExpiryRange
+    );
+
+    if additional_premium > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.mm_token_account.to_account_info(),
+            mint: ctx.accounts.quote_mint.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.market_maker.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        // The MM covers any mint transfer fee so the user receives the full premium
+        let premium_sent = gross_up_for_fee(&ctx.accounts.quote_mint, additional_premium)?;
+        token_interface::transfer_checked(cpi_ctx, premium_sent, ctx.accounts.quote_mint.decimals)?;
+    }
+
+    let position = &mut ctx.accounts.position;
+    position.expiry_timestamp = new_expiry_timestamp;
+    position.premium_paid = position
+        .premium_paid
+        .checked_add(additional_premium)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(PositionExtended {
+        position_id: position.position_id,
+        previous_expiry,
+        new_expiry: new_expiry_timestamp,
+        additional_premium,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::{invoke_hedge_adapter, HedgeRequest, HedgeSide};
+
+// ===== Events =====
+
+#[event]
+pub struct HedgeAdapterAllowlistUpdated {
+    pub program_id: Pubkey,
+    pub allowed: bool,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct FillHedged {
+    pub position_id: u64,
+    pub market_maker: Pubkey,
+    pub adapter: Pubkey,
+    pub side: HedgeSide,
+    pub size: u64,
+}
+
+// ===== Initialize Hedge Adapter Allowlist =====
+
+#[derive(Accounts)]
+pub struct InitializeHedgeAdapterAllowlist<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = HedgeAdapterAllowlist::LEN,
+        seeds = [HEDGE_ADAPTER_ALLOWLIST_SEED],
+        bump
+    )]
+    pub hedge_adapter_allowlist: Account<'info, HedgeAdapterAllowlist>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_hedge_adapter_allowlist(
+    ctx: Context<InitializeHedgeAdapterAllowlist>,
+) -> Result<()> {
+    let allowlist = &mut ctx.accounts.hedge_adapter_allowlist;
+    allowlist.programs = Vec::new();
+    allowlist.bump = ctx.bumps.hedge_adapter_allowlist;
+
+    Ok(())
+}
+
+// ===== Update Hedge Adapter Allowlist =====
+
+#[derive(Accounts)]
+pub struct UpdateHedgeAdapterAllowlist<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [HEDGE_ADAPTER_ALLOWLIST_SEED],
+        bump = hedge_adapter_allowlist.bump
+    )]
+    pub hedge_adapter_allowlist: Account<'info, HedgeAdapterAllowlist>,
+}
+
+pub fn handle_update_hedge_adapter_allowlist(
+    ctx: Context<UpdateHedgeAdapterAllowlist>,
+    program_id: Pubkey,
+    allowed: bool,
+) -> Result<()> {
+    let allowlist = &mut ctx.accounts.hedge_adapter_allowlist;
+
+    if allowed {
+        require!(program_id != crate::ID, ErrorCode::HedgeAdapterNotAllowed);
+        if !allowlist.is_allowed(program_id) {
+            require!(
+                allowlist.programs.len() < MAX_HEDGE_ADAPTERS,
+                ErrorCode::HedgeAdapterAllowlistFull
+            );
+            allowlist.programs.push(program_id);
+        }
+    } else {
+        allowlist.programs.retain(|key| *key != program_id);
+    }
+
+    emit!(HedgeAdapterAllowlistUpdated {
+        program_id,
+        allowed,
+        updated_by: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+// ===== Fill Hedge =====
+
+/// Perp side offsetting the MM's delta on a fresh fill: the MM is long the
+/// call it bought on a covered call, long the put on a cash-secured put
+pub fn hedge_side(strategy: StrategyType) -> HedgeSide {
+    match strategy {
+        StrategyType::CoveredCall => HedgeSide::Short,
+        StrategyType::CashSecuredPut => HedgeSide::Long,
+    }
+}
+
+/// A fill's hedge: `size` underlying base units, at most the position's
+/// contract size, plus the adapter's venue parameters
+pub struct FillHedge {
+    pub size: u64,
+    pub adapter_data: Vec<u8>,
+}
+
+/// Open the MM's offsetting perp through an allowlisted adapter, right after
+/// the fill. `protected` are the fill's own token accounts, which may not be
+/// handed to the adapter; neither may any account this program owns.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn open_fill_hedge<'info>(
+    allowlist: Option<&HedgeAdapterAllowlist>,
+    adapter_program: Option<&AccountInfo<'info>>,
+    market_maker: &AccountInfo<'info>,
+    adapter_accounts: &[AccountInfo<'info>],
+    protected: &[Pubkey],
+    position_key: Pubkey,
+    position: &Position,
+    hedge: FillHedge,
+) -> Result<()> {
+    let (Some(allowlist), Some(adapter_program)) = (allowlist, adapter_program) else {
+        return err!(ErrorCode::HedgeAdapterNotAllowed);
+    };
+    require!(
+        allowlist.is_allowed(adapter_program.key()) && adapter_program.executable,
+        ErrorCode::HedgeAdapterNotAllowed
+    );
+    require!(
+        hedge.size > 0 && hedge.size <= position.contract_size,
+        ErrorCode::InvalidHedge
+    );
+    for account in adapter_accounts {
+        require!(
+            *account.owner != crate::ID && !protected.contains(account.key),
+            ErrorCode::InvalidHedge
+        );
+    }
+
+    let side = hedge_side(position.strategy);
+    invoke_hedge_adapter(
+        adapter_program,
+        market_maker,
+        adapter_accounts,
+        &HedgeRequest {
+            market_maker: market_maker.key(),
+            position: position_key,
+            asset_mint: position.asset_mint,
+            side,
+            size: hedge.size,
+            adapter_data: hedge.adapter_data,
+        },
+    )?;
+
+    emit!(FillHedged {
+        position_id: position.position_id,
+        market_maker: position.market_maker,
+        adapter: adapter_program.key(),
+        side,
+        size: hedge.size,
+    });
+
+    Ok(())
+}
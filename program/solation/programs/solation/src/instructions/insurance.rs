@@ -0,0 +1,708 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::{gross_up_for_fee, transfer_fee_for};
+
+// ===== Events =====
+
+#[event]
+pub struct InsuranceDeposited {
+    pub quote_mint: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub mm_contribution: bool,
+}
+
+#[event]
+pub struct InsuranceFeesAccrued {
+    pub quote_mint: Pubkey,
+    pub fee_amount: u64,
+    pub accrued: u64,
+    pub fee_share_bps: u16,
+}
+
+#[event]
+pub struct InsuranceClaimPaid {
+    pub position_id: u64,
+    pub market_maker: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub fund_balance: u64,
+    pub reason: String,
+}
+
+#[event]
+pub struct DefaultClaimRegistered {
+    pub position_id: u64,
+    pub market_maker: Pubkey,
+    pub expiry_timestamp: i64,
+    pub owed: u64,
+    pub total_owed: u64,
+}
+
+#[event]
+pub struct HaircutFinalized {
+    pub market_maker: Pubkey,
+    pub expiry_timestamp: i64,
+    pub total_owed: u64,
+    pub covered_amount: u64,
+    pub haircut_bps: u16,
+    pub claims_registered: u32,
+}
+
+#[event]
+pub struct HaircutPayoutClaimed {
+    pub position_id: u64,
+    pub market_maker: Pubkey,
+    pub owed: u64,
+    pub paid: u64,
+    pub haircut_bps: u16,
+}
+
+// ===== Initialize Insurance Fund =====
+
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = InsuranceFund::LEN,
+        seeds = [INSURANCE_FUND_SEED, quote_mint.key().as_ref()],
+        bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = quote_mint,
+        token::authority = insurance_fund,
+        seeds = [INSURANCE_VAULT_SEED, quote_mint.key().as_ref()],
+        bump
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle_initialize_insurance_fund(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    insurance_fund.quote_mint = ctx.accounts.quote_mint.key();
+    insurance_fund.vault = ctx.accounts.insurance_vault.key();
+    insurance_fund.total_fee_accruals = 0;
+    insurance_fund.total_mm_contributions = 0;
+    insurance_fund.total_other_deposits = 0;
+    insurance_fund.total_claims_paid = 0;
+    insurance_fund.claims_count = 0;
+    insurance_fund.reserved_for_haircuts = 0;
+    insurance_fund.bump = ctx.bumps.insurance_fund;
+    insurance_fund.vault_bump = ctx.bumps.insurance_vault;
+
+    msg!("Insurance fund initialized for {}", insurance_fund.quote_mint);
+
+    Ok(())
+}
+
+// ===== Deposit Insurance =====
+// Anyone can top up the fund; deposits by a registered MM are credited to it
+
+#[derive(Accounts)]
+pub struct DepositInsurance<'info> {
+    pub depositor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED, insurance_fund.quote_mint.as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        mut,
+        address = insurance_fund.vault @ ErrorCode::InvalidVault
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = depositor_token_account.owner == depositor.key() @ ErrorCode::Unauthorized
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Depositor's MM registry, to record the deposit as an MM contribution
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, depositor.key().as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Option<Account<'info, MMRegistry>>,
+
+    /// Quote mint of the transferred tokens
+    #[account(address = insurance_fund.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_deposit_insurance(ctx: Context<DepositInsurance>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.depositor_token_account.to_account_info(),
+        mint: ctx.accounts.quote_mint.to_account_info(),
+        to: ctx.accounts.insurance_vault.to_account_info(),
+        authority: ctx.accounts.depositor.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    token_interface::transfer_checked(
+        CpiContext::new(cpi_program, cpi_accounts),
+        amount,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+
+    // Only what reaches the vault after any mint transfer fee is credited
+    let received = amount.saturating_sub(transfer_fee_for(&ctx.accounts.quote_mint, amount)?);
+
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    let mm_contribution = match ctx.accounts.mm_registry.as_mut() {
+        Some(mm_registry) => {
+            mm_registry.insurance_contributed =
+                mm_registry.insurance_contributed.saturating_add(received);
+            insurance_fund.total_mm_contributions =
+                insurance_fund.total_mm_contributions.saturating_add(received);
+            true
+        }
+        None => {
+            insurance_fund.total_other_deposits =
+                insurance_fund.total_other_deposits.saturating_add(received);
+            false
+        }
+    };
+
+    emit!(InsuranceDeposited {
+        quote_mint: insurance_fund.quote_mint,
+        depositor: ctx.accounts.depositor.key(),
+        amount: received,
+        mm_contribution,
+    });
+
+    Ok(())
+}
+
+// ===== Accrue Insurance Fees =====
+// Treasury routes insurance_fee_share_bps of the protocol fees it collected
+
+#[derive(Accounts)]
+pub struct AccrueInsuranceFees<'info> {
+    /// Treasury owner (signs for the treasury token account)
+    pub treasury: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.treasury == treasury.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED, insurance_fund.quote_mint.as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        mut,
+        address = insurance_fund.vault @ ErrorCode::InvalidVault
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == treasury.key() @ ErrorCode::Unauthorized,
+        constraint = treasury_token_account.mint == insurance_fund.quote_mint @ ErrorCode::InvalidVault
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Quote mint of the transferred tokens
+    #[account(address = insurance_fund.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_accrue_insurance_fees(
+    ctx: Context<AccrueInsuranceFees>,
+    fee_amount: u64,
+) -> Result<()> {
+    let fee_share_bps = ctx.accounts.global_state.insurance_fee_share_bps;
+    let accrued = (fee_amount as u128)
+        .checked_mul(fee_share_bps as u128)
+        .and_then(|v| v.checked_div(BASIS_POINTS_DIVISOR as u128))
+        .and_then(|v| u64::try_from(v).ok())
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(accrued > 0, ErrorCode::InvalidAmount);
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.treasury_token_account.to_account_info(),
+        mint: ctx.accounts.quote_mint.to_account_info(),
+        to: ctx.accounts.insurance_vault.to_account_info(),
+        authority: ctx.accounts.treasury.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    // The treasury covers any mint transfer fee so the fund receives `accrued`
+    let accrued_sent = gross_up_for_fee(&ctx.accounts.quote_mint, accrued)?;
+    token_interface::transfer_checked(
+        CpiContext::new(cpi_program, cpi_accounts),
+        accrued_sent,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    insurance_fund.total_fee_accruals = insurance_fund.total_fee_accruals.saturating_add(accrued);
+
+    emit!(InsuranceFeesAccrued {
+        quote_mint: insurance_fund.quote_mint,
+        fee_amount,
+        accrued,
+        fee_share_bps,
+    });
+
+    Ok(())
+}
+
+// ===== Pay Insurance Claim =====
+// Authority makes a user whole after their MM defaulted on a settlement
+// obligation (e.g. never delivered on a physical settlement)
+
+#[derive(Accounts)]
+pub struct PayInsuranceClaim<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED, position.quote_mint.as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        mut,
+        address = insurance_fund.vault @ ErrorCode::InvalidVault
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Position the MM defaulted on
+    #[account(mut)]
+    pub position: Account<'info, Position>,
+
+    /// Defaulting MM's registry (claims are recorded against it)
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, position.market_maker.as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.blocks_user_side(&position) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    /// User's destination token account (or their beneficiary's)
+    #[account(
+        mut,
+        constraint = user_destination.owner == position.user_payout_owner(),
+        constraint = user_destination.mint == insurance_fund.quote_mint @ ErrorCode::InvalidVault
+    )]
+    pub user_destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Quote mint of the transferred tokens
+    #[account(address = insurance_fund.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_pay_insurance_claim(
+    ctx: Context<PayInsuranceClaim>,
+    amount: u64,
+    reason: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        reason.len() <= MAX_DISPUTE_REASON_LEN,
+        ErrorCode::DisputeReasonTooLong
+    );
+    // Settlement obligations only fall due at expiry
+    require!(
+        clock.unix_timestamp >= ctx.accounts.position.expiry_timestamp,
+        ErrorCode::PositionNotExpired
+    );
+    // Balance reserved for finalized haircuts is off limits
+    require!(
+        amount <= ctx.accounts.insurance_fund.available(ctx.accounts.insurance_vault.amount),
+        ErrorCode::InsufficientInsuranceFund
+    );
+
+    let quote_mint = ctx.accounts.insurance_fund.quote_mint;
+    let fund_seeds = &[
+        INSURANCE_FUND_SEED,
+        quote_mint.as_ref(),
+        &[ctx.accounts.insurance_fund.bump],
+    ];
+    let signer = &[&fund_seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.insurance_vault.to_account_info(),
+        mint: ctx.accounts.quote_mint.to_account_info(),
+        to: ctx.accounts.user_destination.to_account_info(),
+        authority: ctx.accounts.insurance_fund.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+        amount,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    insurance_fund.total_claims_paid = insurance_fund.total_claims_paid.saturating_add(amount);
+    insurance_fund.claims_count = insurance_fund.claims_count.saturating_add(1);
+
+    let position = &mut ctx.accounts.position;
+    position.insurance_paid = position.insurance_paid.saturating_add(amount);
+
+    let mm_registry = &mut ctx.accounts.mm_registry;
+    mm_registry.insurance_claims_against =
+        mm_registry.insurance_claims_against.saturating_add(amount);
+
+    emit!(InsuranceClaimPaid {
+        position_id: position.position_id,
+        market_maker: position.market_maker,
+        recipient: ctx.accounts.user_destination.owner,
+        amount,
+        fund_balance: ctx.accounts.insurance_vault.amount - amount,
+        reason,
+    });
+
+    Ok(())
+}
+
+// ===== Register Default Claim =====
+// Authority records what an MM failed to pay on one position. Claims for the
+// same MM, expiry and quote mint share a DefaultHaircut and are paid pro-rata
+// from that quote's insurance fund once it is finalized.
+
+#[derive(Accounts)]
+pub struct RegisterDefaultClaim<'info> {
+    pub authority: Signer<'info>,
+
+    /// Funds the DefaultHaircut rent on first claim for an expiry
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// Position the MM defaulted on
+    #[account(
+        mut,
+        constraint = position.default_owed == 0
+            && position.insurance_paid == 0 @ ErrorCode::DefaultClaimAlreadyRegistered
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DefaultHaircut::LEN,
+        seeds = [
+            DEFAULT_HAIRCUT_SEED,
+            position.market_maker.as_ref(),
+            &position.expiry_timestamp.to_le_bytes(),
+            position.quote_mint.as_ref()
+        ],
+        bump
+    )]
+    pub default_haircut: Account<'info, DefaultHaircut>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_register_default_claim(ctx: Context<RegisterDefaultClaim>, owed: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(owed > 0, ErrorCode::InvalidAmount);
+    require!(
+        clock.unix_timestamp >= ctx.accounts.position.expiry_timestamp,
+        ErrorCode::PositionNotExpired
+    );
+
+    let position = &mut ctx.accounts.position;
+    let default_haircut = &mut ctx.accounts.default_haircut;
+
+    if default_haircut.claims_registered == 0 {
+        default_haircut.market_maker = position.market_maker;
+        default_haircut.expiry_timestamp = position.expiry_timestamp;
+        default_haircut.quote_mint = position.quote_mint;
+        default_haircut.bump = ctx.bumps.default_haircut;
+    }
+    require!(!default_haircut.finalized, ErrorCode::HaircutFinalized);
+
+    default_haircut.total_owed = default_haircut
+        .total_owed
+        .checked_add(owed)
+        .ok_or(ErrorCode::MathOverflow)?;
+    default_haircut.claims_registered = default_haircut.claims_registered.saturating_add(1);
+    position.default_owed = owed;
+
+    emit!(DefaultClaimRegistered {
+        position_id: position.position_id,
+        market_maker: position.market_maker,
+        expiry_timestamp: position.expiry_timestamp,
+        owed,
+        total_owed: default_haircut.total_owed,
+    });
+
+    Ok(())
+}
+
+// ===== Finalize Haircut =====
+// Fix the covered share from the fund's unreserved balance and reserve it
+
+#[derive(Accounts)]
+pub struct FinalizeHaircut<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [
+            DEFAULT_HAIRCUT_SEED,
+            default_haircut.market_maker.as_ref(),
+            &default_haircut.expiry_timestamp.to_le_bytes(),
+            default_haircut.quote_mint.as_ref()
+        ],
+        bump = default_haircut.bump,
+        constraint = !default_haircut.finalized @ ErrorCode::HaircutFinalized
+    )]
+    pub default_haircut: Account<'info, DefaultHaircut>,
+
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED, default_haircut.quote_mint.as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        address = insurance_fund.vault @ ErrorCode::InvalidVault
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+pub fn handle_finalize_haircut(ctx: Context<FinalizeHaircut>) -> Result<()> {
+    let clock = Clock::get()?;
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    let default_haircut = &mut ctx.accounts.default_haircut;
+
+    let available = insurance_fund.available(ctx.accounts.insurance_vault.amount);
+    let covered_amount = available.min(default_haircut.total_owed);
+
+    default_haircut.covered_amount = covered_amount;
+    default_haircut.haircut_bps =
+        DefaultHaircut::haircut_bps_for(covered_amount, default_haircut.total_owed);
+    default_haircut.finalized = true;
+    default_haircut.finalized_at = clock.unix_timestamp;
+    insurance_fund.reserved_for_haircuts = insurance_fund
+        .reserved_for_haircuts
+        .saturating_add(covered_amount);
+
+    emit!(HaircutFinalized {
+        market_maker: default_haircut.market_maker,
+        expiry_timestamp: default_haircut.expiry_timestamp,
+        total_owed: default_haircut.total_owed,
+        covered_amount,
+        haircut_bps: default_haircut.haircut_bps,
+        claims_registered: default_haircut.claims_registered,
+    });
+
+    Ok(())
+}
+
+// ===== Claim Haircut Payout =====
+// Permissionless: pays a registered position its pro-rata share to the user
+
+#[derive(Accounts)]
+pub struct ClaimHaircutPayout<'info> {
+    pub claimer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = position.default_owed > 0 @ ErrorCode::NoDefaultClaim,
+        constraint = !position.default_claim_paid @ ErrorCode::DefaultClaimAlreadyRegistered
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [
+            DEFAULT_HAIRCUT_SEED,
+            position.market_maker.as_ref(),
+            &position.expiry_timestamp.to_le_bytes(),
+            position.quote_mint.as_ref()
+        ],
+        bump = default_haircut.bump,
+        constraint = default_haircut.finalized @ ErrorCode::HaircutNotFinalized
+    )]
+    pub default_haircut: Account<'info, DefaultHaircut>,
+
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED, default_haircut.quote_mint.as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        mut,
+        address = insurance_fund.vault @ ErrorCode::InvalidVault
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Defaulting MM's registry (claims are recorded against it)
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, position.market_maker.as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.blocks_user_side(&position) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    /// User's destination token account (or their beneficiary's)
+    #[account(
+        mut,
+        constraint = user_destination.owner == position.user_payout_owner(),
+        constraint = user_destination.mint == insurance_fund.quote_mint @ ErrorCode::InvalidVault
+    )]
+    pub user_destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Quote mint of the transferred tokens
+    #[account(address = insurance_fund.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_claim_haircut_payout(ctx: Context<ClaimHaircutPayout>) -> Result<()> {
+    let owed = ctx.accounts.position.default_owed;
+    let paid = ctx
+        .accounts
+        .default_haircut
+        .payout_for(owed)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if paid > 0 {
+        let quote_mint = ctx.accounts.insurance_fund.quote_mint;
+        let fund_seeds = &[
+            INSURANCE_FUND_SEED,
+            quote_mint.as_ref(),
+            &[ctx.accounts.insurance_fund.bump],
+        ];
+        let signer = &[&fund_seeds[..]];
+
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.insurance_vault.to_account_info(),
+            mint: ctx.accounts.quote_mint.to_account_info(),
+            to: ctx.accounts.user_destination.to_account_info(),
+            authority: ctx.accounts.insurance_fund.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+            paid,
+            ctx.accounts.quote_mint.decimals,
+        )?;
+    }
+
+    let default_haircut = &mut ctx.accounts.default_haircut;
+    default_haircut.total_paid = default_haircut.total_paid.saturating_add(paid);
+    default_haircut.claims_paid = default_haircut.claims_paid.saturating_add(1);
+
+    // Release the reservation as it is paid; rounding dust is freed with the last claim
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    let released = if default_haircut.claims_paid == default_haircut.claims_registered {
+        default_haircut
+            .covered_amount
+            .saturating_sub(default_haircut.total_paid)
+            .saturating_add(paid)
+    } else {
+        paid
+    };
+    insurance_fund.reserved_for_haircuts =
+        insurance_fund.reserved_for_haircuts.saturating_sub(released);
+    insurance_fund.total_claims_paid = insurance_fund.total_claims_paid.saturating_add(paid);
+    insurance_fund.claims_count = insurance_fund.claims_count.saturating_add(1);
+
+    let position = &mut ctx.accounts.position;
+    position.default_claim_paid = true;
+    position.insurance_paid = position.insurance_paid.saturating_add(paid);
+
+    let mm_registry = &mut ctx.accounts.mm_registry;
+    mm_registry.insurance_claims_against =
+        mm_registry.insurance_claims_against.saturating_add(paid);
+
+    emit!(HaircutPayoutClaimed {
+        position_id: position.position_id,
+        market_maker: position.market_maker,
+        owed,
+        paid,
+        haircut_bps: default_haircut.haircut_bps,
+    });
+
+    Ok(())
+}
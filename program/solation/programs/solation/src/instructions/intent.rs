@@ -1,11 +1,22 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_interface::{
+    self, Mint, SetAuthority, TokenAccount, TokenInterface, TransferChecked,
+};
 
 use crate::constants::*;
 use crate::errors::ErrorCode;
+use crate::instructions::compliance::require_asset_access;
+use crate::instructions::hedging::{open_fill_hedge, FillHedge};
+use crate::instructions::keeper::{active_keeper, require_keeper};
+use crate::instructions::option_series::join_series;
+use crate::instructions::settlement::{get_asset_price, split_basket_updates};
 use crate::state::*;
 use crate::utils::ed25519_verify::{construct_quote_message, verify_ed25519_signature};
+use crate::utils::*;
 
 // ===== Events =====
 
@@ -20,6 +31,7 @@ pub struct IntentCreated {
     pub premium: u64,
     pub contract_size: u64,
     pub fill_deadline: i64,
+    pub implied_volatility_bps: u32,
 }
 
 #[event]
@@ -28,6 +40,20 @@ pub struct IntentFilled {
     pub position_id: u64,
     pub market_maker: Pubkey,
     pub user: Pubkey,
+    pub implied_volatility_bps: u32,
+}
+
+#[event]
+pub struct UnderlyingPremiumSet {
+    pub intent_id: u64,
+    pub max_slippage_bps: Option<u16>,
+}
+
+#[event]
+pub struct UnderlyingPremiumPaid {
+    pub intent_id: u64,
+    pub premium: u64,
+    pub underlying_premium: u64,
 }
 
 #[event]
@@ -40,13 +66,25 @@ pub struct IntentCancelled {
 pub struct IntentExpired {
     pub intent_id: u64,
     pub market_maker: Pubkey,
+    pub caller: Pubkey,
+    /// Lamports tipped to the caller
+    pub tip: u64,
+}
+
+#[event]
+pub struct IntentsBatchExpired {
+    pub intents_expired: u64,
+    pub caller: Pubkey,
+    /// Lamports tipped to the caller across the batch
+    pub total_tip: u64,
 }
 
 #[event]
 pub struct DisputeFlagged {
     pub intent_id: u64,
     pub flagged_by: Pubkey,
-    pub reason: String,
+    pub reason_hash: [u8; 32],
+    pub reason_uri: String,
 }
 
 // ===== Register MM =====
@@ -72,7 +110,7 @@ pub struct RegisterMM<'info> {
         seeds = [NONCE_TRACKER_SEED, owner.key().as_ref()],
         bump
     )]
-    pub nonce_tracker: Account<'info, NonceTracker>,
+    pub nonce_tracker: AccountLoader<'info, NonceTracker>,
 
     pub system_program: Program<'info, System>,
 }
@@ -91,15 +129,25 @@ pub fn handle_register_mm(
     mm_registry.total_intents_expired = 0;
     mm_registry.total_volume = 0;
     mm_registry.reputation_score = 100; // Start with base score
+    mm_registry.unwind_enabled = false;
+    mm_registry.unwind_fee_bps = 0;
+    mm_registry.fill_operator = Pubkey::default();
+    mm_registry.portfolio_margin = false;
+    mm_registry.insurance_contributed = 0;
+    mm_registry.insurance_claims_against = 0;
+    mm_registry.disputes_flagged = 0;
+    mm_registry.last_dispute_flagged_at = 0;
+    mm_registry.frivolous_disputes = 0;
     mm_registry.last_active = clock.unix_timestamp;
     mm_registry.registered_at = clock.unix_timestamp;
     mm_registry.bump = ctx.bumps.mm_registry;
 
-    let nonce_tracker = &mut ctx.accounts.nonce_tracker;
+    let mut nonce_tracker = ctx.accounts.nonce_tracker.load_init()?;
     nonce_tracker.market_maker = ctx.accounts.owner.key();
     nonce_tracker.base_nonce = 0;
     nonce_tracker.used_bitmap = [0; 32];
     nonce_tracker.bump = ctx.bumps.nonce_tracker;
+    nonce_tracker.bucket_seconds = 0;
 
     Ok(())
 }
@@ -128,18 +176,75 @@ pub fn handle_update_mm_signing_key(
     Ok(())
 }
 
+// ===== Set Nonce Bucket =====
+
+#[derive(Accounts)]
+pub struct SetNonceBucket<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [NONCE_TRACKER_SEED, owner.key().as_ref()],
+        bump = nonce_tracker.load()?.bump,
+        constraint = nonce_tracker.load()?.market_maker == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub nonce_tracker: AccountLoader<'info, NonceTracker>,
+}
+
+/// Switch to time-bounded nonces with `bucket_seconds`-wide buckets, or back to
+/// plain sequential nonces with 0. Nonces below the current window stay used
+/// either way, so switching never reopens a signed quote to replay.
+pub fn handle_set_nonce_bucket(ctx: Context<SetNonceBucket>, bucket_seconds: u32) -> Result<()> {
+    require!(
+        bucket_seconds == 0
+            || (MIN_NONCE_BUCKET_SECONDS..=MAX_NONCE_BUCKET_SECONDS).contains(&bucket_seconds),
+        ErrorCode::InvalidNonceBucket
+    );
+
+    let mut nonce_tracker = ctx.accounts.nonce_tracker.load_mut()?;
+    nonce_tracker.bucket_seconds = bucket_seconds;
+    nonce_tracker.expire_windows(Clock::get()?.unix_timestamp);
+    Ok(())
+}
+
+// ===== Set MM Fill Operator =====
+
+#[derive(Accounts)]
+pub struct SetMMFillOperator<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, owner.key().as_ref()],
+        bump = mm_registry.bump,
+        constraint = mm_registry.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+}
+
+/// Pubkey::default() revokes the operator
+pub fn handle_set_mm_fill_operator(
+    ctx: Context<SetMMFillOperator>,
+    fill_operator: Pubkey,
+) -> Result<()> {
+    let mm_registry = &mut ctx.accounts.mm_registry;
+    mm_registry.fill_operator = fill_operator;
+    Ok(())
+}
+
 // ===== Submit Intent =====
 
 #[derive(Accounts)]
-#[instruction(intent_id: u64)]
+#[instruction(params: SubmitIntentParams)]
 pub struct SubmitIntent<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump,
-        constraint = !global_state.paused @ ErrorCode::ProtocolPaused
+        constraint = global_state.operations_enabled(OPERATION_NEW_INTENTS) @ ErrorCode::ProtocolPaused
     )]
     pub global_state: Account<'info, GlobalState>,
 
@@ -151,20 +256,41 @@ pub struct SubmitIntent<'info> {
     )]
     pub mm_registry: Account<'info, MMRegistry>,
 
+    /// Compliance blocklist
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.is_blocked(user.key()) @ ErrorCode::AddressBlocked,
+        constraint = !blocklist.is_blocked(mm_registry.owner) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
     /// Nonce tracker for the MM
     #[account(
         mut,
         seeds = [NONCE_TRACKER_SEED, mm_registry.owner.as_ref()],
-        bump = nonce_tracker.bump
+        bump = nonce_tracker.load()?.bump
+    )]
+    pub nonce_tracker: AccountLoader<'info, NonceTracker>,
+
+    /// Asset being written against: listing state, strike and tenor bounds,
+    /// decimals for escrow math
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, params.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.delisted @ ErrorCode::AssetDelisted,
+        constraint = asset_config.enabled @ ErrorCode::AssetNotEnabled,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused,
+        constraint = asset_config.accepts_quote_mint(params.quote_mint) @ ErrorCode::InvalidQuoteParameters
     )]
-    pub nonce_tracker: Account<'info, NonceTracker>,
+    pub asset_config: Account<'info, AssetConfig>,
 
     /// The intent account to create
     #[account(
         init,
         payer = user,
         space = Intent::LEN,
-        seeds = [INTENT_SEED, user.key().as_ref(), &intent_id.to_le_bytes()],
+        seeds = [INTENT_SEED, user.key().as_ref(), &params.intent_id.to_le_bytes()],
         bump
     )]
     pub intent: Account<'info, Intent>,
@@ -173,35 +299,65 @@ pub struct SubmitIntent<'info> {
     #[account(
         init,
         payer = user,
-        token::mint = quote_mint,
+        token::mint = escrow_mint,
         token::authority = intent,
         seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
         bump
     )]
-    pub user_escrow: Account<'info, TokenAccount>,
+    pub user_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's lifetime stats, created on their first intent
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStats::LEN,
+        seeds = [USER_STATS_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
 
-    /// User's source token account
+    /// User's source token account, holding the escrow mint; omit to escrow
+    /// native SOL when the escrow mint is wrapped SOL
     #[account(
         mut,
-        constraint = user_token_account.owner == user.key()
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == escrow_mint.key() @ ErrorCode::InvalidMint
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    /// Quote mint (USDC)
-    pub quote_mint: Account<'info, anchor_spl::token::Mint>,
+    /// Mint of the escrowed collateral: the quote mint for puts, the
+    /// underlying or the asset's LST for covered calls
+    #[account(
+        constraint = asset_config.is_collateral_mint(params.strategy, params.quote_mint, escrow_mint.key())
+            @ ErrorCode::InvalidCollateralMint
+    )]
+    pub escrow_mint: InterfaceAccount<'info, Mint>,
+
+    /// Oracle feeds for the spot price the strike is bounded against, plus
+    /// the LST rate account when escrowing the asset's LST
+    pub oracle: OracleAccounts<'info>,
+
+    /// KYC pass token account, for assets gated on a pass mint
+    pub access_pass: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// User's credential, for assets gated on a credential issuer
+    pub user_credential: Option<Account<'info, UserCredential>>,
+
+    /// Composer allowlist, required when submitted via CPI
+    #[account(seeds = [COMPOSER_ALLOWLIST_SEED], bump = composer_allowlist.bump)]
+    pub composer_allowlist: Option<Account<'info, ComposerAllowlist>>,
 
     /// Instructions sysvar for Ed25519 signature verification
     /// CHECK: This is the instructions sysvar
     #[account(address = INSTRUCTIONS_SYSVAR_ID)]
     pub instructions_sysvar: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 /// Parameters for submitting an intent
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct SubmitIntentParams {
     pub intent_id: u64,
     pub asset_mint: Pubkey,
@@ -212,29 +368,215 @@ pub struct SubmitIntentParams {
     pub contract_size: u64,
     pub quote_expiry: i64,
     pub quote_nonce: u64,
+    /// Implied volatility the MM priced the quote at, in basis points
+    pub implied_volatility_bps: u32,
     pub mm_signature: [u8; 64],
     /// Index of Ed25519Program instruction in the transaction (typically 0)
     pub ed25519_instruction_index: u8,
+    /// User's own floor on the MM's reputation score, on top of the protocol's
+    pub min_mm_reputation: Option<u32>,
+    /// User's own floor on the MM's fill rate (0-100), on top of the protocol's
+    pub min_mm_fill_rate: Option<u8>,
 }
 
 pub fn handle_submit_intent(
     ctx: Context<SubmitIntent>,
     params: SubmitIntentParams,
+) -> Result<()> {
+    submit_intent(ctx, params, None)
+}
+
+/// Submit an intent sized in quote-currency notional rather than contracts.
+/// The size is converted at a fresh spot read and rounded down to the asset's
+/// lot size; the quote's signed contract size is the most that can be taken.
+pub fn handle_submit_notional_intent(
+    ctx: Context<SubmitIntent>,
+    params: SubmitIntentParams,
+    notional: u64,
+) -> Result<()> {
+    submit_intent(ctx, params, Some(notional))
+}
+
+fn submit_intent(
+    ctx: Context<SubmitIntent>,
+    quote: SubmitIntentParams,
+    notional: Option<u64>,
 ) -> Result<()> {
     let clock = Clock::get()?;
 
-    // 1. Verify quote hasn't expired
-    require!(params.quote_expiry > clock.unix_timestamp, ErrorCode::QuoteExpired);
+    // Programs composing over Solation must be allowlisted
+    require_allowed_caller(
+        &ctx.accounts.instructions_sysvar,
+        ctx.accounts.composer_allowlist.as_deref(),
+    )?;
+
+    // Permissioned assets only accept gated users
+    require_asset_access(
+        &ctx.accounts.asset_config,
+        ctx.accounts.user.key(),
+        ctx.accounts.access_pass.as_ref(),
+        ctx.accounts.user_credential.as_ref(),
+        clock.unix_timestamp,
+    )?;
+
+    // The MM must clear both the protocol's quality floor and the user's
+    require_mm_quality(&ctx.accounts.global_state, &ctx.accounts.mm_registry, &quote)?;
+
+    // Notional-sized intents take as many whole lots of the quote as the
+    // notional buys at spot
+    let spot_price = get_asset_price(
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        &ctx.accounts.asset_config,
+        &clock,
+    )?;
+    let mut params = quote.clone();
+    if let Some(notional) = notional {
+        let contract_size = ctx
+            .accounts
+            .asset_config
+            .contracts_for_notional(notional, spot_price)?;
+        require!(contract_size > 0, ErrorCode::InvalidAmount);
+        require!(
+            contract_size <= quote.contract_size,
+            ErrorCode::NotionalExceedsQuote
+        );
+        params.contract_size = contract_size;
+    }
+
+    // Strike and tenor must sit within the asset's listed bounds
+    require_listed_terms(&ctx.accounts.asset_config, &params, spot_price, clock.unix_timestamp)?;
+    require_intent_notional(&ctx.accounts.global_state, &ctx.accounts.asset_config, &params)?;
+
+    // 1-3. Verify quote expiry, nonce and MM signature
+    verify_signed_quote(
+        &quote,
+        &ctx.accounts.mm_registry,
+        &mut *ctx.accounts.nonce_tracker.load_mut()?,
+        &ctx.accounts.instructions_sysvar,
+        clock.unix_timestamp,
+    )?;
+
+    // 4. Calculate escrow amount based on strategy, pricing LST collateral
+    // at its current exchange rate
+    let escrow_amount = calculate_escrow_amount(
+        params.strategy,
+        params.strike_price,
+        params.contract_size,
+        &settlement_terms_for_vault(
+            &ctx.accounts.asset_config,
+            ctx.accounts.escrow_mint.key(),
+            ctx.accounts.oracle.lst_rate.as_ref(),
+            &clock,
+        )?,
+    )?;
+
+    // 5. Transfer user funds to escrow
+    deposit_user_escrow(
+        &ctx.accounts.user,
+        ctx.accounts.user_token_account.as_ref(),
+        &ctx.accounts.user_escrow,
+        &ctx.accounts.escrow_mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        escrow_amount,
+    )?;
+
+    // 6. Create Intent account
+    let intent = &mut ctx.accounts.intent;
+    populate_intent(
+        intent,
+        &params,
+        ctx.accounts.user.key(),
+        ctx.accounts.mm_registry.owner,
+        ctx.accounts.user_escrow.key(),
+        escrow_amount,
+        ctx.accounts.global_state.intent_fill_timeout,
+        clock.unix_timestamp,
+    );
+    intent.reference_price = spot_price;
+    intent.bump = ctx.bumps.intent;
+
+    let user_stats = &mut ctx.accounts.user_stats;
+    if user_stats.user == Pubkey::default() {
+        user_stats.user = ctx.accounts.user.key();
+        user_stats.bump = ctx.bumps.user_stats;
+    }
+    user_stats.record_intent_submitted(ctx.accounts.global_state.max_pending_intents)?;
+    ctx.accounts.global_state.record_intent_opened()?;
 
-    // 2. Check nonce not reused
-    let nonce_tracker = &mut ctx.accounts.nonce_tracker;
+    emit_intent_created(intent)
+}
+
+/// Move `escrow_amount` from the user into an intent's escrow. The user
+/// covers any mint transfer fee, so the escrow holds exactly `escrow_amount`;
+/// without a source token account, native SOL is wrapped straight into a
+/// wSOL escrow.
+pub(crate) fn deposit_user_escrow<'info>(
+    user: &Signer<'info>,
+    user_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    user_escrow: &InterfaceAccount<'info, TokenAccount>,
+    escrow_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    system_program: &Program<'info, System>,
+    escrow_amount: u64,
+) -> Result<()> {
+    match user_token_account {
+        Some(user_token_account) => {
+            let escrow_deposit = gross_up_for_fee(escrow_mint, escrow_amount)?;
+            let cpi_accounts = TransferChecked {
+                from: user_token_account.to_account_info(),
+                mint: escrow_mint.to_account_info(),
+                to: user_escrow.to_account_info(),
+                authority: user.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            token_interface::transfer_checked(cpi_ctx, escrow_deposit, escrow_mint.decimals)
+        }
+        None => {
+            require!(
+                is_native_mint(escrow_mint.key()),
+                ErrorCode::TokenAccountRequired
+            );
+            wrap_native(
+                &user.to_account_info(),
+                &user_escrow.to_account_info(),
+                &system_program.to_account_info(),
+                &token_program.to_account_info(),
+                escrow_amount,
+            )
+        }
+    }
+}
+
+/// Verify an MM-signed quote: expiry, nonce replay protection and Ed25519 signature.
+/// Marks the nonce as used on success. Time-bounded nonces must sit in the
+/// quote's expiry bucket, and buckets that have ended are dropped first.
+pub(crate) fn verify_signed_quote(
+    params: &SubmitIntentParams,
+    mm_registry: &MMRegistry,
+    nonce_tracker: &mut NonceTracker,
+    instructions_sysvar: &AccountInfo,
+    current_timestamp: i64,
+) -> Result<()> {
+    // Verify quote hasn't expired
+    require!(params.quote_expiry > current_timestamp, ErrorCode::QuoteExpired);
+
+    // Time-bounded nonces are only valid in their quote's expiry bucket
+    require!(
+        nonce_tracker.matches_expiry(params.quote_nonce, params.quote_expiry),
+        ErrorCode::NonceOutsideBucket
+    );
+    nonce_tracker.expire_windows(current_timestamp);
+
+    // Check nonce not reused
     require!(
         !nonce_tracker.is_used(params.quote_nonce),
         ErrorCode::NonceAlreadyUsed
     );
     nonce_tracker.mark_used(params.quote_nonce)?;
 
-    // 3. Verify Ed25519 signature
+    // Verify Ed25519 signature
     let expected_message = construct_quote_message(
         &params.asset_mint,
         &params.quote_mint,
@@ -244,37 +586,129 @@ pub fn handle_submit_intent(
         params.contract_size,
         params.quote_expiry,
         params.quote_nonce,
+        params.implied_volatility_bps,
     );
 
     verify_ed25519_signature(
-        &ctx.accounts.instructions_sysvar,
-        &ctx.accounts.mm_registry.signing_key,
+        instructions_sysvar,
+        &mm_registry.signing_key,
         &expected_message,
         params.ed25519_instruction_index,
-    )?;
-    
-    // 4. Calculate escrow amount based on strategy
-    let escrow_amount = calculate_escrow_amount(
-        params.strategy,
-        params.strike_price,
-        params.contract_size,
+    )
+}
+
+/// Require the quoting MM to clear the protocol's reputation and fill-rate
+/// floor and any stricter one in `params`
+pub(crate) fn require_mm_quality(
+    global_state: &GlobalState,
+    mm_registry: &MMRegistry,
+    params: &SubmitIntentParams,
+) -> Result<()> {
+    let min_reputation = params
+        .min_mm_reputation
+        .unwrap_or(0)
+        .max(global_state.min_mm_reputation);
+    let min_fill_rate = params
+        .min_mm_fill_rate
+        .unwrap_or(0)
+        .max(global_state.min_mm_fill_rate);
+    require!(
+        mm_registry.meets_quality(min_reputation, min_fill_rate),
+        ErrorCode::MMBelowQualityFloor
     );
+    Ok(())
+}
 
-    // 5. Transfer user funds to escrow
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.user_token_account.to_account_info(),
-        to: ctx.accounts.user_escrow.to_account_info(),
-        authority: ctx.accounts.user.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer(cpi_ctx, escrow_amount)?;
+/// Require a quote's strategy on the asset's whitelist, its contract size
+/// within the asset's bounds, its strike within the asset's percentage band
+/// around `spot_price` and its expiry within the asset's tenor window
+pub(crate) fn require_listed_terms(
+    asset_config: &AssetConfig,
+    params: &SubmitIntentParams,
+    spot_price: u64,
+    current_timestamp: i64,
+) -> Result<()> {
+    require!(
+        asset_config.allows_strategy(params.strategy),
+        ErrorCode::StrategyNotAllowed
+    );
+    asset_config.require_contract_size(params.contract_size)?;
+    require!(
+        asset_config.strike_within_bounds(params.strike_price, spot_price),
+        ErrorCode::InvalidStrikeRange
+    );
+    require!(
+        asset_config.expiry_within_bounds(params.quote_expiry, current_timestamp),
+        ErrorCode::InvalidExpiryRange
+    );
+    Ok(())
+}
 
-    // 6. Create Intent account
-    let intent = &mut ctx.accounts.intent;
+/// Require a single intent's strike notional within the protocol-wide and
+/// per-asset caps, so a fat-fingered quote can't open a position larger than
+/// dispute resolution is prepared to handle, and its total premium at or
+/// above both floors, so no position earns less than its rent and settlement
+/// cost
+pub(crate) fn require_intent_notional(
+    global_state: &GlobalState,
+    asset_config: &AssetConfig,
+    params: &SubmitIntentParams,
+) -> Result<()> {
+    let notional = asset_config.notional_value(params.contract_size, params.strike_price)?;
+    let within = |cap: u64| cap == 0 || notional <= cap;
+    require!(
+        within(global_state.max_intent_notional) && within(asset_config.max_intent_notional),
+        ErrorCode::IntentNotionalCapExceeded
+    );
+
+    let premium = params
+        .premium_per_contract
+        .checked_mul(params.contract_size)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        premium >= global_state.min_premium.max(asset_config.min_premium),
+        ErrorCode::PremiumBelowFloor
+    );
+    Ok(())
+}
+
+/// Require spot to be within the asset's fill band around the price recorded
+/// at submission, so neither side is filled at a stale quote during a flash
+/// move. The oracle is only read when the asset has a band configured.
+pub(crate) fn require_fill_price_band(
+    asset_config: &AssetConfig,
+    intent: &Intent,
+    oracle: &OracleAccounts,
+    basket_updates: &[AccountInfo],
+    clock: &Clock,
+) -> Result<()> {
+    if asset_config.max_fill_price_move_bps == 0 || intent.reference_price == 0 {
+        return Ok(());
+    }
+    let spot_price = get_asset_price(oracle, basket_updates, asset_config, clock)?;
+    require!(
+        asset_config.fill_price_within_band(intent.reference_price, spot_price),
+        ErrorCode::FillPriceMoved
+    );
+    Ok(())
+}
+
+/// Fill in a freshly created Intent from a verified quote (bump and
+/// reference price are set by the caller)
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn populate_intent(
+    intent: &mut Intent,
+    params: &SubmitIntentParams,
+    user: Pubkey,
+    market_maker: Pubkey,
+    user_escrow: Pubkey,
+    escrow_amount: u64,
+    fill_timeout: i64,
+    current_timestamp: i64,
+) {
     intent.intent_id = params.intent_id;
-    intent.user = ctx.accounts.user.key();
-    intent.market_maker = ctx.accounts.mm_registry.owner;
+    intent.user = user;
+    intent.market_maker = market_maker;
     intent.asset_mint = params.asset_mint;
     intent.quote_mint = params.quote_mint;
     intent.strategy = params.strategy;
@@ -284,15 +718,19 @@ pub fn handle_submit_intent(
     intent.quote_expiry = params.quote_expiry;
     intent.quote_signature = params.mm_signature;
     intent.quote_nonce = params.quote_nonce;
-    intent.user_escrow = ctx.accounts.user_escrow.key();
+    intent.implied_volatility_bps = params.implied_volatility_bps;
+    intent.user_escrow = user_escrow;
     intent.escrow_amount = escrow_amount;
-    intent.created_at = clock.unix_timestamp;
-    intent.fill_deadline = clock.unix_timestamp + INTENT_FILL_TIMEOUT;
+    intent.created_at = current_timestamp;
+    intent.underlying_premium_slippage_bps = None;
+    intent.fill_deadline = current_timestamp + fill_timeout;
     intent.disputed_by = None;
-    intent.dispute_reason = None;
+    intent.dispute_reason_hash = None;
+    intent.resolution = None;
     intent.status = IntentStatus::Pending;
-    intent.bump = ctx.bumps.intent;
+}
 
+pub(crate) fn emit_intent_created(intent: &Intent) -> Result<()> {
     emit!(IntentCreated {
         intent_id: intent.intent_id,
         user: intent.user,
@@ -300,81 +738,192 @@ pub fn handle_submit_intent(
         asset_mint: intent.asset_mint,
         strategy: intent.strategy,
         strike_price: intent.strike_price,
-        premium: intent.calculate_total_premium(),
+        premium: intent.calculate_total_premium()?,
         contract_size: intent.contract_size,
         fill_deadline: intent.fill_deadline,
+        implied_volatility_bps: intent.implied_volatility_bps,
     });
-
     Ok(())
 }
 
 /// Calculate escrow amount based on strategy
-fn calculate_escrow_amount(
+pub(crate) fn calculate_escrow_amount(
     strategy: StrategyType,
     strike_price: u64,
     contract_size: u64,
-) -> u64 {
+    terms: &SettlementTerms,
+) -> Result<u64> {
     match strategy {
-        // Covered Call: User deposits the underlying asset
-        // For simplicity, we'll use contract_size as the escrow
-        StrategyType::CoveredCall => contract_size,
-        // Cash Secured Put: User deposits strike_price * contract_size
-        StrategyType::CashSecuredPut => {
-            strike_price.saturating_mul(contract_size) / 1_000_000 // Adjust for decimals
-        }
+        // Covered Call: User deposits contract_size of the underlying, or
+        // its value in the LST collateral (rounded up)
+        StrategyType::CoveredCall => terms
+            .to_collateral(contract_size, true)
+            .ok_or(error!(ErrorCode::MathOverflow)),
+        // Cash Secured Put: User deposits strike_price * contract_size in quote
+        // units, rounded up so the put is always fully collateralized
+        StrategyType::CashSecuredPut => terms
+            .quote_value(contract_size, strike_price, true)
+            .ok_or(error!(ErrorCode::MathOverflow)),
     }
 }
 
+// ===== Underlying Premium =====
+
+/// User lets the MM pay this intent's premium in the underlying at oracle
+/// spot, accepting up to `max_slippage_bps` less (None withdraws the offer)
+#[derive(Accounts)]
+pub struct SetUnderlyingPremium<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = user @ ErrorCode::Unauthorized,
+        constraint = intent.is_pending() @ ErrorCode::IntentNotPending
+    )]
+    pub intent: Account<'info, Intent>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, intent.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+}
+
+pub fn handle_set_underlying_premium(
+    ctx: Context<SetUnderlyingPremium>,
+    max_slippage_bps: Option<u16>,
+) -> Result<()> {
+    if let Some(bps) = max_slippage_bps {
+        require!(
+            ctx.accounts.asset_config.underlying_premium
+                && bps <= MAX_UNDERLYING_PREMIUM_SLIPPAGE_BPS,
+            ErrorCode::InvalidUnderlyingPremium
+        );
+    }
+
+    let intent = &mut ctx.accounts.intent;
+    intent.underlying_premium_slippage_bps = max_slippage_bps;
+
+    emit!(UnderlyingPremiumSet {
+        intent_id: intent.intent_id,
+        max_slippage_bps,
+    });
+
+    Ok(())
+}
+
+/// Check premium an MM pays in the underlying against the quoted premium
+/// converted at oracle spot, less the user's slippage allowance
+fn require_underlying_premium(
+    asset_config: &AssetConfig,
+    intent: &Intent,
+    oracle: &OracleAccounts,
+    basket_updates: &[AccountInfo],
+    clock: &Clock,
+    premium: u64,
+    underlying_premium: u64,
+) -> Result<()> {
+    let max_slippage_bps = intent
+        .underlying_premium_slippage_bps
+        .ok_or(ErrorCode::InvalidUnderlyingPremium)?;
+    require!(asset_config.underlying_premium, ErrorCode::InvalidUnderlyingPremium);
+
+    let spot_price = get_asset_price(oracle, basket_updates, asset_config, clock)?;
+    let expected = asset_config.premium_in_underlying(premium, spot_price)?;
+    let (_, min_accepted) = split_bps(expected, max_slippage_bps as u64)?;
+    require!(
+        underlying_premium >= min_accepted,
+        ErrorCode::UnderlyingPremiumShortfall
+    );
+    Ok(())
+}
+
 // ===== Fill Intent =====
 
 #[derive(Accounts)]
 pub struct FillIntent<'info> {
+    /// The quoting MM or its fill operator; pays rent for the new accounts
     #[account(mut)]
     pub market_maker: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump,
-        constraint = !global_state.paused @ ErrorCode::ProtocolPaused
+        constraint = global_state.operations_enabled(OPERATION_FILLS) @ ErrorCode::ProtocolPaused
     )]
     pub global_state: Account<'info, GlobalState>,
 
     #[account(
         mut,
-        constraint = intent.is_pending() @ ErrorCode::IntentNotPending,
-        constraint = intent.market_maker == market_maker.key() @ ErrorCode::UnauthorizedFill
+        constraint = intent.is_pending() @ ErrorCode::IntentNotPending
     )]
     pub intent: Account<'info, Intent>,
 
     #[account(
         mut,
-        seeds = [MM_REGISTRY_SEED, market_maker.key().as_ref()],
+        seeds = [ASSET_CONFIG_SEED, intent.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, intent.market_maker.as_ref()],
         bump = mm_registry.bump,
-        constraint = mm_registry.active @ ErrorCode::MMNotActive
+        constraint = mm_registry.active @ ErrorCode::MMNotActive,
+        constraint = mm_registry.can_fill(market_maker.key()) @ ErrorCode::UnauthorizedFill
     )]
     pub mm_registry: Account<'info, MMRegistry>,
 
+    /// Compliance blocklist
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.is_blocked(intent.market_maker) @ ErrorCode::AddressBlocked,
+        constraint = !blocklist.is_blocked(intent.user) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    /// Intent user's lifetime stats
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, intent.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
     /// User's escrow token account
     #[account(
         mut,
         seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
         bump
     )]
-    pub user_escrow: Account<'info, TokenAccount>,
+    pub user_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Intent user, owner of the premium ATA
+    #[account(address = intent.user @ ErrorCode::Unauthorized)]
+    pub user_wallet: UncheckedAccount<'info>,
 
-    /// User's token account to receive premium
+    /// User's ATA to receive premium, created by the MM if missing
     #[account(
-        mut,
-        constraint = user_token_account.owner == intent.user
+        init_if_needed,
+        payer = market_maker,
+        associated_token::mint = quote_mint,
+        associated_token::authority = user_wallet,
+        associated_token::token_program = token_program
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// MM's token account to pay premium from
+    /// Token account to pay premium from: the signer's own, or the MM vault
+    /// held by the registry PDA
     #[account(
         mut,
         constraint = mm_token_account.owner == market_maker.key()
+            || mm_token_account.owner == mm_registry.key() @ ErrorCode::Unauthorized
     )]
-    pub mm_token_account: Account<'info, TokenAccount>,
+    pub mm_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// Position account to create
     #[account(
@@ -386,12 +935,104 @@ pub struct FillIntent<'info> {
     )]
     pub position: Account<'info, Position>,
 
-    pub token_program: Program<'info, Token>,
+    /// Quote mint of the transferred tokens
+    #[account(address = intent.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Underlying mint, when the premium is paid in the underlying
+    #[account(address = intent.asset_mint @ ErrorCode::InvalidMint)]
+    pub underlying_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// User's account receiving premium paid in the underlying
+    #[account(
+        mut,
+        constraint = user_underlying_account.owner == intent.user @ ErrorCode::Unauthorized,
+        constraint = user_underlying_account.mint == intent.asset_mint @ ErrorCode::InvalidMint
+    )]
+    pub user_underlying_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Spot feeds for the fill-time price band and for premium paid in the
+    /// underlying (otherwise only read when the asset sets a band)
+    pub oracle: OracleAccounts<'info>,
+
+    /// Composer allowlist, required when filled via CPI
+    #[account(seeds = [COMPOSER_ALLOWLIST_SEED], bump = composer_allowlist.bump)]
+    pub composer_allowlist: Option<Account<'info, ComposerAllowlist>>,
+
+    /// Instructions sysvar, to identify a calling program
+    /// CHECK: This is the instructions sysvar
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// Series to enroll the new position in, when its terms match one
+    #[account(mut)]
+    pub option_series: Option<Account<'info, OptionSeries>>,
+
+    /// Installment plan the user offered on this intent; passing it pays only
+    /// the first installment now
+    #[account(
+        mut,
+        seeds = [PREMIUM_SCHEDULE_SEED, position.key().as_ref()],
+        bump = premium_schedule.bump,
+        constraint = !premium_schedule.is_started() @ ErrorCode::InstallmentPlanNotRunning
+    )]
+    pub premium_schedule: Option<Account<'info, PremiumSchedule>>,
+
+    /// Perp adapter allowlist, required to hedge the fill
+    #[account(seeds = [HEDGE_ADAPTER_ALLOWLIST_SEED], bump = hedge_adapter_allowlist.bump)]
+    pub hedge_adapter_allowlist: Option<Box<Account<'info, HedgeAdapterAllowlist>>>,
+
+    /// CHECK: Adapter program to hedge through, checked against the allowlist
+    pub hedge_adapter: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle_fill_intent(ctx: Context<FillIntent>) -> Result<()> {
+pub fn handle_fill_intent<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FillIntent<'info>>,
+) -> Result<()> {
+    fill_intent(ctx, None, None)
+}
+
+/// Fill, then hedge the MM's delta with a perp of `hedge_size` through the
+/// adapter passed as `hedge_adapter`; its venue accounts follow in
+/// `remaining_accounts`, after an index asset's component price updates
+pub fn handle_fill_intent_with_hedge<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FillIntent<'info>>,
+    hedge_size: u64,
+    adapter_data: Vec<u8>,
+) -> Result<()> {
+    fill_intent(
+        ctx,
+        None,
+        Some(FillHedge {
+            size: hedge_size,
+            adapter_data,
+        }),
+    )
+}
+
+/// Fill paying the premium as `underlying_premium` of the underlying, which
+/// must be worth the quoted premium at oracle spot less the user's allowance
+pub fn handle_fill_intent_in_underlying<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FillIntent<'info>>,
+    underlying_premium: u64,
+) -> Result<()> {
+    fill_intent(ctx, Some(underlying_premium), None)
+}
+
+fn fill_intent<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FillIntent<'info>>,
+    underlying_premium: Option<u64>,
+    hedge: Option<FillHedge>,
+) -> Result<()> {
     let clock = Clock::get()?;
+    require_allowed_caller(
+        &ctx.accounts.instructions_sysvar,
+        ctx.accounts.composer_allowlist.as_deref(),
+    )?;
     let intent = &ctx.accounts.intent;
 
     // 1. Verify intent hasn't expired
@@ -400,48 +1041,135 @@ pub fn handle_fill_intent(ctx: Context<FillIntent>) -> Result<()> {
         ErrorCode::IntentExpired
     );
 
-    // 2. Calculate premium
-    let total_premium = intent.calculate_total_premium();
+    // Reject fills after a flash move beyond the asset's band, or of a size
+    // the asset's bounds no longer allow. An index asset's component updates
+    // lead remaining_accounts, ahead of any hedge venue accounts.
+    let (basket_updates, hedge_accounts) =
+        split_basket_updates(ctx.remaining_accounts, &ctx.accounts.asset_config)?;
+    require_fill_price_band(
+        &ctx.accounts.asset_config,
+        intent,
+        &ctx.accounts.oracle,
+        basket_updates,
+        &clock,
+    )?;
+    ctx.accounts.asset_config.require_contract_size(intent.contract_size)?;
+
+    // 2. Calculate premium; an installment plan defers all but the first installment
+    let total_premium = intent.calculate_total_premium()?;
+    let upfront_premium = match ctx.accounts.premium_schedule.as_mut() {
+        Some(schedule) => schedule.start(intent.market_maker, total_premium, clock.unix_timestamp),
+        None => total_premium,
+    };
 
-    // 3. Transfer premium from MM to user
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.mm_token_account.to_account_info(),
-        to: ctx.accounts.user_token_account.to_account_info(),
-        authority: ctx.accounts.market_maker.to_account_info(),
+    // 3. Transfer premium from MM to user, in the quote mint or the underlying
+    // The MM covers any mint transfer fee so the user receives the full premium
+    let (premium_mint, premium_destination, premium_amount) = match underlying_premium {
+        None => (
+            &ctx.accounts.quote_mint,
+            ctx.accounts.user_token_account.to_account_info(),
+            upfront_premium,
+        ),
+        Some(underlying_premium) => {
+            require!(
+                ctx.accounts.premium_schedule.is_none(),
+                ErrorCode::InvalidUnderlyingPremium
+            );
+            require_underlying_premium(
+                &ctx.accounts.asset_config,
+                intent,
+                &ctx.accounts.oracle,
+                basket_updates,
+                &clock,
+                upfront_premium,
+                underlying_premium,
+            )?;
+            let (Some(underlying_mint), Some(user_underlying_account)) = (
+                ctx.accounts.underlying_mint.as_ref(),
+                ctx.accounts.user_underlying_account.as_ref(),
+            ) else {
+                return err!(ErrorCode::TokenAccountRequired);
+            };
+            emit!(UnderlyingPremiumPaid {
+                intent_id: intent.intent_id,
+                premium: upfront_premium,
+                underlying_premium,
+            });
+            (
+                underlying_mint,
+                user_underlying_account.to_account_info(),
+                underlying_premium,
+            )
+        }
     };
+    let premium_sent = gross_up_for_fee(premium_mint, premium_amount)?;
     let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer(cpi_ctx, total_premium)?;
+    if ctx.accounts.mm_token_account.owner == ctx.accounts.mm_registry.key() {
+        // Vault-funded: the registry PDA signs
+        let seeds = &[
+            MM_REGISTRY_SEED,
+            intent.market_maker.as_ref(),
+            &[ctx.accounts.mm_registry.bump],
+        ];
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.mm_token_account.to_account_info(),
+            mint: premium_mint.to_account_info(),
+            to: premium_destination,
+            authority: ctx.accounts.mm_registry.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, &[&seeds[..]]),
+            premium_sent,
+            premium_mint.decimals,
+        )?;
+    } else {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.mm_token_account.to_account_info(),
+            mint: premium_mint.to_account_info(),
+            to: premium_destination,
+            authority: ctx.accounts.market_maker.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, premium_sent, premium_mint.decimals)?;
+    }
 
-    // 4. Return user escrow (the collateral stays with intent for now, 
-    // or we can transfer to a position-specific vault)
-    // For simplicity, we leave escrow in place as position collateral
-    // In production, you'd transfer to position-specific vaults
+    // 4. The user escrow stays in place as the position's user vault, handed
+    // to the position PDA that settlement signs with
+    hand_escrow_to_position(
+        intent,
+        &ctx.accounts.user_escrow,
+        ctx.accounts.position.key(),
+        &ctx.accounts.token_program,
+    )?;
 
     // 5. Create Position
+    let notional = ctx
+        .accounts
+        .asset_config
+        .notional_value(intent.contract_size, intent.strike_price)?;
     let position = &mut ctx.accounts.position;
-    position.position_id = intent.intent_id;
-    position.user = intent.user;
-    position.market_maker = intent.market_maker;
-    position.strategy = intent.strategy;
-    position.asset_mint = intent.asset_mint;
-    position.quote_mint = intent.quote_mint;
-    position.strike_price = intent.strike_price;
-    position.premium_paid = total_premium;
-    position.contract_size = intent.contract_size;
-    position.created_at = clock.unix_timestamp;
-    position.expiry_timestamp = intent.quote_expiry;
-    position.settlement_price = None;
-    position.status = PositionStatus::Active;
-    position.user_vault = intent.user_escrow; // Reuse escrow as user vault
-    position.mm_vault_locked = ctx.accounts.mm_token_account.key(); // Track MM account
+    // Track the MM's premium account; nothing is locked on the MM side
+    open_position(
+        position,
+        &intent.position_terms(),
+        notional,
+        upfront_premium,
+        ctx.accounts.mm_token_account.key(),
+        0,
+        clock.unix_timestamp,
+    );
     position.bump = ctx.bumps.position;
-    position.user_vault_bump = 0; // Not using separate vault
-    position.mm_vault_bump = 0;
+    join_series(position, ctx.accounts.option_series.as_mut())?;
+
+    ctx.accounts.asset_config.record_position_opened(notional)?;
+    ctx.accounts.global_state.record_notional_opened(notional)?;
 
-    // 6. Update MM stats
+    // 6. Update MM and user stats
     let mm_registry = &mut ctx.accounts.mm_registry;
     mm_registry.record_fill(intent.contract_size, clock.unix_timestamp);
+    ctx.accounts.user_stats.record_fill(upfront_premium);
+    ctx.accounts.user_stats.release_pending_intent();
+    ctx.accounts.global_state.record_intent_closed();
 
     // 7. Update intent status
     let intent = &mut ctx.accounts.intent;
@@ -450,13 +1178,108 @@ pub fn handle_fill_intent(ctx: Context<FillIntent>) -> Result<()> {
     emit!(IntentFilled {
         intent_id: intent.intent_id,
         position_id: position.position_id,
-        market_maker: ctx.accounts.market_maker.key(),
+        market_maker: intent.market_maker,
         user: intent.user,
+        implied_volatility_bps: intent.implied_volatility_bps,
     });
 
+    // 8. Hedge the MM's delta through an allowlisted perp adapter
+    if let Some(hedge) = hedge {
+        let protected = [
+            ctx.accounts.user_escrow.key(),
+            ctx.accounts.user_token_account.key(),
+            ctx.accounts.mm_token_account.key(),
+        ];
+        open_fill_hedge(
+            ctx.accounts.hedge_adapter_allowlist.as_deref().map(|allowlist| &**allowlist),
+            ctx.accounts.hedge_adapter.as_deref(),
+            &ctx.accounts.market_maker.to_account_info(),
+            hedge_accounts,
+            &protected,
+            ctx.accounts.position.key(),
+            &ctx.accounts.position,
+            hedge,
+        )?;
+    }
+
     Ok(())
 }
 
+/// Make the position PDA the owner of a filled intent's escrow, which the
+/// position takes over as its user vault
+pub(crate) fn hand_escrow_to_position<'info>(
+    intent: &Account<'info, Intent>,
+    user_escrow: &InterfaceAccount<'info, TokenAccount>,
+    position: Pubkey,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let intent_id_bytes = intent.intent_id.to_le_bytes();
+    let seeds = &[
+        INTENT_SEED,
+        intent.user.as_ref(),
+        &intent_id_bytes,
+        &[intent.bump],
+    ];
+    let cpi_accounts = SetAuthority {
+        current_authority: intent.to_account_info(),
+        account_or_mint: user_escrow.to_account_info(),
+    };
+    token_interface::set_authority(
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, &[&seeds[..]]),
+        AuthorityType::AccountOwner,
+        Some(position),
+    )
+}
+
+/// Open a Position on `terms` (bump is set by the caller). A filled intent's
+/// escrow is reused as the position's user vault; callers hand it to the
+/// position PDA first (see `hand_escrow_to_position`).
+pub(crate) fn open_position(
+    position: &mut Position,
+    terms: &PositionTerms,
+    notional: u64,
+    premium_paid: u64,
+    mm_vault_locked: Pubkey,
+    mm_vault_bump: u8,
+    current_timestamp: i64,
+) {
+    position.position_id = terms.position_id;
+    position.user = terms.user;
+    position.market_maker = terms.market_maker;
+    position.strategy = terms.strategy;
+    position.asset_mint = terms.asset_mint;
+    position.quote_mint = terms.quote_mint;
+    position.strike_price = terms.strike_price;
+    position.premium_paid = premium_paid;
+    position.contract_size = terms.contract_size;
+    position.notional = notional;
+    position.created_at = current_timestamp;
+    position.expiry_timestamp = terms.expiry_timestamp;
+    position.settlement_price = None;
+    position.settlement_recorded_at = 0;
+    position.distributable_at = 0;
+    position.status = PositionStatus::Active;
+    position.stop_loss_price = None;
+    position.take_profit_price = None;
+    position.user_beneficiary = None;
+    position.mm_beneficiary = None;
+    position.user_payout_in_underlying = false;
+    position.user_payout_withheld = 0;
+    position.mm_payout_withheld = 0;
+    position.insurance_paid = 0;
+    position.default_owed = 0;
+    position.default_claim_paid = false;
+    position.series = Pubkey::default();
+    position.holder = terms.user;
+    position.resale_nonce = 0;
+    position.collateral_lender = Pubkey::default();
+    position.implied_volatility_bps = terms.implied_volatility_bps;
+    position.user_vault = terms.user_vault;
+    position.mm_vault_locked = mm_vault_locked;
+    position.user_vault_bump = 0; // Not using separate vault
+    position.mm_vault_bump = mm_vault_bump;
+}
+
 // ===== Cancel Intent =====
 
 #[derive(Accounts)]
@@ -464,6 +1287,9 @@ pub struct CancelIntent<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(mut, seeds = [GLOBAL_STATE_SEED], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
     #[account(
         mut,
         seeds = [INTENT_SEED, user.key().as_ref(), &intent.intent_id.to_le_bytes()],
@@ -473,19 +1299,32 @@ pub struct CancelIntent<'info> {
     )]
     pub intent: Account<'info, Intent>,
 
+    /// User's stats, releasing the intent's pending slot
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, intent.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
     /// User's escrow token account
     #[account(
         mut,
         seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
         bump
     )]
-    pub user_escrow: Account<'info, TokenAccount>,
+    pub user_escrow: InterfaceAccount<'info, TokenAccount>,
 
-    /// User's destination token account
+    /// User's destination token account; omit to receive a wSOL escrow back
+    /// as native SOL
     #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Mint of the escrowed collateral
+    #[account(address = user_escrow.mint @ ErrorCode::InvalidMint)]
+    pub escrow_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handle_cancel_intent(ctx: Context<CancelIntent>) -> Result<()> {
@@ -501,16 +1340,20 @@ pub fn handle_cancel_intent(ctx: Context<CancelIntent>) -> Result<()> {
     ];
     let signer_seeds = &[&seeds[..]];
 
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.user_escrow.to_account_info(),
-        to: ctx.accounts.user_token_account.to_account_info(),
-        authority: ctx.accounts.intent.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-    token::transfer(cpi_ctx, escrow_amount)?;
+    return_escrow(
+        &ctx.accounts.user_escrow,
+        ctx.accounts.user_token_account.as_ref(),
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.intent.to_account_info(),
+        &ctx.accounts.escrow_mint,
+        &ctx.accounts.token_program,
+        escrow_amount,
+        signer_seeds,
+    )?;
 
     // Update status
+    ctx.accounts.user_stats.release_pending_intent();
+    ctx.accounts.global_state.record_intent_closed();
     let intent = &mut ctx.accounts.intent;
     intent.status = IntentStatus::Cancelled;
 
@@ -526,15 +1369,27 @@ pub fn handle_cancel_intent(ctx: Context<CancelIntent>) -> Result<()> {
 
 #[derive(Accounts)]
 pub struct ExpireIntent<'info> {
-    /// Anyone can call this after deadline
+    /// Anyone can call this after deadline, earning the expiry tip
+    #[account(mut)]
     pub caller: Signer<'info>,
 
+    #[account(mut, seeds = [GLOBAL_STATE_SEED], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
     #[account(
         mut,
         constraint = intent.is_pending() @ ErrorCode::IntentNotPending
     )]
     pub intent: Account<'info, Intent>,
 
+    /// User's stats, releasing the intent's pending slot
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, intent.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
     #[account(
         mut,
         seeds = [MM_REGISTRY_SEED, intent.market_maker.as_ref()],
@@ -548,16 +1403,37 @@ pub struct ExpireIntent<'info> {
         seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
         bump
     )]
-    pub user_escrow: Account<'info, TokenAccount>,
+    pub user_escrow: InterfaceAccount<'info, TokenAccount>,
 
-    /// User's destination token account
+    /// User's destination token account; omit to return a wSOL escrow to
+    /// `user_wallet` as native SOL
     #[account(
         mut,
         constraint = user_token_account.owner == intent.user
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Intent's user, receiving an unwrapped wSOL escrow
+    #[account(mut, address = intent.user @ ErrorCode::Unauthorized)]
+    pub user_wallet: UncheckedAccount<'info>,
+
+    /// Mint of the escrowed collateral
+    #[account(address = user_escrow.mint @ ErrorCode::InvalidMint)]
+    pub escrow_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    pub token_program: Program<'info, Token>,
+    /// Pays the caller's tip; omit to expire without one
+    #[account(mut, seeds = [TIP_VAULT_SEED], bump = tip_vault.bump)]
+    pub tip_vault: Option<Account<'info, TipVault>>,
+
+    /// Caller's keeper registration; required while cranks are reserved for keepers
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, caller.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handle_expire_intent(ctx: Context<ExpireIntent>) -> Result<()> {
@@ -569,6 +1445,12 @@ pub fn handle_expire_intent(ctx: Context<ExpireIntent>) -> Result<()> {
         clock.unix_timestamp > intent.fill_deadline,
         ErrorCode::IntentNotExpired
     );
+    require_keeper(
+        &ctx.accounts.global_state,
+        ctx.accounts.keeper.as_ref(),
+        intent.fill_deadline,
+        clock.unix_timestamp,
+    )?;
 
     // Return escrow to user
     let escrow_amount = intent.escrow_amount;
@@ -580,39 +1462,284 @@ pub fn handle_expire_intent(ctx: Context<ExpireIntent>) -> Result<()> {
     ];
     let signer_seeds = &[&seeds[..]];
 
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.user_escrow.to_account_info(),
-        to: ctx.accounts.user_token_account.to_account_info(),
-        authority: ctx.accounts.intent.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-    token::transfer(cpi_ctx, escrow_amount)?;
+    return_escrow(
+        &ctx.accounts.user_escrow,
+        ctx.accounts.user_token_account.as_ref(),
+        &ctx.accounts.user_wallet.to_account_info(),
+        &ctx.accounts.intent.to_account_info(),
+        &ctx.accounts.escrow_mint,
+        &ctx.accounts.token_program,
+        escrow_amount,
+        signer_seeds,
+    )?;
 
     // Penalize MM reputation
     let mm_registry = &mut ctx.accounts.mm_registry;
     mm_registry.record_expire();
 
+    // Tip the caller; an underfunded vault skips the tip rather than
+    // blocking the escrow's return
+    let tip = match ctx.accounts.tip_vault.as_mut() {
+        Some(tip_vault) => pay_tip(tip_vault, &ctx.accounts.caller, EXPIRE_INTENT_TIP_LAMPORTS)?,
+        None => 0,
+    };
+    if let Some(keeper) = active_keeper(&mut ctx.accounts.keeper) {
+        keeper.record_expiration(clock.unix_timestamp);
+    }
+
     // Update status
+    ctx.accounts.user_stats.release_pending_intent();
+    ctx.accounts.global_state.record_intent_closed();
     let intent = &mut ctx.accounts.intent;
     intent.status = IntentStatus::Expired;
 
     emit!(IntentExpired {
         intent_id: intent.intent_id,
         market_maker: intent.market_maker,
+        caller: ctx.accounts.caller.key(),
+        tip,
+    });
+
+    Ok(())
+}
+
+// ===== Expire Intents Batch =====
+// Expire many past-deadline intents in one transaction. Intents already
+// cancelled, filled or expired (e.g. by a racing keeper) are skipped.
+
+/// Accounts per intent in `remaining_accounts`:
+/// intent, user_escrow, user_token_account, mm_registry, user_stats
+pub const EXPIRE_BATCH_ACCOUNTS_PER_INTENT: usize = 5;
+
+#[derive(Accounts)]
+pub struct ExpireIntentsBatch<'info> {
+    /// Anyone can call this after the deadlines, earning a tip per intent
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(mut, seeds = [GLOBAL_STATE_SEED], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// Escrow mint shared by every intent in the batch
+    pub escrow_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Pays the caller's tips; omit to expire without them
+    #[account(mut, seeds = [TIP_VAULT_SEED], bump = tip_vault.bump)]
+    pub tip_vault: Option<Account<'info, TipVault>>,
+
+    /// Caller's keeper registration; required while cranks are reserved for keepers
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, caller.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_expire_intents_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExpireIntentsBatch<'info>>,
+) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty()
+            && remaining
+                .chunks_exact(EXPIRE_BATCH_ACCOUNTS_PER_INTENT)
+                .remainder()
+                .is_empty(),
+        ErrorCode::InvalidBatchAccounts
+    );
+
+    let clock = Clock::get()?;
+    let caller = ctx.accounts.caller.key();
+    let mut intents_expired: u64 = 0;
+    let mut total_tip: u64 = 0;
+    for group in remaining.chunks(EXPIRE_BATCH_ACCOUNTS_PER_INTENT) {
+        let [intent_info, escrow_info, user_token_info, mm_registry_info, user_stats_info] = group
+        else {
+            return err!(ErrorCode::InvalidBatchAccounts);
+        };
+
+        let mut intent: Account<'info, Intent> = Account::try_from(intent_info)?;
+        if !intent.is_pending() {
+            continue;
+        }
+        require!(
+            clock.unix_timestamp > intent.fill_deadline,
+            ErrorCode::IntentNotExpired
+        );
+        require_keeper(
+            &ctx.accounts.global_state,
+            ctx.accounts.keeper.as_ref(),
+            intent.fill_deadline,
+            clock.unix_timestamp,
+        )?;
+
+        let intent_key = intent.key();
+        let (expected_escrow, escrow_bump) = Pubkey::find_program_address(
+            &[USER_ESCROW_SEED, intent_key.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(escrow_info.key(), expected_escrow, ErrorCode::InvalidBatchAccounts);
+        let user_escrow: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(escrow_info)?;
+        let user_token_account: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(user_token_info)?;
+        require!(
+            user_escrow.mint == ctx.accounts.escrow_mint.key()
+                && user_token_account.owner == intent.user
+                && user_token_account.mint == ctx.accounts.escrow_mint.key(),
+            ErrorCode::InvalidBatchAccounts
+        );
+
+        // Registries only exist at their PDA, so the owner field pins it
+        let mut mm_registry: Account<'info, MMRegistry> = Account::try_from(mm_registry_info)?;
+        require_keys_eq!(
+            mm_registry.owner,
+            intent.market_maker,
+            ErrorCode::InvalidBatchAccounts
+        );
+
+        // Always paid into a token account, so no wallet is needed for wSOL
+        let seeds = &[USER_ESCROW_SEED, intent_key.as_ref(), &[escrow_bump]];
+        return_escrow(
+            &user_escrow,
+            Some(&user_token_account),
+            intent_info,
+            intent_info,
+            &ctx.accounts.escrow_mint,
+            &ctx.accounts.token_program,
+            intent.escrow_amount,
+            &[&seeds[..]],
+        )?;
+
+        let mut user_stats = user_stats_for(user_stats_info, intent.user)?;
+
+        mm_registry.record_expire();
+        user_stats.release_pending_intent();
+        ctx.accounts.global_state.record_intent_closed();
+        intent.status = IntentStatus::Expired;
+
+        let tip = match ctx.accounts.tip_vault.as_mut() {
+            Some(tip_vault) => pay_tip(tip_vault, &ctx.accounts.caller, EXPIRE_INTENT_TIP_LAMPORTS)?,
+            None => 0,
+        };
+        if let Some(keeper) = active_keeper(&mut ctx.accounts.keeper) {
+            keeper.record_expiration(clock.unix_timestamp);
+        }
+
+        // Persist the updated accounts (not Anchor-managed here)
+        intent.exit(&crate::ID)?;
+        mm_registry.exit(&crate::ID)?;
+        user_stats.exit(&crate::ID)?;
+
+        emit!(IntentExpired {
+            intent_id: intent.intent_id,
+            market_maker: intent.market_maker,
+            caller,
+            tip,
+        });
+
+        intents_expired += 1;
+        total_tip = total_tip.saturating_add(tip);
+    }
+
+    emit!(IntentsBatchExpired {
+        intents_expired,
+        caller,
+        total_tip,
     });
 
     Ok(())
 }
 
+/// Load a batch entry's UserStats, checking it is `user`'s PDA
+pub(crate) fn user_stats_for<'info>(
+    user_stats_info: &'info AccountInfo<'info>,
+    user: Pubkey,
+) -> Result<Account<'info, UserStats>> {
+    let user_stats: Account<'info, UserStats> = Account::try_from(user_stats_info)?;
+    let expected = Pubkey::create_program_address(
+        &[USER_STATS_SEED, user.as_ref(), &[user_stats.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidBatchAccounts))?;
+    require_keys_eq!(user_stats_info.key(), expected, ErrorCode::InvalidBatchAccounts);
+    Ok(user_stats)
+}
+
+/// Move `amount` lamports from the tip vault to `recipient`, keeping the
+/// vault rent-exempt. Returns the tip paid: `amount`, or 0 if underfunded.
+fn pay_tip<'info>(
+    tip_vault: &mut Account<'info, TipVault>,
+    recipient: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<u64> {
+    let vault_info = tip_vault.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(vault_info.data_len());
+    let available = vault_info.lamports().saturating_sub(rent_exempt);
+    if available < amount {
+        return Ok(0);
+    }
+
+    vault_info.sub_lamports(amount)?;
+    recipient.add_lamports(amount)?;
+    tip_vault.total_tipped = tip_vault.total_tipped.saturating_add(amount);
+    Ok(amount)
+}
+
+/// Return an unfilled intent's escrow: into the user's token account, or
+/// unwrapped to their wallet when no account is given and the escrow is wSOL
+#[allow(clippy::too_many_arguments)]
+fn return_escrow<'info>(
+    user_escrow: &InterfaceAccount<'info, TokenAccount>,
+    user_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    user_wallet: &AccountInfo<'info>,
+    intent: &AccountInfo<'info>,
+    escrow_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    escrow_amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let Some(user_token_account) = user_token_account else {
+        require!(
+            is_native_mint(escrow_mint.key()),
+            ErrorCode::TokenAccountRequired
+        );
+        return unwrap_native(
+            &user_escrow.to_account_info(),
+            user_wallet,
+            intent,
+            &token_program.to_account_info(),
+            signer_seeds,
+        );
+    };
+
+    let cpi_accounts = TransferChecked {
+        from: user_escrow.to_account_info(),
+        mint: escrow_mint.to_account_info(),
+        to: user_token_account.to_account_info(),
+        authority: intent.clone(),
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token_interface::transfer_checked(cpi_ctx, escrow_amount, escrow_mint.decimals)
+}
+
 // ===== Flag Dispute =====
 
 #[derive(Accounts)]
 pub struct FlagDispute<'info> {
+    #[account(mut)]
     pub signer: Signer<'info>,
 
+    /// Grown to hold the dispute fields (flagger pays the extra rent)
     #[account(
         mut,
+        realloc = Intent::DISPUTED_LEN,
+        realloc::payer = signer,
+        realloc::zero = false,
         constraint = intent.is_pending() @ ErrorCode::IntentNotPending,
         constraint = 
             signer.key() == intent.user || 
@@ -620,26 +1747,91 @@ pub struct FlagDispute<'info> {
             @ ErrorCode::UnauthorizedDispute
     )]
     pub intent: Account<'info, Intent>,
+
+    #[account(seeds = [GLOBAL_STATE_SEED], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// Intent user's stats, counting flags raised by the user
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, intent.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    /// Quoting MM's registry, counting flags raised by the MM
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, intent.market_maker.as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Box<Account<'info, MMRegistry>>,
+
+    /// Evidence log for the dispute (flagger pays rent and posts the bond)
+    #[account(
+        init,
+        payer = signer,
+        space = DisputeRecord::LEN,
+        seeds = [DISPUTE_RECORD_SEED, intent.key().as_ref()],
+        bump
+    )]
+    pub dispute_record: Account<'info, DisputeRecord>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handle_flag_dispute(
     ctx: Context<FlagDispute>,
-    reason: String,
+    reason_hash: [u8; 32],
+    reason_uri: String,
 ) -> Result<()> {
     require!(
-        reason.len() <= MAX_DISPUTE_REASON_LEN,
+        reason_uri.len() <= MAX_EVIDENCE_URI_LEN,
         ErrorCode::DisputeReasonTooLong
     );
 
+    // Each party may only flag once per cooldown, so neither side can
+    // freeze the other's intents one after another
+    let cooldown = ctx.accounts.global_state.dispute_flag_cooldown;
+    let now = Clock::get()?.unix_timestamp;
+    if ctx.accounts.signer.key() == ctx.accounts.intent.user {
+        ctx.accounts.user_stats.record_dispute_flagged(cooldown, now)?;
+    } else {
+        ctx.accounts.mm_registry.record_dispute_flagged(cooldown, now)?;
+    }
+
     let intent = &mut ctx.accounts.intent;
     intent.status = IntentStatus::Disputed;
     intent.disputed_by = Some(ctx.accounts.signer.key());
-    intent.dispute_reason = Some(reason.clone());
+    intent.dispute_reason_hash = Some(reason_hash);
+
+    let dispute_record = &mut ctx.accounts.dispute_record;
+    dispute_record.intent = intent.key();
+    dispute_record.user = intent.user;
+    dispute_record.market_maker = intent.market_maker;
+    dispute_record.flagged_by = ctx.accounts.signer.key();
+    dispute_record.bond = ctx.accounts.global_state.dispute_bond;
+    dispute_record.entries = Vec::new();
+    dispute_record.bump = ctx.bumps.dispute_record;
+
+    if dispute_record.bond > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: dispute_record.to_account_info(),
+                },
+            ),
+            dispute_record.bond,
+        )?;
+    }
 
     emit!(DisputeFlagged {
         intent_id: intent.intent_id,
         flagged_by: ctx.accounts.signer.key(),
-        reason,
+        reason_hash,
+        reason_uri,
     });
 
     Ok(())
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+// ===== Register Keeper =====
+
+#[derive(Accounts)]
+#[instruction(keeper: Pubkey)]
+pub struct RegisterKeeper<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Keeper::LEN,
+        seeds = [KEEPER_SEED, keeper.as_ref()],
+        bump
+    )]
+    pub keeper_account: Account<'info, Keeper>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays rent for the keeper account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_register_keeper(ctx: Context<RegisterKeeper>, keeper: Pubkey) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let keeper_account = &mut ctx.accounts.keeper_account;
+    keeper_account.keeper = keeper;
+    keeper_account.active = true;
+    keeper_account.intents_expired = 0;
+    keeper_account.positions_settled = 0;
+    keeper_account.triggers_executed = 0;
+    keeper_account.last_active = 0;
+    keeper_account.registered_at = clock.unix_timestamp;
+    keeper_account.bump = ctx.bumps.keeper_account;
+
+    Ok(())
+}
+
+// ===== Set Keeper Active =====
+
+#[derive(Accounts)]
+pub struct SetKeeperActive<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, keeper_account.keeper.as_ref()],
+        bump = keeper_account.bump
+    )]
+    pub keeper_account: Account<'info, Keeper>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handle_set_keeper_active(ctx: Context<SetKeeperActive>, active: bool) -> Result<()> {
+    ctx.accounts.keeper_account.active = active;
+    Ok(())
+}
+
+/// Enforce keeper permissioning for a crank that fell due at `due_at`: while
+/// the grace period runs, the caller must bring its own active Keeper account
+/// (the account's seeds bind it to the caller)
+pub(crate) fn require_keeper(
+    global_state: &GlobalState,
+    keeper: Option<&Account<Keeper>>,
+    due_at: i64,
+    now: i64,
+) -> Result<()> {
+    require!(
+        keeper.is_some_and(|keeper| keeper.active) || !global_state.keeper_only(due_at, now),
+        ErrorCode::KeeperRequired
+    );
+    Ok(())
+}
+
+/// The caller's Keeper account, if it brought an active one, for stats
+pub(crate) fn active_keeper<'a, 'info>(
+    keeper: &'a mut Option<Account<'info, Keeper>>,
+) -> Option<&'a mut Account<'info, Keeper>> {
+    keeper.as_mut().filter(|keeper| keeper.active)
+}
@@ -0,0 +1,799 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    self, Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::instructions::option_vault::{VaultFeesCharged, VaultFeesSet};
+use crate::state::*;
+use crate::utils::*;
+
+// ===== Events =====
+
+#[event]
+pub struct LiquidityVaultCreated {
+    pub vault: Pubkey,
+    pub quote_mint: Pubkey,
+    pub share_mint: Pubkey,
+}
+
+#[event]
+pub struct LiquidityVaultConfigured {
+    pub vault: Pubkey,
+    pub premium_share_bps: u16,
+    pub max_utilization_bps: u16,
+    pub epoch_duration: i64,
+    pub deposit_cap: u64,
+}
+
+#[event]
+pub struct LpBackingSet {
+    pub vault: Pubkey,
+    pub market_maker: Pubkey,
+    pub credit_limit: u64,
+}
+
+#[event]
+pub struct LiquidityDeposited {
+    pub vault: Pubkey,
+    pub lp: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct LpWithdrawalRequested {
+    pub vault: Pubkey,
+    pub lp: Pubkey,
+    pub shares: u64,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct LpWithdrawalClaimed {
+    pub vault: Pubkey,
+    pub lp: Pubkey,
+    pub shares: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LpBackingRepaid {
+    pub vault: Pubkey,
+    pub market_maker: Pubkey,
+    pub amount: u64,
+    pub remaining_owed: u64,
+}
+
+fn require_liquidity_terms(
+    premium_share_bps: u16,
+    max_utilization_bps: u16,
+    epoch_duration: i64,
+) -> Result<()> {
+    require!(
+        premium_share_bps <= MAX_LP_PREMIUM_SHARE_BPS
+            && max_utilization_bps as u64 <= BASIS_POINTS_DIVISOR
+            && epoch_duration >= MIN_LP_EPOCH_DURATION,
+        ErrorCode::InvalidLiquidityVaultConfig
+    );
+    Ok(())
+}
+
+// ===== Create Liquidity Vault =====
+// One pool per quote mint. LP quote sits in the vault's ATA until a
+// whitelisted MM draws it as escrowed-fill collateral (complete_escrowed_fill
+// with the vault's ATA as the collateral source).
+
+#[derive(Accounts)]
+pub struct CreateLiquidityVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = LiquidityVault::LEN,
+        seeds = [LIQUIDITY_VAULT_SEED, quote_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Box<Account<'info, LiquidityVault>>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = quote_mint.decimals,
+        mint::authority = vault,
+        mint::token_program = token_program,
+        seeds = [LP_SHARE_MINT_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Idle LP liquidity
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Shares held for queued withdrawals
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = share_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub share_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_liquidity_vault(
+    ctx: Context<CreateLiquidityVault>,
+    premium_share_bps: u16,
+    max_utilization_bps: u16,
+    epoch_duration: i64,
+) -> Result<()> {
+    require_liquidity_terms(premium_share_bps, max_utilization_bps, epoch_duration)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.quote_mint = ctx.accounts.quote_mint.key();
+    vault.share_mint = ctx.accounts.share_mint.key();
+    vault.premium_share_bps = premium_share_bps;
+    vault.max_utilization_bps = max_utilization_bps;
+    vault.deposit_cap = 0;
+    vault.outstanding = 0;
+    vault.total_premium_shared = 0;
+    vault.epoch = 0;
+    vault.epoch_started_at = Clock::get()?.unix_timestamp;
+    vault.epoch_duration = epoch_duration;
+    vault.pending_withdrawal_shares = 0;
+    vault.fees = VaultFees::new(vault.epoch_started_at);
+    vault.bump = ctx.bumps.vault;
+    vault.share_mint_bump = ctx.bumps.share_mint;
+
+    emit!(LiquidityVaultCreated {
+        vault: vault.key(),
+        quote_mint: vault.quote_mint,
+        share_mint: vault.share_mint,
+    });
+    emit!(LiquidityVaultConfigured {
+        vault: vault.key(),
+        premium_share_bps,
+        max_utilization_bps,
+        epoch_duration,
+        deposit_cap: 0,
+    });
+
+    Ok(())
+}
+
+// ===== Configure Liquidity Vault =====
+// New terms apply to later draws and to the epoch after the current one; a
+// lower deposit cap only stops new deposits.
+
+#[derive(Accounts)]
+pub struct ConfigureLiquidityVault<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_VAULT_SEED, vault.quote_mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, LiquidityVault>,
+}
+
+pub fn handle_configure_liquidity_vault(
+    ctx: Context<ConfigureLiquidityVault>,
+    premium_share_bps: u16,
+    max_utilization_bps: u16,
+    epoch_duration: i64,
+    deposit_cap: u64,
+) -> Result<()> {
+    require_liquidity_terms(premium_share_bps, max_utilization_bps, epoch_duration)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.premium_share_bps = premium_share_bps;
+    vault.max_utilization_bps = max_utilization_bps;
+    vault.epoch_duration = epoch_duration;
+    vault.deposit_cap = deposit_cap;
+
+    emit!(LiquidityVaultConfigured {
+        vault: vault.key(),
+        premium_share_bps,
+        max_utilization_bps,
+        epoch_duration,
+        deposit_cap,
+    });
+
+    Ok(())
+}
+
+// ===== Set Liquidity Vault Fees =====
+
+#[derive(Accounts)]
+pub struct SetLiquidityVaultFees<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_VAULT_SEED, vault.quote_mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, LiquidityVault>,
+}
+
+pub fn handle_set_liquidity_vault_fees(
+    ctx: Context<SetLiquidityVaultFees>,
+    management_fee_bps: u16,
+    performance_fee_bps: u16,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.fees.set_rates(management_fee_bps, performance_fee_bps)?;
+
+    emit!(VaultFeesSet {
+        vault: vault.key(),
+        management_fee_bps,
+        performance_fee_bps,
+    });
+
+    Ok(())
+}
+
+// ===== Set LP Backing =====
+// Authority whitelists a registered MM on the vault with a credit limit.
+// Lowering the limit (to zero to suspend) stops new draws; what the MM
+// already owes stays owed.
+
+#[derive(Accounts)]
+#[instruction(market_maker: Pubkey)]
+pub struct SetLpBacking<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [LIQUIDITY_VAULT_SEED, vault.quote_mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, LiquidityVault>,
+
+    #[account(
+        seeds = [MM_REGISTRY_SEED, market_maker.as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = LpBacking::LEN,
+        seeds = [LP_BACKING_SEED, vault.key().as_ref(), market_maker.as_ref()],
+        bump
+    )]
+    pub lp_backing: Account<'info, LpBacking>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_set_lp_backing(
+    ctx: Context<SetLpBacking>,
+    market_maker: Pubkey,
+    credit_limit: u64,
+) -> Result<()> {
+    let lp_backing = &mut ctx.accounts.lp_backing;
+    if lp_backing.market_maker == Pubkey::default() {
+        lp_backing.vault = ctx.accounts.vault.key();
+        lp_backing.market_maker = market_maker;
+        lp_backing.bump = ctx.bumps.lp_backing;
+    }
+    lp_backing.credit_limit = credit_limit;
+
+    emit!(LpBackingSet {
+        vault: lp_backing.vault,
+        market_maker,
+        credit_limit,
+    });
+
+    Ok(())
+}
+
+// ===== Deposit Liquidity =====
+// Shares are priced against idle liquidity plus what MMs owe the vault.
+
+#[derive(Accounts)]
+pub struct DepositLiquidity<'info> {
+    #[account(mut)]
+    pub lp: Signer<'info>,
+
+    #[account(
+        seeds = [LIQUIDITY_VAULT_SEED, vault.quote_mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, LiquidityVault>>,
+
+    /// Compliance blocklist
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.is_blocked(lp.key()) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Box<Account<'info, Blocklist>>,
+
+    #[account(address = vault.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = vault.share_mint @ ErrorCode::InvalidMint)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = lp_quote_account.owner == lp.key() @ ErrorCode::Unauthorized,
+        constraint = lp_quote_account.mint == vault.quote_mint @ ErrorCode::InvalidMint
+    )]
+    pub lp_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = lp,
+        associated_token::mint = share_mint,
+        associated_token::authority = lp,
+        associated_token::token_program = token_program
+    )]
+    pub lp_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_deposit_liquidity(ctx: Context<DepositLiquidity>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    let vault = &ctx.accounts.vault;
+
+    let nav = vault
+        .net_asset_value(ctx.accounts.vault_quote_account.amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    // Shares are minted for what arrives after any mint transfer fee
+    let received = amount - transfer_fee_for(&ctx.accounts.quote_mint, amount)?;
+    require!(
+        vault.deposit_cap == 0 || nav.saturating_add(received) <= vault.deposit_cap,
+        ErrorCode::DepositCapExceeded
+    );
+    require!(
+        nav > 0 || ctx.accounts.share_mint.supply == 0,
+        ErrorCode::VaultNavDepleted
+    );
+    let shares = LiquidityVault::shares_for_deposit(received, nav, ctx.accounts.share_mint.supply)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(shares > 0, ErrorCode::InvalidAmount);
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.lp_quote_account.to_account_info(),
+        mint: ctx.accounts.quote_mint.to_account_info(),
+        to: ctx.accounts.vault_quote_account.to_account_info(),
+        authority: ctx.accounts.lp.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        amount,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+
+    let seeds = &[LIQUIDITY_VAULT_SEED, vault.quote_mint.as_ref(), &[vault.bump]];
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        to: ctx.accounts.lp_share_account.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &[&seeds[..]],
+        ),
+        shares,
+    )?;
+
+    emit!(LiquidityDeposited {
+        vault: vault.key(),
+        lp: ctx.accounts.lp.key(),
+        amount,
+        shares,
+    });
+
+    Ok(())
+}
+
+// ===== Request LP Withdrawal =====
+// The LP's shares move into the vault's share escrow; they keep earning until
+// the request is claimed after the current epoch ends.
+
+#[derive(Accounts)]
+pub struct RequestLpWithdrawal<'info> {
+    #[account(mut)]
+    pub lp: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_VAULT_SEED, vault.quote_mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, LiquidityVault>>,
+
+    #[account(address = vault.share_mint @ ErrorCode::InvalidMint)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = lp_share_account.owner == lp.key() @ ErrorCode::Unauthorized,
+        constraint = lp_share_account.mint == vault.share_mint @ ErrorCode::InvalidMint
+    )]
+    pub lp_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub share_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// One queued request per LP at a time
+    #[account(
+        init,
+        payer = lp,
+        space = LpWithdrawal::LEN,
+        seeds = [LP_WITHDRAWAL_SEED, vault.key().as_ref(), lp.key().as_ref()],
+        bump
+    )]
+    pub withdrawal: Box<Account<'info, LpWithdrawal>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_request_lp_withdrawal(ctx: Context<RequestLpWithdrawal>, shares: u64) -> Result<()> {
+    require!(shares > 0, ErrorCode::InvalidAmount);
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.lp_share_account.to_account_info(),
+        mint: ctx.accounts.share_mint.to_account_info(),
+        to: ctx.accounts.share_escrow.to_account_info(),
+        authority: ctx.accounts.lp.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        shares,
+        ctx.accounts.share_mint.decimals,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.pending_withdrawal_shares = vault
+        .pending_withdrawal_shares
+        .checked_add(shares)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let withdrawal = &mut ctx.accounts.withdrawal;
+    withdrawal.vault = vault.key();
+    withdrawal.lp = ctx.accounts.lp.key();
+    withdrawal.shares = shares;
+    withdrawal.epoch = vault.epoch;
+    withdrawal.bump = ctx.bumps.withdrawal;
+
+    emit!(LpWithdrawalRequested {
+        vault: vault.key(),
+        lp: withdrawal.lp,
+        shares,
+        epoch: withdrawal.epoch,
+    });
+
+    Ok(())
+}
+
+// ===== Advance Liquidity Epoch =====
+// Permissionless once the epoch's duration has passed. The epoch's
+// management and performance fees are minted to the treasury in shares.
+
+#[derive(Accounts)]
+pub struct AdvanceLiquidityEpoch<'info> {
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_VAULT_SEED, vault.quote_mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, LiquidityVault>>,
+
+    #[account(seeds = [GLOBAL_STATE_SEED], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(mut, address = vault.share_mint @ ErrorCode::InvalidMint)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        associated_token::mint = vault.quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury's share account, receiving the fee shares
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = global_state.treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_advance_liquidity_epoch(ctx: Context<AdvanceLiquidityEpoch>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vault = &mut ctx.accounts.vault;
+    require!(vault.epoch_ended(now), ErrorCode::LpEpochNotEnded);
+
+    let nav = vault
+        .net_asset_value(ctx.accounts.vault_quote_account.amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let charge = vault
+        .fees
+        .charge(nav, ctx.accounts.share_mint.supply, now)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if charge.fee_shares > 0 {
+        let seeds = &[LIQUIDITY_VAULT_SEED, vault.quote_mint.as_ref(), &[vault.bump]];
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            to: ctx.accounts.treasury_share_account.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                &[&seeds[..]],
+            ),
+            charge.fee_shares,
+        )?;
+    }
+    emit!(VaultFeesCharged {
+        vault: vault.key(),
+        treasury: ctx.accounts.global_state.treasury,
+        management_fee: charge.management_fee,
+        performance_fee: charge.performance_fee,
+        fee_shares: charge.fee_shares,
+        high_water_mark: vault.fees.high_water_mark,
+    });
+
+    vault.epoch += 1;
+    vault.epoch_started_at = now;
+
+    Ok(())
+}
+
+// ===== Claim LP Withdrawal =====
+// Pays the escrowed shares' value at claim time out of idle liquidity.
+
+#[derive(Accounts)]
+pub struct ClaimLpWithdrawal<'info> {
+    #[account(mut)]
+    pub lp: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_VAULT_SEED, vault.quote_mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, LiquidityVault>>,
+
+    #[account(
+        mut,
+        seeds = [LP_WITHDRAWAL_SEED, vault.key().as_ref(), lp.key().as_ref()],
+        bump = withdrawal.bump,
+        constraint = withdrawal.epoch < vault.epoch @ ErrorCode::LpEpochNotEnded,
+        close = lp
+    )]
+    pub withdrawal: Box<Account<'info, LpWithdrawal>>,
+
+    #[account(address = vault.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = vault.share_mint @ ErrorCode::InvalidMint)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub share_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = lp_quote_account.owner == lp.key() @ ErrorCode::Unauthorized,
+        constraint = lp_quote_account.mint == vault.quote_mint @ ErrorCode::InvalidMint
+    )]
+    pub lp_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_claim_lp_withdrawal(ctx: Context<ClaimLpWithdrawal>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let shares = ctx.accounts.withdrawal.shares;
+    let idle_balance = ctx.accounts.vault_quote_account.amount;
+
+    let nav = vault
+        .net_asset_value(idle_balance)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let amount = LiquidityVault::redemption_amount(nav, shares, ctx.accounts.share_mint.supply)
+        .ok_or(ErrorCode::InvalidAmount)?;
+    require!(amount <= idle_balance, ErrorCode::LiquidityUtilized);
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let seeds = &[LIQUIDITY_VAULT_SEED, vault.quote_mint.as_ref(), &[vault.bump]];
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        from: ctx.accounts.share_escrow.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    token_interface::burn(
+        CpiContext::new_with_signer(token_program.clone(), cpi_accounts, &[&seeds[..]]),
+        shares,
+    )?;
+
+    if amount > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_quote_account.to_account_info(),
+            mint: ctx.accounts.quote_mint.to_account_info(),
+            to: ctx.accounts.lp_quote_account.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(token_program, cpi_accounts, &[&seeds[..]]),
+            amount,
+            ctx.accounts.quote_mint.decimals,
+        )?;
+    }
+
+    let vault = &mut ctx.accounts.vault;
+    vault.pending_withdrawal_shares = vault.pending_withdrawal_shares.saturating_sub(shares);
+
+    emit!(LpWithdrawalClaimed {
+        vault: vault.key(),
+        lp: ctx.accounts.lp.key(),
+        shares,
+        amount,
+    });
+
+    Ok(())
+}
+
+// ===== Repay LP Backing =====
+// MM pays down what it owes the vault: premium share first, then any drawn
+// collateral that did not come back on release (e.g. delivered physically).
+
+#[derive(Accounts)]
+pub struct RepayLpBacking<'info> {
+    pub market_maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_VAULT_SEED, vault.quote_mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, LiquidityVault>>,
+
+    #[account(
+        mut,
+        seeds = [LP_BACKING_SEED, vault.key().as_ref(), market_maker.key().as_ref()],
+        bump = lp_backing.bump
+    )]
+    pub lp_backing: Box<Account<'info, LpBacking>>,
+
+    #[account(address = vault.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = mm_token_account.owner == market_maker.key() @ ErrorCode::Unauthorized,
+        constraint = mm_token_account.mint == vault.quote_mint @ ErrorCode::InvalidMint
+    )]
+    pub mm_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_repay_lp_backing(ctx: Context<RepayLpBacking>, amount: u64) -> Result<()> {
+    let applied = ctx.accounts.lp_backing.record_repayment(amount);
+    require!(applied > 0, ErrorCode::InvalidAmount);
+
+    // The MM covers any mint transfer fee so the vault receives `applied`
+    let amount_sent = gross_up_for_fee(&ctx.accounts.quote_mint, applied)?;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.mm_token_account.to_account_info(),
+        mint: ctx.accounts.quote_mint.to_account_info(),
+        to: ctx.accounts.vault_quote_account.to_account_info(),
+        authority: ctx.accounts.market_maker.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        amount_sent,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+
+    ctx.accounts.vault.record_repayment(applied);
+
+    emit!(LpBackingRepaid {
+        vault: ctx.accounts.vault.key(),
+        market_maker: ctx.accounts.market_maker.key(),
+        amount: applied,
+        remaining_owed: ctx.accounts.lp_backing.owed(),
+    });
+
+    Ok(())
+}
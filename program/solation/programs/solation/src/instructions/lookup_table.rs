@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use anchor_lang::solana_program::sysvar::rent::ID as RENT_SYSVAR_ID;
+use anchor_spl::token::ID as TOKEN_PROGRAM_ID;
+use anchor_spl::token_2022::ID as TOKEN_2022_PROGRAM_ID;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::{
+    create_lookup_table, derive_lookup_table_address, extend_lookup_table,
+    ADDRESS_LOOKUP_TABLE_PROGRAM_ID, ED25519_PROGRAM_ID,
+};
+
+// ===== Events =====
+
+#[event]
+pub struct LookupTableExtended {
+    pub lookup_table: Pubkey,
+    pub addresses: Vec<Pubkey>,
+    pub address_count: u16,
+}
+
+// ===== Create Lookup Table =====
+// Authority creates the protocol lookup table and seeds it with the static
+// accounts every intent and fill transaction references
+
+#[derive(Accounts)]
+#[instruction(recent_slot: u64)]
+pub struct CreateProtocolLookupTable<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ProtocolLookupTable::LEN,
+        seeds = [PROTOCOL_LOOKUP_TABLE_SEED],
+        bump
+    )]
+    pub protocol_lookup_table: Account<'info, ProtocolLookupTable>,
+
+    /// CHECK: Created by the lookup table program; address checked in the handler
+    #[account(mut)]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: Address lookup table program
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
+    pub lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_protocol_lookup_table(
+    ctx: Context<CreateProtocolLookupTable>,
+    recent_slot: u64,
+) -> Result<()> {
+    let authority_key = ctx.accounts.protocol_lookup_table.key();
+    let (expected_table, table_bump) = derive_lookup_table_address(&authority_key, recent_slot);
+    require_keys_eq!(
+        ctx.accounts.lookup_table.key(),
+        expected_table,
+        ErrorCode::InvalidLookupTable
+    );
+
+    let bump = ctx.bumps.protocol_lookup_table;
+    let signer_seeds: &[&[&[u8]]] = &[&[PROTOCOL_LOOKUP_TABLE_SEED, &[bump]]];
+    let lookup_table_program = ctx.accounts.lookup_table_program.to_account_info();
+    let lookup_table = ctx.accounts.lookup_table.to_account_info();
+    let table_authority = ctx.accounts.protocol_lookup_table.to_account_info();
+    let payer = ctx.accounts.authority.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    create_lookup_table(
+        &lookup_table_program,
+        &lookup_table,
+        &table_authority,
+        &payer,
+        &system_program,
+        recent_slot,
+        table_bump,
+        signer_seeds,
+    )?;
+
+    let (blocklist, _) = Pubkey::find_program_address(&[BLOCKLIST_SEED], &crate::ID);
+    let static_addresses = vec![
+        crate::ID,
+        ctx.accounts.global_state.key(),
+        blocklist,
+        anchor_lang::system_program::ID,
+        TOKEN_PROGRAM_ID,
+        TOKEN_2022_PROGRAM_ID,
+        ED25519_PROGRAM_ID,
+        INSTRUCTIONS_SYSVAR_ID,
+        RENT_SYSVAR_ID,
+    ];
+    extend_lookup_table(
+        &lookup_table_program,
+        &lookup_table,
+        &table_authority,
+        &payer,
+        &system_program,
+        &static_addresses,
+        signer_seeds,
+    )?;
+
+    let protocol_lookup_table = &mut ctx.accounts.protocol_lookup_table;
+    protocol_lookup_table.lookup_table = expected_table;
+    protocol_lookup_table.address_count = static_addresses.len() as u16;
+    protocol_lookup_table.bump = bump;
+
+    emit!(LookupTableExtended {
+        lookup_table: expected_table,
+        addresses: static_addresses,
+        address_count: protocol_lookup_table.address_count,
+    });
+
+    Ok(())
+}
+
+// ===== Extend Lookup Table =====
+// Authority adds further shared accounts (quote/asset mints, asset configs)
+
+#[derive(Accounts)]
+pub struct ExtendProtocolLookupTable<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_LOOKUP_TABLE_SEED],
+        bump = protocol_lookup_table.bump
+    )]
+    pub protocol_lookup_table: Account<'info, ProtocolLookupTable>,
+
+    /// CHECK: Protocol lookup table
+    #[account(
+        mut,
+        address = protocol_lookup_table.lookup_table @ ErrorCode::InvalidLookupTable
+    )]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: Address lookup table program
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
+    pub lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_extend_protocol_lookup_table(
+    ctx: Context<ExtendProtocolLookupTable>,
+    addresses: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        !addresses.is_empty() && addresses.len() <= MAX_LOOKUP_TABLE_EXTENSION,
+        ErrorCode::LookupTableFull
+    );
+    let address_count =
+        ctx.accounts.protocol_lookup_table.address_count as usize + addresses.len();
+    require!(
+        address_count <= MAX_LOOKUP_TABLE_ADDRESSES,
+        ErrorCode::LookupTableFull
+    );
+
+    let bump = ctx.accounts.protocol_lookup_table.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[PROTOCOL_LOOKUP_TABLE_SEED, &[bump]]];
+    extend_lookup_table(
+        &ctx.accounts.lookup_table_program.to_account_info(),
+        &ctx.accounts.lookup_table.to_account_info(),
+        &ctx.accounts.protocol_lookup_table.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &addresses,
+        signer_seeds,
+    )?;
+
+    let protocol_lookup_table = &mut ctx.accounts.protocol_lookup_table;
+    protocol_lookup_table.address_count = address_count as u16;
+
+    emit!(LookupTableExtended {
+        lookup_table: protocol_lookup_table.lookup_table,
+        addresses,
+        address_count: protocol_lookup_table.address_count,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+// ===== Events =====
+
+#[event]
+pub struct MMVaultWithdrawn {
+    pub market_maker: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+// ===== Open MM Vault =====
+// Per-mint float held under the MM registry PDA. Anyone may deposit with a
+// plain token transfer; fill_intent spends it as premium, and only the MM
+// owner can withdraw, so a fill operator never holds withdrawal rights.
+
+#[derive(Accounts)]
+pub struct OpenMMVault<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [MM_REGISTRY_SEED, owner.key().as_ref()],
+        bump = mm_registry.bump,
+        constraint = mm_registry.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    #[account(
+        init,
+        payer = owner,
+        token::mint = mint,
+        token::authority = mm_registry,
+        seeds = [MM_VAULT_SEED, mm_registry.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub mm_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_open_mm_vault(_ctx: Context<OpenMMVault>) -> Result<()> {
+    Ok(())
+}
+
+// ===== Withdraw From MM Vault =====
+
+#[derive(Accounts)]
+pub struct WithdrawFromMMVault<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [MM_REGISTRY_SEED, owner.key().as_ref()],
+        bump = mm_registry.bump,
+        constraint = mm_registry.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    #[account(
+        mut,
+        seeds = [MM_VAULT_SEED, mm_registry.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub mm_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = destination.mint == mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_withdraw_from_mm_vault(
+    ctx: Context<WithdrawFromMMVault>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let owner = ctx.accounts.owner.key();
+    let seeds = &[MM_REGISTRY_SEED, owner.as_ref(), &[ctx.accounts.mm_registry.bump]];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.mm_vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.mm_registry.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &[&seeds[..]],
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(MMVaultWithdrawn {
+        market_maker: owner,
+        mint: ctx.accounts.mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,9 +1,63 @@
 pub mod admin;
+pub mod arbitration;
+pub mod archive;
+pub mod auto_roll;
+pub mod compliance;
+pub mod composer;
+pub mod dispute_evidence;
+pub mod escrowed_fill;
+pub mod extension;
+pub mod hedging;
+pub mod insurance;
 pub mod intent;
+pub mod keeper;
+pub mod liquidity_vault;
+pub mod lookup_table;
+pub mod mm_vault;
+pub mod option_series;
+pub mod option_vault;
 pub mod owner_override;
+pub mod physical_settlement;
+pub mod posted_quote;
+pub mod premium_installments;
+pub mod resale;
+pub mod risk;
 pub mod settlement;
+pub mod settlement_attestation;
+pub mod settlement_dispute;
+pub mod standing_quote;
+pub mod timelock;
+pub mod triggers;
+pub mod view;
 
 pub use admin::*;
+pub use arbitration::*;
+pub use archive::*;
+pub use auto_roll::*;
+pub use compliance::*;
+pub use composer::*;
+pub use dispute_evidence::*;
+pub use escrowed_fill::*;
+pub use extension::*;
+pub use hedging::*;
+pub use insurance::*;
 pub use intent::*;
+pub use keeper::*;
+pub use liquidity_vault::*;
+pub use lookup_table::*;
+pub use mm_vault::*;
+pub use option_series::*;
+pub use option_vault::*;
 pub use owner_override::*;
+pub use physical_settlement::*;
+pub use posted_quote::*;
+pub use premium_installments::*;
+pub use resale::*;
+pub use risk::*;
 pub use settlement::*;
+pub use settlement_attestation::*;
+pub use settlement_dispute::*;
+pub use standing_quote::*;
+pub use timelock::*;
+pub use triggers::*;
+pub use view::*;
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+// ===== Events =====
+
+#[event]
+pub struct OptionSeriesCreated {
+    pub option_series: Pubkey,
+    pub asset_mint: Pubkey,
+    pub strategy: StrategyType,
+    pub strike_price: u64,
+    pub expiry_timestamp: i64,
+}
+
+// ===== Create Option Series =====
+// Permissionless: anyone may list a series on the asset's strike grid and
+// the expiry calendar, paying its rent.
+
+#[derive(Accounts)]
+#[instruction(strategy: StrategyType, strike_price: u64, expiry_timestamp: i64)]
+pub struct CreateOptionSeries<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, asset_config.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.delisted @ ErrorCode::AssetDelisted,
+        constraint = asset_config.enabled @ ErrorCode::AssetNotEnabled
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = OptionSeries::LEN,
+        seeds = [
+            OPTION_SERIES_SEED,
+            asset_config.asset_mint.as_ref(),
+            &[strategy as u8],
+            &strike_price.to_le_bytes(),
+            &expiry_timestamp.to_le_bytes()
+        ],
+        bump
+    )]
+    pub option_series: Account<'info, OptionSeries>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_option_series(
+    ctx: Context<CreateOptionSeries>,
+    strategy: StrategyType,
+    strike_price: u64,
+    expiry_timestamp: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let asset_config = &ctx.accounts.asset_config;
+
+    let step = asset_config.series_strike_step;
+    require!(
+        step > 0 && strike_price > 0 && strike_price.is_multiple_of(step),
+        ErrorCode::InvalidSeriesStrike
+    );
+    require!(
+        OptionSeries::is_calendar_expiry(expiry_timestamp)
+            && asset_config.expiry_within_bounds(expiry_timestamp, clock.unix_timestamp),
+        ErrorCode::InvalidSeriesExpiry
+    );
+
+    let option_series = &mut ctx.accounts.option_series;
+    option_series.asset_mint = asset_config.asset_mint;
+    option_series.quote_mint = asset_config.quote_mint;
+    option_series.strategy = strategy;
+    option_series.strike_price = strike_price;
+    option_series.expiry_timestamp = expiry_timestamp;
+    option_series.open_positions = 0;
+    option_series.open_contracts = 0;
+    option_series.open_notional = 0;
+    option_series.created_at = clock.unix_timestamp;
+    option_series.bump = ctx.bumps.option_series;
+
+    emit!(OptionSeriesCreated {
+        option_series: option_series.key(),
+        asset_mint: option_series.asset_mint,
+        strategy,
+        strike_price,
+        expiry_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Enroll a newly opened position in `series` when one is passed; its terms
+/// must match the series exactly
+pub(crate) fn join_series(
+    position: &mut Position,
+    series: Option<&mut Account<OptionSeries>>,
+) -> Result<()> {
+    if let Some(series) = series {
+        require!(series.matches(position), ErrorCode::SeriesMismatch);
+        position.series = series.key();
+        series.record_opened(position.contract_size, position.notional);
+    }
+    Ok(())
+}
+
+/// Take a closing position out of its series' open interest. The series
+/// account is required whenever the position joined one.
+pub(crate) fn leave_series(
+    position: &Position,
+    series: Option<&mut Account<OptionSeries>>,
+) -> Result<()> {
+    if !position.in_series() {
+        return Ok(());
+    }
+    let series = series.ok_or(ErrorCode::SeriesMismatch)?;
+    require_keys_eq!(series.key(), position.series, ErrorCode::SeriesMismatch);
+    series.record_closed(position.contract_size, position.notional);
+    Ok(())
+}
@@ -0,0 +1,1232 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    self, Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::instructions::compliance::require_asset_access;
+use crate::instructions::intent::{
+    calculate_escrow_amount, emit_intent_created, populate_intent, require_intent_notional,
+    require_listed_terms, require_mm_quality, verify_signed_quote, SubmitIntentParams,
+};
+use crate::instructions::settlement::get_asset_price;
+use crate::state::*;
+use crate::utils::*;
+
+// ===== Events =====
+
+#[event]
+pub struct OptionVaultCreated {
+    pub vault: Pubkey,
+    pub asset_mint: Pubkey,
+    pub strategy: StrategyType,
+    pub share_mint: Pubkey,
+    pub manager: Pubkey,
+}
+
+#[event]
+pub struct VaultDeposited {
+    pub vault: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct VaultWithdrawn {
+    pub vault: Pubkey,
+    pub withdrawer: Pubkey,
+    pub shares: u64,
+    pub asset_amount: u64,
+    pub quote_amount: u64,
+}
+
+#[event]
+pub struct VaultRoundFinished {
+    pub vault: Pubkey,
+    pub epoch: u64,
+    pub premium_earned: u64,
+}
+
+#[event]
+pub struct OptionVaultConfigured {
+    pub vault: Pubkey,
+    pub deposit_cap: u64,
+    pub window_duration: i64,
+}
+
+#[event]
+pub struct VaultWithdrawalQueued {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub shares: u64,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct VaultFeesSet {
+    pub vault: Pubkey,
+    pub management_fee_bps: u16,
+    pub performance_fee_bps: u16,
+}
+
+#[event]
+pub struct VaultFeesCharged {
+    pub vault: Pubkey,
+    pub treasury: Pubkey,
+    pub management_fee: u64,
+    pub performance_fee: u64,
+    pub fee_shares: u64,
+    pub high_water_mark: u64,
+}
+
+// ===== Create Option Vault =====
+
+#[derive(Accounts)]
+#[instruction(strategy: StrategyType)]
+pub struct CreateOptionVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, asset_config.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.delisted @ ErrorCode::AssetDelisted,
+        constraint = asset_config.enabled @ ErrorCode::AssetNotEnabled
+    )]
+    pub asset_config: Box<Account<'info, AssetConfig>>,
+
+    #[account(address = asset_config.asset_mint @ ErrorCode::InvalidMint)]
+    pub asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = asset_config.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = OptionVault::LEN,
+        seeds = [OPTION_VAULT_SEED, asset_mint.key().as_ref(), &[strategy as u8]],
+        bump
+    )]
+    pub vault: Box<Account<'info, OptionVault>>,
+
+    /// Shares carry the decimals of the mint depositors pay in
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = match strategy {
+            StrategyType::CoveredCall => asset_mint.decimals,
+            StrategyType::CashSecuredPut => quote_mint.decimals,
+        },
+        mint::authority = vault,
+        mint::token_program = token_program,
+        seeds = [VAULT_SHARE_MINT_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Vault's underlying: covered-call deposits, or assets delivered to a
+    /// put vault on assignment
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = asset_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Vault's quote balance: put-vault deposits and every round's premium
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Shares held for queued withdrawals
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = share_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub share_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_option_vault(
+    ctx: Context<CreateOptionVault>,
+    strategy: StrategyType,
+    manager: Pubkey,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.asset_mint = ctx.accounts.asset_mint.key();
+    vault.quote_mint = ctx.accounts.quote_mint.key();
+    vault.strategy = strategy;
+    vault.share_mint = ctx.accounts.share_mint.key();
+    vault.manager = manager;
+    vault.epoch = 0;
+    vault.round_active = false;
+    vault.deposit_cap = 0;
+    vault.window_duration = DEFAULT_VAULT_WINDOW;
+    vault.queued_withdrawal_shares = 0;
+    vault.claimable_withdrawal_shares = 0;
+    vault.total_premium_earned = 0;
+    let now = Clock::get()?.unix_timestamp;
+    vault.window_closes_at = now + DEFAULT_VAULT_WINDOW;
+    vault.fees = VaultFees::new(now);
+    vault.bump = ctx.bumps.vault;
+    vault.share_mint_bump = ctx.bumps.share_mint;
+
+    emit!(OptionVaultCreated {
+        vault: vault.key(),
+        asset_mint: vault.asset_mint,
+        strategy,
+        share_mint: vault.share_mint,
+        manager,
+    });
+
+    Ok(())
+}
+
+// ===== Configure Option Vault =====
+// A new window length applies from the next round's close; a lower cap only
+// stops new deposits.
+
+#[derive(Accounts)]
+pub struct ConfigureOptionVault<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [OPTION_VAULT_SEED, vault.asset_mint.as_ref(), &[vault.strategy as u8]],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, OptionVault>,
+}
+
+pub fn handle_configure_option_vault(
+    ctx: Context<ConfigureOptionVault>,
+    deposit_cap: u64,
+    window_duration: i64,
+) -> Result<()> {
+    require!(
+        (MIN_VAULT_WINDOW..=MAX_VAULT_WINDOW).contains(&window_duration),
+        ErrorCode::InvalidVaultWindow
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    vault.deposit_cap = deposit_cap;
+    vault.window_duration = window_duration;
+
+    emit!(OptionVaultConfigured {
+        vault: vault.key(),
+        deposit_cap,
+        window_duration,
+    });
+
+    Ok(())
+}
+
+// ===== Set Option Vault Fees =====
+
+#[derive(Accounts)]
+pub struct SetOptionVaultFees<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [OPTION_VAULT_SEED, vault.asset_mint.as_ref(), &[vault.strategy as u8]],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, OptionVault>,
+}
+
+pub fn handle_set_option_vault_fees(
+    ctx: Context<SetOptionVaultFees>,
+    management_fee_bps: u16,
+    performance_fee_bps: u16,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.fees.set_rates(management_fee_bps, performance_fee_bps)?;
+
+    emit!(VaultFeesSet {
+        vault: vault.key(),
+        management_fee_bps,
+        performance_fee_bps,
+    });
+
+    Ok(())
+}
+
+/// Vault value in deposit-mint units, reading the oracle (or an index asset's
+/// component updates) only when the vault holds a balance outside its
+/// deposit mint
+fn option_vault_nav(
+    vault: &OptionVault,
+    vault_asset_account: &InterfaceAccount<TokenAccount>,
+    vault_quote_account: &InterfaceAccount<TokenAccount>,
+    oracle: &OracleAccounts,
+    basket_updates: &[AccountInfo],
+    asset_config: &AssetConfig,
+) -> Result<u64> {
+    let asset_balance = vault_asset_account.amount;
+    let quote_balance = vault_quote_account.amount;
+    let other_balance = match vault.strategy {
+        StrategyType::CoveredCall => quote_balance,
+        StrategyType::CashSecuredPut => asset_balance,
+    };
+    let spot_price = if other_balance > 0 {
+        get_asset_price(oracle, basket_updates, asset_config, &Clock::get()?)?
+    } else {
+        0
+    };
+    let nav = vault
+        .net_asset_value(
+            asset_balance,
+            quote_balance,
+            &asset_config.settlement_terms(),
+            spot_price,
+        )
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(nav)
+}
+
+// ===== Deposit =====
+// Deposits are in the vault's deposit mint and priced against the vault's
+// value in that mint, counting its other balance at the oracle spot price.
+// Only open in the window between rounds, and up to the deposit cap.
+
+#[derive(Accounts)]
+pub struct DepositToVault<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    #[account(
+        seeds = [OPTION_VAULT_SEED, vault.asset_mint.as_ref(), &[vault.strategy as u8]],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, OptionVault>>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, vault.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Box<Account<'info, AssetConfig>>,
+
+    /// Compliance blocklist
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.is_blocked(depositor.key()) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Box<Account<'info, Blocklist>>,
+
+    /// Spot feeds, read only when the vault holds a balance outside its
+    /// deposit mint
+    pub oracle: OracleAccounts<'info>,
+
+    #[account(address = vault.deposit_mint() @ ErrorCode::InvalidMint)]
+    pub deposit_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = vault.share_mint @ ErrorCode::InvalidMint)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault.asset_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault.quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = depositor_token_account.owner == depositor.key() @ ErrorCode::Unauthorized,
+        constraint = depositor_token_account.mint == deposit_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub depositor_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = share_mint,
+        associated_token::authority = depositor,
+        associated_token::token_program = token_program
+    )]
+    pub depositor_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_deposit_to_vault(ctx: Context<DepositToVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    let vault = &ctx.accounts.vault;
+    require!(
+        vault.window_open(Clock::get()?.unix_timestamp),
+        ErrorCode::VaultWindowClosed
+    );
+
+    // Vault value in deposit-mint units, before this deposit
+    let nav = option_vault_nav(
+        vault,
+        &ctx.accounts.vault_asset_account,
+        &ctx.accounts.vault_quote_account,
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        &ctx.accounts.asset_config,
+    )?;
+
+    // Shares are minted for what arrives after any mint transfer fee
+    let received = amount - transfer_fee_for(&ctx.accounts.deposit_mint, amount)?;
+    require!(
+        vault.deposit_cap == 0 || nav.saturating_add(received) <= vault.deposit_cap,
+        ErrorCode::DepositCapExceeded
+    );
+    require!(
+        nav > 0 || ctx.accounts.share_mint.supply == 0,
+        ErrorCode::VaultNavDepleted
+    );
+    let shares = OptionVault::shares_for_deposit(received, nav, ctx.accounts.share_mint.supply)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(shares > 0, ErrorCode::InvalidAmount);
+
+    let vault_deposit_account = match vault.strategy {
+        StrategyType::CoveredCall => &ctx.accounts.vault_asset_account,
+        StrategyType::CashSecuredPut => &ctx.accounts.vault_quote_account,
+    };
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.depositor_token_account.to_account_info(),
+        mint: ctx.accounts.deposit_mint.to_account_info(),
+        to: vault_deposit_account.to_account_info(),
+        authority: ctx.accounts.depositor.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        amount,
+        ctx.accounts.deposit_mint.decimals,
+    )?;
+
+    let seeds = &[
+        OPTION_VAULT_SEED,
+        vault.asset_mint.as_ref(),
+        &[vault.strategy as u8],
+        &[vault.bump],
+    ];
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        to: ctx.accounts.depositor_share_account.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &[&seeds[..]],
+        ),
+        shares,
+    )?;
+
+    emit!(VaultDeposited {
+        vault: vault.key(),
+        depositor: ctx.accounts.depositor.key(),
+        amount,
+        shares,
+    });
+
+    Ok(())
+}
+
+// ===== Withdraw =====
+// Shares redeem a pro-rata part of both balances: the deposit mint plus
+// premium, or any asset a put vault took delivery of. Instant withdrawals
+// are only open in the window between rounds; outside it, queue them.
+
+#[derive(Accounts)]
+pub struct WithdrawFromVault<'info> {
+    pub withdrawer: Signer<'info>,
+
+    #[account(
+        seeds = [OPTION_VAULT_SEED, vault.asset_mint.as_ref(), &[vault.strategy as u8]],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, OptionVault>>,
+
+    #[account(address = vault.asset_mint @ ErrorCode::InvalidMint)]
+    pub asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = vault.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = vault.share_mint @ ErrorCode::InvalidMint)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = asset_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = withdrawer_share_account.owner == withdrawer.key() @ ErrorCode::Unauthorized,
+        constraint = withdrawer_share_account.mint == vault.share_mint @ ErrorCode::InvalidMint
+    )]
+    pub withdrawer_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = withdrawer_asset_account.owner == withdrawer.key() @ ErrorCode::Unauthorized,
+        constraint = withdrawer_asset_account.mint == vault.asset_mint @ ErrorCode::InvalidMint
+    )]
+    pub withdrawer_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = withdrawer_quote_account.owner == withdrawer.key() @ ErrorCode::Unauthorized,
+        constraint = withdrawer_quote_account.mint == vault.quote_mint @ ErrorCode::InvalidMint
+    )]
+    pub withdrawer_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_withdraw_from_vault(ctx: Context<WithdrawFromVault>, shares: u64) -> Result<()> {
+    require!(shares > 0, ErrorCode::InvalidAmount);
+    require!(
+        ctx.accounts.vault.window_open(Clock::get()?.unix_timestamp),
+        ErrorCode::VaultWindowClosed
+    );
+
+    let share_supply = ctx.accounts.share_mint.supply;
+    let asset_amount = OptionVault::redemption_amount(
+        ctx.accounts.vault_asset_account.amount,
+        shares,
+        share_supply,
+    )
+    .ok_or(ErrorCode::InvalidAmount)?;
+    let quote_amount = OptionVault::redemption_amount(
+        ctx.accounts.vault_quote_account.amount,
+        shares,
+        share_supply,
+    )
+    .ok_or(ErrorCode::InvalidAmount)?;
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        from: ctx.accounts.withdrawer_share_account.to_account_info(),
+        authority: ctx.accounts.withdrawer.to_account_info(),
+    };
+    token_interface::burn(CpiContext::new(token_program.clone(), cpi_accounts), shares)?;
+
+    let vault = &ctx.accounts.vault;
+    let seeds = &[
+        OPTION_VAULT_SEED,
+        vault.asset_mint.as_ref(),
+        &[vault.strategy as u8],
+        &[vault.bump],
+    ];
+    for (from, mint, to, amount) in [
+        (
+            &ctx.accounts.vault_asset_account,
+            &ctx.accounts.asset_mint,
+            &ctx.accounts.withdrawer_asset_account,
+            asset_amount,
+        ),
+        (
+            &ctx.accounts.vault_quote_account,
+            &ctx.accounts.quote_mint,
+            &ctx.accounts.withdrawer_quote_account,
+            quote_amount,
+        ),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            mint: mint.to_account_info(),
+            to: to.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, &[&seeds[..]]),
+            amount,
+            mint.decimals,
+        )?;
+    }
+
+    emit!(VaultWithdrawn {
+        vault: vault.key(),
+        withdrawer: ctx.accounts.withdrawer.key(),
+        shares,
+        asset_amount,
+        quote_amount,
+    });
+
+    Ok(())
+}
+
+// ===== Queue Vault Withdrawal =====
+// Outside the window the holder's shares move into the vault's share escrow;
+// they keep their part of the round's result and are paid once it finishes.
+
+#[derive(Accounts)]
+pub struct QueueVaultWithdrawal<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [OPTION_VAULT_SEED, vault.asset_mint.as_ref(), &[vault.strategy as u8]],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, OptionVault>>,
+
+    #[account(address = vault.share_mint @ ErrorCode::InvalidMint)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = owner_share_account.owner == owner.key() @ ErrorCode::Unauthorized,
+        constraint = owner_share_account.mint == vault.share_mint @ ErrorCode::InvalidMint
+    )]
+    pub owner_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub share_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// One queued request per holder at a time
+    #[account(
+        init,
+        payer = owner,
+        space = VaultWithdrawal::LEN,
+        seeds = [VAULT_WITHDRAWAL_SEED, vault.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub withdrawal: Box<Account<'info, VaultWithdrawal>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_queue_vault_withdrawal(
+    ctx: Context<QueueVaultWithdrawal>,
+    shares: u64,
+) -> Result<()> {
+    require!(shares > 0, ErrorCode::InvalidAmount);
+    require!(
+        !ctx.accounts.vault.window_open(Clock::get()?.unix_timestamp),
+        ErrorCode::VaultWindowOpen
+    );
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.owner_share_account.to_account_info(),
+        mint: ctx.accounts.share_mint.to_account_info(),
+        to: ctx.accounts.share_escrow.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        shares,
+        ctx.accounts.share_mint.decimals,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.queued_withdrawal_shares = vault
+        .queued_withdrawal_shares
+        .checked_add(shares)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let withdrawal = &mut ctx.accounts.withdrawal;
+    withdrawal.vault = vault.key();
+    withdrawal.owner = ctx.accounts.owner.key();
+    withdrawal.shares = shares;
+    withdrawal.epoch = vault.epoch;
+    withdrawal.bump = ctx.bumps.withdrawal;
+
+    emit!(VaultWithdrawalQueued {
+        vault: vault.key(),
+        owner: withdrawal.owner,
+        shares,
+        epoch: withdrawal.epoch,
+    });
+
+    Ok(())
+}
+
+// ===== Claim Vault Withdrawal =====
+// Permissionless once the request's round has finished, so the manager can
+// pay out the queue before starting the next round. Pays to the owner's ATAs.
+
+#[derive(Accounts)]
+pub struct ClaimVaultWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [OPTION_VAULT_SEED, vault.asset_mint.as_ref(), &[vault.strategy as u8]],
+        bump = vault.bump,
+        constraint = !vault.round_active @ ErrorCode::VaultRoundActive
+    )]
+    pub vault: Box<Account<'info, OptionVault>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_WITHDRAWAL_SEED, vault.key().as_ref(), owner.key().as_ref()],
+        bump = withdrawal.bump,
+        constraint = withdrawal.epoch < vault.epoch @ ErrorCode::VaultRoundNotFinished,
+        close = owner
+    )]
+    pub withdrawal: Box<Account<'info, VaultWithdrawal>>,
+
+    /// CHECK: Request owner; receives the payout and the request's rent
+    #[account(mut, address = withdrawal.owner @ ErrorCode::Unauthorized)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(address = vault.asset_mint @ ErrorCode::InvalidMint)]
+    pub asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = vault.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = vault.share_mint @ ErrorCode::InvalidMint)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = asset_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub share_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = asset_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_claim_vault_withdrawal(ctx: Context<ClaimVaultWithdrawal>) -> Result<()> {
+    let shares = ctx.accounts.withdrawal.shares;
+    let share_supply = ctx.accounts.share_mint.supply;
+    let asset_amount = OptionVault::redemption_amount(
+        ctx.accounts.vault_asset_account.amount,
+        shares,
+        share_supply,
+    )
+    .ok_or(ErrorCode::InvalidAmount)?;
+    let quote_amount = OptionVault::redemption_amount(
+        ctx.accounts.vault_quote_account.amount,
+        shares,
+        share_supply,
+    )
+    .ok_or(ErrorCode::InvalidAmount)?;
+
+    let vault = &ctx.accounts.vault;
+    let seeds = &[
+        OPTION_VAULT_SEED,
+        vault.asset_mint.as_ref(),
+        &[vault.strategy as u8],
+        &[vault.bump],
+    ];
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        from: ctx.accounts.share_escrow.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    token_interface::burn(
+        CpiContext::new_with_signer(token_program.clone(), cpi_accounts, &[&seeds[..]]),
+        shares,
+    )?;
+
+    for (from, mint, to, amount) in [
+        (
+            &ctx.accounts.vault_asset_account,
+            &ctx.accounts.asset_mint,
+            &ctx.accounts.owner_asset_account,
+            asset_amount,
+        ),
+        (
+            &ctx.accounts.vault_quote_account,
+            &ctx.accounts.quote_mint,
+            &ctx.accounts.owner_quote_account,
+            quote_amount,
+        ),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            mint: mint.to_account_info(),
+            to: to.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, &[&seeds[..]]),
+            amount,
+            mint.decimals,
+        )?;
+    }
+
+    let vault = &mut ctx.accounts.vault;
+    vault.claimable_withdrawal_shares = vault.claimable_withdrawal_shares.saturating_sub(shares);
+
+    emit!(VaultWithdrawn {
+        vault: vault.key(),
+        withdrawer: ctx.accounts.owner.key(),
+        shares,
+        asset_amount,
+        quote_amount,
+    });
+
+    Ok(())
+}
+
+// ===== Vault Submit Intent =====
+// The manager starts the epoch's round: the vault writes its strategy on an
+// MM quote, escrowing its own deposit-mint balance. The fill pays the premium
+// into the vault's quote account and settlement or physical delivery pays
+// back into its ATAs.
+
+#[derive(Accounts)]
+#[instruction(params: SubmitIntentParams)]
+pub struct VaultSubmitIntent<'info> {
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [OPTION_VAULT_SEED, vault.asset_mint.as_ref(), &[vault.strategy as u8]],
+        bump = vault.bump,
+        has_one = manager @ ErrorCode::Unauthorized,
+        constraint = !vault.round_active @ ErrorCode::VaultRoundActive
+    )]
+    pub vault: Box<Account<'info, OptionVault>>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_NEW_INTENTS) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [MM_REGISTRY_SEED, mm_registry.owner.as_ref()],
+        bump = mm_registry.bump,
+        constraint = mm_registry.active @ ErrorCode::MMNotActive
+    )]
+    pub mm_registry: Box<Account<'info, MMRegistry>>,
+
+    /// Compliance blocklist
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.is_blocked(mm_registry.owner) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Box<Account<'info, Blocklist>>,
+
+    #[account(
+        mut,
+        seeds = [NONCE_TRACKER_SEED, mm_registry.owner.as_ref()],
+        bump = nonce_tracker.load()?.bump
+    )]
+    pub nonce_tracker: AccountLoader<'info, NonceTracker>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, vault.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.delisted @ ErrorCode::AssetDelisted,
+        constraint = asset_config.enabled @ ErrorCode::AssetNotEnabled,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused
+    )]
+    pub asset_config: Box<Account<'info, AssetConfig>>,
+
+    /// The round's intent, keyed by the vault and its epoch
+    #[account(
+        init,
+        payer = manager,
+        space = Intent::LEN,
+        seeds = [INTENT_SEED, vault.key().as_ref(), &vault.epoch.to_le_bytes()],
+        bump
+    )]
+    pub intent: Box<Account<'info, Intent>>,
+
+    #[account(
+        init,
+        payer = manager,
+        token::mint = deposit_mint,
+        token::authority = intent,
+        token::token_program = token_program,
+        seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
+        bump
+    )]
+    pub user_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Vault's lifetime stats as an intent user
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = UserStats::LEN,
+        seeds = [USER_STATS_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    /// Vault's deposit-mint account the round escrows from
+    #[account(
+        mut,
+        associated_token::mint = deposit_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_deposit_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = vault.deposit_mint() @ ErrorCode::InvalidMint)]
+    pub deposit_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Spot feeds the strike is bounded against
+    pub oracle: OracleAccounts<'info>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_vault_submit_intent(
+    ctx: Context<VaultSubmitIntent>,
+    params: SubmitIntentParams,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault_key = ctx.accounts.vault.key();
+
+    // The vault only writes its own strategy on its own asset, one per epoch,
+    // once the window has closed and queued withdrawals are paid
+    let vault = &ctx.accounts.vault;
+    require!(clock.unix_timestamp >= vault.window_closes_at, ErrorCode::VaultWindowOpen);
+    require!(vault.claimable_withdrawal_shares == 0, ErrorCode::WithdrawalsPending);
+    require!(
+        params.strategy == vault.strategy
+            && params.asset_mint == vault.asset_mint
+            && params.quote_mint == vault.quote_mint
+            && params.intent_id == vault.epoch,
+        ErrorCode::InvalidQuoteParameters
+    );
+    require_asset_access(&ctx.accounts.asset_config, vault_key, None, None, clock.unix_timestamp)?;
+    require_mm_quality(&ctx.accounts.global_state, &ctx.accounts.mm_registry, &params)?;
+
+    // An index asset is priced from its component updates in remaining_accounts
+    let spot_price = get_asset_price(
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        &ctx.accounts.asset_config,
+        &clock,
+    )?;
+    require_listed_terms(&ctx.accounts.asset_config, &params, spot_price, clock.unix_timestamp)?;
+    require_intent_notional(&ctx.accounts.global_state, &ctx.accounts.asset_config, &params)?;
+
+    verify_signed_quote(
+        &params,
+        &ctx.accounts.mm_registry,
+        &mut *ctx.accounts.nonce_tracker.load_mut()?,
+        &ctx.accounts.instructions_sysvar,
+        clock.unix_timestamp,
+    )?;
+
+    let escrow_amount = calculate_escrow_amount(
+        params.strategy,
+        params.strike_price,
+        params.contract_size,
+        &ctx.accounts.asset_config.settlement_terms(),
+    )?;
+
+    // The vault covers any mint transfer fee, so the escrow holds exactly `escrow_amount`
+    let escrow_deposit = gross_up_for_fee(&ctx.accounts.deposit_mint, escrow_amount)?;
+    let seeds = &[
+        OPTION_VAULT_SEED,
+        vault.asset_mint.as_ref(),
+        &[vault.strategy as u8],
+        &[vault.bump],
+    ];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault_deposit_account.to_account_info(),
+        mint: ctx.accounts.deposit_mint.to_account_info(),
+        to: ctx.accounts.user_escrow.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &[&seeds[..]],
+        ),
+        escrow_deposit,
+        ctx.accounts.deposit_mint.decimals,
+    )?;
+
+    let intent = &mut ctx.accounts.intent;
+    populate_intent(
+        intent,
+        &params,
+        vault_key,
+        ctx.accounts.mm_registry.owner,
+        ctx.accounts.user_escrow.key(),
+        escrow_amount,
+        ctx.accounts.global_state.intent_fill_timeout,
+        clock.unix_timestamp,
+    );
+    intent.reference_price = spot_price;
+    intent.bump = ctx.bumps.intent;
+
+    let user_stats = &mut ctx.accounts.user_stats;
+    if user_stats.user == Pubkey::default() {
+        user_stats.user = vault_key;
+        user_stats.bump = ctx.bumps.user_stats;
+    }
+    user_stats.record_intent_submitted(ctx.accounts.global_state.max_pending_intents)?;
+    ctx.accounts.global_state.record_intent_opened()?;
+
+    ctx.accounts.vault.round_active = true;
+
+    emit_intent_created(intent)
+}
+
+// ===== Finish Vault Round =====
+// Permissionless: once the round's intent went unfilled or its position has
+// settled and paid out, the vault opens its deposit/withdraw window and the
+// epoch's queued withdrawals become claimable. An asset delivered to a put
+// vault is simply held; it counts toward the vault's value until withdrawn
+// with the shares. Management and performance fees are then minted to the
+// treasury in shares.
+
+#[derive(Accounts)]
+pub struct FinishVaultRound<'info> {
+    #[account(
+        mut,
+        seeds = [OPTION_VAULT_SEED, vault.asset_mint.as_ref(), &[vault.strategy as u8]],
+        bump = vault.bump,
+        constraint = vault.round_active @ ErrorCode::VaultRoundNotFinished
+    )]
+    pub vault: Account<'info, OptionVault>,
+
+    #[account(
+        seeds = [INTENT_SEED, vault.key().as_ref(), &vault.epoch.to_le_bytes()],
+        bump = intent.bump
+    )]
+    pub intent: Account<'info, Intent>,
+
+    /// The round's position, once the intent was filled
+    #[account(
+        seeds = [POSITION_SEED, vault.key().as_ref(), &vault.epoch.to_le_bytes()],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+
+    #[account(seeds = [GLOBAL_STATE_SEED], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, vault.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Box<Account<'info, AssetConfig>>,
+
+    /// Spot feeds, read only when the vault holds a balance outside its
+    /// deposit mint
+    pub oracle: OracleAccounts<'info>,
+
+    #[account(mut, address = vault.share_mint @ ErrorCode::InvalidMint)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        associated_token::mint = vault.asset_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        associated_token::mint = vault.quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury's share account, receiving the fee shares
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = global_state.treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_finish_vault_round(ctx: Context<FinishVaultRound>) -> Result<()> {
+    let premium_earned = match ctx.accounts.intent.status {
+        IntentStatus::Expired
+        | IntentStatus::Cancelled
+        | IntentStatus::ResolvedToUser
+        | IntentStatus::ResolvedToMM
+        | IntentStatus::ResolvedSplit => 0,
+        IntentStatus::Filled => {
+            let position = ctx
+                .accounts
+                .position
+                .as_ref()
+                .ok_or(ErrorCode::VaultRoundNotFinished)?;
+            require!(position.is_terminal(), ErrorCode::VaultRoundNotFinished);
+            position.premium_paid
+        }
+        _ => return err!(ErrorCode::VaultRoundNotFinished),
+    };
+
+    let nav = option_vault_nav(
+        &ctx.accounts.vault,
+        &ctx.accounts.vault_asset_account,
+        &ctx.accounts.vault_quote_account,
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        &ctx.accounts.asset_config,
+    )?;
+    let now = Clock::get()?.unix_timestamp;
+    let vault = &mut ctx.accounts.vault;
+    let charge = vault
+        .fees
+        .charge(nav, ctx.accounts.share_mint.supply, now)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if charge.fee_shares > 0 {
+        let seeds = &[
+            OPTION_VAULT_SEED,
+            vault.asset_mint.as_ref(),
+            &[vault.strategy as u8],
+            &[vault.bump],
+        ];
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            to: ctx.accounts.treasury_share_account.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                &[&seeds[..]],
+            ),
+            charge.fee_shares,
+        )?;
+    }
+    emit!(VaultFeesCharged {
+        vault: vault.key(),
+        treasury: ctx.accounts.global_state.treasury,
+        management_fee: charge.management_fee,
+        performance_fee: charge.performance_fee,
+        fee_shares: charge.fee_shares,
+        high_water_mark: vault.fees.high_water_mark,
+    });
+
+    vault.total_premium_earned = vault.total_premium_earned.saturating_add(premium_earned);
+    vault.finish_round(now);
+
+    emit!(VaultRoundFinished {
+        vault: vault.key(),
+        epoch: vault.epoch - 1,
+        premium_earned,
+    });
+
+    Ok(())
+}
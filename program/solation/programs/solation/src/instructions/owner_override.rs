@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::constants::*;
 use crate::errors::ErrorCode;
+use crate::instructions::arbitration::require_committee_approval;
+use crate::instructions::intent::{hand_escrow_to_position, open_position, user_stats_for};
 use crate::state::*;
+use crate::utils::{split_bps, split_bps_to_poster};
 
 // ===== Resolution Events =====
 
@@ -30,6 +33,15 @@ pub struct ForceContinue {
     pub reason: String,
 }
 
+#[event]
+pub struct DisputeBondForfeited {
+    pub intent_id: u64,
+    pub flagged_by: Pubkey,
+    pub counterparty: Pubkey,
+    pub counterparty_amount: u64,
+    pub treasury_amount: u64,
+}
+
 #[event]
 pub struct ForceSettleNow {
     pub intent_id: u64,
@@ -52,18 +64,45 @@ pub struct EmergencyShutdown {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct TreasuryDistribution {
+    pub intent_id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub purpose: String,
+}
+
+#[event]
+pub struct IntentsBatchUnwound {
+    pub intents_unwound: u64,
+    pub total_returned: u64,
+    pub resolved_by: Pubkey,
+    pub reason: String,
+}
+
+#[event]
+pub struct ProtocolResumed {
+    pub resumed_by: Pubkey,
+    pub stage_enabled: u8,
+    pub enabled_operations: u8,
+    pub fully_resumed: bool,
+    pub timestamp: i64,
+}
+
 // ===== 1. MUTUAL UNWIND =====
 // Both user and MM get their deposits back, no position created
 
 #[derive(Accounts)]
 pub struct MutualUnwindIntent<'info> {
+    /// Dispute resolver, or the global authority
     #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump,
-        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized
+        constraint = global_state.can_resolve_disputes(authority.key()) @ ErrorCode::Unauthorized
     )]
     pub global_state: Account<'info, GlobalState>,
 
@@ -73,22 +112,42 @@ pub struct MutualUnwindIntent<'info> {
     )]
     pub intent: Account<'info, Intent>,
 
+    /// Committee approval, required in arbitration committee mode
+    #[account(
+        mut,
+        seeds = [RESOLUTION_VOTE_SEED, intent.key().as_ref()],
+        bump = resolution_vote.bump
+    )]
+    pub resolution_vote: Option<Account<'info, ResolutionVote>>,
+
+    /// User's stats, releasing the intent's pending slot
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, intent.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
     /// User's escrow token account
     #[account(
         mut,
         seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
         bump
     )]
-    pub user_escrow: Account<'info, TokenAccount>,
+    pub user_escrow: InterfaceAccount<'info, TokenAccount>,
 
     /// User's destination token account
     #[account(
         mut,
         constraint = user_token_account.owner == intent.user
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the escrowed collateral
+    #[account(address = user_escrow.mint @ ErrorCode::InvalidMint)]
+    pub escrow_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handle_mutual_unwind(
@@ -100,6 +159,13 @@ pub fn handle_mutual_unwind(
         ErrorCode::DisputeReasonTooLong
     );
 
+    require_committee_approval(
+        &ctx.accounts.global_state,
+        ctx.accounts.resolution_vote.as_mut(),
+        ctx.accounts.intent.key(),
+        ResolutionProposal::MutualUnwind,
+    )?;
+
     let intent = &ctx.accounts.intent;
     let escrow_amount = intent.escrow_amount;
 
@@ -112,18 +178,29 @@ pub fn handle_mutual_unwind(
     ];
     let signer_seeds = &[&seeds[..]];
 
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.user_escrow.to_account_info(),
+        mint: ctx.accounts.escrow_mint.to_account_info(),
         to: ctx.accounts.user_token_account.to_account_info(),
         authority: ctx.accounts.intent.to_account_info(),
     };
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-    token::transfer(cpi_ctx, escrow_amount)?;
+    token_interface::transfer_checked(cpi_ctx, escrow_amount, ctx.accounts.escrow_mint.decimals)?;
 
     // Update status
+    ctx.accounts.user_stats.release_pending_intent();
+    ctx.accounts.global_state.record_intent_closed();
     let intent = &mut ctx.accounts.intent;
     intent.status = IntentStatus::ResolvedToUser; // Mutual unwind = back to user
+    intent.resolution = Some(ResolutionRecord {
+        kind: ResolutionKind::MutualUnwind,
+        user_amount: escrow_amount,
+        mm_amount: 0,
+        treasury_amount: 0,
+        resolver: ctx.accounts.authority.key(),
+        resolved_at: Clock::get()?.unix_timestamp,
+    });
 
     emit!(MutualUnwind {
         intent_id: intent.intent_id,
@@ -144,17 +221,60 @@ pub fn handle_mutual_unwind(
 }
 
 // ===== 2. FORCE CONTINUE =====
-// Force create the position as if MM had filled normally
+// Force create the position as if MM had filled normally. The Position is
+// funded beforehand by prepare_force_continue, so a multisig resolver never
+// pays rent.
 
 #[derive(Accounts)]
-pub struct ForceContinueIntent<'info> {
+pub struct PrepareForceContinue<'info> {
+    /// Anyone funding the Position rent on the resolver's behalf
     #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Disputed, or pending past its fill deadline so the MM can no longer fill
+    #[account(
+        constraint = intent.can_be_resolved() @ ErrorCode::IntentNotResolvable
+    )]
+    pub intent: Account<'info, Intent>,
+
+    /// Position shell, populated by force_continue
+    #[account(
+        init,
+        payer = payer,
+        space = Position::LEN,
+        seeds = [POSITION_SEED, intent.user.as_ref(), &intent.intent_id.to_le_bytes()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_prepare_force_continue(ctx: Context<PrepareForceContinue>) -> Result<()> {
+    let intent = &ctx.accounts.intent;
+    require!(
+        intent.is_disputed() || intent.is_expired(Clock::get()?.unix_timestamp),
+        ErrorCode::IntentNotResolvable
+    );
+
+    let position = &mut ctx.accounts.position;
+    position.position_id = intent.intent_id;
+    position.user = intent.user;
+    position.bump = ctx.bumps.position;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ForceContinueIntent<'info> {
+    /// Dispute resolver, or the global authority
     pub authority: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump,
-        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized
+        constraint = global_state.can_resolve_disputes(authority.key()) @ ErrorCode::Unauthorized
     )]
     pub global_state: Account<'info, GlobalState>,
 
@@ -164,6 +284,29 @@ pub struct ForceContinueIntent<'info> {
     )]
     pub intent: Account<'info, Intent>,
 
+    /// Committee approval, required in arbitration committee mode
+    #[account(
+        mut,
+        seeds = [RESOLUTION_VOTE_SEED, intent.key().as_ref()],
+        bump = resolution_vote.bump
+    )]
+    pub resolution_vote: Option<Account<'info, ResolutionVote>>,
+
+    /// User's stats, releasing the intent's pending slot
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, intent.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, intent.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
     #[account(
         mut,
         seeds = [MM_REGISTRY_SEED, intent.market_maker.as_ref()],
@@ -177,88 +320,146 @@ pub struct ForceContinueIntent<'info> {
         seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
         bump
     )]
-    pub user_escrow: Account<'info, TokenAccount>,
+    pub user_escrow: InterfaceAccount<'info, TokenAccount>,
 
     /// User's token account to receive premium
     #[account(
         mut,
         constraint = user_token_account.owner == intent.user
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// MM's token account to pay premium from (authority pays on behalf)
+    /// Account the premium is paid from (MM, treasury or a funding wallet)
     /// In force continue, we might skip premium or use treasury
-    #[account(mut)]
-    pub premium_source: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = premium_source.owner == premium_payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub premium_source: InterfaceAccount<'info, TokenAccount>,
 
-    /// Position to create
+    /// Owner of premium_source; need not be the resolver
+    pub premium_payer: Signer<'info>,
+
+    /// Position created by prepare_force_continue, not yet opened
     #[account(
-        init,
-        payer = authority,
-        space = Position::LEN,
+        mut,
         seeds = [POSITION_SEED, intent.user.as_ref(), &intent.intent_id.to_le_bytes()],
-        bump
+        bump = position.bump,
+        constraint = position.created_at == 0 @ ErrorCode::PositionAlreadyOpen
     )]
     pub position: Account<'info, Position>,
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    /// Quote mint of the transferred tokens
+    #[account(address = intent.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Dispute evidence log holding the flagger's bond; required for a
+    /// frivolous ruling
+    #[account(
+        mut,
+        seeds = [DISPUTE_RECORD_SEED, intent.key().as_ref()],
+        bump = dispute_record.bump
+    )]
+    pub dispute_record: Option<Box<Account<'info, DisputeRecord>>>,
+
+    /// CHECK: Receives the counterparty's share of a forfeited bond;
+    /// checked against the dispute record
+    #[account(mut)]
+    pub bond_counterparty: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Protocol treasury, receives its share of a forfeited bond
+    #[account(mut, address = global_state.treasury @ ErrorCode::Unauthorized)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handle_force_continue(
     ctx: Context<ForceContinueIntent>,
     reason: String,
     pay_premium: bool,
+    frivolous: bool,
 ) -> Result<()> {
     require!(
         reason.len() <= MAX_DISPUTE_REASON_LEN,
         ErrorCode::DisputeReasonTooLong
     );
 
+    require_committee_approval(
+        &ctx.accounts.global_state,
+        ctx.accounts.resolution_vote.as_mut(),
+        ctx.accounts.intent.key(),
+        ResolutionProposal::ForceContinue { pay_premium, frivolous },
+    )?;
+
+    if frivolous {
+        forfeit_dispute_bond(ctx.accounts)?;
+    }
+
     let clock = Clock::get()?;
     let intent = &ctx.accounts.intent;
 
     // Optionally pay premium to user
     if pay_premium {
-        let total_premium = intent.calculate_total_premium();
-        let cpi_accounts = Transfer {
+        let total_premium = intent.calculate_total_premium()?;
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.premium_source.to_account_info(),
+            mint: ctx.accounts.quote_mint.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
-            authority: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.premium_payer.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, total_premium)?;
+        token_interface::transfer_checked(
+            cpi_ctx,
+            total_premium,
+            ctx.accounts.quote_mint.decimals,
+        )?;
     }
 
-    // Create Position
+    // Create Position, handing it the user escrow as its user vault
+    hand_escrow_to_position(
+        intent,
+        &ctx.accounts.user_escrow,
+        ctx.accounts.position.key(),
+        &ctx.accounts.token_program,
+    )?;
+    let notional = ctx
+        .accounts
+        .asset_config
+        .notional_value(intent.contract_size, intent.strike_price)?;
+    let premium_paid = if pay_premium { intent.calculate_total_premium()? } else { 0 };
     let position = &mut ctx.accounts.position;
-    position.position_id = intent.intent_id;
-    position.user = intent.user;
-    position.market_maker = intent.market_maker;
-    position.strategy = intent.strategy;
-    position.asset_mint = intent.asset_mint;
-    position.quote_mint = intent.quote_mint;
-    position.strike_price = intent.strike_price;
-    position.premium_paid = if pay_premium { intent.calculate_total_premium() } else { 0 };
-    position.contract_size = intent.contract_size;
-    position.created_at = clock.unix_timestamp;
-    position.expiry_timestamp = intent.quote_expiry;
-    position.settlement_price = None;
-    position.status = PositionStatus::Active;
-    position.user_vault = intent.user_escrow;
-    position.mm_vault_locked = ctx.accounts.premium_source.key();
-    position.bump = ctx.bumps.position;
-    position.user_vault_bump = 0;
-    position.mm_vault_bump = 0;
+    open_position(
+        position,
+        &intent.position_terms(),
+        notional,
+        premium_paid,
+        ctx.accounts.premium_source.key(),
+        0,
+        clock.unix_timestamp,
+    );
+
+    ctx.accounts.asset_config.record_position_opened(notional)?;
+    ctx.accounts.global_state.record_notional_opened(notional)?;
 
     // Update MM stats
     let mm_registry = &mut ctx.accounts.mm_registry;
     mm_registry.record_fill(intent.contract_size, clock.unix_timestamp);
 
     // Update intent
+    ctx.accounts.user_stats.release_pending_intent();
+    ctx.accounts.global_state.record_intent_closed();
     let intent = &mut ctx.accounts.intent;
     intent.status = IntentStatus::Filled;
+    intent.resolution = Some(ResolutionRecord {
+        kind: ResolutionKind::ForceContinue,
+        user_amount: position.premium_paid,
+        mm_amount: 0,
+        treasury_amount: 0,
+        resolver: ctx.accounts.authority.key(),
+        resolved_at: clock.unix_timestamp,
+    });
 
     emit!(ForceContinue {
         intent_id: intent.intent_id,
@@ -277,18 +478,62 @@ pub fn handle_force_continue(
     Ok(())
 }
 
+/// Pay a frivolous flagger's bond out to the counterparty and treasury, and
+/// count the ruling against the flagger
+fn forfeit_dispute_bond(accounts: &mut ForceContinueIntent) -> Result<()> {
+    require!(accounts.intent.is_disputed(), ErrorCode::IntentNotDisputed);
+    let (Some(dispute_record), Some(counterparty), Some(treasury)) = (
+        accounts.dispute_record.as_mut(),
+        accounts.bond_counterparty.as_ref(),
+        accounts.treasury.as_ref(),
+    ) else {
+        return err!(ErrorCode::DisputeBondAccountsRequired);
+    };
+    require_keys_eq!(
+        counterparty.key(),
+        dispute_record.counterparty(),
+        ErrorCode::Unauthorized
+    );
+
+    let bond = dispute_record.bond;
+    let (treasury_amount, counterparty_amount) = split_bps(bond, FRIVOLOUS_BOND_TREASURY_BPS)?;
+    dispute_record.bond = 0;
+    dispute_record.to_account_info().sub_lamports(bond)?;
+    counterparty.add_lamports(counterparty_amount)?;
+    treasury.add_lamports(treasury_amount)?;
+
+    let flagged_by = dispute_record.flagged_by;
+    if flagged_by == accounts.intent.user {
+        accounts.user_stats.record_frivolous_dispute();
+    } else {
+        accounts.mm_registry.record_frivolous_dispute();
+    }
+
+    emit!(DisputeBondForfeited {
+        intent_id: accounts.intent.intent_id,
+        flagged_by,
+        counterparty: counterparty.key(),
+        counterparty_amount,
+        treasury_amount,
+    });
+
+    Ok(())
+}
+
 // ===== 3. FORCE SETTLE NOW =====
 // Settle position immediately at current/specified price
 
 #[derive(Accounts)]
 pub struct ForceSettleNowIntent<'info> {
+    /// Dispute resolver, or the global authority
     #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump,
-        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized
+        constraint = global_state.can_resolve_disputes(authority.key()) @ ErrorCode::Unauthorized
     )]
     pub global_state: Account<'info, GlobalState>,
 
@@ -298,29 +543,49 @@ pub struct ForceSettleNowIntent<'info> {
     )]
     pub intent: Account<'info, Intent>,
 
+    /// Committee approval, required in arbitration committee mode
+    #[account(
+        mut,
+        seeds = [RESOLUTION_VOTE_SEED, intent.key().as_ref()],
+        bump = resolution_vote.bump
+    )]
+    pub resolution_vote: Option<Account<'info, ResolutionVote>>,
+
+    /// User's stats, releasing the intent's pending slot
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, intent.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
     /// User's escrow
     #[account(
         mut,
         seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
         bump
     )]
-    pub user_escrow: Account<'info, TokenAccount>,
+    pub user_escrow: InterfaceAccount<'info, TokenAccount>,
 
     /// User's token account
     #[account(
         mut,
         constraint = user_token_account.owner == intent.user
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// MM's token account
     #[account(
         mut,
         constraint = mm_token_account.owner == intent.market_maker
     )]
-    pub mm_token_account: Account<'info, TokenAccount>,
+    pub mm_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the escrowed collateral
+    #[account(address = user_escrow.mint @ ErrorCode::InvalidMint)]
+    pub escrow_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handle_force_settle_now(
@@ -335,12 +600,21 @@ pub fn handle_force_settle_now(
         ErrorCode::DisputeReasonTooLong
     );
 
+    require_committee_approval(
+        &ctx.accounts.global_state,
+        ctx.accounts.resolution_vote.as_mut(),
+        ctx.accounts.intent.key(),
+        ResolutionProposal::ForceSettleNow {
+            settlement_price,
+            user_payout_bps,
+        },
+    )?;
+
     let intent = &ctx.accounts.intent;
     let escrow_amount = intent.escrow_amount;
 
     // Calculate payouts
-    let user_payout = (escrow_amount as u128 * user_payout_bps as u128 / 10000) as u64;
-    let mm_payout = escrow_amount.saturating_sub(user_payout);
+    let (user_payout, mm_payout) = split_bps_to_poster(escrow_amount, user_payout_bps as u64)?;
 
     let intent_key = intent.key();
     let seeds = &[
@@ -352,31 +626,43 @@ pub fn handle_force_settle_now(
 
     // Pay user
     if user_payout > 0 {
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.user_escrow.to_account_info(),
+            mint: ctx.accounts.escrow_mint.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
             authority: ctx.accounts.intent.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, user_payout)?;
+        token_interface::transfer_checked(cpi_ctx, user_payout, ctx.accounts.escrow_mint.decimals)?;
     }
 
     // Pay MM
     if mm_payout > 0 {
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.user_escrow.to_account_info(),
+            mint: ctx.accounts.escrow_mint.to_account_info(),
             to: ctx.accounts.mm_token_account.to_account_info(),
             authority: ctx.accounts.intent.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, mm_payout)?;
+        token_interface::transfer_checked(cpi_ctx, mm_payout, ctx.accounts.escrow_mint.decimals)?;
     }
 
     // Update intent
+    ctx.accounts.user_stats.release_pending_intent();
+    ctx.accounts.global_state.record_intent_closed();
     let intent = &mut ctx.accounts.intent;
     intent.status = IntentStatus::ResolvedSplit;
+    intent.resolution = Some(ResolutionRecord {
+        kind: ResolutionKind::ForceSettleNow,
+        user_amount: user_payout,
+        mm_amount: mm_payout,
+        treasury_amount: 0,
+        resolver: ctx.accounts.authority.key(),
+        resolved_at: Clock::get()?.unix_timestamp,
+    });
 
     emit!(ForceSettleNow {
         intent_id: intent.intent_id,
@@ -401,13 +687,15 @@ pub fn handle_force_settle_now(
 
 #[derive(Accounts)]
 pub struct EscrowToTreasuryIntent<'info> {
+    /// Dispute resolver, or the global authority
     #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump,
-        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized
+        constraint = global_state.can_resolve_disputes(authority.key()) @ ErrorCode::Unauthorized
     )]
     pub global_state: Account<'info, GlobalState>,
 
@@ -417,22 +705,42 @@ pub struct EscrowToTreasuryIntent<'info> {
     )]
     pub intent: Account<'info, Intent>,
 
+    /// Committee approval, required in arbitration committee mode
+    #[account(
+        mut,
+        seeds = [RESOLUTION_VOTE_SEED, intent.key().as_ref()],
+        bump = resolution_vote.bump
+    )]
+    pub resolution_vote: Option<Account<'info, ResolutionVote>>,
+
+    /// User's stats, releasing the intent's pending slot
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, intent.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
     /// User's escrow
     #[account(
         mut,
         seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
         bump
     )]
-    pub user_escrow: Account<'info, TokenAccount>,
+    pub user_escrow: InterfaceAccount<'info, TokenAccount>,
 
     /// Treasury token account
     #[account(
         mut,
         constraint = treasury_token_account.owner == global_state.treasury
     )]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the escrowed collateral
+    #[account(address = user_escrow.mint @ ErrorCode::InvalidMint)]
+    pub escrow_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handle_escrow_to_treasury(
@@ -444,6 +752,13 @@ pub fn handle_escrow_to_treasury(
         ErrorCode::DisputeReasonTooLong
     );
 
+    require_committee_approval(
+        &ctx.accounts.global_state,
+        ctx.accounts.resolution_vote.as_mut(),
+        ctx.accounts.intent.key(),
+        ResolutionProposal::EscrowToTreasury,
+    )?;
+
     let intent = &ctx.accounts.intent;
     let escrow_amount = intent.escrow_amount;
 
@@ -456,18 +771,29 @@ pub fn handle_escrow_to_treasury(
     let signer_seeds = &[&seeds[..]];
 
     // Transfer to treasury
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.user_escrow.to_account_info(),
+        mint: ctx.accounts.escrow_mint.to_account_info(),
         to: ctx.accounts.treasury_token_account.to_account_info(),
         authority: ctx.accounts.intent.to_account_info(),
     };
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-    token::transfer(cpi_ctx, escrow_amount)?;
+    token_interface::transfer_checked(cpi_ctx, escrow_amount, ctx.accounts.escrow_mint.decimals)?;
 
     // Update intent - use Disputed status to indicate pending manual resolution
+    ctx.accounts.user_stats.release_pending_intent();
+    ctx.accounts.global_state.record_intent_closed();
     let intent = &mut ctx.accounts.intent;
     intent.status = IntentStatus::Disputed; // Remains disputed until manual distribution
+    intent.resolution = Some(ResolutionRecord {
+        kind: ResolutionKind::EscrowToTreasury,
+        user_amount: 0,
+        mm_amount: 0,
+        treasury_amount: escrow_amount,
+        resolver: ctx.accounts.authority.key(),
+        resolved_at: Clock::get()?.unix_timestamp,
+    });
 
     emit!(EscrowToTreasury {
         intent_id: intent.intent_id,
@@ -491,13 +817,15 @@ pub fn handle_escrow_to_treasury(
 
 #[derive(Accounts)]
 pub struct ProportionalSplitIntent<'info> {
+    /// Dispute resolver, or the global authority
     #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump,
-        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized
+        constraint = global_state.can_resolve_disputes(authority.key()) @ ErrorCode::Unauthorized
     )]
     pub global_state: Account<'info, GlobalState>,
 
@@ -507,29 +835,49 @@ pub struct ProportionalSplitIntent<'info> {
     )]
     pub intent: Account<'info, Intent>,
 
+    /// Committee approval, required in arbitration committee mode
+    #[account(
+        mut,
+        seeds = [RESOLUTION_VOTE_SEED, intent.key().as_ref()],
+        bump = resolution_vote.bump
+    )]
+    pub resolution_vote: Option<Account<'info, ResolutionVote>>,
+
+    /// User's stats, releasing the intent's pending slot
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, intent.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
     /// User's escrow
     #[account(
         mut,
         seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
         bump
     )]
-    pub user_escrow: Account<'info, TokenAccount>,
+    pub user_escrow: InterfaceAccount<'info, TokenAccount>,
 
     /// User's token account
     #[account(
         mut,
         constraint = user_token_account.owner == intent.user
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// MM's token account
     #[account(
         mut,
         constraint = mm_token_account.owner == intent.market_maker
     )]
-    pub mm_token_account: Account<'info, TokenAccount>,
+    pub mm_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    /// Mint of the escrowed collateral
+    #[account(address = user_escrow.mint @ ErrorCode::InvalidMint)]
+    pub escrow_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handle_proportional_split(
@@ -543,11 +891,17 @@ pub fn handle_proportional_split(
         ErrorCode::DisputeReasonTooLong
     );
 
+    require_committee_approval(
+        &ctx.accounts.global_state,
+        ctx.accounts.resolution_vote.as_mut(),
+        ctx.accounts.intent.key(),
+        ResolutionProposal::ProportionalSplit { user_bps },
+    )?;
+
     let intent = &ctx.accounts.intent;
     let escrow_amount = intent.escrow_amount;
 
-    let user_amount = (escrow_amount as u128 * user_bps as u128 / 10000) as u64;
-    let mm_amount = escrow_amount.saturating_sub(user_amount);
+    let (user_amount, mm_amount) = split_bps_to_poster(escrow_amount, user_bps as u64)?;
 
     let intent_key = intent.key();
     let seeds = &[
@@ -559,30 +913,42 @@ pub fn handle_proportional_split(
 
     // Transfer user portion
     if user_amount > 0 {
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.user_escrow.to_account_info(),
+            mint: ctx.accounts.escrow_mint.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
             authority: ctx.accounts.intent.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, user_amount)?;
+        token_interface::transfer_checked(cpi_ctx, user_amount, ctx.accounts.escrow_mint.decimals)?;
     }
 
     // Transfer MM portion
     if mm_amount > 0 {
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.user_escrow.to_account_info(),
+            mint: ctx.accounts.escrow_mint.to_account_info(),
             to: ctx.accounts.mm_token_account.to_account_info(),
             authority: ctx.accounts.intent.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, mm_amount)?;
+        token_interface::transfer_checked(cpi_ctx, mm_amount, ctx.accounts.escrow_mint.decimals)?;
     }
 
+    ctx.accounts.user_stats.release_pending_intent();
+    ctx.accounts.global_state.record_intent_closed();
     let intent = &mut ctx.accounts.intent;
     intent.status = IntentStatus::ResolvedSplit;
+    intent.resolution = Some(ResolutionRecord {
+        kind: ResolutionKind::ProportionalSplit,
+        user_amount,
+        mm_amount,
+        treasury_amount: 0,
+        resolver: ctx.accounts.authority.key(),
+        resolved_at: Clock::get()?.unix_timestamp,
+    });
 
     emit!(DisputeResolved {
         intent_id: intent.intent_id,
@@ -619,9 +985,9 @@ pub fn handle_emergency_shutdown(
 ) -> Result<()> {
     let clock = Clock::get()?;
     
-    // Pause the protocol
+    // Pause the protocol and disable every instruction family
     let global_state = &mut ctx.accounts.global_state;
-    global_state.paused = true;
+    global_state.halt();
 
     emit!(EmergencyShutdown {
         triggered_by: ctx.accounts.authority.key(),
@@ -630,7 +996,305 @@ pub fn handle_emergency_shutdown(
     });
 
     msg!("EMERGENCY SHUTDOWN triggered. Protocol paused. Reason: {}", reason);
-    msg!("All pending intents should be unwound manually via mutual_unwind.");
+    msg!("Pending intents should be unwound via batch_unwind_intents or mutual_unwind.");
     
     Ok(())
 }
+
+// ===== 7. RESUME PROTOCOL =====
+// Staged recovery from a shutdown: each call re-enables the next instruction
+// family (settlement, then fills, then new intents). The protocol is unpaused
+// once all families are back.
+
+#[derive(Accounts)]
+pub struct ResumeProtocol<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized,
+        constraint = global_state.paused @ ErrorCode::ProtocolNotPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn handle_resume_protocol(ctx: Context<ResumeProtocol>) -> Result<()> {
+    let clock = Clock::get()?;
+    let global_state = &mut ctx.accounts.global_state;
+
+    let stage = global_state
+        .next_resume_stage()
+        .ok_or(ErrorCode::ProtocolNotPaused)?;
+    global_state.enabled_operations |= stage;
+
+    let fully_resumed = global_state.operations_enabled(OPERATION_ALL);
+    if fully_resumed {
+        global_state.paused = false;
+    }
+
+    emit!(ProtocolResumed {
+        resumed_by: ctx.accounts.authority.key(),
+        stage_enabled: stage,
+        enabled_operations: global_state.enabled_operations,
+        fully_resumed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Protocol resume stage {} enabled. Operations: {:#05b}",
+         stage, global_state.enabled_operations);
+
+    Ok(())
+}
+
+// ===== 8. BATCH UNWIND INTENTS =====
+// Mutual unwind of many intents at once, to clear the book after a shutdown
+
+/// Accounts per intent in `remaining_accounts`:
+/// intent, user_escrow, user_token_account, user_stats
+pub const UNWIND_BATCH_ACCOUNTS_PER_INTENT: usize = 4;
+
+#[derive(Accounts)]
+pub struct BatchUnwindIntents<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized,
+        constraint = global_state.paused @ ErrorCode::ProtocolNotPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// Escrow mint shared by every intent in the batch
+    pub escrow_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_batch_unwind_intents<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchUnwindIntents<'info>>,
+    reason: String,
+) -> Result<()> {
+    require!(
+        reason.len() <= MAX_DISPUTE_REASON_LEN,
+        ErrorCode::DisputeReasonTooLong
+    );
+
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty()
+            && remaining
+                .chunks_exact(UNWIND_BATCH_ACCOUNTS_PER_INTENT)
+                .remainder()
+                .is_empty(),
+        ErrorCode::InvalidBatchAccounts
+    );
+
+    let clock = Clock::get()?;
+    let mut intents_unwound: u64 = 0;
+    let mut total_returned: u64 = 0;
+    for group in remaining.chunks(UNWIND_BATCH_ACCOUNTS_PER_INTENT) {
+        let [intent_info, escrow_info, user_token_info, user_stats_info] = group else {
+            return err!(ErrorCode::InvalidBatchAccounts);
+        };
+
+        let mut intent: Account<'info, Intent> = Account::try_from(intent_info)?;
+        require!(intent.can_be_resolved(), ErrorCode::IntentNotResolvable);
+
+        let intent_key = intent.key();
+        let (expected_escrow, escrow_bump) = Pubkey::find_program_address(
+            &[USER_ESCROW_SEED, intent_key.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(escrow_info.key(), expected_escrow, ErrorCode::InvalidBatchAccounts);
+
+        let user_token_account: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(user_token_info)?;
+        // The token program holds the escrow to the same mint
+        require!(
+            user_token_account.owner == intent.user
+                && user_token_account.mint == ctx.accounts.escrow_mint.key(),
+            ErrorCode::InvalidBatchAccounts
+        );
+
+        // Return user escrow to user
+        let escrow_amount = intent.escrow_amount;
+        let seeds = &[USER_ESCROW_SEED, intent_key.as_ref(), &[escrow_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = TransferChecked {
+            from: escrow_info.clone(),
+            mint: ctx.accounts.escrow_mint.to_account_info(),
+            to: user_token_account.to_account_info(),
+            authority: intent.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(
+            cpi_ctx,
+            escrow_amount,
+            ctx.accounts.escrow_mint.decimals,
+        )?;
+
+        let mut user_stats = user_stats_for(user_stats_info, intent.user)?;
+        user_stats.release_pending_intent();
+        ctx.accounts.global_state.record_intent_closed();
+
+        intent.status = IntentStatus::ResolvedToUser; // Mutual unwind = back to user
+        intent.resolution = Some(ResolutionRecord {
+            kind: ResolutionKind::MutualUnwind,
+            user_amount: escrow_amount,
+            mm_amount: 0,
+            treasury_amount: 0,
+            resolver: ctx.accounts.authority.key(),
+            resolved_at: clock.unix_timestamp,
+        });
+
+        // Persist the updated accounts (not Anchor-managed here)
+        intent.exit(&crate::ID)?;
+        user_stats.exit(&crate::ID)?;
+
+        emit!(MutualUnwind {
+            intent_id: intent.intent_id,
+            user: intent.user,
+            market_maker: intent.market_maker,
+            user_returned: escrow_amount,
+        });
+
+        intents_unwound += 1;
+        total_returned = total_returned.saturating_add(escrow_amount);
+    }
+
+    emit!(IntentsBatchUnwound {
+        intents_unwound,
+        total_returned,
+        resolved_by: ctx.accounts.authority.key(),
+        reason,
+    });
+
+    msg!("Batch unwind complete. {} intents returned {} to users.",
+         intents_unwound, total_returned);
+    Ok(())
+}
+
+// ===== 9. DISTRIBUTE FROM TREASURY =====
+// Treasury pays out funds parked by escrow_to_treasury and closes the dispute
+
+#[derive(Accounts)]
+pub struct DistributeFromTreasury<'info> {
+    /// Treasury owner (signs for the treasury token account)
+    pub treasury: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.treasury == treasury.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        constraint = intent.is_disputed() @ ErrorCode::IntentNotDisputed,
+        constraint = matches!(
+            intent.resolution,
+            Some(ResolutionRecord { kind: ResolutionKind::EscrowToTreasury, .. })
+        ) @ ErrorCode::NotTreasuryHeld
+    )]
+    pub intent: Account<'info, Intent>,
+
+    /// Treasury token account holding the parked escrow (in the escrow's mint)
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == treasury.key() @ ErrorCode::Unauthorized
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the escrowed collateral
+    #[account(address = treasury_token_account.mint @ ErrorCode::InvalidMint)]
+    pub escrow_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pays `amounts[i]` to the i-th token account in `remaining_accounts`.
+/// Whatever is not distributed stays with the treasury.
+pub fn handle_distribute_from_treasury<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeFromTreasury<'info>>,
+    amounts: Vec<u64>,
+    purpose: String,
+) -> Result<()> {
+    require!(
+        purpose.len() <= MAX_DISPUTE_REASON_LEN,
+        ErrorCode::DisputeReasonTooLong
+    );
+
+    let recipients = ctx.remaining_accounts;
+    require!(
+        !recipients.is_empty() && recipients.len() == amounts.len(),
+        ErrorCode::InvalidBatchAccounts
+    );
+
+    let intent = &ctx.accounts.intent;
+    let mut resolution = intent.resolution.ok_or(ErrorCode::NotTreasuryHeld)?;
+    let total = amounts
+        .iter()
+        .try_fold(0u64, |sum, &amount| sum.checked_add(amount))
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        total <= resolution.treasury_amount,
+        ErrorCode::DistributionExceedsHeld
+    );
+
+    for (recipient_info, &amount) in recipients.iter().zip(amounts.iter()) {
+        let recipient: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(recipient_info)?;
+        require!(
+            recipient.mint == ctx.accounts.escrow_mint.key(),
+            ErrorCode::InvalidBatchAccounts
+        );
+
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.treasury_token_account.to_account_info(),
+            mint: ctx.accounts.escrow_mint.to_account_info(),
+            to: recipient.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token_interface::transfer_checked(
+            CpiContext::new(cpi_program, cpi_accounts),
+            amount,
+            ctx.accounts.escrow_mint.decimals,
+        )?;
+
+        // Attribute payouts to the parties for the on-chain record
+        if recipient.owner == intent.user {
+            resolution.user_amount = resolution.user_amount.saturating_add(amount);
+        } else if recipient.owner == intent.market_maker {
+            resolution.mm_amount = resolution.mm_amount.saturating_add(amount);
+        }
+
+        emit!(TreasuryDistribution {
+            intent_id: intent.intent_id,
+            recipient: recipient.owner,
+            amount,
+            purpose: purpose.clone(),
+        });
+    }
+    resolution.treasury_amount -= total;
+    resolution.resolved_at = Clock::get()?.unix_timestamp;
+
+    let intent = &mut ctx.accounts.intent;
+    intent.status = match (resolution.user_amount > 0, resolution.mm_amount > 0) {
+        (true, false) => IntentStatus::ResolvedToUser,
+        (false, true) => IntentStatus::ResolvedToMM,
+        _ => IntentStatus::ResolvedSplit,
+    };
+    intent.resolution = Some(resolution);
+
+    msg!("Treasury distributed {} for intent {}. Retained: {}",
+         total, intent.intent_id, resolution.treasury_amount);
+    Ok(())
+}
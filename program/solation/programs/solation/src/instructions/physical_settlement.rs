@@ -0,0 +1,301 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::instructions::settlement::{
+    calculate_settlement, get_settlement_price, physical_delivery_amount, price_in_quote,
+    record_position_settled, transfer_from_position_vault,
+};
+use crate::state::*;
+use crate::utils::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+// ===== Events =====
+
+#[event]
+pub struct PhysicalSettlementDelivered {
+    pub position_id: u64,
+    pub settlement_price: u64,
+    pub delivery_mint: Pubkey,
+    pub delivered_to_user: u64,
+    pub collateral_to_mm: u64,
+}
+
+// ===== Deliver Physical Settlement =====
+// MM delivers its leg of an ITM physical settlement and takes the collateral:
+// strike proceeds against the underlying for calls, the underlying against
+// the user's USDC for puts.
+
+#[derive(Accounts)]
+pub struct DeliverPhysicalSettlement<'info> {
+    pub market_maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_SETTLEMENT) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        constraint = position.market_maker == market_maker.key() @ ErrorCode::Unauthorized,
+        constraint = matches!(
+            position.status,
+            PositionStatus::Active | PositionStatus::AwaitingDistribution
+        ) @ ErrorCode::PositionNotActive
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused,
+        constraint = asset_config.settlement_kind == SettlementKind::Physical @ ErrorCode::PhysicalDeliveryNotRequired
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// MM's registry (for stats tracking)
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, position.market_maker.as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    /// Position user's lifetime stats
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, position.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// Position's user vault (user's locked collateral)
+    #[account(
+        mut,
+        constraint = position_user_vault.key() == position.user_vault @ ErrorCode::InvalidVault
+    )]
+    pub position_user_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the position vault
+    #[account(address = position_user_vault.mint @ ErrorCode::InvalidMint)]
+    pub vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA authority for position vaults
+    #[account(
+        seeds = [POSITION_SEED, position.user.as_ref(), &position.position_id.to_le_bytes()],
+        bump = position.bump
+    )]
+    pub position_authority: AccountInfo<'info>,
+
+    /// MM's account funding the delivered leg, or the position's MM vault
+    /// when the collateral was locked by an escrowed fill
+    #[account(
+        mut,
+        constraint = mm_delivery_source.owner == market_maker.key()
+            || (position.has_locked_mm_collateral()
+                && mm_delivery_source.key() == position.mm_vault_locked) @ ErrorCode::Unauthorized,
+        constraint = mm_delivery_source.mint == position.physical_delivery_mint() @ ErrorCode::InvalidVault
+    )]
+    pub mm_delivery_source: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the delivered leg
+    #[account(address = position.physical_delivery_mint() @ ErrorCode::InvalidVault)]
+    pub delivery_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// MM's locked collateral on the asset; required when delivering from
+    /// the position's MM vault
+    #[account(
+        mut,
+        seeds = [MM_EXPOSURE_SEED, position.market_maker.as_ref(), position.asset_mint.as_ref()],
+        bump = mm_exposure.bump
+    )]
+    pub mm_exposure: Option<Account<'info, MMExposure>>,
+
+    /// User's account receiving the delivered leg (or their beneficiary's)
+    #[account(
+        mut,
+        constraint = user_delivery_destination.owner == position.user_payout_owner(),
+        constraint = user_delivery_destination.mint == position.physical_delivery_mint() @ ErrorCode::InvalidVault
+    )]
+    pub user_delivery_destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// MM's destination for the collateral (or their beneficiary's)
+    #[account(
+        mut,
+        constraint = mm_destination.owner == position.mm_payout_owner()
+    )]
+    pub mm_destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Compliance blocklist; blocked parties are cash-settled with payouts withheld
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.blocks_either_side(&position) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    /// Oracle feeds, read when the position has not been snapshotted
+    pub oracle: OracleAccounts<'info>,
+
+    /// Pyth update for the position's quote currency, read with `oracle`
+    /// when the asset has a feed for it
+    pub quote_price_update: Option<Account<'info, PriceUpdateV2>>,
+
+    /// Series the position belongs to; required when it joined one
+    #[account(mut)]
+    pub option_series: Option<Account<'info, OptionSeries>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_deliver_physical_settlement(ctx: Context<DeliverPhysicalSettlement>) -> Result<()> {
+    let clock = Clock::get()?;
+    let position = &ctx.accounts.position;
+
+    // Snapshotted positions use the recorded price once the dispute window closes
+    let settlement_price = if position.status == PositionStatus::AwaitingDistribution {
+        require!(
+            clock.unix_timestamp >= position.distributable_at,
+            ErrorCode::DisputeWindowOpen
+        );
+        position
+            .settlement_price
+            .ok_or(ErrorCode::SettlementNotSnapshotted)?
+    } else {
+        require!(
+            ctx.accounts.global_state.settlement_dispute_window == 0
+                && !ctx.accounts.asset_config.uses_twap(),
+            ErrorCode::SettlementRequiresSnapshot
+        );
+        require!(
+            clock.unix_timestamp >= position.expiry_timestamp,
+            ErrorCode::PositionNotExpired
+        );
+        let asset_config = &ctx.accounts.asset_config;
+        let quote_price = asset_config
+            .quote_feed_id(position.quote_mint)
+            .map(|feed_id| {
+                read_quote_price(
+                    ctx.accounts.quote_price_update.as_ref(),
+                    &feed_id,
+                    asset_config,
+                    &clock,
+                )
+            })
+            .transpose()?;
+        price_in_quote(
+            get_settlement_price(&ctx.accounts.oracle, asset_config, &clock)?,
+            quote_price,
+        )?
+    };
+
+    let (_, collateral_to_mm, status) = calculate_settlement(
+        position.strategy,
+        ctx.accounts.asset_config.settlement_terms(),
+        settlement_price,
+        position.strike_price,
+        position.contract_size,
+        ctx.accounts.position_user_vault.amount,
+    )?;
+    require!(
+        status == PositionStatus::SettledITM,
+        ErrorCode::PhysicalDeliveryNotRequired
+    );
+
+    // 1. MM delivers its leg to the user
+    let delivered_to_user = physical_delivery_amount(
+        position.strategy,
+        position.strike_price,
+        position.contract_size,
+        &ctx.accounts.asset_config.settlement_terms(),
+    )?;
+    // The MM covers any mint transfer fee so the user receives the full leg
+    let delivery_sent = gross_up_for_fee(&ctx.accounts.delivery_mint, delivered_to_user)?;
+    if ctx.accounts.mm_delivery_source.key() == position.mm_vault_locked
+        && position.has_locked_mm_collateral()
+    {
+        // Collateral locked at fill is delivered out of the position's MM vault
+        transfer_from_position_vault(
+            position,
+            &ctx.accounts.mm_delivery_source,
+            &ctx.accounts.delivery_mint,
+            &ctx.accounts.user_delivery_destination,
+            &ctx.accounts.position_authority,
+            &ctx.accounts.token_program,
+            delivery_sent,
+        )?;
+        ctx.accounts
+            .mm_exposure
+            .as_mut()
+            .ok_or(ErrorCode::InvalidVault)?
+            .record_unlock(ctx.accounts.delivery_mint.key(), delivery_sent);
+    } else {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.mm_delivery_source.to_account_info(),
+            mint: ctx.accounts.delivery_mint.to_account_info(),
+            to: ctx.accounts.user_delivery_destination.to_account_info(),
+            authority: ctx.accounts.market_maker.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token_interface::transfer_checked(
+            CpiContext::new(cpi_program, cpi_accounts),
+            delivery_sent,
+            ctx.accounts.delivery_mint.decimals,
+        )?;
+    }
+
+    // 2. Collateral goes to the MM
+    transfer_from_position_vault(
+        position,
+        &ctx.accounts.position_user_vault,
+        &ctx.accounts.vault_mint,
+        &ctx.accounts.mm_destination,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_program,
+        collateral_to_mm,
+    )?;
+
+    // The delivered leg counts toward PnL at its quote value
+    let delivered_value = match position.strategy {
+        StrategyType::CoveredCall => delivered_to_user,
+        StrategyType::CashSecuredPut => ctx
+            .accounts
+            .asset_config
+            .settlement_terms()
+            .quote_value(delivered_to_user, settlement_price, false)
+            .ok_or(ErrorCode::MathOverflow)?,
+    };
+    let vault_amount = ctx.accounts.position_user_vault.amount;
+
+    let delivery_mint = position.physical_delivery_mint();
+    let position = &mut ctx.accounts.position;
+    position.settlement_price = Some(settlement_price);
+    position.status = status;
+    record_position_settled(
+        position,
+        vault_amount,
+        delivered_value,
+        collateral_to_mm,
+        ctx.accounts.market_maker.key(),
+        &mut ctx.accounts.asset_config,
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.mm_registry,
+        ctx.accounts.option_series.as_mut(),
+    )?;
+
+    emit!(PhysicalSettlementDelivered {
+        position_id: position.position_id,
+        settlement_price,
+        delivery_mint,
+        delivered_to_user,
+        collateral_to_mm,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,493 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::instructions::compliance::require_asset_access;
+use crate::instructions::intent::{
+    calculate_escrow_amount, deposit_user_escrow, emit_intent_created, populate_intent,
+    require_intent_notional, require_listed_terms, require_mm_quality, verify_signed_quote,
+    SubmitIntentParams,
+};
+use crate::instructions::settlement::get_asset_price;
+use crate::state::*;
+use crate::utils::*;
+
+// ===== Events =====
+
+#[event]
+pub struct QuotePosted {
+    pub posted_quote: Pubkey,
+    pub market_maker: Pubkey,
+    pub asset_mint: Pubkey,
+    pub strategy: StrategyType,
+    pub strike_price: u64,
+    pub premium_per_contract: u64,
+    pub contract_size: u64,
+    pub valid_until: i64,
+}
+
+#[event]
+pub struct PostedQuoteTaken {
+    pub posted_quote: Pubkey,
+    pub intent_id: u64,
+    pub user: Pubkey,
+    pub contract_size: u64,
+    pub remaining_contracts: u64,
+}
+
+#[event]
+pub struct PostedQuoteClosed {
+    pub posted_quote: Pubkey,
+    pub market_maker: Pubkey,
+    pub remaining_contracts: u64,
+    pub closed_by: Pubkey,
+}
+
+// ===== Post Quote =====
+// Anyone may post an MM-signed quote on-chain. The Ed25519 signature and
+// nonce are checked once here, so intents taken from the quote need neither.
+
+/// A signed quote to post, with its Ed25519 instruction in the transaction
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PostQuoteParams {
+    pub asset_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub strategy: StrategyType,
+    pub strike_price: u64,
+    pub premium_per_contract: u64,
+    pub contract_size: u64,
+    pub quote_expiry: i64,
+    pub quote_nonce: u64,
+    /// Implied volatility the MM priced the quote at, in basis points
+    pub implied_volatility_bps: u32,
+    pub mm_signature: [u8; 64],
+    /// Index of Ed25519Program instruction in the transaction (typically 0)
+    pub ed25519_instruction_index: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(params: PostQuoteParams)]
+pub struct PostQuote<'info> {
+    #[account(mut)]
+    pub poster: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_NEW_INTENTS) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [MM_REGISTRY_SEED, mm_registry.owner.as_ref()],
+        bump = mm_registry.bump,
+        constraint = mm_registry.active @ ErrorCode::MMNotActive
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    #[account(
+        mut,
+        seeds = [NONCE_TRACKER_SEED, mm_registry.owner.as_ref()],
+        bump = nonce_tracker.load()?.bump
+    )]
+    pub nonce_tracker: AccountLoader<'info, NonceTracker>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, params.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.delisted @ ErrorCode::AssetDelisted,
+        constraint = asset_config.enabled @ ErrorCode::AssetNotEnabled,
+        constraint = asset_config.accepts_quote_mint(params.quote_mint) @ ErrorCode::InvalidQuoteParameters
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    #[account(
+        init,
+        payer = poster,
+        space = PostedQuote::LEN,
+        seeds = [
+            POSTED_QUOTE_SEED,
+            mm_registry.owner.as_ref(),
+            &params.quote_nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub posted_quote: Account<'info, PostedQuote>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_post_quote(ctx: Context<PostQuote>, params: PostQuoteParams) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(params.contract_size > 0, ErrorCode::InvalidAmount);
+
+    // Marks the nonce used, so the same signed quote can't also be submitted directly
+    verify_signed_quote(
+        &SubmitIntentParams {
+            intent_id: 0,
+            asset_mint: params.asset_mint,
+            quote_mint: params.quote_mint,
+            strategy: params.strategy,
+            strike_price: params.strike_price,
+            premium_per_contract: params.premium_per_contract,
+            contract_size: params.contract_size,
+            quote_expiry: params.quote_expiry,
+            quote_nonce: params.quote_nonce,
+            implied_volatility_bps: params.implied_volatility_bps,
+            mm_signature: params.mm_signature,
+            ed25519_instruction_index: params.ed25519_instruction_index,
+            min_mm_reputation: None,
+            min_mm_fill_rate: None,
+        },
+        &ctx.accounts.mm_registry,
+        &mut *ctx.accounts.nonce_tracker.load_mut()?,
+        &ctx.accounts.instructions_sysvar,
+        clock.unix_timestamp,
+    )?;
+
+    let posted_quote = &mut ctx.accounts.posted_quote;
+    posted_quote.market_maker = ctx.accounts.mm_registry.owner;
+    posted_quote.poster = ctx.accounts.poster.key();
+    posted_quote.asset_mint = params.asset_mint;
+    posted_quote.quote_mint = params.quote_mint;
+    posted_quote.strategy = params.strategy;
+    posted_quote.strike_price = params.strike_price;
+    posted_quote.premium_per_contract = params.premium_per_contract;
+    posted_quote.contract_size = params.contract_size;
+    posted_quote.remaining_contracts = params.contract_size;
+    posted_quote.quote_expiry = params.quote_expiry;
+    posted_quote.quote_nonce = params.quote_nonce;
+    posted_quote.implied_volatility_bps = params.implied_volatility_bps;
+    posted_quote.mm_signature = params.mm_signature;
+    posted_quote.posted_at = clock.unix_timestamp;
+    posted_quote.valid_until = params
+        .quote_expiry
+        .min(clock.unix_timestamp + POSTED_QUOTE_LIFETIME);
+    posted_quote.bump = ctx.bumps.posted_quote;
+
+    emit!(QuotePosted {
+        posted_quote: posted_quote.key(),
+        market_maker: posted_quote.market_maker,
+        asset_mint: posted_quote.asset_mint,
+        strategy: posted_quote.strategy,
+        strike_price: posted_quote.strike_price,
+        premium_per_contract: posted_quote.premium_per_contract,
+        contract_size: posted_quote.contract_size,
+        valid_until: posted_quote.valid_until,
+    });
+
+    Ok(())
+}
+
+// ===== Submit Intent From Posted Quote =====
+
+/// A user's take of a posted quote
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TakePostedQuoteParams {
+    pub intent_id: u64,
+    /// Contracts to take, up to the quote's remaining size
+    pub contract_size: u64,
+    /// User's own floor on the MM's reputation score, on top of the protocol's
+    pub min_mm_reputation: Option<u32>,
+    /// User's own floor on the MM's fill rate (0-100), on top of the protocol's
+    pub min_mm_fill_rate: Option<u8>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: TakePostedQuoteParams)]
+pub struct SubmitIntentFromQuote<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_NEW_INTENTS) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [
+            POSTED_QUOTE_SEED,
+            posted_quote.market_maker.as_ref(),
+            &posted_quote.quote_nonce.to_le_bytes()
+        ],
+        bump = posted_quote.bump
+    )]
+    pub posted_quote: Account<'info, PostedQuote>,
+
+    /// The quoting market maker's registry
+    #[account(
+        seeds = [MM_REGISTRY_SEED, posted_quote.market_maker.as_ref()],
+        bump = mm_registry.bump,
+        constraint = mm_registry.active @ ErrorCode::MMNotActive
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    /// Compliance blocklist
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.is_blocked(user.key()) @ ErrorCode::AddressBlocked,
+        constraint = !blocklist.is_blocked(posted_quote.market_maker) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, posted_quote.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.delisted @ ErrorCode::AssetDelisted,
+        constraint = asset_config.enabled @ ErrorCode::AssetNotEnabled,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    #[account(
+        init,
+        payer = user,
+        space = Intent::LEN,
+        seeds = [INTENT_SEED, user.key().as_ref(), &params.intent_id.to_le_bytes()],
+        bump
+    )]
+    pub intent: Account<'info, Intent>,
+
+    #[account(
+        init,
+        payer = user,
+        token::mint = escrow_mint,
+        token::authority = intent,
+        seeds = [USER_ESCROW_SEED, intent.key().as_ref()],
+        bump
+    )]
+    pub user_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStats::LEN,
+        seeds = [USER_STATS_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// User's source token account; omit to escrow native SOL
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == escrow_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = asset_config.is_collateral_mint(
+            posted_quote.strategy,
+            posted_quote.quote_mint,
+            escrow_mint.key()
+        ) @ ErrorCode::InvalidCollateralMint
+    )]
+    pub escrow_mint: InterfaceAccount<'info, Mint>,
+
+    /// Spot feeds for the strike band, plus the LST rate account when
+    /// escrowing the asset's LST
+    pub oracle: OracleAccounts<'info>,
+
+    /// KYC pass token account, for assets gated on a pass mint
+    pub access_pass: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// User's credential, for assets gated on a credential issuer
+    pub user_credential: Option<Account<'info, UserCredential>>,
+
+    /// Composer allowlist, required when submitted via CPI
+    #[account(seeds = [COMPOSER_ALLOWLIST_SEED], bump = composer_allowlist.bump)]
+    pub composer_allowlist: Option<Account<'info, ComposerAllowlist>>,
+
+    /// Instructions sysvar, to identify a calling program
+    /// CHECK: This is the instructions sysvar
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_submit_intent_from_quote(
+    ctx: Context<SubmitIntentFromQuote>,
+    params: TakePostedQuoteParams,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require_allowed_caller(
+        &ctx.accounts.instructions_sysvar,
+        ctx.accounts.composer_allowlist.as_deref(),
+    )?;
+    require_asset_access(
+        &ctx.accounts.asset_config,
+        ctx.accounts.user.key(),
+        ctx.accounts.access_pass.as_ref(),
+        ctx.accounts.user_credential.as_ref(),
+        clock.unix_timestamp,
+    )?;
+
+    let posted_quote = &ctx.accounts.posted_quote;
+    require!(
+        clock.unix_timestamp <= posted_quote.valid_until,
+        ErrorCode::QuoteExpired
+    );
+    require!(params.contract_size > 0, ErrorCode::InvalidAmount);
+    require!(
+        params.contract_size <= posted_quote.remaining_contracts,
+        ErrorCode::PostedQuoteExhausted
+    );
+
+    // The take is held to the same checks as a directly submitted quote,
+    // minus the signature and nonce already verified at posting
+    let intent_params = SubmitIntentParams {
+        intent_id: params.intent_id,
+        asset_mint: posted_quote.asset_mint,
+        quote_mint: posted_quote.quote_mint,
+        strategy: posted_quote.strategy,
+        strike_price: posted_quote.strike_price,
+        premium_per_contract: posted_quote.premium_per_contract,
+        contract_size: params.contract_size,
+        quote_expiry: posted_quote.quote_expiry,
+        quote_nonce: posted_quote.quote_nonce,
+        implied_volatility_bps: posted_quote.implied_volatility_bps,
+        mm_signature: posted_quote.mm_signature,
+        ed25519_instruction_index: 0,
+        min_mm_reputation: params.min_mm_reputation,
+        min_mm_fill_rate: params.min_mm_fill_rate,
+    };
+    require_mm_quality(&ctx.accounts.global_state, &ctx.accounts.mm_registry, &intent_params)?;
+    // An index asset is priced from its component updates in remaining_accounts
+    let spot_price = get_asset_price(
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        &ctx.accounts.asset_config,
+        &clock,
+    )?;
+    require_listed_terms(
+        &ctx.accounts.asset_config,
+        &intent_params,
+        spot_price,
+        clock.unix_timestamp,
+    )?;
+    require_intent_notional(&ctx.accounts.global_state, &ctx.accounts.asset_config, &intent_params)?;
+
+    let escrow_amount = calculate_escrow_amount(
+        intent_params.strategy,
+        intent_params.strike_price,
+        intent_params.contract_size,
+        &settlement_terms_for_vault(
+            &ctx.accounts.asset_config,
+            ctx.accounts.escrow_mint.key(),
+            ctx.accounts.oracle.lst_rate.as_ref(),
+            &clock,
+        )?,
+    )?;
+    deposit_user_escrow(
+        &ctx.accounts.user,
+        ctx.accounts.user_token_account.as_ref(),
+        &ctx.accounts.user_escrow,
+        &ctx.accounts.escrow_mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        escrow_amount,
+    )?;
+
+    let intent = &mut ctx.accounts.intent;
+    populate_intent(
+        intent,
+        &intent_params,
+        ctx.accounts.user.key(),
+        ctx.accounts.mm_registry.owner,
+        ctx.accounts.user_escrow.key(),
+        escrow_amount,
+        ctx.accounts.global_state.intent_fill_timeout,
+        clock.unix_timestamp,
+    );
+    intent.reference_price = spot_price;
+    intent.bump = ctx.bumps.intent;
+
+    let user_stats = &mut ctx.accounts.user_stats;
+    if user_stats.user == Pubkey::default() {
+        user_stats.user = ctx.accounts.user.key();
+        user_stats.bump = ctx.bumps.user_stats;
+    }
+    user_stats.record_intent_submitted(ctx.accounts.global_state.max_pending_intents)?;
+    ctx.accounts.global_state.record_intent_opened()?;
+
+    let posted_quote = &mut ctx.accounts.posted_quote;
+    posted_quote.remaining_contracts -= params.contract_size;
+
+    emit!(PostedQuoteTaken {
+        posted_quote: posted_quote.key(),
+        intent_id: params.intent_id,
+        user: ctx.accounts.user.key(),
+        contract_size: params.contract_size,
+        remaining_contracts: posted_quote.remaining_contracts,
+    });
+
+    emit_intent_created(&ctx.accounts.intent)
+}
+
+// ===== Close Posted Quote =====
+// The MM (owner or signing key) may withdraw its quote at any time; once it
+// has expired or been fully taken, anyone may close it. Rent goes back to the
+// poster either way.
+
+#[derive(Accounts)]
+pub struct ClosePostedQuote<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [MM_REGISTRY_SEED, posted_quote.market_maker.as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    #[account(
+        mut,
+        seeds = [
+            POSTED_QUOTE_SEED,
+            posted_quote.market_maker.as_ref(),
+            &posted_quote.quote_nonce.to_le_bytes()
+        ],
+        bump = posted_quote.bump,
+        close = poster
+    )]
+    pub posted_quote: Account<'info, PostedQuote>,
+
+    /// CHECK: Rent refund for the closed PostedQuote
+    #[account(
+        mut,
+        address = posted_quote.poster @ ErrorCode::Unauthorized
+    )]
+    pub poster: AccountInfo<'info>,
+}
+
+pub fn handle_close_posted_quote(ctx: Context<ClosePostedQuote>) -> Result<()> {
+    let clock = Clock::get()?;
+    let posted_quote = &ctx.accounts.posted_quote;
+    let caller = ctx.accounts.caller.key();
+    require!(
+        ctx.accounts.mm_registry.can_quote(caller) || !posted_quote.is_live(clock.unix_timestamp),
+        ErrorCode::PostedQuoteLive
+    );
+
+    emit!(PostedQuoteClosed {
+        posted_quote: posted_quote.key(),
+        market_maker: posted_quote.market_maker,
+        remaining_contracts: posted_quote.remaining_contracts,
+        closed_by: caller,
+    });
+
+    Ok(())
+}
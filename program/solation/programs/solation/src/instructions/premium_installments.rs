@@ -0,0 +1,333 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::instructions::settlement::transfer_from_position_vault;
+use crate::state::*;
+use crate::utils::gross_up_for_fee;
+
+// ===== Events =====
+
+#[event]
+pub struct PremiumInstallmentsOffered {
+    pub intent_id: u64,
+    pub position: Pubkey,
+    pub installments: u8,
+    pub interval_seconds: i64,
+}
+
+#[event]
+pub struct PremiumInstallmentPaid {
+    pub position_id: u64,
+    pub amount: u64,
+    pub installments_paid: u8,
+    pub next_due: i64,
+}
+
+#[event]
+pub struct UnpaidCoverageCancelled {
+    pub position_id: u64,
+    pub unpaid_premium: u64,
+    pub contracts_released: u64,
+    pub collateral_released: u64,
+}
+
+// ===== Offer Premium Installments =====
+// The user lets the MM pay the premium in installments. A fill that passes
+// the plan pays only the first one; a missed installment lets the holder cut
+// the unpaid share of the coverage.
+
+#[derive(Accounts)]
+pub struct OfferPremiumInstallments<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        constraint = intent.user == user.key() @ ErrorCode::Unauthorized,
+        constraint = intent.is_pending() @ ErrorCode::IntentNotPending
+    )]
+    pub intent: Account<'info, Intent>,
+
+    /// CHECK: address of the position the intent opens on fill
+    #[account(
+        seeds = [POSITION_SEED, intent.user.as_ref(), &intent.intent_id.to_le_bytes()],
+        bump
+    )]
+    pub position: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        space = PremiumSchedule::LEN,
+        seeds = [PREMIUM_SCHEDULE_SEED, position.key().as_ref()],
+        bump
+    )]
+    pub premium_schedule: Account<'info, PremiumSchedule>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_offer_premium_installments(
+    ctx: Context<OfferPremiumInstallments>,
+    installments: u8,
+    interval_seconds: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let intent = &ctx.accounts.intent;
+
+    // The last installment must fall due before the option expires
+    require!(
+        (2..=MAX_PREMIUM_INSTALLMENTS).contains(&installments)
+            && interval_seconds >= MIN_INSTALLMENT_INTERVAL
+            && clock.unix_timestamp + (installments as i64 - 1) * interval_seconds
+                < intent.quote_expiry,
+        ErrorCode::InvalidInstallmentPlan
+    );
+
+    let premium_schedule = &mut ctx.accounts.premium_schedule;
+    premium_schedule.position = ctx.accounts.position.key();
+    premium_schedule.user = intent.user;
+    premium_schedule.market_maker = Pubkey::default();
+    premium_schedule.installments = installments;
+    premium_schedule.installments_paid = 0;
+    premium_schedule.interval_seconds = interval_seconds;
+    premium_schedule.total_premium = 0;
+    premium_schedule.premium_paid = 0;
+    premium_schedule.next_due = 0;
+    premium_schedule.cancelled = false;
+    premium_schedule.bump = ctx.bumps.premium_schedule;
+
+    emit!(PremiumInstallmentsOffered {
+        intent_id: intent.intent_id,
+        position: premium_schedule.position,
+        installments,
+        interval_seconds,
+    });
+
+    Ok(())
+}
+
+// ===== Close Premium Schedule =====
+
+/// User withdraws an unused offer, or reclaims rent once the plan is over
+#[derive(Accounts)]
+pub struct ClosePremiumSchedule<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        close = user,
+        has_one = user @ ErrorCode::Unauthorized,
+        constraint = !premium_schedule.is_running() @ ErrorCode::InstallmentPlanNotRunning
+    )]
+    pub premium_schedule: Account<'info, PremiumSchedule>,
+}
+
+pub fn handle_close_premium_schedule(_ctx: Context<ClosePremiumSchedule>) -> Result<()> {
+    Ok(())
+}
+
+// ===== Pay Installment =====
+
+/// MM (or anyone on its behalf) pays the next installment to the holder
+#[derive(Accounts)]
+pub struct PayInstallment<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [PREMIUM_SCHEDULE_SEED, position.key().as_ref()],
+        bump = premium_schedule.bump,
+        constraint = premium_schedule.is_running() @ ErrorCode::InstallmentPlanNotRunning
+    )]
+    pub premium_schedule: Account<'info, PremiumSchedule>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == payer.key() @ ErrorCode::Unauthorized,
+        constraint = payer_token_account.mint == position.quote_mint @ ErrorCode::InvalidMint
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Holder's quote-mint account receiving the installment
+    #[account(
+        mut,
+        constraint = holder_token_account.owner == position.holder @ ErrorCode::Unauthorized,
+        constraint = holder_token_account.mint == position.quote_mint @ ErrorCode::InvalidMint
+    )]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = position.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_pay_installment(ctx: Context<PayInstallment>) -> Result<()> {
+    let amount = ctx.accounts.premium_schedule.next_installment();
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.payer_token_account.to_account_info(),
+        mint: ctx.accounts.quote_mint.to_account_info(),
+        to: ctx.accounts.holder_token_account.to_account_info(),
+        authority: ctx.accounts.payer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    // The payer covers any mint transfer fee so the holder receives the full installment
+    let amount_sent = gross_up_for_fee(&ctx.accounts.quote_mint, amount)?;
+    token_interface::transfer_checked(cpi_ctx, amount_sent, ctx.accounts.quote_mint.decimals)?;
+
+    let premium_schedule = &mut ctx.accounts.premium_schedule;
+    premium_schedule.record_payment();
+    let position = &mut ctx.accounts.position;
+    position.premium_paid = position
+        .premium_paid
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(PremiumInstallmentPaid {
+        position_id: position.position_id,
+        amount,
+        installments_paid: premium_schedule.installments_paid,
+        next_due: premium_schedule.next_due,
+    });
+
+    Ok(())
+}
+
+// ===== Cancel Unpaid Coverage =====
+
+/// Holder cuts the coverage the MM has not paid for after a missed
+/// installment, taking back that share of the collateral
+#[derive(Accounts)]
+pub struct CancelUnpaidCoverage<'info> {
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive,
+        constraint = position.holder == holder.key() @ ErrorCode::Unauthorized
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [PREMIUM_SCHEDULE_SEED, position.key().as_ref()],
+        bump = premium_schedule.bump
+    )]
+    pub premium_schedule: Account<'info, PremiumSchedule>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Position's user vault holding the collateral
+    #[account(
+        mut,
+        constraint = position_user_vault.key() == position.user_vault @ ErrorCode::InvalidVault
+    )]
+    pub position_user_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the position vault
+    #[account(address = position_user_vault.mint @ ErrorCode::InvalidMint)]
+    pub vault_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA authority for position vaults
+    #[account(
+        seeds = [POSITION_SEED, position.user.as_ref(), &position.position_id.to_le_bytes()],
+        bump = position.bump
+    )]
+    pub position_authority: AccountInfo<'info>,
+
+    /// Holder's account for the released collateral
+    #[account(
+        mut,
+        constraint = holder_destination.owner == holder.key() @ ErrorCode::Unauthorized,
+        constraint = holder_destination.mint == position_user_vault.mint @ ErrorCode::InvalidMint
+    )]
+    pub holder_destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// The position's series, required when it joined one
+    #[account(mut)]
+    pub option_series: Option<Account<'info, OptionSeries>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_cancel_unpaid_coverage(ctx: Context<CancelUnpaidCoverage>) -> Result<()> {
+    let clock = Clock::get()?;
+    let position = &ctx.accounts.position;
+    require!(clock.unix_timestamp < position.expiry_timestamp, ErrorCode::PositionExpired);
+
+    let premium_schedule = &ctx.accounts.premium_schedule;
+    require!(
+        premium_schedule.is_overdue(clock.unix_timestamp),
+        ErrorCode::InstallmentNotOverdue
+    );
+
+    // Coverage is kept in proportion to the premium paid
+    let total_premium = premium_schedule.total_premium as u128;
+    let unpaid_premium = premium_schedule.total_premium - premium_schedule.premium_paid;
+    require!(unpaid_premium > 0, ErrorCode::InstallmentNotOverdue);
+    let unpaid_share = |amount: u64| -> Result<u64> {
+        u64::try_from(amount as u128 * unpaid_premium as u128 / total_premium)
+            .map_err(|_| ErrorCode::MathOverflow.into())
+    };
+    let contracts_released = unpaid_share(position.contract_size)?;
+    let notional_released = unpaid_share(position.notional)?;
+    let collateral_released = unpaid_share(ctx.accounts.position_user_vault.amount)?;
+
+    transfer_from_position_vault(
+        position,
+        &ctx.accounts.position_user_vault,
+        &ctx.accounts.vault_mint,
+        &ctx.accounts.holder_destination,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_program,
+        collateral_released,
+    )?;
+
+    if position.in_series() {
+        let series = ctx
+            .accounts
+            .option_series
+            .as_mut()
+            .ok_or(ErrorCode::SeriesMismatch)?;
+        require_keys_eq!(series.key(), position.series, ErrorCode::SeriesMismatch);
+        series.record_reduced(contracts_released, notional_released);
+    }
+    ctx.accounts.asset_config.record_position_closed(notional_released);
+    ctx.accounts.global_state.record_notional_closed(notional_released);
+
+    let position = &mut ctx.accounts.position;
+    position.contract_size -= contracts_released;
+    position.notional -= notional_released;
+    ctx.accounts.premium_schedule.cancelled = true;
+
+    emit!(UnpaidCoverageCancelled {
+        position_id: position.position_id,
+        unpaid_premium,
+        contracts_released,
+        collateral_released,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::ed25519_verify::{construct_ask_message, verify_ed25519_signature};
+
+// ===== Events =====
+
+#[event]
+pub struct PositionResold {
+    pub position_id: u64,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+pub struct ResaleAsksCancelled {
+    pub position_id: u64,
+    pub holder: Pubkey,
+    pub resale_nonce: u64,
+}
+
+// ===== Buy Position =====
+// The holder signs an ask off-chain; a buyer submits it with the Ed25519
+// instruction, pays the price in the quote mint and becomes the holder.
+
+/// A holder-signed ask, with its Ed25519 instruction in the transaction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct BuyPositionParams {
+    /// Asking price in quote units
+    pub price: u64,
+    pub ask_expiry: i64,
+    /// Must equal the position's current resale nonce
+    pub resale_nonce: u64,
+    pub holder_signature: [u8; 64],
+    /// Index of Ed25519Program instruction in the transaction (typically 0)
+    pub ed25519_instruction_index: u8,
+}
+
+#[derive(Accounts)]
+pub struct BuyPosition<'info> {
+    pub buyer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = !global_state.paused @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive,
+        constraint = position.holder != buyer.key() @ ErrorCode::Unauthorized
+    )]
+    pub position: Account<'info, Position>,
+
+    /// Compliance blocklist
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.is_blocked(buyer.key()) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    /// Buyer's quote-mint account paying the price
+    #[account(
+        mut,
+        constraint = buyer_token_account.owner == buyer.key() @ ErrorCode::Unauthorized,
+        constraint = buyer_token_account.mint == position.quote_mint @ ErrorCode::InvalidMint
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Seller's quote-mint account receiving the price
+    #[account(
+        mut,
+        constraint = seller_token_account.owner == position.holder @ ErrorCode::Unauthorized,
+        constraint = seller_token_account.mint == position.quote_mint @ ErrorCode::InvalidMint
+    )]
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = position.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    /// Instructions sysvar for Ed25519 signature verification
+    /// CHECK: This is the instructions sysvar
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_buy_position(ctx: Context<BuyPosition>, params: BuyPositionParams) -> Result<()> {
+    let clock = Clock::get()?;
+    let position = &ctx.accounts.position;
+
+    require!(clock.unix_timestamp < position.expiry_timestamp, ErrorCode::PositionExpired);
+    require!(clock.unix_timestamp <= params.ask_expiry, ErrorCode::AskExpired);
+    require!(params.resale_nonce == position.resale_nonce, ErrorCode::NonceAlreadyUsed);
+
+    let message = construct_ask_message(
+        &position.key(),
+        params.price,
+        params.ask_expiry,
+        params.resale_nonce,
+    );
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        &position.holder,
+        &message,
+        params.ed25519_instruction_index,
+    )?;
+
+    if params.price > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.buyer_token_account.to_account_info(),
+            mint: ctx.accounts.quote_mint.to_account_info(),
+            to: ctx.accounts.seller_token_account.to_account_info(),
+            authority: ctx.accounts.buyer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, params.price, ctx.accounts.quote_mint.decimals)?;
+    }
+
+    // The seller's payout choices and triggers don't carry over to the buyer
+    let position = &mut ctx.accounts.position;
+    let seller = position.holder;
+    position.holder = ctx.accounts.buyer.key();
+    position.resale_nonce = position.resale_nonce.saturating_add(1);
+    position.user_beneficiary = None;
+    position.user_payout_in_underlying = false;
+    position.stop_loss_price = None;
+    position.take_profit_price = None;
+
+    emit!(PositionResold {
+        position_id: position.position_id,
+        seller,
+        buyer: position.holder,
+        price: params.price,
+    });
+
+    Ok(())
+}
+
+// ===== Cancel Resale Asks =====
+
+/// Holder invalidates every outstanding ask by advancing the resale nonce
+#[derive(Accounts)]
+pub struct CancelResaleAsks<'info> {
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = position.holder == holder.key() @ ErrorCode::Unauthorized
+    )]
+    pub position: Account<'info, Position>,
+}
+
+pub fn handle_cancel_resale_asks(ctx: Context<CancelResaleAsks>) -> Result<()> {
+    let position = &mut ctx.accounts.position;
+    position.resale_nonce = position.resale_nonce.saturating_add(1);
+
+    emit!(ResaleAsksCancelled {
+        position_id: position.position_id,
+        holder: position.holder,
+        resale_nonce: position.resale_nonce,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+// ===== Events =====
+
+#[event]
+pub struct PortfolioMarginSet {
+    pub market_maker: Pubkey,
+    pub enabled: bool,
+}
+
+// ===== Initialize Risk Config =====
+
+#[derive(Accounts)]
+pub struct InitializeRiskConfig<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RiskConfig::LEN,
+        seeds = [RISK_CONFIG_SEED],
+        bump
+    )]
+    pub risk_config: Account<'info, RiskConfig>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays rent for the risk config
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_risk_config(
+    ctx: Context<InitializeRiskConfig>,
+    risk_admin: Pubkey,
+) -> Result<()> {
+    let risk_config = &mut ctx.accounts.risk_config;
+    risk_config.risk_admin = risk_admin;
+    risk_config.stress_move_bps = BASIS_POINTS_DIVISOR as u16; // Most conservative
+    risk_config.bump = ctx.bumps.risk_config;
+
+    Ok(())
+}
+
+// ===== Update Risk Config =====
+
+#[derive(Accounts)]
+pub struct UpdateRiskConfig<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [RISK_CONFIG_SEED],
+        bump = risk_config.bump,
+        constraint = signer.key() == risk_config.risk_admin
+            || signer.key() == global_state.authority @ ErrorCode::Unauthorized
+    )]
+    pub risk_config: Account<'info, RiskConfig>,
+
+    /// Risk admin or protocol authority
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_update_risk_config(
+    ctx: Context<UpdateRiskConfig>,
+    new_risk_admin: Option<Pubkey>,
+    stress_move_bps: Option<u16>,
+) -> Result<()> {
+    let risk_config = &mut ctx.accounts.risk_config;
+
+    // Only the authority reassigns the risk admin
+    if let Some(risk_admin) = new_risk_admin {
+        require_keys_eq!(
+            ctx.accounts.signer.key(),
+            ctx.accounts.global_state.authority,
+            ErrorCode::Unauthorized
+        );
+        risk_config.risk_admin = risk_admin;
+    }
+
+    if let Some(bps) = stress_move_bps {
+        require!(
+            (MIN_PORTFOLIO_STRESS_BPS..=BASIS_POINTS_DIVISOR as u16).contains(&bps),
+            ErrorCode::InvalidStressMove
+        );
+        risk_config.stress_move_bps = bps;
+    }
+
+    Ok(())
+}
+
+// ===== Set Portfolio Margin =====
+
+#[derive(Accounts)]
+pub struct SetPortfolioMargin<'info> {
+    #[account(
+        seeds = [RISK_CONFIG_SEED],
+        bump = risk_config.bump,
+        constraint = risk_config.risk_admin == risk_admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub risk_config: Account<'info, RiskConfig>,
+
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, mm_registry.owner.as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    pub risk_admin: Signer<'info>,
+}
+
+pub fn handle_set_portfolio_margin(ctx: Context<SetPortfolioMargin>, enabled: bool) -> Result<()> {
+    let mm_registry = &mut ctx.accounts.mm_registry;
+    mm_registry.portfolio_margin = enabled;
+
+    emit!(PortfolioMarginSet {
+        market_maker: mm_registry.owner,
+        enabled,
+    });
+
+    Ok(())
+}
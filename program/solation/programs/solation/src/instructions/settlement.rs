@@ -1,16 +1,134 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::ErrorCode;
+use crate::instructions::compliance::PayoutWithheld;
+use crate::instructions::intent::calculate_escrow_amount;
+use crate::instructions::keeper::{active_keeper, require_keeper};
+use crate::instructions::option_series::leave_series;
+use crate::utils::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+// ===== Events =====
+
+#[event]
+pub struct PositionSettled {
+    pub position_id: u64,
+    pub settlement_price: u64,
+    pub user_payout: u64,
+    pub mm_payout: u64,
+    pub status: PositionStatus,
+    pub settler: Pubkey,
+}
+
+#[event]
+pub struct PayoutBeneficiarySet {
+    pub position_id: u64,
+    pub set_by: Pubkey,
+    pub beneficiary: Option<Pubkey>,
+}
+
+#[event]
+pub struct PayoutSwapSet {
+    pub position_id: u64,
+    pub output_mint: Pubkey,
+    pub max_slippage_bps: u16,
+}
+
+#[event]
+pub struct PayoutSwapped {
+    pub position_id: u64,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+#[event]
+pub struct UnderlyingPayoutElected {
+    pub position_id: u64,
+    pub elected: bool,
+}
+
+#[event]
+pub struct PositionsBatchSettled {
+    pub asset_mint: Pubkey,
+    pub expiry_timestamp: i64,
+    pub settlement_price: u64,
+    pub positions_settled: u64,
+}
+
+#[event]
+pub struct QuoteDepegDetected {
+    pub asset_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub quote_price: u64,
+    pub deviation_bps: u64,
+    pub detected_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SettlementPriceSnapshotted {
+    pub position_id: u64,
+    pub settlement_price: u64,
+    pub snapshotted_by: Pubkey,
+}
+
+#[event]
+pub struct AdminSettlementOverride {
+    pub position_id: u64,
+    pub settlement_price: u64,
+    pub user_payout: u64,
+    pub mm_payout: u64,
+    pub authority: Pubkey,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleFallbackUsed {
+    pub asset_mint: Pubkey,
+    pub switchboard_feed: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+pub struct DustSwept {
+    pub position_id: u64,
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub sweeper: Pubkey,
+}
+
+// ===== Settle Position =====
 
 /// Settle a position at expiry using Pyth oracle price
 #[derive(Accounts)]
 pub struct SettlePosition<'info> {
-    /// Anyone can call settle (permissionless settlement)
+    /// Anyone can call settle, unless reserved for keepers
+    #[account(mut)]
     pub settler: Signer<'info>,
 
+    /// Caller's keeper registration; required while cranks are reserved for keepers
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, settler.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_SETTLEMENT) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
     #[account(
         mut,
         constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive
@@ -18,8 +136,11 @@ pub struct SettlePosition<'info> {
     pub position: Account<'info, Position>,
 
     #[account(
-        seeds = [ASSET_CONFIG_SEED, asset_config.asset_mint.as_ref()],
-        bump = asset_config.bump
+        mut,
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused,
+        constraint = !asset_config.quote_depegged @ ErrorCode::QuoteDepegged
     )]
     pub asset_config: Account<'info, AssetConfig>,
 
@@ -31,16 +152,31 @@ pub struct SettlePosition<'info> {
     )]
     pub mm_registry: Account<'info, MMRegistry>,
 
+    /// Position user's lifetime stats
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, position.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
     /// Position's user vault (user's locked collateral)
     #[account(
         mut,
         constraint = position_user_vault.key() == position.user_vault @ ErrorCode::InvalidVault
     )]
-    pub position_user_vault: Account<'info, TokenAccount>,
+    pub position_user_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the position vault
+    #[account(address = position_user_vault.mint @ ErrorCode::InvalidMint)]
+    pub vault_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// Position's MM vault (MM's locked collateral if any)
-    #[account(mut)]
-    pub position_mm_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = position.mm_vault_locked @ ErrorCode::InvalidVault
+    )]
+    pub position_mm_vault: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA authority for position vaults
     #[account(
@@ -49,187 +185,1968 @@ pub struct SettlePosition<'info> {
     )]
     pub position_authority: AccountInfo<'info>,
 
-    /// User's destination token account
+    /// CHECK: User (or their beneficiary), owner of the user payout ATA
+    #[account(address = position.user_payout_owner() @ ErrorCode::Unauthorized)]
+    pub user_payout_owner: UncheckedAccount<'info>,
+
+    /// User's payout ATA, created by the settler if missing
     #[account(
-        mut,
-        constraint = user_destination.owner == position.user
+        init_if_needed,
+        payer = settler,
+        associated_token::mint = vault_mint,
+        associated_token::authority = user_payout_owner,
+        associated_token::token_program = token_program
     )]
-    pub user_destination: Account<'info, TokenAccount>,
+    pub user_destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// User's underlying account, required when they elected an underlying payout
+    #[account(mut)]
+    pub user_underlying_destination: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: User's wallet (or their beneficiary's); when given, a wSOL vault
+    /// pays the user side out as native SOL
+    #[account(mut)]
+    pub user_wallet: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: MM (or their beneficiary), owner of the MM payout ATA
+    #[account(address = position.mm_payout_owner() @ ErrorCode::Unauthorized)]
+    pub mm_payout_owner: UncheckedAccount<'info>,
 
-    /// MM's destination token account  
+    /// MM's payout ATA, created by the settler if missing
     #[account(
-        mut,
-        constraint = mm_destination.owner == position.market_maker
+        init_if_needed,
+        payer = settler,
+        associated_token::mint = vault_mint,
+        associated_token::authority = mm_payout_owner,
+        associated_token::token_program = token_program
+    )]
+    pub mm_destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// User's standing payout swap request, when the settler routes a swap
+    #[account(
+        seeds = [PAYOUT_SWAP_SEED, position.key().as_ref()],
+        bump = payout_swap.bump
+    )]
+    pub payout_swap: Option<Account<'info, PayoutSwap>>,
+
+    /// User's account receiving the swapped payout (or their beneficiary's)
+    #[account(mut)]
+    pub swap_destination: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Jupiter aggregator; route accounts follow in remaining accounts
+    #[account(address = JUPITER_PROGRAM_ID)]
+    pub jupiter_program: Option<UncheckedAccount<'info>>,
+
+    /// Oracle feeds for the asset's configured source
+    pub oracle: OracleAccounts<'info>,
+
+    /// Pyth update for the quote currency, required when the asset checks
+    /// its quote peg
+    pub quote_price_update: Option<Account<'info, PriceUpdateV2>>,
+
+    /// Compliance blocklist; blocked parties' payouts are withheld
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
     )]
-    pub mm_destination: Account<'info, TokenAccount>,
+    pub blocklist: Account<'info, Blocklist>,
 
-    /// Pyth price feed
-    /// CHECK: Validated by Pyth SDK
-    pub price_update: AccountInfo<'info>,
+    /// Series the position belongs to; required when it joined one
+    #[account(mut)]
+    pub option_series: Option<Account<'info, OptionSeries>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handle_settle_position(ctx: Context<SettlePosition>) -> Result<()> {
+pub fn handle_settle_position<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettlePosition<'info>>,
+    swap_route: Option<Vec<u8>>,
+) -> Result<()> {
     let clock = Clock::get()?;
 
+    // With a dispute window or TWAP pricing, prices must go through snapshot/distribute
+    require!(
+        ctx.accounts.global_state.settlement_dispute_window == 0
+            && !ctx.accounts.asset_config.uses_twap(),
+        ErrorCode::SettlementRequiresSnapshot
+    );
+
     // Check position has expired
     require!(
         clock.unix_timestamp >= ctx.accounts.position.expiry_timestamp,
         ErrorCode::PositionNotExpired
     );
-
-    // Load Pyth price and validate
-    let settlement_price = get_pyth_price(
-        &ctx.accounts.price_update,
-        &ctx.accounts.asset_config.pyth_feed_id,
+    require_keeper(
+        &ctx.accounts.global_state,
+        ctx.accounts.keeper.as_ref(),
+        ctx.accounts.position.expiry_timestamp,
         clock.unix_timestamp,
     )?;
 
+    let quote_price = match check_quote_peg(
+        &mut ctx.accounts.asset_config,
+        ctx.accounts.position.quote_mint,
+        ctx.accounts.quote_price_update.as_ref(),
+        ctx.accounts.settler.key(),
+        &clock,
+    )? {
+        QuoteCheck::Settle(quote_price) => quote_price,
+        QuoteCheck::Halted => return Ok(()),
+    };
+
+    // Load the oracle price, falling back to Switchboard if configured; an
+    // index asset's component updates lead remaining_accounts, ahead of any
+    // swap route
+    let (basket_updates, route_accounts) =
+        split_basket_updates(ctx.remaining_accounts, &ctx.accounts.asset_config)?;
+    let settlement_price = get_asset_price(
+        &ctx.accounts.oracle,
+        basket_updates,
+        &ctx.accounts.asset_config,
+        &clock,
+    )?;
+    let settlement_price = price_in_quote(settlement_price, quote_price)?;
+
     msg!("Settlement price: {}", settlement_price);
     msg!("Strike price: {}", ctx.accounts.position.strike_price);
 
-    // Store settlement price
-    let position = &mut ctx.accounts.position;
-    position.settlement_price = Some(settlement_price);
+    let terms = settlement_terms_for_vault(
+        &ctx.accounts.asset_config,
+        ctx.accounts.vault_mint.key(),
+        ctx.accounts.oracle.lst_rate.as_ref(),
+        &clock,
+    )?;
+    let vault_amount = ctx.accounts.position_user_vault.amount;
 
-    let strike_price = position.strike_price;
-    let contract_size = position.contract_size;
-    let strategy = position.strategy;
+    // A settler-supplied route swaps the user's share per their payout swap;
+    // without one the payout goes out unswapped
+    let user_route = if swap_route.is_some() {
+        UserPayoutRoute::Retain
+    } else {
+        UserPayoutRoute::from_wallet(ctx.accounts.user_wallet.as_deref())
+    };
 
-    // Calculate payout based on strategy and ITM/OTM
-    let (user_amount, mm_amount, status) = calculate_settlement(
-        strategy,
+    // Split the vault and pay both sides
+    let (user_amount, mm_amount) = settle_at_price(
+        &mut ctx.accounts.position,
+        &ctx.accounts.position_user_vault,
+        &ctx.accounts.vault_mint,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.user_destination,
+        ctx.accounts.user_underlying_destination.as_ref(),
+        user_route,
+        &ctx.accounts.mm_destination,
+        &ctx.accounts.token_program,
+        &ctx.accounts.blocklist,
+        terms,
         settlement_price,
-        strike_price,
-        contract_size,
-        ctx.accounts.position_user_vault.amount,
+    )?;
+    if let Some(route_data) = swap_route {
+        // A withheld share stays in the vault
+        if ctx.accounts.position.user_payout_withheld == 0 {
+            swap_user_payout(
+                ctx.accounts,
+                route_accounts,
+                route_data,
+                user_amount,
+                terms,
+                settlement_price,
+            )?;
+        }
+    }
+
+    record_position_settled(
+        &ctx.accounts.position,
+        vault_amount,
+        user_amount,
+        mm_amount,
+        ctx.accounts.settler.key(),
+        &mut ctx.accounts.asset_config,
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.mm_registry,
+        ctx.accounts.option_series.as_mut(),
+    )?;
+    if let Some(keeper) = active_keeper(&mut ctx.accounts.keeper) {
+        keeper.record_settlements(1, clock.unix_timestamp);
+    }
+
+    msg!("Position {} settled. User: {}, MM: {}", 
+         ctx.accounts.position.position_id, user_amount, mm_amount);
+
+    Ok(())
+}
+
+/// Outcome of checking a position's quote currency before it is priced
+pub(crate) enum QuoteCheck {
+    /// Quote holds its peg; restate USD prices with its price, if it has one
+    Settle(Option<QuotePrice>),
+    /// Quote depegged and the asset's settlement is now halted
+    Halted,
+}
+
+/// Read the USD price of `quote_mint` when the asset has a feed for it, and
+/// check it still holds its peg. A deviation past the asset's threshold
+/// halts its settlement until the authority clears it; that is reported as
+/// `Halted` rather than an error, since the halt has to be committed.
+pub(crate) fn check_quote_peg(
+    asset_config: &mut AssetConfig,
+    quote_mint: Pubkey,
+    quote_price_update: Option<&Account<PriceUpdateV2>>,
+    detected_by: Pubkey,
+    clock: &Clock,
+) -> Result<QuoteCheck> {
+    let Some(feed_id) = asset_config.quote_feed_id(quote_mint) else {
+        return Ok(QuoteCheck::Settle(None));
+    };
+    let quote_price = read_quote_price(quote_price_update, &feed_id, asset_config, clock)?;
+    let deviation_bps = quote_price.depeg_bps();
+    if asset_config.max_quote_depeg_bps == 0
+        || deviation_bps <= asset_config.max_quote_depeg_bps as u64
+    {
+        return Ok(QuoteCheck::Settle(Some(quote_price)));
+    }
+
+    asset_config.quote_depegged = true;
+    emit!(QuoteDepegDetected {
+        asset_mint: asset_config.asset_mint,
+        quote_mint,
+        quote_price: quote_price.price,
+        deviation_bps,
+        detected_by,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Quote depegged by {} bps; settlement halted", deviation_bps);
+    Ok(QuoteCheck::Halted)
+}
+
+/// Oracle prices are in USD; positions quoted in a stablecoin with a feed
+/// settle at the price restated in that stablecoin, so every quote mint on an
+/// asset settles at the same canonical value
+pub(crate) fn price_in_quote(usd_price: u64, quote_price: Option<QuotePrice>) -> Result<u64> {
+    match quote_price {
+        Some(quote_price) => quote_price
+            .restate(usd_price)
+            .ok_or(error!(ErrorCode::MathOverflow)),
+        None => Ok(usd_price),
+    }
+}
+
+/// Swap the user's retained share out of the vault through the settler's
+/// Jupiter route, then pay out whatever the route left unspent. The route is
+/// untrusted: only balance changes and the user's slippage bound are relied on.
+fn swap_user_payout<'info>(
+    accounts: &mut SettlePosition<'info>,
+    route_accounts: &[AccountInfo<'info>],
+    route_data: Vec<u8>,
+    user_amount: u64,
+    terms: SettlementTerms,
+    settlement_price: u64,
+) -> Result<()> {
+    let payout_swap = accounts
+        .payout_swap
+        .as_ref()
+        .ok_or(ErrorCode::InvalidPayoutSwap)?;
+    let jupiter_program = accounts
+        .jupiter_program
+        .as_ref()
+        .ok_or(ErrorCode::InvalidSwapRoute)?;
+    let swap_destination = accounts
+        .swap_destination
+        .as_mut()
+        .ok_or(ErrorCode::InvalidSwapRoute)?;
+    let position = &accounts.position;
+    let input_mint = accounts.vault_mint.key();
+    require!(
+        swap_destination.owner == position.user_payout_owner()
+            && swap_destination.mint == payout_swap.output_mint
+            && payout_swap.output_mint != input_mint
+            && (input_mint == position.asset_mint || input_mint == position.quote_mint),
+        ErrorCode::InvalidPayoutSwap
     );
 
-    // Prepare PDA signer
+    let vault = &mut accounts.position_user_vault;
+    vault.reload()?;
+    let vault_before = vault.amount;
+    let mm_vault_before = accounts.position_mm_vault.amount;
+    let destination_before = swap_destination.amount;
+
+    let position_id_bytes = position.position_id.to_le_bytes();
     let position_seeds = &[
         POSITION_SEED,
         position.user.as_ref(),
-        &position.position_id.to_le_bytes(),
+        &position_id_bytes,
         &[position.bump],
     ];
-    let signer = &[&position_seeds[..]];
+    invoke_jupiter_route(
+        jupiter_program,
+        route_accounts,
+        route_data,
+        &accounts.position_authority,
+        &[&position_seeds[..]],
+    )?;
 
-    // Transfer user's share
-    if user_amount > 0 {
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.position_user_vault.to_account_info(),
-            to: ctx.accounts.user_destination.to_account_info(),
-            authority: ctx.accounts.position_authority.to_account_info(),
-        };
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                cpi_accounts,
-                signer,
-            ),
-            user_amount,
-        )?;
-    }
+    vault.reload()?;
+    accounts.position_mm_vault.reload()?;
+    swap_destination.reload()?;
+    let amount_in = vault_before
+        .checked_sub(vault.amount)
+        .ok_or(ErrorCode::InvalidSwapRoute)?;
+    require!(
+        amount_in <= user_amount && accounts.position_mm_vault.amount == mm_vault_before,
+        ErrorCode::InvalidSwapRoute
+    );
+    let amount_out = swap_destination
+        .amount
+        .checked_sub(destination_before)
+        .ok_or(ErrorCode::InvalidSwapRoute)?;
 
-    // Transfer MM's share
-    if mm_amount > 0 {
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.position_user_vault.to_account_info(),
-            to: ctx.accounts.mm_destination.to_account_info(),
-            authority: ctx.accounts.position_authority.to_account_info(),
-        };
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                cpi_accounts,
-                signer,
-            ),
-            mm_amount,
-        )?;
+    // Hold the route to the settlement price, less the user's slippage allowance
+    let expected_out = if input_mint == position.quote_mint {
+        terms.underlying_value(amount_in, settlement_price)
+    } else {
+        terms.quote_value(amount_in, settlement_price, false)
     }
+    .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        amount_out >= payout_swap.min_output(expected_out),
+        ErrorCode::SwapSlippageExceeded
+    );
 
-    // Update position status
-    let position = &mut ctx.accounts.position;
-    position.status = status;
+    transfer_from_position_vault(
+        position,
+        vault,
+        &accounts.vault_mint,
+        &accounts.user_destination,
+        &accounts.position_authority,
+        &accounts.token_program,
+        user_amount - amount_in,
+    )?;
 
-    // Update MM stats
-    let mm_registry = &mut ctx.accounts.mm_registry;
-    mm_registry.total_intents_filled = mm_registry.total_intents_filled.saturating_add(1);
+    emit!(PayoutSwapped {
+        position_id: position.position_id,
+        input_mint,
+        output_mint: payout_swap.output_mint,
+        amount_in,
+        amount_out,
+    });
 
-    msg!("Position {} settled. User: {}, MM: {}", 
-         position.position_id, user_amount, mm_amount);
+    Ok(())
+}
+
+// ===== Admin Settle With Price =====
+// Last resort when the asset's oracles are dead past expiry (or an MM never
+// delivers on a physical settlement): the authority cash-settles at a manually
+// supplied price once ADMIN_SETTLEMENT_DELAY has passed.
+
+#[derive(Accounts)]
+pub struct AdminSettleWithPrice<'info> {
+    pub authority: Signer<'info>,
+
+    /// Funds any payout ATAs created here, so a multisig authority never pays
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        constraint = matches!(
+            position.status,
+            PositionStatus::Active | PositionStatus::AwaitingDistribution
+        ) @ ErrorCode::PositionNotActive
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// MM's registry (for stats tracking)
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, position.market_maker.as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    /// Position user's lifetime stats
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, position.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// Position's user vault (user's locked collateral)
+    #[account(
+        mut,
+        constraint = position_user_vault.key() == position.user_vault @ ErrorCode::InvalidVault
+    )]
+    pub position_user_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the position vault
+    #[account(address = position_user_vault.mint @ ErrorCode::InvalidMint)]
+    pub vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA authority for position vaults
+    #[account(
+        seeds = [POSITION_SEED, position.user.as_ref(), &position.position_id.to_le_bytes()],
+        bump = position.bump
+    )]
+    pub position_authority: AccountInfo<'info>,
+
+    /// CHECK: User (or their beneficiary), owner of the user payout ATA
+    #[account(address = position.user_payout_owner() @ ErrorCode::Unauthorized)]
+    pub user_payout_owner: UncheckedAccount<'info>,
+
+    /// User's payout ATA, created by the payer if missing
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = vault_mint,
+        associated_token::authority = user_payout_owner,
+        associated_token::token_program = token_program
+    )]
+    pub user_destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// User's underlying account, required when they elected an underlying payout
+    #[account(mut)]
+    pub user_underlying_destination: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: User's wallet (or their beneficiary's); when given, a wSOL vault
+    /// pays the user side out as native SOL
+    #[account(mut)]
+    pub user_wallet: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: MM (or their beneficiary), owner of the MM payout ATA
+    #[account(address = position.mm_payout_owner() @ ErrorCode::Unauthorized)]
+    pub mm_payout_owner: UncheckedAccount<'info>,
+
+    /// MM's payout ATA, created by the payer if missing
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = vault_mint,
+        associated_token::authority = mm_payout_owner,
+        associated_token::token_program = token_program
+    )]
+    pub mm_destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Stake pool or rate feed, for positions collateralized in the
+    /// asset's LST; matched against the asset's configured LST rate account
+    pub lst_rate: Option<UncheckedAccount<'info>>,
+
+    /// Compliance blocklist; blocked parties' payouts are withheld
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    /// Series the position belongs to; required when it joined one
+    #[account(mut)]
+    pub option_series: Option<Account<'info, OptionSeries>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_admin_settle_with_price(
+    ctx: Context<AdminSettleWithPrice>,
+    settlement_price: u64,
+    reason: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(settlement_price > 0, ErrorCode::InvalidSettlementPrice);
+    require!(
+        reason.len() <= MAX_DISPUTE_REASON_LEN,
+        ErrorCode::DisputeReasonTooLong
+    );
+
+    // Permissionless oracle settlement gets the first ADMIN_SETTLEMENT_DELAY
+    require!(
+        clock.unix_timestamp
+            >= ctx.accounts.position.expiry_timestamp.saturating_add(ADMIN_SETTLEMENT_DELAY),
+        ErrorCode::AdminSettlementTooEarly
+    );
+
+    let (user_amount, mm_amount) = settle_at_price(
+        &mut ctx.accounts.position,
+        &ctx.accounts.position_user_vault,
+        &ctx.accounts.vault_mint,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.user_destination,
+        ctx.accounts.user_underlying_destination.as_ref(),
+        UserPayoutRoute::from_wallet(ctx.accounts.user_wallet.as_deref()),
+        &ctx.accounts.mm_destination,
+        &ctx.accounts.token_program,
+        &ctx.accounts.blocklist,
+        SettlementTerms {
+            kind: SettlementKind::Cash,
+            ..settlement_terms_for_vault(
+                &ctx.accounts.asset_config,
+                ctx.accounts.vault_mint.key(),
+                ctx.accounts.lst_rate.as_deref(),
+                &clock,
+            )?
+        },
+        settlement_price,
+    )?;
+    record_position_settled(
+        &ctx.accounts.position,
+        ctx.accounts.position_user_vault.amount,
+        user_amount,
+        mm_amount,
+        ctx.accounts.authority.key(),
+        &mut ctx.accounts.asset_config,
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.mm_registry,
+        ctx.accounts.option_series.as_mut(),
+    )?;
+
+    emit!(AdminSettlementOverride {
+        position_id: ctx.accounts.position.position_id,
+        settlement_price,
+        user_payout: user_amount,
+        mm_payout: mm_amount,
+        authority: ctx.accounts.authority.key(),
+        reason,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("ADMIN SETTLEMENT OVERRIDE: position {} at price {}",
+         ctx.accounts.position.position_id, settlement_price);
 
     Ok(())
 }
 
-/// Get Pyth price with validation
-fn get_pyth_price(
-    price_update_account: &AccountInfo,
-    expected_feed_id: &[u8; 32],
-    current_timestamp: i64,
-) -> Result<u64> {
-    let price_update_data = price_update_account.try_borrow_data()
-        .map_err(|_| ErrorCode::PriceTooStale)?;
+// ===== Record Settlement Observation =====
+// Permissionless crank feeding the TWAP for assets that settle on one
+
+#[derive(Accounts)]
+pub struct RecordSettlementObservation<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused,
+        constraint = asset_config.uses_twap() @ ErrorCode::InvalidTwapConfig
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = SettlementObservations::LEN,
+        seeds = [SETTLEMENT_OBSERVATIONS_SEED, position.key().as_ref()],
+        bump
+    )]
+    pub settlement_observations: AccountLoader<'info, SettlementObservations>,
+
+    /// Oracle feeds for the asset's configured source
+    pub oracle: OracleAccounts<'info>,
 
-    let price_update = PriceUpdateV2::try_from_slice(&price_update_data)
-        .map_err(|_| ErrorCode::PriceTooStale)?;
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_record_settlement_observation(
+    ctx: Context<RecordSettlementObservation>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let expiry = ctx.accounts.position.expiry_timestamp;
+    let window = ctx.accounts.asset_config.twap_window_seconds;
+    let window_start = expiry - window;
 
-    // Get price
-    let price = price_update.get_price_unchecked(expected_feed_id)
-        .map_err(|_| ErrorCode::PythFeedIdMismatch)?;
+    let OraclePrice { price, publish_time, .. } = read_asset_price(
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        &ctx.accounts.asset_config,
+        &clock,
+    )?;
 
-    // Staleness check
-    let price_timestamp = price_update.price_message.publish_time;
+    // Only prints published inside [expiry - window, expiry] count
     require!(
-        current_timestamp - price_timestamp < PYTH_STALENESS_THRESHOLD as i64,
-        ErrorCode::PriceTooStale
+        publish_time >= window_start && publish_time <= expiry,
+        ErrorCode::ObservationOutsideWindow
     );
 
-    // Verify feed ID
+    // First observation creates the buffer; later ones load it in place
+    let loader = &ctx.accounts.settlement_observations;
+    let mut observations = loader.load_init().or_else(|_| loader.load_mut())?;
+    if observations.count == 0 {
+        observations.position = ctx.accounts.position.key();
+        observations.bump = ctx.bumps.settlement_observations;
+    }
+
     require!(
-        price_update.price_message.feed_id == *expected_feed_id,
-        ErrorCode::PythFeedIdMismatch
+        (observations.count as usize) < MAX_SETTLEMENT_OBSERVATIONS,
+        ErrorCode::ObservationTooFrequent
     );
 
-    // Convert to u64 (handle negative prices)
-    Ok(price.price.unsigned_abs())
+    // Spread observations evenly across the window
+    let min_spacing = window / MAX_SETTLEMENT_OBSERVATIONS as i64;
+    if let Some(last) = observations.last_publish_time() {
+        require!(
+            publish_time >= last + min_spacing && publish_time > last,
+            ErrorCode::ObservationTooFrequent
+        );
+    }
+
+    observations.record(price, publish_time);
+
+    msg!("Observation {} recorded: {} at {}", observations.count, price, publish_time);
+
+    Ok(())
 }
 
-/// Calculate settlement amounts based on strategy
-fn calculate_settlement(
-    strategy: StrategyType,
-    settlement_price: u64,
-    strike_price: u64,
-    _contract_size: u64,
-    vault_amount: u64,
-) -> (u64, u64, PositionStatus) {
-    match strategy {
-        StrategyType::CoveredCall => {
-            if settlement_price > strike_price {
-                // ITM: MM exercises, gets the difference value
-                // User gets strike price worth
-                // MM gets the rest (upside)
-                let strike_value = vault_amount.saturating_mul(strike_price) / settlement_price;
-                let mm_gain = vault_amount.saturating_sub(strike_value);
-                (strike_value, mm_gain, PositionStatus::SettledITM)
-            } else {
-                // OTM: Expires worthless, user keeps collateral, MM keeps premium
-                (vault_amount, 0, PositionStatus::SettledOTM)
-            }
-        }
-        StrategyType::CashSecuredPut => {
-            if settlement_price < strike_price {
-                // ITM: User must buy at strike, MM delivers asset value
-                // MM gets the collateral (user's USDC at strike)
-                // User gets underlying value worth of USDC
-                let user_value = vault_amount.saturating_mul(settlement_price) / strike_price;
-                let mm_gain = vault_amount.saturating_sub(user_value);
-                (user_value, mm_gain, PositionStatus::SettledITM)
-            } else {
-                // OTM: Expires worthless, user keeps USDC, MM keeps premium
-                (vault_amount, 0, PositionStatus::SettledOTM)
-            }
-        }
+// ===== Snapshot Settlement Price =====
+// Step 1 of two-step settlement: capture the expiry price without touching
+// token accounts, so a bad destination can't block price capture.
+
+#[derive(Accounts)]
+pub struct SnapshotSettlementPrice<'info> {
+    /// Anyone can snapshot, unless reserved for keepers
+    pub settler: Signer<'info>,
+
+    /// Caller's keeper registration; required while cranks are reserved for keepers
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, settler.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_SETTLEMENT) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused,
+        constraint = !asset_config.quote_depegged @ ErrorCode::QuoteDepegged
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Pre-expiry observations, required when the asset settles at a TWAP
+    #[account(
+        seeds = [SETTLEMENT_OBSERVATIONS_SEED, position.key().as_ref()],
+        bump = settlement_observations.load()?.bump
+    )]
+    pub settlement_observations: Option<AccountLoader<'info, SettlementObservations>>,
+
+    /// Oracle feeds for the asset's configured source
+    pub oracle: OracleAccounts<'info>,
+
+    /// Pyth update for the quote currency, required when the asset checks
+    /// its quote peg
+    pub quote_price_update: Option<Account<'info, PriceUpdateV2>>,
+}
+
+pub fn handle_snapshot_settlement_price(ctx: Context<SnapshotSettlementPrice>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= ctx.accounts.position.expiry_timestamp,
+        ErrorCode::PositionNotExpired
+    );
+    require_keeper(
+        &ctx.accounts.global_state,
+        ctx.accounts.keeper.as_ref(),
+        ctx.accounts.position.expiry_timestamp,
+        clock.unix_timestamp,
+    )?;
+
+    let quote_price = match check_quote_peg(
+        &mut ctx.accounts.asset_config,
+        ctx.accounts.position.quote_mint,
+        ctx.accounts.quote_price_update.as_ref(),
+        ctx.accounts.settler.key(),
+        &clock,
+    )? {
+        QuoteCheck::Settle(quote_price) => quote_price,
+        QuoteCheck::Halted => return Ok(()),
+    };
+
+    let asset_config = &ctx.accounts.asset_config;
+    let usd_price = if asset_config.uses_twap() {
+        let observations = ctx
+            .accounts
+            .settlement_observations
+            .as_ref()
+            .ok_or(ErrorCode::InsufficientObservations)?
+            .load()?;
+        require!(
+            observations.count >= asset_config.min_twap_observations,
+            ErrorCode::InsufficientObservations
+        );
+        observations.twap().ok_or(ErrorCode::InsufficientObservations)?
+    } else {
+        get_asset_price(
+            &ctx.accounts.oracle,
+            ctx.remaining_accounts,
+            asset_config,
+            &clock,
+        )?
+    };
+    let settlement_price = price_in_quote(usd_price, quote_price)?;
+
+    let position = &mut ctx.accounts.position;
+    position.settlement_price = Some(settlement_price);
+    position.settlement_recorded_at = clock.unix_timestamp;
+    position.distributable_at = clock
+        .unix_timestamp
+        .saturating_add(ctx.accounts.global_state.settlement_dispute_window);
+    position.status = PositionStatus::AwaitingDistribution;
+    // The position counts toward the keeper's settlements once distributed
+    if let Some(keeper) = active_keeper(&mut ctx.accounts.keeper) {
+        keeper.record_settlements(0, clock.unix_timestamp);
+    }
+
+    emit!(SettlementPriceSnapshotted {
+        position_id: position.position_id,
+        settlement_price,
+        snapshotted_by: ctx.accounts.settler.key(),
+    });
+
+    Ok(())
+}
+
+// ===== Distribute Settlement =====
+// Step 2 of two-step settlement: pay out at the snapshotted price
+
+#[derive(Accounts)]
+pub struct DistributeSettlement<'info> {
+    /// Anyone can distribute, unless reserved for keepers
+    #[account(mut)]
+    pub settler: Signer<'info>,
+
+    /// Caller's keeper registration; required while cranks are reserved for keepers
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, settler.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_SETTLEMENT) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        constraint = position.status != PositionStatus::SettlementDisputed @ ErrorCode::SettlementDisputed,
+        constraint = position.status == PositionStatus::AwaitingDistribution @ ErrorCode::SettlementNotSnapshotted
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Position user's lifetime stats
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, position.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// MM's registry (for stats tracking)
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, position.market_maker.as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    /// Position's user vault (user's locked collateral)
+    #[account(
+        mut,
+        constraint = position_user_vault.key() == position.user_vault @ ErrorCode::InvalidVault
+    )]
+    pub position_user_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the position vault
+    #[account(address = position_user_vault.mint @ ErrorCode::InvalidMint)]
+    pub vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA authority for position vaults
+    #[account(
+        seeds = [POSITION_SEED, position.user.as_ref(), &position.position_id.to_le_bytes()],
+        bump = position.bump
+    )]
+    pub position_authority: AccountInfo<'info>,
+
+    /// CHECK: User (or their beneficiary), owner of the user payout ATA
+    #[account(address = position.user_payout_owner() @ ErrorCode::Unauthorized)]
+    pub user_payout_owner: UncheckedAccount<'info>,
+
+    /// User's payout ATA, created by the settler if missing
+    #[account(
+        init_if_needed,
+        payer = settler,
+        associated_token::mint = vault_mint,
+        associated_token::authority = user_payout_owner,
+        associated_token::token_program = token_program
+    )]
+    pub user_destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// User's underlying account, required when they elected an underlying payout
+    #[account(mut)]
+    pub user_underlying_destination: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: User's wallet (or their beneficiary's); when given, a wSOL vault
+    /// pays the user side out as native SOL
+    #[account(mut)]
+    pub user_wallet: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: MM (or their beneficiary), owner of the MM payout ATA
+    #[account(address = position.mm_payout_owner() @ ErrorCode::Unauthorized)]
+    pub mm_payout_owner: UncheckedAccount<'info>,
+
+    /// MM's payout ATA, created by the settler if missing
+    #[account(
+        init_if_needed,
+        payer = settler,
+        associated_token::mint = vault_mint,
+        associated_token::authority = mm_payout_owner,
+        associated_token::token_program = token_program
+    )]
+    pub mm_destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Stake pool or rate feed, for positions collateralized in the
+    /// asset's LST; matched against the asset's configured LST rate account
+    pub lst_rate: Option<UncheckedAccount<'info>>,
+
+    /// Compliance blocklist; blocked parties' payouts are withheld
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    /// Series the position belongs to; required when it joined one
+    #[account(mut)]
+    pub option_series: Option<Account<'info, OptionSeries>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_distribute_settlement(ctx: Context<DistributeSettlement>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= ctx.accounts.position.distributable_at,
+        ErrorCode::DisputeWindowOpen
+    );
+    require_keeper(
+        &ctx.accounts.global_state,
+        ctx.accounts.keeper.as_ref(),
+        ctx.accounts.position.expiry_timestamp,
+        clock.unix_timestamp,
+    )?;
+
+    let settlement_price = ctx
+        .accounts
+        .position
+        .settlement_price
+        .ok_or(ErrorCode::SettlementNotSnapshotted)?;
+    let vault_amount = ctx.accounts.position_user_vault.amount;
+
+    let (user_amount, mm_amount) = settle_at_price(
+        &mut ctx.accounts.position,
+        &ctx.accounts.position_user_vault,
+        &ctx.accounts.vault_mint,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.user_destination,
+        ctx.accounts.user_underlying_destination.as_ref(),
+        UserPayoutRoute::from_wallet(ctx.accounts.user_wallet.as_deref()),
+        &ctx.accounts.mm_destination,
+        &ctx.accounts.token_program,
+        &ctx.accounts.blocklist,
+        settlement_terms_for_vault(
+            &ctx.accounts.asset_config,
+            ctx.accounts.vault_mint.key(),
+            ctx.accounts.lst_rate.as_deref(),
+            &clock,
+        )?,
+        settlement_price,
+    )?;
+    record_position_settled(
+        &ctx.accounts.position,
+        vault_amount,
+        user_amount,
+        mm_amount,
+        ctx.accounts.settler.key(),
+        &mut ctx.accounts.asset_config,
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.mm_registry,
+        ctx.accounts.option_series.as_mut(),
+    )?;
+    if let Some(keeper) = active_keeper(&mut ctx.accounts.keeper) {
+        keeper.record_settlements(1, clock.unix_timestamp);
+    }
+
+    msg!("Position {} distributed. User: {}, MM: {}",
+         ctx.accounts.position.position_id, user_amount, mm_amount);
+
+    Ok(())
+}
+
+// ===== Settle Positions Batch =====
+
+/// Accounts per position in `remaining_accounts`, after an index asset's
+/// component price updates:
+/// position, position_user_vault, position_authority, user_destination, mm_destination,
+/// user_stats, mm_registry, user_underlying_destination (the user's underlying
+/// account when they elected an underlying payout; otherwise user_destination again)
+pub const SETTLE_BATCH_ACCOUNTS_PER_POSITION: usize = 8;
+
+/// Settle many expired positions of one asset, expiry and series against a
+/// single price update
+#[derive(Accounts)]
+pub struct SettlePositionsBatch<'info> {
+    /// Anyone can call settle, unless reserved for keepers
+    pub settler: Signer<'info>,
+
+    /// Caller's keeper registration; required while cranks are reserved for keepers
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, settler.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_SETTLEMENT) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, asset_config.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused,
+        constraint = !asset_config.quote_depegged @ ErrorCode::QuoteDepegged
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Oracle feeds for the asset's configured source
+    pub oracle: OracleAccounts<'info>,
+
+    /// Pyth update for the quote currency, required when the asset checks
+    /// its quote peg
+    pub quote_price_update: Option<Account<'info, PriceUpdateV2>>,
+
+    /// Compliance blocklist; blocked parties' payouts are withheld
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    /// Asset's quote mint (vaults holding quote)
+    #[account(address = asset_config.quote_mint @ ErrorCode::InvalidVault)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Asset's underlying mint (vaults holding the underlying); required
+    /// unless the asset is off-chain and has none
+    #[account(address = asset_config.asset_mint @ ErrorCode::InvalidVault)]
+    pub underlying_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Asset's LST collateral mint; required when a batched covered call is
+    /// collateralized in it
+    #[account(address = asset_config.lst_mint @ ErrorCode::InvalidVault)]
+    pub lst_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Series every batched position belongs to; omitted for a batch of
+    /// positions outside any series
+    #[account(mut)]
+    pub option_series: Option<Account<'info, OptionSeries>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_settle_positions_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettlePositionsBatch<'info>>,
+    expiry_timestamp: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        ctx.accounts.global_state.settlement_dispute_window == 0
+            && !ctx.accounts.asset_config.uses_twap(),
+        ErrorCode::SettlementRequiresSnapshot
+    );
+    require!(
+        clock.unix_timestamp >= expiry_timestamp,
+        ErrorCode::PositionNotExpired
+    );
+    require_keeper(
+        &ctx.accounts.global_state,
+        ctx.accounts.keeper.as_ref(),
+        expiry_timestamp,
+        clock.unix_timestamp,
+    )?;
+    require!(
+        ctx.accounts.asset_config.is_offchain() || ctx.accounts.underlying_mint.is_some(),
+        ErrorCode::InvalidMint
+    );
+
+    // Batches settle positions in the primary quote only, at one price
+    let quote_mint = ctx.accounts.asset_config.quote_mint;
+    let quote_price = match check_quote_peg(
+        &mut ctx.accounts.asset_config,
+        quote_mint,
+        ctx.accounts.quote_price_update.as_ref(),
+        ctx.accounts.settler.key(),
+        &clock,
+    )? {
+        QuoteCheck::Settle(quote_price) => quote_price,
+        QuoteCheck::Halted => return Ok(()),
+    };
+
+    // An index asset's component updates lead remaining_accounts
+    let (basket_updates, remaining) =
+        split_basket_updates(ctx.remaining_accounts, &ctx.accounts.asset_config)?;
+    require!(
+        !remaining.is_empty()
+            && remaining
+                .chunks_exact(SETTLE_BATCH_ACCOUNTS_PER_POSITION)
+                .remainder()
+                .is_empty(),
+        ErrorCode::InvalidBatchAccounts
+    );
+
+    let asset_mint = ctx.accounts.asset_config.asset_mint;
+    let series = ctx
+        .accounts
+        .option_series
+        .as_ref()
+        .map(|series| series.key())
+        .unwrap_or_default();
+    let settlement_price = get_asset_price(
+        &ctx.accounts.oracle,
+        basket_updates,
+        &ctx.accounts.asset_config,
+        &clock,
+    )?;
+    let settlement_price = price_in_quote(settlement_price, quote_price)?;
+
+    let mut positions_settled: u64 = 0;
+    for group in remaining.chunks(SETTLE_BATCH_ACCOUNTS_PER_POSITION) {
+        let [
+            position_info,
+            vault_info,
+            authority_info,
+            user_destination_info,
+            mm_destination_info,
+            user_stats_info,
+            mm_registry_info,
+            user_underlying_destination_info,
+        ] = group
+        else {
+            return err!(ErrorCode::InvalidBatchAccounts);
+        };
+
+        let mut position: Account<'info, Position> = Account::try_from(position_info)?;
+        require!(
+            position.status == PositionStatus::Active,
+            ErrorCode::PositionNotActive
+        );
+        require!(
+            position.asset_mint == asset_mint
+                && position.quote_mint == quote_mint
+                && position.expiry_timestamp == expiry_timestamp,
+            ErrorCode::InvalidBatchAccounts
+        );
+        require_keys_eq!(position.series, series, ErrorCode::SeriesMismatch);
+
+        let vault: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(vault_info)?;
+        require!(vault.key() == position.user_vault, ErrorCode::InvalidVault);
+        let collateral_mints = [&ctx.accounts.underlying_mint, &ctx.accounts.lst_mint];
+        let vault_mint = if vault.mint == ctx.accounts.quote_mint.key() {
+            &ctx.accounts.quote_mint
+        } else {
+            collateral_mints
+                .into_iter()
+                .flatten()
+                .find(|mint| mint.key() == vault.mint)
+                .ok_or(ErrorCode::InvalidVault)?
+        };
+
+        let expected_authority = Pubkey::create_program_address(
+            &[
+                POSITION_SEED,
+                position.user.as_ref(),
+                &position.position_id.to_le_bytes(),
+                &[position.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidBatchAccounts)?;
+        require_keys_eq!(
+            authority_info.key(),
+            expected_authority,
+            ErrorCode::InvalidBatchAccounts
+        );
+
+        let user_destination: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(user_destination_info)?;
+        let mm_destination: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(mm_destination_info)?;
+        require!(
+            user_destination.owner == position.user_payout_owner()
+                && mm_destination.owner == position.mm_payout_owner(),
+            ErrorCode::InvalidBatchAccounts
+        );
+
+        let user_underlying_destination: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(user_underlying_destination_info)?;
+
+        let (user_amount, mm_amount) = settle_at_price(
+            &mut position,
+            &vault,
+            vault_mint,
+            authority_info,
+            &user_destination,
+            Some(&user_underlying_destination),
+            UserPayoutRoute::Token,
+            &mm_destination,
+            &ctx.accounts.token_program,
+            &ctx.accounts.blocklist,
+            settlement_terms_for_vault(
+                &ctx.accounts.asset_config,
+                vault.mint,
+                ctx.accounts.oracle.lst_rate.as_ref(),
+                &clock,
+            )?,
+            settlement_price,
+        )?;
+        let mut user_stats: Account<'info, UserStats> = Account::try_from(user_stats_info)?;
+        require_keys_eq!(user_stats.user, position.user, ErrorCode::InvalidBatchAccounts);
+        let mut mm_registry: Account<'info, MMRegistry> = Account::try_from(mm_registry_info)?;
+        require_keys_eq!(
+            mm_registry.owner,
+            position.market_maker,
+            ErrorCode::InvalidBatchAccounts
+        );
+        record_position_settled(
+            &position,
+            vault.amount,
+            user_amount,
+            mm_amount,
+            ctx.accounts.settler.key(),
+            &mut ctx.accounts.asset_config,
+            &mut ctx.accounts.global_state,
+            &mut user_stats,
+            &mut mm_registry,
+            ctx.accounts.option_series.as_mut(),
+        )?;
+
+        // Persist the updated accounts (not Anchor-managed here)
+        position.exit(&crate::ID)?;
+        user_stats.exit(&crate::ID)?;
+        mm_registry.exit(&crate::ID)?;
+
+        msg!("Position {} settled. User: {}, MM: {}",
+             position.position_id, user_amount, mm_amount);
+        positions_settled += 1;
+    }
+    if let Some(keeper) = active_keeper(&mut ctx.accounts.keeper) {
+        keeper.record_settlements(positions_settled, clock.unix_timestamp);
+    }
+
+    emit!(PositionsBatchSettled {
+        asset_mint,
+        expiry_timestamp,
+        settlement_price,
+        positions_settled,
+    });
+
+    Ok(())
+}
+
+// ===== Set Payout Beneficiary =====
+
+/// User or MM redirects their side of the settlement payout
+#[derive(Accounts)]
+pub struct SetPayoutBeneficiary<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive,
+        constraint =
+            signer.key() == position.holder ||
+            signer.key() == position.market_maker
+            @ ErrorCode::Unauthorized
+    )]
+    pub position: Account<'info, Position>,
+}
+
+pub fn handle_set_payout_beneficiary(
+    ctx: Context<SetPayoutBeneficiary>,
+    beneficiary: Option<Pubkey>,
+) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    let position = &mut ctx.accounts.position;
+
+    // Passing None reverts to the original key
+    if signer == position.holder {
+        position.user_beneficiary = beneficiary;
+    } else {
+        position.mm_beneficiary = beneficiary;
+    }
+
+    emit!(PayoutBeneficiarySet {
+        position_id: position.position_id,
+        set_by: signer,
+        beneficiary,
+    });
+
+    Ok(())
+}
+
+// ===== Elect Underlying Payout =====
+
+/// Covered-call user opts to receive their payout in the underlying asset
+#[derive(Accounts)]
+pub struct ElectUnderlyingPayout<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive,
+        constraint = position.holder == user.key() @ ErrorCode::Unauthorized,
+        constraint = position.strategy == StrategyType::CoveredCall @ ErrorCode::UnderlyingPayoutUnavailable
+    )]
+    pub position: Account<'info, Position>,
+
+    /// Position's user vault (must hold the underlying to pay out in it)
+    #[account(
+        constraint = position_user_vault.key() == position.user_vault @ ErrorCode::InvalidVault
+    )]
+    pub position_user_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+pub fn handle_elect_underlying_payout(ctx: Context<ElectUnderlyingPayout>, elect: bool) -> Result<()> {
+    // Quote-escrowed calls have no underlying to pay out
+    require!(
+        !elect || ctx.accounts.position_user_vault.mint == ctx.accounts.position.asset_mint,
+        ErrorCode::UnderlyingPayoutUnavailable
+    );
+
+    let position = &mut ctx.accounts.position;
+    position.user_payout_in_underlying = elect;
+
+    emit!(UnderlyingPayoutElected {
+        position_id: position.position_id,
+        elected: elect,
+    });
+
+    Ok(())
+}
+
+// ===== Payout Swap =====
+
+/// User asks for their settlement payout to be swapped into the position's
+/// other leg, within `max_slippage_bps` of the settlement price
+#[derive(Accounts)]
+pub struct SetPayoutSwap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive,
+        constraint = position.holder == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = PayoutSwap::LEN,
+        seeds = [PAYOUT_SWAP_SEED, position.key().as_ref()],
+        bump
+    )]
+    pub payout_swap: Account<'info, PayoutSwap>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_set_payout_swap(
+    ctx: Context<SetPayoutSwap>,
+    output_mint: Pubkey,
+    max_slippage_bps: u16,
+) -> Result<()> {
+    let position = &ctx.accounts.position;
+    require!(
+        (output_mint == position.asset_mint || output_mint == position.quote_mint)
+            && max_slippage_bps <= MAX_PAYOUT_SWAP_SLIPPAGE_BPS,
+        ErrorCode::InvalidPayoutSwap
+    );
+
+    let payout_swap = &mut ctx.accounts.payout_swap;
+    payout_swap.position = position.key();
+    payout_swap.user = position.user;
+    payout_swap.output_mint = output_mint;
+    payout_swap.max_slippage_bps = max_slippage_bps;
+    payout_swap.bump = ctx.bumps.payout_swap;
+
+    emit!(PayoutSwapSet {
+        position_id: position.position_id,
+        output_mint,
+        max_slippage_bps,
+    });
+
+    Ok(())
+}
+
+/// User withdraws their payout swap request and reclaims its rent
+#[derive(Accounts)]
+pub struct ClearPayoutSwap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Position the request was made for; may already be archived
+    pub position: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [PAYOUT_SWAP_SEED, position.key().as_ref()],
+        bump = payout_swap.bump,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub payout_swap: Account<'info, PayoutSwap>,
+}
+
+pub fn handle_clear_payout_swap(_ctx: Context<ClearPayoutSwap>) -> Result<()> {
+    Ok(())
+}
+
+// ===== Sweep Dust =====
+// Permissionless: once a position has paid out in full, whatever is still in
+// its vaults (stray transfers, lamports sent to a wSOL vault) goes to the
+// treasury. Must run before the position is archived, which orphans the vaults.
+
+#[derive(Accounts)]
+pub struct SweepDust<'info> {
+    pub sweeper: Signer<'info>,
+
+    #[account(seeds = [GLOBAL_STATE_SEED], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [POSITION_SEED, position.user.as_ref(), &position.position_id.to_le_bytes()],
+        bump = position.bump,
+        constraint = position.is_archivable() @ ErrorCode::PositionNotArchivable
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// One of the position's vaults, still owned by the position PDA
+    #[account(
+        mut,
+        constraint = vault.key() == position.user_vault
+            || vault.key() == position.mm_vault_locked @ ErrorCode::InvalidVault,
+        constraint = vault.owner == position.key() @ ErrorCode::InvalidVault
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = vault.mint @ ErrorCode::InvalidMint)]
+    pub vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_mint,
+        associated_token::authority = global_state.treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+    // Lamports sent straight to a wSOL vault only count once synced
+    if is_native_mint(ctx.accounts.vault.mint) {
+        sync_native(
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+        ctx.accounts.vault.reload()?;
+    }
+
+    let amount = ctx.accounts.vault.amount;
+    require!(amount > 0, ErrorCode::NoDustToSweep);
+
+    transfer_from_position_vault(
+        &ctx.accounts.position,
+        &ctx.accounts.vault,
+        &ctx.accounts.vault_mint,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.position.to_account_info(),
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(DustSwept {
+        position_id: ctx.accounts.position.position_id,
+        vault: ctx.accounts.vault.key(),
+        mint: ctx.accounts.vault.mint,
+        amount,
+        sweeper: ctx.accounts.sweeper.key(),
+    });
+
+    Ok(())
+}
+
+/// Where `settle_at_price` sends the user's share
+#[derive(Clone, Copy)]
+pub(crate) enum UserPayoutRoute<'a, 'info> {
+    /// Transfer to the user's destination token account
+    Token,
+    /// Close a wSOL vault to the user's wallet once the MM is paid
+    Native(&'a AccountInfo<'info>),
+    /// Leave it in the vault for the caller to route (payout swaps)
+    Retain,
+}
+
+impl<'a, 'info> UserPayoutRoute<'a, 'info> {
+    /// Native when the settler passed the user's wallet, otherwise Token
+    pub(crate) fn from_wallet(user_wallet: Option<&'a AccountInfo<'info>>) -> Self {
+        user_wallet.map_or(Self::Token, Self::Native)
+    }
+}
+
+/// Split a position vault at `settlement_price`, pay both sides (withholding
+/// blocklisted parties' shares) and mark the position settled.
+/// Returns (user_amount, mm_amount).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn settle_at_price<'info>(
+    position: &mut Position,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    vault_mint: &InterfaceAccount<'info, Mint>,
+    position_authority: &AccountInfo<'info>,
+    user_destination: &InterfaceAccount<'info, TokenAccount>,
+    user_underlying_destination: Option<&InterfaceAccount<'info, TokenAccount>>,
+    user_route: UserPayoutRoute<'_, 'info>,
+    mm_destination: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    blocklist: &Blocklist,
+    terms: SettlementTerms,
+    settlement_price: u64,
+) -> Result<(u64, u64)> {
+    // Calculate payout based on strategy and ITM/OTM
+    let (user_amount, mm_amount, status) = calculate_settlement(
+        position.strategy,
+        terms,
+        settlement_price,
+        position.strike_price,
+        position.contract_size,
+        vault.amount,
+    )?;
+
+    // The MM must deliver its leg before taking the collateral
+    require!(
+        !(terms.kind == SettlementKind::Physical && status == PositionStatus::SettledITM),
+        ErrorCode::PhysicalDeliveryRequired
+    );
+
+    // A wSOL vault is closed to the user's wallet once the MM is paid, so the
+    // user's share arrives as native SOL (only when nothing is withheld in it)
+    let user_blocked = blocklist.blocks_user_side(position);
+    let mm_blocked = blocklist.blocks_mm_side(position);
+    let user_route = match user_route {
+        UserPayoutRoute::Native(_)
+            if user_blocked || mm_blocked || !is_native_mint(vault.mint) =>
+        {
+            UserPayoutRoute::Token
+        }
+        route => route,
+    };
+
+    // Blocklisted parties' shares stay in the vault until claimed after review
+    if user_blocked {
+        position.user_payout_withheld = user_amount;
+        emit!(PayoutWithheld {
+            position_id: position.position_id,
+            party: position.holder,
+            amount: user_amount,
+        });
+    } else if matches!(user_route, UserPayoutRoute::Token) {
+        // Covered-call users who elected the underlying are paid into their underlying account
+        let user_destination = if position.user_payout_in_underlying && vault.mint == position.asset_mint {
+            let destination = user_underlying_destination
+                .ok_or(ErrorCode::UnderlyingDestinationRequired)?;
+            require!(
+                destination.owner == position.user_payout_owner()
+                    && destination.mint == position.asset_mint,
+                ErrorCode::InvalidVault
+            );
+            destination
+        } else {
+            user_destination
+        };
+
+        // Transfer user's share
+        transfer_from_position_vault(
+            position,
+            vault,
+            vault_mint,
+            user_destination,
+            position_authority,
+            token_program,
+            user_amount,
+        )?;
+    }
+
+    if mm_blocked {
+        position.mm_payout_withheld = mm_amount;
+        emit!(PayoutWithheld {
+            position_id: position.position_id,
+            party: position.market_maker,
+            amount: mm_amount,
+        });
+    } else {
+        // Transfer MM's share
+        transfer_from_position_vault(
+            position,
+            vault,
+            vault_mint,
+            mm_destination,
+            position_authority,
+            token_program,
+            mm_amount,
+        )?;
+    }
+
+    if let UserPayoutRoute::Native(user_wallet) = user_route {
+        require_keys_eq!(
+            user_wallet.key(),
+            position.user_payout_owner(),
+            ErrorCode::Unauthorized
+        );
+        let position_id_bytes = position.position_id.to_le_bytes();
+        let position_seeds = &[
+            POSITION_SEED,
+            position.user.as_ref(),
+            &position_id_bytes,
+            &[position.bump],
+        ];
+        unwrap_native(
+            &vault.to_account_info(),
+            user_wallet,
+            position_authority,
+            &token_program.to_account_info(),
+            &[&position_seeds[..]],
+        )?;
+    }
+
+    position.settlement_price = Some(settlement_price);
+    position.status = status;
+
+    Ok((user_amount, mm_amount))
+}
+
+/// Bookkeeping every settlement path runs once a position's vault of
+/// `vault_amount` is split, `user_amount` paid to the user and `mm_amount`
+/// to the MM: leave its series, release open interest, record both parties'
+/// stats and emit PositionSettled at the position's recorded price and status
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn record_position_settled(
+    position: &Position,
+    vault_amount: u64,
+    user_amount: u64,
+    mm_amount: u64,
+    settler: Pubkey,
+    asset_config: &mut AssetConfig,
+    global_state: &mut GlobalState,
+    user_stats: &mut UserStats,
+    mm_registry: &mut MMRegistry,
+    option_series: Option<&mut Account<OptionSeries>>,
+) -> Result<()> {
+    leave_series(position, option_series)?;
+    asset_config.record_position_closed(position.notional);
+    global_state.record_notional_closed(position.notional);
+    user_stats.record_settlement(position.premium_paid, vault_amount, user_amount);
+    mm_registry.total_intents_filled = mm_registry.total_intents_filled.saturating_add(1);
+
+    emit!(PositionSettled {
+        position_id: position.position_id,
+        settlement_price: position.settlement_price.unwrap_or_default(),
+        user_payout: user_amount,
+        mm_payout: mm_amount,
+        status: position.status,
+        settler,
+    });
+
+    Ok(())
+}
+
+/// Transfer `amount` out of a position vault, signed by the position PDA.
+/// For fee-bearing mints the recipient bears the transfer fee.
+/// No-op for zero amounts.
+pub(crate) fn transfer_from_position_vault<'info>(
+    position: &Position,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    destination: &InterfaceAccount<'info, TokenAccount>,
+    position_authority: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let position_id_bytes = position.position_id.to_le_bytes();
+    let position_seeds = &[
+        POSITION_SEED,
+        position.user.as_ref(),
+        &position_id_bytes,
+        &[position.bump],
+    ];
+    let signer = &[&position_seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: vault.to_account_info(),
+        mint: mint.to_account_info(),
+        to: destination.to_account_info(),
+        authority: position_authority.clone(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer),
+        amount,
+        mint.decimals,
+    )
+}
+
+/// Get the settlement price from the asset's oracle, falling back to its
+/// Switchboard feed when the primary read is stale, unverified or too
+/// uncertain. A fair-value priced asset settles at base price x exchange rate.
+pub(crate) fn get_settlement_price(
+    oracle: &OracleAccounts,
+    asset_config: &AssetConfig,
+    clock: &Clock,
+) -> Result<u64> {
+    require!(!asset_config.has_basket(), ErrorCode::BasketPriceUpdatesRequired);
+    let primary_error =
+        match read_oracle_source(asset_config.oracle_source, oracle, asset_config, clock) {
+            Ok(price) => return Ok(fair_value_price(price, oracle, asset_config, clock)?.price),
+            Err(e) => e,
+        };
+
+    if !is_recoverable_oracle_error(primary_error) || !asset_config.has_switchboard_fallback() {
+        return Err(primary_error.into());
+    }
+
+    let fallback = read_oracle_source(OracleSource::Switchboard, oracle, asset_config, clock)?;
+    let fallback = fair_value_price(fallback, oracle, asset_config, clock)?;
+
+    emit!(OracleFallbackUsed {
+        asset_mint: asset_config.asset_mint,
+        switchboard_feed: asset_config.switchboard_feed,
+        price: fallback.price,
+    });
+
+    Ok(fallback.price)
+}
+
+/// Price of an asset that may be an index: its basket level from
+/// `basket_updates`, otherwise `get_settlement_price`
+pub(crate) fn get_asset_price(
+    oracle: &OracleAccounts,
+    basket_updates: &[AccountInfo],
+    asset_config: &AssetConfig,
+    clock: &Clock,
+) -> Result<u64> {
+    if asset_config.has_basket() {
+        return Ok(read_basket_price(basket_updates, asset_config, clock)?.price);
+    }
+    get_settlement_price(oracle, asset_config, clock)
+}
+
+/// Split an index asset's component price updates, which lead
+/// `remaining_accounts` in basket order, from the accounts that follow
+pub(crate) fn split_basket_updates<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    asset_config: &AssetConfig,
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    let basket_len = asset_config.basket_len();
+    require!(
+        remaining_accounts.len() >= basket_len,
+        ErrorCode::BasketPriceUpdatesRequired
+    );
+    Ok(remaining_accounts.split_at(basket_len))
+}
+
+/// Calculate settlement amounts based on strategy.
+///
+/// Cash settlement pays the MM the option's intrinsic value on
+/// `contract_size` (rounded down, capped at the vault) and the user the rest,
+/// so rounding always favors the collateral poster and the vault is emptied.
+/// Physical settlement hands the whole vault to the MM when ITM; the MM's leg
+/// is then delivered separately (see `deliver_physical_settlement`). Prices
+/// within `min_itm_bps` of the strike settle OTM, avoiding dust transfers.
+/// Amounts that do not fit in u64 fail with `MathOverflow` rather than
+/// being clamped.
+pub(crate) fn calculate_settlement(
+    strategy: StrategyType,
+    terms: SettlementTerms,
+    settlement_price: u64,
+    strike_price: u64,
+    contract_size: u64,
+    vault_amount: u64,
+) -> Result<(u64, u64, PositionStatus)> {
+    // Within the auto-exercise threshold of the strike counts as OTM
+    let min_itm_move = apply_bps(strike_price, terms.min_itm_bps as u64)?;
+
+    match strategy {
+        StrategyType::CoveredCall => {
+            let itm_above = strike_price
+                .checked_add(min_itm_move)
+                .ok_or(ErrorCode::MathOverflow)?;
+            if settlement_price > itm_above {
+                if terms.kind == SettlementKind::Physical {
+                    // ITM: Underlying delivered to MM against strike proceeds
+                    return Ok((0, vault_amount, PositionStatus::SettledITM));
+                }
+                // ITM: MM gets the upside above strike, in underlying units:
+                // contract_size * (price - strike) / price, paid in collateral
+                let mm_gain_underlying = (contract_size as u128)
+                    .checked_mul((settlement_price - strike_price) as u128)
+                    .and_then(|product| product.checked_div(settlement_price as u128))
+                    .and_then(|gain| u64::try_from(gain).ok())
+                    .ok_or(ErrorCode::MathOverflow)?;
+                let mm_gain = terms
+                    .to_collateral(mm_gain_underlying, false)
+                    .ok_or(ErrorCode::MathOverflow)?
+                    .min(vault_amount);
+                Ok((vault_amount - mm_gain, mm_gain, PositionStatus::SettledITM))
+            } else {
+                // OTM: Expires worthless, user keeps collateral, MM keeps premium
+                Ok((vault_amount, 0, PositionStatus::SettledOTM))
+            }
+        }
+        StrategyType::CashSecuredPut => {
+            let itm_below = strike_price
+                .checked_sub(min_itm_move)
+                .ok_or(ErrorCode::MathOverflow)?;
+            if settlement_price < itm_below {
+                if terms.kind == SettlementKind::Physical {
+                    // ITM: User's USDC pays the strike against delivered underlying
+                    return Ok((0, vault_amount, PositionStatus::SettledITM));
+                }
+                // ITM: MM gets the shortfall below strike, in quote units:
+                // contract_size * (strike - price)
+                let mm_gain = terms
+                    .quote_value(contract_size, strike_price - settlement_price, false)
+                    .ok_or(ErrorCode::MathOverflow)?
+                    .min(vault_amount);
+                Ok((vault_amount - mm_gain, mm_gain, PositionStatus::SettledITM))
+            } else {
+                // OTM: Expires worthless, user keeps USDC, MM keeps premium
+                Ok((vault_amount, 0, PositionStatus::SettledOTM))
+            }
+        }
+    }
+}
+
+/// MM's leg of an ITM physical settlement: strike proceeds for calls,
+/// the underlying for puts
+pub(crate) fn physical_delivery_amount(
+    strategy: StrategyType,
+    strike_price: u64,
+    contract_size: u64,
+    terms: &SettlementTerms,
+) -> Result<u64> {
+    match strategy {
+        StrategyType::CoveredCall => calculate_escrow_amount(
+            StrategyType::CashSecuredPut,
+            strike_price,
+            contract_size,
+            terms,
+        ),
+        StrategyType::CashSecuredPut => Ok(contract_size),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::intent::calculate_escrow_amount;
+
+    // SOL-like asset (9 decimals) priced in USDC (6 decimals) with a 1e-6 price
+    fn cash_terms() -> SettlementTerms {
+        SettlementTerms {
+            kind: SettlementKind::Cash,
+            min_itm_bps: 0,
+            asset_decimals: 9,
+            quote_decimals: 6,
+            price_exponent: -6,
+            collateral_rate: None,
+        }
+    }
+
+    #[test]
+    fn test_escrow_overflow_is_an_error() {
+        let terms = cash_terms();
+        assert!(calculate_escrow_amount(
+            StrategyType::CashSecuredPut,
+            u64::MAX,
+            u64::MAX,
+            &terms,
+        )
+        .is_err());
+        // A covered call escrows the size itself, whatever the strike
+        assert_eq!(
+            calculate_escrow_amount(StrategyType::CoveredCall, u64::MAX, u64::MAX, &terms)
+                .unwrap(),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn test_premium_overflow_is_an_error() {
+        let intent = Intent {
+            intent_id: 0,
+            user: Pubkey::default(),
+            market_maker: Pubkey::default(),
+            asset_mint: Pubkey::default(),
+            quote_mint: Pubkey::default(),
+            strategy: StrategyType::CoveredCall,
+            strike_price: 1,
+            premium_per_contract: u64::MAX,
+            contract_size: 2,
+            quote_expiry: 0,
+            quote_signature: [0; 64],
+            quote_nonce: 0,
+            implied_volatility_bps: 0,
+            user_escrow: Pubkey::default(),
+            escrow_amount: 0,
+            created_at: 0,
+            fill_deadline: 0,
+            reference_price: 0,
+            underlying_premium_slippage_bps: None,
+            resolution: None,
+            status: IntentStatus::Pending,
+            bump: 0,
+            disputed_by: None,
+            dispute_reason_hash: None,
+        };
+        assert!(intent.calculate_total_premium().is_err());
+    }
+
+    #[test]
+    fn test_extreme_call_settlement_stays_within_vault() {
+        let (user, mm, status) = calculate_settlement(
+            StrategyType::CoveredCall,
+            cash_terms(),
+            u64::MAX,
+            1,
+            u64::MAX,
+            u64::MAX,
+        )
+        .unwrap();
+        assert_eq!(status, PositionStatus::SettledITM);
+        assert_eq!(user as u128 + mm as u128, u64::MAX as u128);
+        assert!(mm < u64::MAX);
+    }
+
+    #[test]
+    fn test_extreme_put_settlement_caps_at_vault() {
+        let vault = calculate_escrow_amount(
+            StrategyType::CashSecuredPut,
+            u64::MAX / 1_000_000,
+            1_000_000_000,
+            &cash_terms(),
+        )
+        .unwrap();
+        let (user, mm, status) = calculate_settlement(
+            StrategyType::CashSecuredPut,
+            cash_terms(),
+            0,
+            u64::MAX / 1_000_000,
+            1_000_000_000,
+            vault,
+        )
+        .unwrap();
+        assert_eq!(status, PositionStatus::SettledITM);
+        assert_eq!((user, mm), (0, vault));
+    }
+
+    #[test]
+    fn test_itm_threshold_overflow_is_an_error() {
+        let terms = SettlementTerms {
+            min_itm_bps: 100,
+            ..cash_terms()
+        };
+        assert!(calculate_settlement(
+            StrategyType::CoveredCall,
+            terms,
+            u64::MAX,
+            u64::MAX,
+            1,
+            1,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_price_in_quote_restates_usd_price() {
+        // USDT at $0.98 (Pyth exponent -8): a $150 asset costs ~153.06 USDT
+        let usdt = QuotePrice { price: 98_000_000, exponent: -8 };
+        assert_eq!(usdt.depeg_bps(), 200);
+        assert_eq!(price_in_quote(150_000_000, Some(usdt)).unwrap(), 153_061_224);
+        assert_eq!(price_in_quote(150_000_000, None).unwrap(), 150_000_000);
     }
 }
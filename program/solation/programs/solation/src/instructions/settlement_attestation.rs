@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::{
+    post_wormhole_message, wormhole_message_fee, wormhole_next_sequence,
+    WORMHOLE_BRIDGE_SEED, WORMHOLE_CORE_BRIDGE_ID, WORMHOLE_FEE_COLLECTOR_SEED,
+    WORMHOLE_SEQUENCE_SEED,
+};
+
+// ===== Events =====
+
+#[event]
+pub struct SettlementAttested {
+    pub position: Pubkey,
+    pub position_id: u64,
+    pub emitter: Pubkey,
+    pub sequence: u64,
+    pub payload_hash: [u8; 32],
+}
+
+// ===== Attest Settlement =====
+// Optional extension: post a settled position's outcome through Wormhole so
+// a companion contract on another chain can release mirrored collateral.
+// Permissionless; the caller pays the bridge fee and rent. Must run before
+// the position is archived.
+
+#[derive(Accounts)]
+pub struct AttestSettlement<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = position.is_terminal() @ ErrorCode::PositionNotSettled
+    )]
+    pub position: Account<'info, Position>,
+
+    /// Replay protection: a position's outcome is attested once
+    #[account(
+        init,
+        payer = payer,
+        space = SettlementAttestation::LEN,
+        seeds = [SETTLEMENT_ATTESTATION_SEED, position.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, SettlementAttestation>,
+
+    /// CHECK: Core bridge config, read for the message fee
+    #[account(
+        mut,
+        seeds = [WORMHOLE_BRIDGE_SEED],
+        bump,
+        seeds::program = wormhole_program.key()
+    )]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    /// CHECK: Message account the core bridge creates, a PDA of this program
+    /// per position
+    #[account(
+        mut,
+        seeds = [WORMHOLE_MESSAGE_SEED, position.key().as_ref()],
+        bump
+    )]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    /// CHECK: This program's Wormhole emitter
+    #[account(seeds = [WORMHOLE_EMITTER_SEED], bump)]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+
+    /// CHECK: Emitter's sequence tracker, owned by the core bridge
+    #[account(
+        mut,
+        seeds = [WORMHOLE_SEQUENCE_SEED, wormhole_emitter.key().as_ref()],
+        bump,
+        seeds::program = wormhole_program.key()
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Core bridge fee collector
+    #[account(
+        mut,
+        seeds = [WORMHOLE_FEE_COLLECTOR_SEED],
+        bump,
+        seeds::program = wormhole_program.key()
+    )]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    /// CHECK: Wormhole core bridge
+    #[account(address = WORMHOLE_CORE_BRIDGE_ID)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_attest_settlement(ctx: Context<AttestSettlement>) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let position_key = accounts.position.key();
+    let attested_at = accounts.clock.unix_timestamp;
+    let (payload, payload_hash) =
+        SettlementAttestationPayload::new(position_key, &accounts.position, attested_at)
+            .encode()?;
+
+    let fee = wormhole_message_fee(&accounts.wormhole_bridge)?;
+    if fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: accounts.payer.to_account_info(),
+                    to: accounts.wormhole_fee_collector.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+    }
+
+    let sequence = wormhole_next_sequence(&accounts.wormhole_sequence)?;
+    let emitter_seeds: &[&[u8]] = &[WORMHOLE_EMITTER_SEED, &[ctx.bumps.wormhole_emitter]];
+    let message_seeds: &[&[u8]] = &[
+        WORMHOLE_MESSAGE_SEED,
+        position_key.as_ref(),
+        &[ctx.bumps.wormhole_message],
+    ];
+    post_wormhole_message(
+        &accounts.wormhole_program,
+        &accounts.wormhole_bridge,
+        &accounts.wormhole_message,
+        &accounts.wormhole_emitter,
+        &accounts.wormhole_sequence,
+        &accounts.payer,
+        &accounts.wormhole_fee_collector,
+        &accounts.clock.to_account_info(),
+        &accounts.rent.to_account_info(),
+        &accounts.system_program,
+        payload,
+        &[emitter_seeds, message_seeds],
+    )?;
+
+    let attestation = &mut ctx.accounts.attestation;
+    attestation.position = position_key;
+    attestation.sequence = sequence;
+    attestation.payload_hash = payload_hash;
+    attestation.attested_at = attested_at;
+    attestation.bump = ctx.bumps.attestation;
+
+    emit!(SettlementAttested {
+        position: position_key,
+        position_id: ctx.accounts.position.position_id,
+        emitter: ctx.accounts.wormhole_emitter.key(),
+        sequence,
+        payload_hash,
+    });
+
+    Ok(())
+}
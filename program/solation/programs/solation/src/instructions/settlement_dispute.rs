@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+// ===== Events =====
+
+#[event]
+pub struct SettlementPriceDisputed {
+    pub position_id: u64,
+    pub disputed_by: Pubkey,
+    pub settlement_price: u64,
+    pub reason: String,
+}
+
+#[event]
+pub struct SettlementDisputeResolved {
+    pub position_id: u64,
+    pub resolved_by: Pubkey,
+    pub original_price: u64,
+    pub final_price: u64,
+}
+
+// ===== Dispute Settlement Price =====
+// Challenge an obviously wrong oracle print before funds move
+
+#[derive(Accounts)]
+pub struct DisputeSettlementPrice<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        constraint = position.status == PositionStatus::AwaitingDistribution @ ErrorCode::SettlementNotSnapshotted,
+        constraint =
+            signer.key() == position.holder ||
+            signer.key() == position.market_maker ||
+            signer.key() == global_state.authority
+            @ ErrorCode::UnauthorizedDispute
+    )]
+    pub position: Account<'info, Position>,
+}
+
+pub fn handle_dispute_settlement_price(
+    ctx: Context<DisputeSettlementPrice>,
+    reason: String,
+) -> Result<()> {
+    require!(
+        reason.len() <= MAX_DISPUTE_REASON_LEN,
+        ErrorCode::DisputeReasonTooLong
+    );
+
+    let clock = Clock::get()?;
+    let position = &mut ctx.accounts.position;
+
+    require!(
+        clock.unix_timestamp < position.distributable_at,
+        ErrorCode::DisputeWindowClosed
+    );
+
+    position.status = PositionStatus::SettlementDisputed;
+
+    emit!(SettlementPriceDisputed {
+        position_id: position.position_id,
+        disputed_by: ctx.accounts.signer.key(),
+        settlement_price: position.settlement_price.unwrap_or_default(),
+        reason,
+    });
+
+    Ok(())
+}
+
+// ===== Resolve Settlement Dispute =====
+// Authority confirms or corrects the price; payouts can then be distributed
+
+#[derive(Accounts)]
+pub struct ResolveSettlementDispute<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        constraint = position.status == PositionStatus::SettlementDisputed @ ErrorCode::IntentNotResolvable
+    )]
+    pub position: Account<'info, Position>,
+}
+
+pub fn handle_resolve_settlement_dispute(
+    ctx: Context<ResolveSettlementDispute>,
+    corrected_price: Option<u64>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let position = &mut ctx.accounts.position;
+
+    let original_price = position.settlement_price.unwrap_or_default();
+    if let Some(price) = corrected_price {
+        require!(price > 0, ErrorCode::InvalidSettlementPrice);
+        position.settlement_price = Some(price);
+    }
+
+    // Authority has ruled; no further challenge period
+    position.distributable_at = clock.unix_timestamp;
+    position.status = PositionStatus::AwaitingDistribution;
+
+    emit!(SettlementDisputeResolved {
+        position_id: position.position_id,
+        resolved_by: ctx.accounts.authority.key(),
+        original_price,
+        final_price: position.settlement_price.unwrap_or_default(),
+    });
+
+    Ok(())
+}
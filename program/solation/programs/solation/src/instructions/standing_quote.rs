@@ -0,0 +1,514 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::instructions::compliance::require_asset_access;
+use crate::instructions::intent::{
+    calculate_escrow_amount, deposit_user_escrow, open_position, require_intent_notional,
+    require_listed_terms, require_mm_quality, SubmitIntentParams,
+};
+use crate::instructions::option_series::join_series;
+use crate::instructions::settlement::get_asset_price;
+use crate::state::*;
+use crate::utils::*;
+
+// ===== Events =====
+
+#[event]
+pub struct StandingQuoteUpdated {
+    pub standing_quote: Pubkey,
+    pub market_maker: Pubkey,
+    pub asset_mint: Pubkey,
+    pub strategy: StrategyType,
+    pub expiry_timestamp: i64,
+    pub levels: u8,
+    pub remaining_contracts: u64,
+    pub valid_until: i64,
+}
+
+#[event]
+pub struct StandingQuoteTaken {
+    pub standing_quote: Pubkey,
+    pub position_id: u64,
+    pub user: Pubkey,
+    pub market_maker: Pubkey,
+    pub strike_price: u64,
+    pub contract_size: u64,
+    pub premium: u64,
+}
+
+#[event]
+pub struct StandingQuoteCancelled {
+    pub standing_quote: Pubkey,
+    pub market_maker: Pubkey,
+}
+
+// ===== Set Standing Quote =====
+// Creates the quote on first use; later calls replace the grid, size and
+// expiry in place. Takers are protected from a grid changing under them by
+// their minimum premium.
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StandingQuoteParams {
+    pub quote_id: u64,
+    pub asset_mint: Pubkey,
+    pub strategy: StrategyType,
+    pub expiry_timestamp: i64,
+    pub levels: Vec<QuoteLevel>,
+    /// Contracts offered across all strikes
+    pub max_contracts: u64,
+    pub valid_until: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: StandingQuoteParams)]
+pub struct SetStandingQuote<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [MM_REGISTRY_SEED, owner.key().as_ref()],
+        bump = mm_registry.bump,
+        constraint = mm_registry.owner == owner.key() @ ErrorCode::Unauthorized,
+        constraint = mm_registry.active @ ErrorCode::MMNotActive
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, params.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.delisted @ ErrorCode::AssetDelisted,
+        constraint = asset_config.enabled @ ErrorCode::AssetNotEnabled
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = StandingQuote::LEN,
+        seeds = [STANDING_QUOTE_SEED, owner.key().as_ref(), &params.quote_id.to_le_bytes()],
+        bump
+    )]
+    pub standing_quote: Account<'info, StandingQuote>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_set_standing_quote(
+    ctx: Context<SetStandingQuote>,
+    params: StandingQuoteParams,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        !params.levels.is_empty() && params.levels.len() <= MAX_STANDING_QUOTE_LEVELS,
+        ErrorCode::InvalidQuoteLevels
+    );
+    for (i, level) in params.levels.iter().enumerate() {
+        require!(
+            level.premium_per_contract > 0
+                && params.levels[..i]
+                    .iter()
+                    .all(|other| other.strike_price != level.strike_price),
+            ErrorCode::InvalidQuoteLevels
+        );
+    }
+    require!(params.max_contracts > 0, ErrorCode::InvalidAmount);
+    require!(
+        params.valid_until > clock.unix_timestamp
+            && params.valid_until <= clock.unix_timestamp + MAX_STANDING_QUOTE_LIFETIME
+            && params.valid_until < params.expiry_timestamp,
+        ErrorCode::QuoteExpired
+    );
+
+    let standing_quote = &mut ctx.accounts.standing_quote;
+    standing_quote.market_maker = ctx.accounts.owner.key();
+    standing_quote.quote_id = params.quote_id;
+    standing_quote.asset_mint = params.asset_mint;
+    standing_quote.quote_mint = ctx.accounts.asset_config.quote_mint;
+    standing_quote.strategy = params.strategy;
+    standing_quote.expiry_timestamp = params.expiry_timestamp;
+    standing_quote.levels = params.levels;
+    standing_quote.remaining_contracts = params.max_contracts;
+    standing_quote.valid_until = params.valid_until;
+    standing_quote.updated_at = clock.unix_timestamp;
+    standing_quote.bump = ctx.bumps.standing_quote;
+
+    emit!(StandingQuoteUpdated {
+        standing_quote: standing_quote.key(),
+        market_maker: standing_quote.market_maker,
+        asset_mint: standing_quote.asset_mint,
+        strategy: standing_quote.strategy,
+        expiry_timestamp: standing_quote.expiry_timestamp,
+        levels: standing_quote.levels.len() as u8,
+        remaining_contracts: standing_quote.remaining_contracts,
+        valid_until: standing_quote.valid_until,
+    });
+
+    Ok(())
+}
+
+// ===== Cancel Standing Quote =====
+
+#[derive(Accounts)]
+pub struct CancelStandingQuote<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            STANDING_QUOTE_SEED,
+            owner.key().as_ref(),
+            &standing_quote.quote_id.to_le_bytes()
+        ],
+        bump = standing_quote.bump,
+        close = owner
+    )]
+    pub standing_quote: Account<'info, StandingQuote>,
+}
+
+pub fn handle_cancel_standing_quote(ctx: Context<CancelStandingQuote>) -> Result<()> {
+    emit!(StandingQuoteCancelled {
+        standing_quote: ctx.accounts.standing_quote.key(),
+        market_maker: ctx.accounts.owner.key(),
+    });
+    Ok(())
+}
+
+// ===== Take Quote =====
+// The user escrows collateral into a fresh position vault and receives the
+// premium from the MM's vault in the same instruction; the Position is open
+// on return, with no intent or fill window.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct TakeQuoteParams {
+    /// ID of the new position, unique per user (shared with intent IDs)
+    pub position_id: u64,
+    pub strike_price: u64,
+    pub contract_size: u64,
+    /// Reject the take if the MM has lowered the premium below this
+    pub min_premium_per_contract: u64,
+    /// User's own floor on the MM's reputation score, on top of the protocol's
+    pub min_mm_reputation: Option<u32>,
+    /// User's own floor on the MM's fill rate (0-100), on top of the protocol's
+    pub min_mm_fill_rate: Option<u8>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: TakeQuoteParams)]
+pub struct TakeQuote<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.operations_enabled(OPERATION_NEW_INTENTS) @ ErrorCode::ProtocolPaused,
+        constraint = global_state.operations_enabled(OPERATION_FILLS) @ ErrorCode::ProtocolPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            STANDING_QUOTE_SEED,
+            standing_quote.market_maker.as_ref(),
+            &standing_quote.quote_id.to_le_bytes()
+        ],
+        bump = standing_quote.bump
+    )]
+    pub standing_quote: Box<Account<'info, StandingQuote>>,
+
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, standing_quote.market_maker.as_ref()],
+        bump = mm_registry.bump,
+        constraint = mm_registry.active @ ErrorCode::MMNotActive
+    )]
+    pub mm_registry: Box<Account<'info, MMRegistry>>,
+
+    /// Compliance blocklist
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.is_blocked(user.key()) @ ErrorCode::AddressBlocked,
+        constraint = !blocklist.is_blocked(standing_quote.market_maker) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Box<Account<'info, Blocklist>>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, standing_quote.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.delisted @ ErrorCode::AssetDelisted,
+        constraint = asset_config.enabled @ ErrorCode::AssetNotEnabled,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused
+    )]
+    pub asset_config: Box<Account<'info, AssetConfig>>,
+
+    #[account(
+        init,
+        payer = user,
+        space = Position::LEN,
+        seeds = [POSITION_SEED, user.key().as_ref(), &params.position_id.to_le_bytes()],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// User's collateral, held by the position PDA
+    #[account(
+        init,
+        payer = user,
+        token::mint = escrow_mint,
+        token::authority = position,
+        seeds = [POSITION_USER_VAULT_SEED, position.key().as_ref()],
+        bump
+    )]
+    pub position_user_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStats::LEN,
+        seeds = [USER_STATS_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    /// User's source token account; omit to escrow native SOL
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == escrow_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub user_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        constraint = asset_config.is_collateral_mint(
+            standing_quote.strategy,
+            standing_quote.quote_mint,
+            escrow_mint.key()
+        ) @ ErrorCode::InvalidCollateralMint
+    )]
+    pub escrow_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// MM vault the premium is paid from
+    #[account(
+        mut,
+        seeds = [MM_VAULT_SEED, mm_registry.key().as_ref(), quote_mint.key().as_ref()],
+        bump
+    )]
+    pub mm_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// User's account receiving the premium
+    #[account(
+        mut,
+        constraint = user_premium_account.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = user_premium_account.mint == quote_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub user_premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = standing_quote.quote_mint @ ErrorCode::InvalidMint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Spot feeds for the strike band, plus the LST rate account when
+    /// escrowing the asset's LST
+    pub oracle: OracleAccounts<'info>,
+
+    /// KYC pass token account, for assets gated on a pass mint
+    pub access_pass: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// User's credential, for assets gated on a credential issuer
+    pub user_credential: Option<Box<Account<'info, UserCredential>>>,
+
+    /// Composer allowlist, required when taken via CPI
+    #[account(seeds = [COMPOSER_ALLOWLIST_SEED], bump = composer_allowlist.bump)]
+    pub composer_allowlist: Option<Box<Account<'info, ComposerAllowlist>>>,
+
+    /// Instructions sysvar, to identify a calling program
+    /// CHECK: This is the instructions sysvar
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// Series to enroll the new position in, when its terms match one
+    #[account(mut)]
+    pub option_series: Option<Account<'info, OptionSeries>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_take_quote(ctx: Context<TakeQuote>, params: TakeQuoteParams) -> Result<()> {
+    let clock = Clock::get()?;
+    require_allowed_caller(
+        &ctx.accounts.instructions_sysvar,
+        ctx.accounts.composer_allowlist.as_deref().map(|allowlist| &**allowlist),
+    )?;
+    require_asset_access(
+        &ctx.accounts.asset_config,
+        ctx.accounts.user.key(),
+        ctx.accounts.access_pass.as_deref(),
+        ctx.accounts.user_credential.as_deref(),
+        clock.unix_timestamp,
+    )?;
+
+    let standing_quote = &ctx.accounts.standing_quote;
+    require!(
+        clock.unix_timestamp <= standing_quote.valid_until,
+        ErrorCode::QuoteExpired
+    );
+    let premium_per_contract = standing_quote
+        .premium_at(params.strike_price)
+        .ok_or(ErrorCode::StrikeNotQuoted)?;
+    require!(
+        premium_per_contract >= params.min_premium_per_contract,
+        ErrorCode::PremiumBelowMinimum
+    );
+    require!(params.contract_size > 0, ErrorCode::InvalidAmount);
+    require!(
+        params.contract_size <= standing_quote.remaining_contracts,
+        ErrorCode::StandingQuoteExhausted
+    );
+
+    // Held to the same listing, notional and MM-quality checks as an intent
+    let terms = SubmitIntentParams {
+        intent_id: params.position_id,
+        asset_mint: standing_quote.asset_mint,
+        quote_mint: standing_quote.quote_mint,
+        strategy: standing_quote.strategy,
+        strike_price: params.strike_price,
+        premium_per_contract,
+        contract_size: params.contract_size,
+        quote_expiry: standing_quote.expiry_timestamp,
+        quote_nonce: 0,
+        implied_volatility_bps: 0,
+        mm_signature: [0; 64],
+        ed25519_instruction_index: 0,
+        min_mm_reputation: params.min_mm_reputation,
+        min_mm_fill_rate: params.min_mm_fill_rate,
+    };
+    require_mm_quality(&ctx.accounts.global_state, &ctx.accounts.mm_registry, &terms)?;
+    // An index asset is priced from its component updates in remaining_accounts
+    let spot_price = get_asset_price(
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        &ctx.accounts.asset_config,
+        &clock,
+    )?;
+    require_listed_terms(&ctx.accounts.asset_config, &terms, spot_price, clock.unix_timestamp)?;
+    require_intent_notional(&ctx.accounts.global_state, &ctx.accounts.asset_config, &terms)?;
+
+    // 1. User collateral into the position vault
+    let escrow_amount = calculate_escrow_amount(
+        terms.strategy,
+        terms.strike_price,
+        terms.contract_size,
+        &settlement_terms_for_vault(
+            &ctx.accounts.asset_config,
+            ctx.accounts.escrow_mint.key(),
+            ctx.accounts.oracle.lst_rate.as_ref(),
+            &clock,
+        )?,
+    )?;
+    deposit_user_escrow(
+        &ctx.accounts.user,
+        ctx.accounts.user_token_account.as_deref(),
+        &ctx.accounts.position_user_vault,
+        &ctx.accounts.escrow_mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        escrow_amount,
+    )?;
+
+    // 2. Premium out of the MM vault, signed by the registry PDA. The MM
+    // covers any mint transfer fee so the user receives the full premium.
+    let total_premium = premium_per_contract
+        .checked_mul(params.contract_size)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let premium_sent = gross_up_for_fee(&ctx.accounts.quote_mint, total_premium)?;
+    let market_maker = ctx.accounts.standing_quote.market_maker;
+    let seeds = &[
+        MM_REGISTRY_SEED,
+        market_maker.as_ref(),
+        &[ctx.accounts.mm_registry.bump],
+    ];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.mm_vault.to_account_info(),
+        mint: ctx.accounts.quote_mint.to_account_info(),
+        to: ctx.accounts.user_premium_account.to_account_info(),
+        authority: ctx.accounts.mm_registry.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &[&seeds[..]],
+        ),
+        premium_sent,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+
+    // 3. Open the position; nothing is locked on the MM side
+    let notional = ctx
+        .accounts
+        .asset_config
+        .notional_value(terms.contract_size, terms.strike_price)?;
+    let position_terms = PositionTerms {
+        position_id: params.position_id,
+        user: ctx.accounts.user.key(),
+        market_maker,
+        strategy: terms.strategy,
+        asset_mint: terms.asset_mint,
+        quote_mint: terms.quote_mint,
+        strike_price: terms.strike_price,
+        contract_size: terms.contract_size,
+        expiry_timestamp: terms.quote_expiry,
+        implied_volatility_bps: terms.implied_volatility_bps,
+        user_vault: ctx.accounts.position_user_vault.key(),
+    };
+    let position = &mut ctx.accounts.position;
+    open_position(
+        position,
+        &position_terms,
+        notional,
+        total_premium,
+        ctx.accounts.mm_vault.key(),
+        0,
+        clock.unix_timestamp,
+    );
+    position.bump = ctx.bumps.position;
+    position.user_vault_bump = ctx.bumps.position_user_vault;
+    join_series(position, ctx.accounts.option_series.as_mut())?;
+
+    ctx.accounts.asset_config.record_position_opened(notional)?;
+    ctx.accounts.global_state.record_notional_opened(notional)?;
+
+    // 4. Stats and remaining size
+    ctx.accounts
+        .mm_registry
+        .record_fill(terms.contract_size, clock.unix_timestamp);
+    let user_stats = &mut ctx.accounts.user_stats;
+    if user_stats.user == Pubkey::default() {
+        user_stats.user = ctx.accounts.user.key();
+        user_stats.bump = ctx.bumps.user_stats;
+    }
+    user_stats.record_fill(total_premium);
+
+    let standing_quote = &mut ctx.accounts.standing_quote;
+    standing_quote.remaining_contracts -= params.contract_size;
+
+    emit!(StandingQuoteTaken {
+        standing_quote: standing_quote.key(),
+        position_id: params.position_id,
+        user: ctx.accounts.user.key(),
+        market_maker,
+        strike_price: params.strike_price,
+        contract_size: params.contract_size,
+        premium: total_premium,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,311 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+// ===== Events =====
+
+#[event]
+pub struct ParameterChangeQueued {
+    pub change_id: u64,
+    pub change: ParameterChange,
+    pub proposer: Pubkey,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct ParameterChangeExecuted {
+    pub change_id: u64,
+    pub change: ParameterChange,
+    pub executor: Pubkey,
+}
+
+#[event]
+pub struct ParameterChangeCancelled {
+    pub change_id: u64,
+    pub change: ParameterChange,
+    pub cancelled_by: Pubkey,
+}
+
+// ===== Queue Parameter Change =====
+// Fees, treasury, timelock delay, asset limits and intent sizing, the
+// insurance fee share and the dispute bond only change through here
+
+#[derive(Accounts)]
+pub struct QueueParameterChange<'info> {
+    pub authority: Signer<'info>,
+
+    /// Funds the PendingChange rent, so a multisig authority never pays
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = PendingChange::LEN,
+        seeds = [PENDING_CHANGE_SEED, &global_state.pending_change_count.to_le_bytes()],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_queue_parameter_change(
+    ctx: Context<QueueParameterChange>,
+    change: ParameterChange,
+) -> Result<()> {
+    validate_parameter_change(&change)?;
+
+    let clock = Clock::get()?;
+    let global_state = &mut ctx.accounts.global_state;
+    let change_id = global_state.pending_change_count;
+    let executable_at = clock
+        .unix_timestamp
+        .checked_add(global_state.timelock_delay)
+        .ok_or(ErrorCode::MathOverflow)?;
+    global_state.pending_change_count = change_id.saturating_add(1);
+
+    let pending_change = &mut ctx.accounts.pending_change;
+    pending_change.change_id = change_id;
+    pending_change.proposer = ctx.accounts.authority.key();
+    pending_change.change = change;
+    pending_change.queued_at = clock.unix_timestamp;
+    pending_change.executable_at = executable_at;
+    pending_change.bump = ctx.bumps.pending_change;
+
+    emit!(ParameterChangeQueued {
+        change_id,
+        change,
+        proposer: pending_change.proposer,
+        executable_at,
+    });
+
+    Ok(())
+}
+
+// ===== Execute Parameter Change =====
+// Permissionless once the delay has elapsed
+
+#[derive(Accounts)]
+pub struct ExecuteParameterChange<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [PENDING_CHANGE_SEED, &pending_change.change_id.to_le_bytes()],
+        bump = pending_change.bump,
+        close = proposer
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    /// Asset targeted by an asset-scoped change
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, asset_config.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Option<Account<'info, AssetConfig>>,
+
+    /// CHECK: Rent refund for the closed PendingChange
+    #[account(
+        mut,
+        address = pending_change.proposer @ ErrorCode::Unauthorized
+    )]
+    pub proposer: AccountInfo<'info>,
+}
+
+pub fn handle_execute_parameter_change(ctx: Context<ExecuteParameterChange>) -> Result<()> {
+    let clock = Clock::get()?;
+    let pending_change = &ctx.accounts.pending_change;
+    require!(
+        clock.unix_timestamp >= pending_change.executable_at,
+        ErrorCode::TimelockNotElapsed
+    );
+
+    let change = pending_change.change;
+    let global_state = &mut ctx.accounts.global_state;
+    match change {
+        ParameterChange::ProtocolFee { fee_bps } => {
+            global_state.protocol_fee_bps = fee_bps;
+        }
+        ParameterChange::Treasury { treasury } => {
+            global_state.treasury = treasury;
+        }
+        ParameterChange::TimelockDelay { delay_seconds } => {
+            global_state.timelock_delay = delay_seconds;
+        }
+        ParameterChange::AssetLimits { asset_mint, limits } => {
+            let asset_config = target_asset(&mut ctx.accounts.asset_config, asset_mint)?;
+            asset_config.min_strike_percentage = limits.min_strike_percentage;
+            asset_config.max_strike_percentage = limits.max_strike_percentage;
+            asset_config.min_expiry_seconds = limits.min_expiry_seconds;
+            asset_config.max_expiry_seconds = limits.max_expiry_seconds;
+        }
+        ParameterChange::InsuranceFeeShare { fee_share_bps } => {
+            global_state.insurance_fee_share_bps = fee_share_bps;
+        }
+        ParameterChange::KeeperGracePeriod { grace_seconds } => {
+            global_state.keeper_grace_period = grace_seconds;
+        }
+        ParameterChange::MmQualityFloor { min_reputation, min_fill_rate } => {
+            global_state.min_mm_reputation = min_reputation;
+            global_state.min_mm_fill_rate = min_fill_rate;
+        }
+        ParameterChange::DisputeBond { bond } => {
+            global_state.dispute_bond = bond;
+        }
+        ParameterChange::AssetMaxIntentNotional { asset_mint, cap } => {
+            target_asset(&mut ctx.accounts.asset_config, asset_mint)?.max_intent_notional = cap;
+        }
+        ParameterChange::AssetContractSize {
+            asset_mint,
+            min_contract_size,
+            max_contract_size,
+        } => {
+            let asset_config = target_asset(&mut ctx.accounts.asset_config, asset_mint)?;
+            asset_config.min_contract_size = min_contract_size;
+            asset_config.max_contract_size = max_contract_size;
+        }
+        ParameterChange::AssetMinPremium { asset_mint, floor } => {
+            target_asset(&mut ctx.accounts.asset_config, asset_mint)?.min_premium = floor;
+        }
+        ParameterChange::AssetLotSize { asset_mint, lot_size } => {
+            target_asset(&mut ctx.accounts.asset_config, asset_mint)?.lot_size = lot_size;
+        }
+    }
+
+    emit!(ParameterChangeExecuted {
+        change_id: pending_change.change_id,
+        change,
+        executor: ctx.accounts.executor.key(),
+    });
+
+    Ok(())
+}
+
+// ===== Cancel Parameter Change =====
+
+#[derive(Accounts)]
+pub struct CancelParameterChange<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [PENDING_CHANGE_SEED, &pending_change.change_id.to_le_bytes()],
+        bump = pending_change.bump,
+        close = proposer
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    /// CHECK: Rent refund for the closed PendingChange
+    #[account(
+        mut,
+        address = pending_change.proposer @ ErrorCode::Unauthorized
+    )]
+    pub proposer: AccountInfo<'info>,
+}
+
+pub fn handle_cancel_parameter_change(ctx: Context<CancelParameterChange>) -> Result<()> {
+    let pending_change = &ctx.accounts.pending_change;
+
+    emit!(ParameterChangeCancelled {
+        change_id: pending_change.change_id,
+        change: pending_change.change,
+        cancelled_by: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+/// The AssetConfig an asset-scoped change applies to
+fn target_asset<'a, 'info>(
+    asset_config: &'a mut Option<Account<'info, AssetConfig>>,
+    asset_mint: Pubkey,
+) -> Result<&'a mut Account<'info, AssetConfig>> {
+    let asset_config = asset_config.as_mut().ok_or(ErrorCode::AssetConfigMismatch)?;
+    require_keys_eq!(
+        asset_config.asset_mint,
+        asset_mint,
+        ErrorCode::AssetConfigMismatch
+    );
+    Ok(asset_config)
+}
+
+/// Reject invalid values at queue time, so a change that clears the delay
+/// can always be executed
+fn validate_parameter_change(change: &ParameterChange) -> Result<()> {
+    match change {
+        ParameterChange::ProtocolFee { fee_bps }
+        | ParameterChange::InsuranceFeeShare { fee_share_bps: fee_bps } => {
+            require!(
+                *fee_bps as u64 <= BASIS_POINTS_DIVISOR,
+                ErrorCode::InvalidPercentage
+            );
+        }
+        ParameterChange::Treasury { .. }
+        | ParameterChange::DisputeBond { .. }
+        | ParameterChange::AssetMaxIntentNotional { .. }
+        | ParameterChange::AssetMinPremium { .. }
+        | ParameterChange::AssetLotSize { .. } => {}
+        ParameterChange::MmQualityFloor { min_fill_rate, .. } => {
+            require!(*min_fill_rate <= 100, ErrorCode::InvalidPercentage);
+        }
+        ParameterChange::KeeperGracePeriod { grace_seconds } => {
+            require!(
+                (0..=MAX_KEEPER_GRACE_PERIOD).contains(grace_seconds),
+                ErrorCode::InvalidKeeperGracePeriod
+            );
+        }
+        ParameterChange::TimelockDelay { delay_seconds } => {
+            require!(
+                (0..=MAX_TIMELOCK_DELAY).contains(delay_seconds),
+                ErrorCode::InvalidTimelockDelay
+            );
+        }
+        ParameterChange::AssetLimits { limits, .. } => {
+            require!(
+                limits.min_strike_percentage < limits.max_strike_percentage,
+                ErrorCode::InvalidStrikeRange
+            );
+            require!(
+                limits.min_expiry_seconds < limits.max_expiry_seconds,
+                ErrorCode::InvalidExpiryRange
+            );
+        }
+        ParameterChange::AssetContractSize {
+            min_contract_size,
+            max_contract_size,
+            ..
+        } => {
+            require!(
+                *max_contract_size == 0 || min_contract_size <= max_contract_size,
+                ErrorCode::InvalidContractSizeBounds
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,314 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::instructions::keeper::{active_keeper, require_keeper};
+use crate::instructions::option_series::leave_series;
+use crate::instructions::settlement::{
+    calculate_settlement, transfer_from_position_vault,
+};
+use crate::state::*;
+use crate::utils::*;
+
+// ===== Events =====
+
+#[event]
+pub struct TriggersUpdated {
+    pub position_id: u64,
+    pub stop_loss_price: Option<u64>,
+    pub take_profit_price: Option<u64>,
+}
+
+#[event]
+pub struct TriggerExecuted {
+    pub position_id: u64,
+    pub trigger_price: u64,
+    pub user_payout: u64,
+    pub mm_payout: u64,
+    pub unwind_fee: u64,
+    pub executor: Pubkey,
+}
+
+// ===== Set Unwind Terms =====
+
+#[derive(Accounts)]
+pub struct SetUnwindTerms<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MM_REGISTRY_SEED, owner.key().as_ref()],
+        bump = mm_registry.bump,
+        constraint = mm_registry.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+}
+
+pub fn handle_set_unwind_terms(
+    ctx: Context<SetUnwindTerms>,
+    enabled: bool,
+    fee_bps: u16,
+) -> Result<()> {
+    require!(
+        fee_bps as u64 <= BASIS_POINTS_DIVISOR,
+        ErrorCode::InvalidPercentage
+    );
+
+    let mm_registry = &mut ctx.accounts.mm_registry;
+    mm_registry.unwind_enabled = enabled;
+    mm_registry.unwind_fee_bps = fee_bps;
+
+    Ok(())
+}
+
+// ===== Set Position Triggers =====
+
+#[derive(Accounts)]
+pub struct SetPositionTriggers<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = position.holder == user.key() @ ErrorCode::Unauthorized,
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive
+    )]
+    pub position: Account<'info, Position>,
+}
+
+pub fn handle_set_position_triggers(
+    ctx: Context<SetPositionTriggers>,
+    stop_loss_price: Option<u64>,
+    take_profit_price: Option<u64>,
+) -> Result<()> {
+    let position = &mut ctx.accounts.position;
+
+    require!(
+        stop_loss_price != Some(0) && take_profit_price != Some(0),
+        ErrorCode::InvalidTriggerPrice
+    );
+
+    // Stop-loss must sit on the adverse side of take-profit
+    if let (Some(stop), Some(profit)) = (stop_loss_price, take_profit_price) {
+        let ordered = match position.strategy {
+            StrategyType::CoveredCall => stop > profit,
+            StrategyType::CashSecuredPut => stop < profit,
+        };
+        require!(ordered, ErrorCode::InvalidTriggerPrice);
+    }
+
+    position.stop_loss_price = stop_loss_price;
+    position.take_profit_price = take_profit_price;
+
+    emit!(TriggersUpdated {
+        position_id: position.position_id,
+        stop_loss_price,
+        take_profit_price,
+    });
+
+    Ok(())
+}
+
+// ===== Execute Trigger =====
+// Permissionless early close at the MM's standing unwind terms. Triggers can
+// fire any time after open, so a keeper reservation runs from position open.
+
+#[derive(Accounts)]
+pub struct ExecuteTrigger<'info> {
+    pub executor: Signer<'info>,
+
+    /// Caller's keeper registration; required while cranks are reserved for keepers
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, executor.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump,
+        constraint = !asset_config.paused @ ErrorCode::AssetPaused
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// MM's registry holding the standing unwind terms
+    #[account(
+        seeds = [MM_REGISTRY_SEED, position.market_maker.as_ref()],
+        bump = mm_registry.bump,
+        constraint = mm_registry.unwind_enabled @ ErrorCode::UnwindNotOffered
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    /// Position user's lifetime stats
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, position.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// Position's user vault (user's locked collateral)
+    #[account(
+        mut,
+        constraint = position_user_vault.key() == position.user_vault @ ErrorCode::InvalidVault
+    )]
+    pub position_user_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the position vault
+    #[account(address = position_user_vault.mint @ ErrorCode::InvalidMint)]
+    pub vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA authority for position vaults
+    #[account(
+        seeds = [POSITION_SEED, position.user.as_ref(), &position.position_id.to_le_bytes()],
+        bump = position.bump
+    )]
+    pub position_authority: AccountInfo<'info>,
+
+    /// User's destination token account
+    #[account(
+        mut,
+        constraint = user_destination.owner == position.user_payout_owner()
+    )]
+    pub user_destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// MM's destination token account
+    #[account(
+        mut,
+        constraint = mm_destination.owner == position.mm_payout_owner()
+    )]
+    pub mm_destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Compliance blocklist; blocked parties settle at expiry with payouts withheld
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump,
+        constraint = !blocklist.blocks_either_side(&position) @ ErrorCode::AddressBlocked
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    /// Oracle feeds for the asset's configured source
+    pub oracle: OracleAccounts<'info>,
+
+    /// Series the position belongs to; required when it joined one
+    #[account(mut)]
+    pub option_series: Option<Account<'info, OptionSeries>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_execute_trigger(ctx: Context<ExecuteTrigger>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Past expiry the position settles normally
+    require!(
+        clock.unix_timestamp < ctx.accounts.position.expiry_timestamp,
+        ErrorCode::PositionExpired
+    );
+    require_keeper(
+        &ctx.accounts.global_state,
+        ctx.accounts.keeper.as_ref(),
+        ctx.accounts.position.created_at,
+        clock.unix_timestamp,
+    )?;
+
+    // An index asset is read from its component updates in remaining_accounts
+    let trigger_price = read_asset_price(
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        &ctx.accounts.asset_config,
+        &clock,
+    )?
+    .price;
+
+    let position = &ctx.accounts.position;
+    require!(position.trigger_hit(trigger_price), ErrorCode::TriggerNotHit);
+
+    // Intrinsic cash split at the trigger price, then the MM's unwind fee
+    let (user_amount, mm_amount, _) = calculate_settlement(
+        position.strategy,
+        SettlementTerms {
+            kind: SettlementKind::Cash,
+            ..settlement_terms_for_vault(
+                &ctx.accounts.asset_config,
+                ctx.accounts.vault_mint.key(),
+                ctx.accounts.oracle.lst_rate.as_ref(),
+                &clock,
+            )?
+        },
+        trigger_price,
+        position.strike_price,
+        position.contract_size,
+        ctx.accounts.position_user_vault.amount,
+    )?;
+    let (unwind_fee, user_payout) =
+        split_bps(user_amount, ctx.accounts.mm_registry.unwind_fee_bps as u64)?;
+    let mm_payout = mm_amount
+        .checked_add(unwind_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    transfer_from_position_vault(
+        position,
+        &ctx.accounts.position_user_vault,
+        &ctx.accounts.vault_mint,
+        &ctx.accounts.user_destination,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_program,
+        user_payout,
+    )?;
+    transfer_from_position_vault(
+        position,
+        &ctx.accounts.position_user_vault,
+        &ctx.accounts.vault_mint,
+        &ctx.accounts.mm_destination,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_program,
+        mm_payout,
+    )?;
+
+    leave_series(&ctx.accounts.position, ctx.accounts.option_series.as_mut())?;
+    let notional = ctx.accounts.position.notional;
+    ctx.accounts.asset_config.record_position_closed(notional);
+    ctx.accounts.global_state.record_notional_closed(notional);
+
+    let user_stats = &mut ctx.accounts.user_stats;
+    user_stats.premium_paid = user_stats.premium_paid.saturating_add(unwind_fee);
+    user_stats.record_settlement(
+        ctx.accounts.position.premium_paid,
+        ctx.accounts.position_user_vault.amount,
+        user_payout,
+    );
+
+    let position = &mut ctx.accounts.position;
+    position.settlement_price = Some(trigger_price);
+    position.status = PositionStatus::ClosedEarly;
+    if let Some(keeper) = active_keeper(&mut ctx.accounts.keeper) {
+        keeper.record_trigger(clock.unix_timestamp);
+    }
+
+    emit!(TriggerExecuted {
+        position_id: position.position_id,
+        trigger_price,
+        user_payout,
+        mm_payout,
+        unwind_fee,
+        executor: ctx.accounts.executor.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,480 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::instructions::intent::calculate_escrow_amount;
+use crate::instructions::settlement::calculate_settlement;
+use crate::state::*;
+use crate::utils::*;
+
+/// Mark-to-market snapshot of a position, returned via `set_return_data`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct PositionValue {
+    pub position_id: u64,
+    /// Oracle price used for the valuation
+    pub spot_price: u64,
+    pub strike_price: u64,
+    /// Per-contract intrinsic value in price units (0 when OTM)
+    pub intrinsic_value: u64,
+    /// User's share of the vault if settled at `spot_price`
+    pub user_amount: u64,
+    /// MM's share of the vault if settled at `spot_price`
+    pub mm_amount: u64,
+    pub in_the_money: bool,
+    /// Whether the position is already past expiry (settleable)
+    pub expired: bool,
+}
+
+/// Insurance fund balance and flows, returned via `set_return_data`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct InsuranceFundReport {
+    pub quote_mint: Pubkey,
+    /// Current vault balance
+    pub balance: u64,
+    /// Share of protocol fees currently routed to the fund
+    pub fee_share_bps: u16,
+    pub total_fee_accruals: u64,
+    pub total_mm_contributions: u64,
+    pub total_other_deposits: u64,
+    pub total_claims_paid: u64,
+    pub claims_count: u64,
+    /// Part of the balance set aside for finalized haircuts
+    pub reserved_for_haircuts: u64,
+}
+
+/// Escrow a user must deposit for an intent, returned via `set_return_data`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct EscrowQuote {
+    pub asset_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    /// Amount `submit_intent` would move into escrow
+    pub escrow_amount: u64,
+    /// Strike notional counted toward open interest
+    pub notional: u64,
+}
+
+/// Payout split of a position at a given price, returned via `set_return_data`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SettlementPreview {
+    pub position_id: u64,
+    pub settlement_price: u64,
+    pub user_amount: u64,
+    pub mm_amount: u64,
+    /// Status settlement would leave the position in
+    pub status: PositionStatus,
+}
+
+/// MM collateral on one asset, returned via `set_return_data`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct MMCollateralReport {
+    pub market_maker: Pubkey,
+    pub asset_mint: Pubkey,
+    /// Locked in this asset's position MM vaults
+    pub locked_quote: u64,
+    pub locked_underlying: u64,
+    pub locked_positions: u64,
+    /// MM vault balances, free for new fills on any asset
+    pub free_quote: u64,
+    pub free_underlying: u64,
+    /// Locked share of locked + free, per mint
+    pub quote_utilization_bps: u16,
+    pub underlying_utilization_bps: u16,
+}
+
+/// Market maker reliability figures, returned via `set_return_data`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct MMFillRate {
+    pub market_maker: Pubkey,
+    /// Filled share of intents that reached fill or expiry (0-100)
+    pub fill_rate: u8,
+    pub total_intents_filled: u64,
+    pub total_intents_expired: u64,
+    pub reputation_score: u32,
+    pub active: bool,
+}
+
+/// Open interest in one option series, returned via `set_return_data`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SeriesOpenInterest {
+    pub option_series: Pubkey,
+    pub asset_mint: Pubkey,
+    pub strategy: StrategyType,
+    pub strike_price: u64,
+    pub expiry_timestamp: i64,
+    pub open_positions: u64,
+    pub open_contracts: u64,
+    pub open_notional: u64,
+    /// Open strike notional across the whole asset
+    pub asset_open_notional: u64,
+    /// Series share of the asset's open notional, in basis points
+    pub asset_share_bps: u16,
+}
+
+// ===== Get Position Value =====
+
+#[derive(Accounts)]
+pub struct GetPositionValue<'info> {
+    #[account(
+        constraint = position.status == PositionStatus::Active @ ErrorCode::PositionNotActive
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Position's user vault (collateral being split)
+    #[account(
+        constraint = position_user_vault.key() == position.user_vault @ ErrorCode::InvalidVault
+    )]
+    pub position_user_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Oracle feeds for the asset's configured source
+    pub oracle: OracleAccounts<'info>,
+}
+
+pub fn handle_get_position_value(ctx: Context<GetPositionValue>) -> Result<()> {
+    let clock = Clock::get()?;
+    let position = &ctx.accounts.position;
+
+    // An index asset is read from its component updates in remaining_accounts
+    let spot_price = read_asset_price(
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        &ctx.accounts.asset_config,
+        &clock,
+    )?
+    .price;
+
+    // Same math settle_position would apply at this price
+    let (user_amount, mm_amount, status) = calculate_settlement(
+        position.strategy,
+        settlement_terms_for_vault(
+            &ctx.accounts.asset_config,
+            ctx.accounts.position_user_vault.mint,
+            ctx.accounts.oracle.lst_rate.as_ref(),
+            &clock,
+        )?,
+        spot_price,
+        position.strike_price,
+        position.contract_size,
+        ctx.accounts.position_user_vault.amount,
+    )?;
+
+    let intrinsic_value = match position.strategy {
+        StrategyType::CoveredCall => spot_price.saturating_sub(position.strike_price),
+        StrategyType::CashSecuredPut => position.strike_price.saturating_sub(spot_price),
+    };
+
+    let value = PositionValue {
+        position_id: position.position_id,
+        spot_price,
+        strike_price: position.strike_price,
+        intrinsic_value,
+        user_amount,
+        mm_amount,
+        in_the_money: status == PositionStatus::SettledITM,
+        expired: clock.unix_timestamp >= position.expiry_timestamp,
+    };
+
+    set_return_data(&value.try_to_vec()?);
+
+    Ok(())
+}
+
+// ===== Get Insurance Fund Report =====
+
+#[derive(Accounts)]
+pub struct GetInsuranceFundReport<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [INSURANCE_FUND_SEED, insurance_fund.quote_mint.as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        address = insurance_fund.vault @ ErrorCode::InvalidVault
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+pub fn handle_get_insurance_fund_report(ctx: Context<GetInsuranceFundReport>) -> Result<()> {
+    let insurance_fund = &ctx.accounts.insurance_fund;
+
+    let report = InsuranceFundReport {
+        quote_mint: insurance_fund.quote_mint,
+        balance: ctx.accounts.insurance_vault.amount,
+        fee_share_bps: ctx.accounts.global_state.insurance_fee_share_bps,
+        total_fee_accruals: insurance_fund.total_fee_accruals,
+        total_mm_contributions: insurance_fund.total_mm_contributions,
+        total_other_deposits: insurance_fund.total_other_deposits,
+        total_claims_paid: insurance_fund.total_claims_paid,
+        claims_count: insurance_fund.claims_count,
+        reserved_for_haircuts: insurance_fund.reserved_for_haircuts,
+    };
+
+    set_return_data(&report.try_to_vec()?);
+
+    Ok(())
+}
+
+// ===== Quote Escrow Amount =====
+
+#[derive(Accounts)]
+pub struct QuoteEscrowAmount<'info> {
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, asset_config.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+}
+
+pub fn handle_quote_escrow_amount(
+    ctx: Context<QuoteEscrowAmount>,
+    strategy: StrategyType,
+    strike_price: u64,
+    contract_size: u64,
+) -> Result<()> {
+    let asset_config = &ctx.accounts.asset_config;
+
+    let quote = EscrowQuote {
+        asset_mint: asset_config.asset_mint,
+        quote_mint: asset_config.quote_mint,
+        escrow_amount: calculate_escrow_amount(
+            strategy,
+            strike_price,
+            contract_size,
+            &asset_config.settlement_terms(),
+        )?,
+        notional: asset_config.notional_value(contract_size, strike_price)?,
+    };
+
+    set_return_data(&quote.try_to_vec()?);
+
+    Ok(())
+}
+
+// ===== Preview Settlement =====
+// Settlement math at a caller-supplied price, without reading the oracle
+
+#[derive(Accounts)]
+pub struct PreviewSettlement<'info> {
+    pub position: Account<'info, Position>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, position.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Position's user vault (collateral being split)
+    #[account(
+        constraint = position_user_vault.key() == position.user_vault @ ErrorCode::InvalidVault
+    )]
+    pub position_user_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Stake pool or rate feed, for positions collateralized in the
+    /// asset's LST; matched against the asset's configured LST rate account
+    pub lst_rate: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handle_preview_settlement(
+    ctx: Context<PreviewSettlement>,
+    settlement_price: u64,
+) -> Result<()> {
+    let position = &ctx.accounts.position;
+
+    let (user_amount, mm_amount, status) = calculate_settlement(
+        position.strategy,
+        settlement_terms_for_vault(
+            &ctx.accounts.asset_config,
+            ctx.accounts.position_user_vault.mint,
+            ctx.accounts.lst_rate.as_deref(),
+            &Clock::get()?,
+        )?,
+        settlement_price,
+        position.strike_price,
+        position.contract_size,
+        ctx.accounts.position_user_vault.amount,
+    )?;
+
+    let preview = SettlementPreview {
+        position_id: position.position_id,
+        settlement_price,
+        user_amount,
+        mm_amount,
+        status,
+    };
+
+    set_return_data(&preview.try_to_vec()?);
+
+    Ok(())
+}
+
+// ===== Get MM Fill Rate =====
+
+#[derive(Accounts)]
+pub struct GetMMFillRate<'info> {
+    #[account(
+        seeds = [MM_REGISTRY_SEED, mm_registry.owner.as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+}
+
+pub fn handle_get_mm_fill_rate(ctx: Context<GetMMFillRate>) -> Result<()> {
+    let mm_registry = &ctx.accounts.mm_registry;
+
+    let fill_rate = MMFillRate {
+        market_maker: mm_registry.owner,
+        fill_rate: mm_registry.fill_rate(),
+        total_intents_filled: mm_registry.total_intents_filled,
+        total_intents_expired: mm_registry.total_intents_expired,
+        reputation_score: mm_registry.reputation_score,
+        active: mm_registry.active,
+    };
+
+    set_return_data(&fill_rate.try_to_vec()?);
+
+    Ok(())
+}
+
+// ===== Get MM Collateral =====
+
+#[derive(Accounts)]
+pub struct GetMMCollateral<'info> {
+    #[account(
+        seeds = [MM_REGISTRY_SEED, mm_registry.owner.as_ref()],
+        bump = mm_registry.bump
+    )]
+    pub mm_registry: Account<'info, MMRegistry>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, asset_config.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Omit if the MM has never locked collateral on this asset
+    #[account(
+        seeds = [MM_EXPOSURE_SEED, mm_registry.owner.as_ref(), asset_config.asset_mint.as_ref()],
+        bump = mm_exposure.bump
+    )]
+    pub mm_exposure: Option<Account<'info, MMExposure>>,
+
+    /// MM vault for the quote mint, if opened
+    #[account(
+        seeds = [MM_VAULT_SEED, mm_registry.key().as_ref(), asset_config.quote_mint.as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// MM vault for the underlying, if opened
+    #[account(
+        seeds = [MM_VAULT_SEED, mm_registry.key().as_ref(), asset_config.asset_mint.as_ref()],
+        bump
+    )]
+    pub underlying_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+pub fn handle_get_mm_collateral(ctx: Context<GetMMCollateral>) -> Result<()> {
+    let (locked_quote, locked_underlying, locked_positions) = ctx
+        .accounts
+        .mm_exposure
+        .as_ref()
+        .map_or((0, 0, 0), |exposure| {
+            (exposure.locked_quote, exposure.locked_underlying, exposure.locked_positions)
+        });
+    let free_quote = ctx.accounts.quote_vault.as_ref().map_or(0, |vault| vault.amount);
+    let free_underlying = ctx
+        .accounts
+        .underlying_vault
+        .as_ref()
+        .map_or(0, |vault| vault.amount);
+
+    let report = MMCollateralReport {
+        market_maker: ctx.accounts.mm_registry.owner,
+        asset_mint: ctx.accounts.asset_config.asset_mint,
+        locked_quote,
+        locked_underlying,
+        locked_positions,
+        free_quote,
+        free_underlying,
+        quote_utilization_bps: utilization_bps(locked_quote, free_quote),
+        underlying_utilization_bps: utilization_bps(locked_underlying, free_underlying),
+    };
+
+    set_return_data(&report.try_to_vec()?);
+
+    Ok(())
+}
+
+/// `locked / (locked + free)` in basis points (0 when both are empty)
+fn utilization_bps(locked: u64, free: u64) -> u16 {
+    let total = locked as u128 + free as u128;
+    if total == 0 {
+        return 0;
+    }
+    (locked as u128 * BASIS_POINTS_DIVISOR as u128 / total) as u16
+}
+
+// ===== Get Series Open Interest =====
+
+#[derive(Accounts)]
+pub struct GetSeriesOpenInterest<'info> {
+    #[account(
+        seeds = [
+            OPTION_SERIES_SEED,
+            option_series.asset_mint.as_ref(),
+            &[option_series.strategy as u8],
+            &option_series.strike_price.to_le_bytes(),
+            &option_series.expiry_timestamp.to_le_bytes()
+        ],
+        bump = option_series.bump
+    )]
+    pub option_series: Account<'info, OptionSeries>,
+
+    #[account(
+        seeds = [ASSET_CONFIG_SEED, option_series.asset_mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+}
+
+pub fn handle_get_series_open_interest(ctx: Context<GetSeriesOpenInterest>) -> Result<()> {
+    let series = &ctx.accounts.option_series;
+    let asset_open_notional = ctx.accounts.asset_config.open_notional;
+    let asset_share_bps = if asset_open_notional == 0 {
+        0
+    } else {
+        (series.open_notional.min(asset_open_notional) as u128 * BASIS_POINTS_DIVISOR as u128
+            / asset_open_notional as u128) as u16
+    };
+
+    let report = SeriesOpenInterest {
+        option_series: series.key(),
+        asset_mint: series.asset_mint,
+        strategy: series.strategy,
+        strike_price: series.strike_price,
+        expiry_timestamp: series.expiry_timestamp,
+        open_positions: series.open_positions,
+        open_contracts: series.open_contracts,
+        open_notional: series.open_notional,
+        asset_open_notional,
+        asset_share_bps,
+    };
+
+    set_return_data(&report.try_to_vec()?);
+
+    Ok(())
+}
@@ -7,7 +7,7 @@ pub mod state;
 pub mod utils;
 
 use instructions::*;
-use state::*;
+use state::{AssetClass, BasketComponent, ParameterChange, ResolutionProposal, StrategyType};
 
 declare_id!("4XkfZ5hHr43pSZBioix3ps8Y8UR1ghN6fjP1zccEFYXQ");
 
@@ -26,20 +26,12 @@ pub mod solation {
 
     pub fn update_global_state(
         ctx: Context<UpdateGlobalState>,
-        new_authority: Option<Pubkey>,
-        new_treasury: Option<Pubkey>,
-        new_fee_bps: Option<u16>,
-        paused: Option<bool>,
+        params: UpdateGlobalStateParams,
     ) -> Result<()> {
-        instructions::handle_update_global_state(
-            ctx,
-            new_authority,
-            new_treasury,
-            new_fee_bps,
-            paused,
-        )
+        instructions::handle_update_global_state(ctx, params)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn add_asset(
         ctx: Context<AddAsset>,
         asset_mint: Pubkey,
@@ -50,6 +42,8 @@ pub mod solation {
         min_expiry_seconds: i64,
         max_expiry_seconds: i64,
         decimals: u8,
+        allow_transfer_fee: bool,
+        asset_class: AssetClass,
     ) -> Result<()> {
         instructions::handle_add_asset(
             ctx,
@@ -61,25 +55,169 @@ pub mod solation {
             min_expiry_seconds,
             max_expiry_seconds,
             decimals,
+            allow_transfer_fee,
+            asset_class,
         )
     }
 
-    pub fn update_asset(
-        ctx: Context<UpdateAsset>,
-        enabled: Option<bool>,
-        min_strike_percentage: Option<u16>,
-        max_strike_percentage: Option<u16>,
-        min_expiry_seconds: Option<i64>,
-        max_expiry_seconds: Option<i64>,
+    pub fn update_asset(ctx: Context<UpdateAsset>, params: UpdateAssetParams) -> Result<()> {
+        instructions::handle_update_asset(ctx, params)
+    }
+
+    /// List (or, with a zero feed ID, delist) an alternative stablecoin quote
+    pub fn set_alt_quote(ctx: Context<SetAltQuote>, pyth_feed_id: [u8; 32]) -> Result<()> {
+        instructions::handle_set_alt_quote(ctx, pyth_feed_id)
+    }
+
+    /// Make an asset an index over a weighted basket of Pyth feeds (empty =
+    /// back to its single feed)
+    pub fn set_basket(ctx: Context<SetBasket>, components: Vec<BasketComponent>) -> Result<()> {
+        instructions::handle_set_basket(ctx, components)
+    }
+
+    /// Pauser or authority halts intents, fills and settlement for one asset
+    pub fn set_asset_paused(ctx: Context<SetAssetPaused>, paused: bool) -> Result<()> {
+        instructions::handle_set_asset_paused(ctx, paused)
+    }
+
+    /// Stop new intents on an asset and let existing ones wind down
+    pub fn delist_asset(ctx: Context<DelistAsset>) -> Result<()> {
+        instructions::handle_delist_asset(ctx)
+    }
+
+    /// Reclaim a delisted asset's config once open interest is zero
+    pub fn close_asset_config(ctx: Context<CloseAssetConfig>) -> Result<()> {
+        instructions::handle_close_asset_config(ctx)
+    }
+
+    /// Create the SOL pool that tips callers of expire_intent
+    pub fn initialize_tip_vault(ctx: Context<InitializeTipVault>) -> Result<()> {
+        instructions::handle_initialize_tip_vault(ctx)
+    }
+
+    // ===== Timelocked Parameter Changes =====
+
+    /// Queue a fee, treasury, delay or asset-limit change behind the timelock
+    pub fn queue_parameter_change(
+        ctx: Context<QueueParameterChange>,
+        change: ParameterChange,
     ) -> Result<()> {
-        instructions::handle_update_asset(
-            ctx,
-            enabled,
-            min_strike_percentage,
-            max_strike_percentage,
-            min_expiry_seconds,
-            max_expiry_seconds,
-        )
+        instructions::handle_queue_parameter_change(ctx, change)
+    }
+
+    /// Apply a queued change once its delay has elapsed (permissionless)
+    pub fn execute_parameter_change(ctx: Context<ExecuteParameterChange>) -> Result<()> {
+        instructions::handle_execute_parameter_change(ctx)
+    }
+
+    /// Authority drops a queued change before it executes
+    pub fn cancel_parameter_change(ctx: Context<CancelParameterChange>) -> Result<()> {
+        instructions::handle_cancel_parameter_change(ctx)
+    }
+
+    // ===== Compliance =====
+
+    /// Create the compliance blocklist PDA
+    pub fn initialize_blocklist(ctx: Context<InitializeBlocklist>) -> Result<()> {
+        instructions::handle_initialize_blocklist(ctx)
+    }
+
+    /// Authority adds (blocked = true) or removes an address from the blocklist
+    pub fn update_blocklist(
+        ctx: Context<UpdateBlocklist>,
+        address: Pubkey,
+        blocked: bool,
+    ) -> Result<()> {
+        instructions::handle_update_blocklist(ctx, address, blocked)
+    }
+
+    /// Party claims a settlement payout withheld while they were blocklisted
+    pub fn claim_withheld_payout(ctx: Context<ClaimWithheldPayout>) -> Result<()> {
+        instructions::handle_claim_withheld_payout(ctx)
+    }
+
+    /// Issuer attests (or renews) a user's credential for permissioned assets
+    pub fn issue_credential(
+        ctx: Context<IssueCredential>,
+        user: Pubkey,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::handle_issue_credential(ctx, user, expires_at)
+    }
+
+    /// Issuer revokes a credential and reclaims its rent
+    pub fn revoke_credential(ctx: Context<RevokeCredential>) -> Result<()> {
+        instructions::handle_revoke_credential(ctx)
+    }
+
+    // ===== Composers =====
+
+    /// Create the allowlist of programs that may submit or fill intents via CPI
+    pub fn initialize_composer_allowlist(ctx: Context<InitializeComposerAllowlist>) -> Result<()> {
+        instructions::handle_initialize_composer_allowlist(ctx)
+    }
+
+    /// Authority adds (allowed = true) or removes a composer program
+    pub fn update_composer_allowlist(
+        ctx: Context<UpdateComposerAllowlist>,
+        program_id: Pubkey,
+        allowed: bool,
+    ) -> Result<()> {
+        instructions::handle_update_composer_allowlist(ctx, program_id, allowed)
+    }
+
+    // ===== Hedge Adapters =====
+
+    /// Create the allowlist of perp adapters fills may be hedged through
+    pub fn initialize_hedge_adapter_allowlist(
+        ctx: Context<InitializeHedgeAdapterAllowlist>,
+    ) -> Result<()> {
+        instructions::handle_initialize_hedge_adapter_allowlist(ctx)
+    }
+
+    /// Authority adds (allowed = true) or removes a hedge adapter program
+    pub fn update_hedge_adapter_allowlist(
+        ctx: Context<UpdateHedgeAdapterAllowlist>,
+        program_id: Pubkey,
+        allowed: bool,
+    ) -> Result<()> {
+        instructions::handle_update_hedge_adapter_allowlist(ctx, program_id, allowed)
+    }
+
+    // ===== Keepers =====
+
+    /// Authority registers a keeper for cranks reserved by the keeper grace period
+    pub fn register_keeper(ctx: Context<RegisterKeeper>, keeper: Pubkey) -> Result<()> {
+        instructions::handle_register_keeper(ctx, keeper)
+    }
+
+    /// Authority suspends (active = false) or reinstates a keeper
+    pub fn set_keeper_active(ctx: Context<SetKeeperActive>, active: bool) -> Result<()> {
+        instructions::handle_set_keeper_active(ctx, active)
+    }
+
+    // ===== Risk =====
+
+    /// Create the portfolio margin config, naming its risk admin
+    pub fn initialize_risk_config(
+        ctx: Context<InitializeRiskConfig>,
+        risk_admin: Pubkey,
+    ) -> Result<()> {
+        instructions::handle_initialize_risk_config(ctx, risk_admin)
+    }
+
+    /// Risk admin tunes the stress move; only the authority reassigns the admin
+    pub fn update_risk_config(
+        ctx: Context<UpdateRiskConfig>,
+        new_risk_admin: Option<Pubkey>,
+        stress_move_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::handle_update_risk_config(ctx, new_risk_admin, stress_move_bps)
+    }
+
+    /// Risk admin whitelists (or removes) an MM for portfolio margin
+    pub fn set_portfolio_margin(ctx: Context<SetPortfolioMargin>, enabled: bool) -> Result<()> {
+        instructions::handle_set_portfolio_margin(ctx, enabled)
     }
 
     // ===== Market Maker Registration (Off-Chain RFQ) =====
@@ -97,6 +235,29 @@ pub mod solation {
         instructions::handle_update_mm_signing_key(ctx, new_signing_key)
     }
 
+    /// MM switches its quotes to time-bounded nonces (0 = sequential nonces)
+    pub fn set_nonce_bucket(ctx: Context<SetNonceBucket>, bucket_seconds: u32) -> Result<()> {
+        instructions::handle_set_nonce_bucket(ctx, bucket_seconds)
+    }
+
+    /// MM sets (or clears, with the default key) the bot allowed to fill for it
+    pub fn set_mm_fill_operator(
+        ctx: Context<SetMMFillOperator>,
+        fill_operator: Pubkey,
+    ) -> Result<()> {
+        instructions::handle_set_mm_fill_operator(ctx, fill_operator)
+    }
+
+    /// MM opens its per-mint vault, which fill_intent can draw premium from
+    pub fn open_mm_vault(ctx: Context<OpenMMVault>) -> Result<()> {
+        instructions::handle_open_mm_vault(ctx)
+    }
+
+    /// MM owner withdraws from its vault
+    pub fn withdraw_from_mm_vault(ctx: Context<WithdrawFromMMVault>, amount: u64) -> Result<()> {
+        instructions::handle_withdraw_from_mm_vault(ctx, amount)
+    }
+
     // ===== Intent Lifecycle (Off-Chain RFQ) =====
 
     /// User submits intent with MM's signed quote
@@ -104,40 +265,386 @@ pub mod solation {
         instructions::handle_submit_intent(ctx, params)
     }
 
+    /// User submits an intent sized in quote-currency notional, converted to
+    /// contracts at spot and capped at the quote's signed size
+    pub fn submit_notional_intent(
+        ctx: Context<SubmitIntent>,
+        params: SubmitIntentParams,
+        notional: u64,
+    ) -> Result<()> {
+        instructions::handle_submit_notional_intent(ctx, params, notional)
+    }
+
+    /// Post an MM-signed quote on-chain, verifying its signature once
+    pub fn post_quote(ctx: Context<PostQuote>, params: PostQuoteParams) -> Result<()> {
+        instructions::handle_post_quote(ctx, params)
+    }
+
+    /// User submits an intent against a posted quote, without an Ed25519 instruction
+    pub fn submit_intent_from_quote(
+        ctx: Context<SubmitIntentFromQuote>,
+        params: TakePostedQuoteParams,
+    ) -> Result<()> {
+        instructions::handle_submit_intent_from_quote(ctx, params)
+    }
+
+    /// MM withdraws a posted quote, or anyone closes one that is spent or expired
+    pub fn close_posted_quote(ctx: Context<ClosePostedQuote>) -> Result<()> {
+        instructions::handle_close_posted_quote(ctx)
+    }
+
     /// MM fills the intent (creates Position, pays premium)
-    pub fn fill_intent(ctx: Context<FillIntent>) -> Result<()> {
+    pub fn fill_intent<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FillIntent<'info>>,
+    ) -> Result<()> {
         instructions::handle_fill_intent(ctx)
     }
 
+    /// User lets the MM pay the intent's premium in the underlying, within
+    /// a slippage bound on its value at oracle spot
+    pub fn set_underlying_premium(
+        ctx: Context<SetUnderlyingPremium>,
+        max_slippage_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::handle_set_underlying_premium(ctx, max_slippage_bps)
+    }
+
+    /// MM fills the intent paying the premium in the underlying
+    pub fn fill_intent_in_underlying<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FillIntent<'info>>,
+        underlying_premium: u64,
+    ) -> Result<()> {
+        instructions::handle_fill_intent_in_underlying(ctx, underlying_premium)
+    }
+
+    /// MM fills the intent, then opens an offsetting perp of `hedge_size`
+    /// through an allowlisted adapter (venue accounts in remaining_accounts)
+    pub fn fill_intent_with_hedge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FillIntent<'info>>,
+        hedge_size: u64,
+        adapter_data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::handle_fill_intent_with_hedge(ctx, hedge_size, adapter_data)
+    }
+
+    /// MM escrows the premium for an intent, starting a two-phase fill
+    pub fn begin_escrowed_fill<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BeginEscrowedFill<'info>>,
+    ) -> Result<()> {
+        instructions::handle_begin_escrowed_fill(ctx)
+    }
+
+    /// MM locks its collateral; the premium is released and the Position opens
+    pub fn complete_escrowed_fill(ctx: Context<CompleteEscrowedFill>) -> Result<()> {
+        instructions::handle_complete_escrowed_fill(ctx)
+    }
+
+    /// MM (or anyone, once the window lapses) unwinds an incomplete escrowed fill
+    pub fn unwind_escrowed_fill(ctx: Context<UnwindEscrowedFill>) -> Result<()> {
+        instructions::handle_unwind_escrowed_fill(ctx)
+    }
+
+    /// MM reclaims collateral locked by an escrowed fill once the Position settles
+    pub fn release_mm_collateral<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReleaseMmCollateral<'info>>,
+    ) -> Result<()> {
+        instructions::handle_release_mm_collateral(ctx)
+    }
+
     /// User cancels unfilled intent (reclaims escrow)
     pub fn cancel_intent(ctx: Context<CancelIntent>) -> Result<()> {
         instructions::handle_cancel_intent(ctx)
     }
 
-    /// Anyone can cleanup expired intents
+    /// Anyone can cleanup expired intents, earning a tip from the tip vault
     pub fn expire_intent(ctx: Context<ExpireIntent>) -> Result<()> {
         instructions::handle_expire_intent(ctx)
     }
 
-    /// User or MM flags intent for dispute
-    pub fn flag_dispute(ctx: Context<FlagDispute>, reason: String) -> Result<()> {
-        instructions::handle_flag_dispute(ctx, reason)
+    /// Anyone can expire many past-deadline intents at once (remaining accounts)
+    pub fn expire_intents_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExpireIntentsBatch<'info>>,
+    ) -> Result<()> {
+        instructions::handle_expire_intents_batch(ctx)
+    }
+
+    /// User or MM flags intent for dispute, referencing an off-chain statement
+    pub fn flag_dispute(
+        ctx: Context<FlagDispute>,
+        reason_hash: [u8; 32],
+        reason_uri: String,
+    ) -> Result<()> {
+        instructions::handle_flag_dispute(ctx, reason_hash, reason_uri)
+    }
+
+    /// User or MM attaches an evidence reference to a disputed intent
+    pub fn submit_dispute_evidence(
+        ctx: Context<SubmitDisputeEvidence>,
+        content_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        instructions::handle_submit_dispute_evidence(ctx, content_hash, uri)
+    }
+
+    /// Flagger takes back a dispute bond that was not forfeited
+    pub fn reclaim_dispute_bond(ctx: Context<ReclaimDisputeBond>) -> Result<()> {
+        instructions::handle_reclaim_dispute_bond(ctx)
+    }
+
+    // ===== Standing Quotes =====
+
+    /// MM creates or replaces a persistent strike grid users can take directly
+    pub fn set_standing_quote(
+        ctx: Context<SetStandingQuote>,
+        params: StandingQuoteParams,
+    ) -> Result<()> {
+        instructions::handle_set_standing_quote(ctx, params)
+    }
+
+    /// MM withdraws a standing quote and reclaims its rent
+    pub fn cancel_standing_quote(ctx: Context<CancelStandingQuote>) -> Result<()> {
+        instructions::handle_cancel_standing_quote(ctx)
+    }
+
+    /// User takes a standing quote, opening the Position immediately
+    pub fn take_quote(ctx: Context<TakeQuote>, params: TakeQuoteParams) -> Result<()> {
+        instructions::handle_take_quote(ctx, params)
+    }
+
+    // ===== Option Series =====
+
+    /// List a standardized series on the asset's strike grid and expiry calendar
+    pub fn create_option_series(
+        ctx: Context<CreateOptionSeries>,
+        strategy: StrategyType,
+        strike_price: u64,
+        expiry_timestamp: i64,
+    ) -> Result<()> {
+        instructions::handle_create_option_series(ctx, strategy, strike_price, expiry_timestamp)
+    }
+
+    // ===== Position Resale =====
+
+    /// Buyer takes a holder-signed ask, paying the holder and becoming the
+    /// position's holder
+    pub fn buy_position(ctx: Context<BuyPosition>, params: BuyPositionParams) -> Result<()> {
+        instructions::handle_buy_position(ctx, params)
+    }
+
+    /// Holder invalidates all of their outstanding resale asks
+    pub fn cancel_resale_asks(ctx: Context<CancelResaleAsks>) -> Result<()> {
+        instructions::handle_cancel_resale_asks(ctx)
+    }
+
+    // ===== Premium Installments =====
+
+    /// User offers to take the premium on their pending intent in installments
+    pub fn offer_premium_installments(
+        ctx: Context<OfferPremiumInstallments>,
+        installments: u8,
+        interval_seconds: i64,
+    ) -> Result<()> {
+        instructions::handle_offer_premium_installments(ctx, installments, interval_seconds)
+    }
+
+    /// User withdraws an unused installment offer or closes a finished plan
+    pub fn close_premium_schedule(ctx: Context<ClosePremiumSchedule>) -> Result<()> {
+        instructions::handle_close_premium_schedule(ctx)
+    }
+
+    /// Pay the next premium installment to the position holder
+    pub fn pay_installment(ctx: Context<PayInstallment>) -> Result<()> {
+        instructions::handle_pay_installment(ctx)
+    }
+
+    /// Holder cuts the unpaid share of coverage after a missed installment
+    pub fn cancel_unpaid_coverage(ctx: Context<CancelUnpaidCoverage>) -> Result<()> {
+        instructions::handle_cancel_unpaid_coverage(ctx)
+    }
+
+    // ===== Option Vaults =====
+
+    /// Authority creates the pooled covered-call or cash-secured-put vault
+    /// for an asset
+    pub fn create_option_vault(
+        ctx: Context<CreateOptionVault>,
+        strategy: StrategyType,
+        manager: Pubkey,
+    ) -> Result<()> {
+        instructions::handle_create_option_vault(ctx, strategy, manager)
+    }
+
+    /// Authority sets the vault's deposit cap (0 = uncapped) and the length of
+    /// its deposit/withdraw window after each round
+    pub fn configure_option_vault(
+        ctx: Context<ConfigureOptionVault>,
+        deposit_cap: u64,
+        window_duration: i64,
+    ) -> Result<()> {
+        instructions::handle_configure_option_vault(ctx, deposit_cap, window_duration)
+    }
+
+    /// Authority sets the vault's management and performance fees
+    pub fn set_option_vault_fees(
+        ctx: Context<SetOptionVaultFees>,
+        management_fee_bps: u16,
+        performance_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::handle_set_option_vault_fees(ctx, management_fee_bps, performance_fee_bps)
+    }
+
+    /// Deposit the vault's deposit mint in the window between rounds for vault shares
+    pub fn deposit_to_vault(ctx: Context<DepositToVault>, amount: u64) -> Result<()> {
+        instructions::handle_deposit_to_vault(ctx, amount)
+    }
+
+    /// Redeem vault shares in the window between rounds for a pro-rata part
+    /// of both balances
+    pub fn withdraw_from_vault(ctx: Context<WithdrawFromVault>, shares: u64) -> Result<()> {
+        instructions::handle_withdraw_from_vault(ctx, shares)
+    }
+
+    /// Queue vault shares outside the window, to be paid once the round finishes
+    pub fn queue_vault_withdrawal(ctx: Context<QueueVaultWithdrawal>, shares: u64) -> Result<()> {
+        instructions::handle_queue_vault_withdrawal(ctx, shares)
+    }
+
+    /// Pay out a queued vault withdrawal once its round has finished
+    pub fn claim_vault_withdrawal(ctx: Context<ClaimVaultWithdrawal>) -> Result<()> {
+        instructions::handle_claim_vault_withdrawal(ctx)
+    }
+
+    /// Manager starts the epoch's round with an intent of the vault's strategy
+    pub fn vault_submit_intent(
+        ctx: Context<VaultSubmitIntent>,
+        params: SubmitIntentParams,
+    ) -> Result<()> {
+        instructions::handle_vault_submit_intent(ctx, params)
+    }
+
+    /// Close out the round once its intent lapsed or its position paid out,
+    /// charging the round's fees
+    pub fn finish_vault_round(ctx: Context<FinishVaultRound>) -> Result<()> {
+        instructions::handle_finish_vault_round(ctx)
+    }
+
+    // ===== Liquidity Vaults =====
+
+    /// Authority creates the passive LP vault for a quote mint
+    pub fn create_liquidity_vault(
+        ctx: Context<CreateLiquidityVault>,
+        premium_share_bps: u16,
+        max_utilization_bps: u16,
+        epoch_duration: i64,
+    ) -> Result<()> {
+        instructions::handle_create_liquidity_vault(
+            ctx,
+            premium_share_bps,
+            max_utilization_bps,
+            epoch_duration,
+        )
+    }
+
+    /// Authority updates the LP premium share, utilization cap, epoch length
+    /// and deposit cap (0 = uncapped)
+    pub fn configure_liquidity_vault(
+        ctx: Context<ConfigureLiquidityVault>,
+        premium_share_bps: u16,
+        max_utilization_bps: u16,
+        epoch_duration: i64,
+        deposit_cap: u64,
+    ) -> Result<()> {
+        instructions::handle_configure_liquidity_vault(
+            ctx,
+            premium_share_bps,
+            max_utilization_bps,
+            epoch_duration,
+            deposit_cap,
+        )
+    }
+
+    /// Authority sets the vault's management and performance fees
+    pub fn set_liquidity_vault_fees(
+        ctx: Context<SetLiquidityVaultFees>,
+        management_fee_bps: u16,
+        performance_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::handle_set_liquidity_vault_fees(ctx, management_fee_bps, performance_fee_bps)
+    }
+
+    /// Authority whitelists an MM on the vault with a credit limit
+    pub fn set_lp_backing(
+        ctx: Context<SetLpBacking>,
+        market_maker: Pubkey,
+        credit_limit: u64,
+    ) -> Result<()> {
+        instructions::handle_set_lp_backing(ctx, market_maker, credit_limit)
+    }
+
+    /// LP deposits quote for vault shares
+    pub fn deposit_liquidity(ctx: Context<DepositLiquidity>, amount: u64) -> Result<()> {
+        instructions::handle_deposit_liquidity(ctx, amount)
+    }
+
+    /// LP queues shares for withdrawal after the current epoch
+    pub fn request_lp_withdrawal(ctx: Context<RequestLpWithdrawal>, shares: u64) -> Result<()> {
+        instructions::handle_request_lp_withdrawal(ctx, shares)
+    }
+
+    /// Charge the epoch's fees and start the next withdrawal epoch once the
+    /// current one has run its length
+    pub fn advance_liquidity_epoch(ctx: Context<AdvanceLiquidityEpoch>) -> Result<()> {
+        instructions::handle_advance_liquidity_epoch(ctx)
+    }
+
+    /// LP claims a queued withdrawal from idle liquidity
+    pub fn claim_lp_withdrawal(ctx: Context<ClaimLpWithdrawal>) -> Result<()> {
+        instructions::handle_claim_lp_withdrawal(ctx)
+    }
+
+    /// MM pays down its premium share and unreturned collateral
+    pub fn repay_lp_backing(ctx: Context<RepayLpBacking>, amount: u64) -> Result<()> {
+        instructions::handle_repay_lp_backing(ctx, amount)
     }
 
     // ===== Dispute Resolution (Owner Override) =====
 
+    /// Authority sets the arbitration committee (threshold 0 = single-key mode)
+    pub fn set_arbitration_committee(
+        ctx: Context<SetArbitrationCommittee>,
+        arbiters: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::handle_set_arbitration_committee(ctx, arbiters, threshold)
+    }
+
+    /// Arbiter approves an owner-override resolution for a disputed intent
+    pub fn vote_resolution(
+        ctx: Context<VoteResolution>,
+        proposal: ResolutionProposal,
+    ) -> Result<()> {
+        instructions::handle_vote_resolution(ctx, proposal)
+    }
+
     /// 1. MUTUAL_UNWIND: Return all funds to original parties
     pub fn mutual_unwind(ctx: Context<MutualUnwindIntent>, reason: String) -> Result<()> {
         instructions::handle_mutual_unwind(ctx, reason)
     }
 
-    /// 2. FORCE_CONTINUE: Force-create position as if MM had filled
+    /// Fund the Position account ahead of force_continue (any payer)
+    pub fn prepare_force_continue(ctx: Context<PrepareForceContinue>) -> Result<()> {
+        instructions::handle_prepare_force_continue(ctx)
+    }
+
+    /// 2. FORCE_CONTINUE: Force-create position as if MM had filled; a
+    /// frivolous ruling also forfeits the flagger's dispute bond
     pub fn force_continue(
         ctx: Context<ForceContinueIntent>,
         reason: String,
         pay_premium: bool,
+        frivolous: bool,
     ) -> Result<()> {
-        instructions::handle_force_continue(ctx, reason, pay_premium)
+        instructions::handle_force_continue(ctx, reason, pay_premium, frivolous)
     }
 
     /// 3. FORCE_SETTLE_NOW: Settle immediately at specified price/split
@@ -169,9 +676,306 @@ pub mod solation {
         instructions::handle_emergency_shutdown(ctx, reason)
     }
 
+    /// 7. RESUME_PROTOCOL: Re-enable the next instruction family after a shutdown
+    pub fn resume_protocol(ctx: Context<ResumeProtocol>) -> Result<()> {
+        instructions::handle_resume_protocol(ctx)
+    }
+
+    /// 8. BATCH_UNWIND_INTENTS: Mutual unwind of many intents while shut down
+    pub fn batch_unwind_intents<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchUnwindIntents<'info>>,
+        reason: String,
+    ) -> Result<()> {
+        instructions::handle_batch_unwind_intents(ctx, reason)
+    }
+
+    /// 9. DISTRIBUTE_FROM_TREASURY: Pay out escrow parked at the treasury and close the dispute
+    pub fn distribute_from_treasury<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeFromTreasury<'info>>,
+        amounts: Vec<u64>,
+        purpose: String,
+    ) -> Result<()> {
+        instructions::handle_distribute_from_treasury(ctx, amounts, purpose)
+    }
+
     // ===== Settlement =====
 
-    pub fn settle_position(ctx: Context<SettlePosition>) -> Result<()> {
-        instructions::handle_settle_position(ctx)
+    /// Permissionless settlement; `swap_route` is a Jupiter route (accounts in
+    /// remaining accounts) for users who requested a payout swap
+    pub fn settle_position<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettlePosition<'info>>,
+        swap_route: Option<Vec<u8>>,
+    ) -> Result<()> {
+        instructions::handle_settle_position(ctx, swap_route)
+    }
+
+    /// Authority settles at a manual price once oracles have been dead past expiry
+    pub fn admin_settle_with_price(
+        ctx: Context<AdminSettleWithPrice>,
+        settlement_price: u64,
+        reason: String,
+    ) -> Result<()> {
+        instructions::handle_admin_settle_with_price(ctx, settlement_price, reason)
+    }
+
+    /// MM delivers its leg of an ITM physical settlement and takes the collateral
+    pub fn deliver_physical_settlement(ctx: Context<DeliverPhysicalSettlement>) -> Result<()> {
+        instructions::handle_deliver_physical_settlement(ctx)
+    }
+
+    /// Crank a pre-expiry oracle observation for TWAP-settled assets
+    pub fn record_settlement_observation(ctx: Context<RecordSettlementObservation>) -> Result<()> {
+        instructions::handle_record_settlement_observation(ctx)
+    }
+
+    /// Two-step settlement, step 1: record the validated expiry price
+    pub fn snapshot_settlement_price(ctx: Context<SnapshotSettlementPrice>) -> Result<()> {
+        instructions::handle_snapshot_settlement_price(ctx)
+    }
+
+    /// Two-step settlement, step 2: move tokens at the recorded price
+    pub fn distribute_settlement(ctx: Context<DistributeSettlement>) -> Result<()> {
+        instructions::handle_distribute_settlement(ctx)
+    }
+
+    /// User, MM or authority challenges a snapshotted price inside the dispute window
+    pub fn dispute_settlement_price(
+        ctx: Context<DisputeSettlementPrice>,
+        reason: String,
+    ) -> Result<()> {
+        instructions::handle_dispute_settlement_price(ctx, reason)
+    }
+
+    /// Authority resolves a settlement dispute, optionally correcting the price
+    pub fn resolve_settlement_dispute(
+        ctx: Context<ResolveSettlementDispute>,
+        corrected_price: Option<u64>,
+    ) -> Result<()> {
+        instructions::handle_resolve_settlement_dispute(ctx, corrected_price)
+    }
+
+    /// Settle many same-asset, same-expiry positions with one price update
+    pub fn settle_positions_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettlePositionsBatch<'info>>,
+        expiry_timestamp: i64,
+    ) -> Result<()> {
+        instructions::handle_settle_positions_batch(ctx, expiry_timestamp)
+    }
+
+    /// User or MM designates an alternate payout owner ahead of settlement
+    pub fn set_payout_beneficiary(
+        ctx: Context<SetPayoutBeneficiary>,
+        beneficiary: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::handle_set_payout_beneficiary(ctx, beneficiary)
+    }
+
+    /// Covered-call user elects to be paid in the underlying at settlement
+    pub fn elect_underlying_payout(ctx: Context<ElectUnderlyingPayout>, elect: bool) -> Result<()> {
+        instructions::handle_elect_underlying_payout(ctx, elect)
+    }
+
+    /// User asks for their payout to be swapped into the position's other leg
+    pub fn set_payout_swap(
+        ctx: Context<SetPayoutSwap>,
+        output_mint: Pubkey,
+        max_slippage_bps: u16,
+    ) -> Result<()> {
+        instructions::handle_set_payout_swap(ctx, output_mint, max_slippage_bps)
+    }
+
+    /// User withdraws their payout swap request
+    pub fn clear_payout_swap(ctx: Context<ClearPayoutSwap>) -> Result<()> {
+        instructions::handle_clear_payout_swap(ctx)
+    }
+
+    /// User and MM co-sign to push expiry later for an additional premium
+    pub fn extend_expiry(
+        ctx: Context<ExtendExpiry>,
+        new_expiry_timestamp: i64,
+        additional_premium: u64,
+    ) -> Result<()> {
+        instructions::handle_extend_expiry(ctx, new_expiry_timestamp, additional_premium)
+    }
+
+    /// Sweep whatever is left in a paid-out position's vault to the treasury
+    pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+        instructions::handle_sweep_dust(ctx)
+    }
+
+    // ===== Early-Close Triggers =====
+
+    /// MM publishes (or withdraws) standing terms for trigger-driven unwinds
+    pub fn set_unwind_terms(
+        ctx: Context<SetUnwindTerms>,
+        enabled: bool,
+        fee_bps: u16,
+    ) -> Result<()> {
+        instructions::handle_set_unwind_terms(ctx, enabled, fee_bps)
+    }
+
+    /// User registers stop-loss / take-profit prices on their position
+    pub fn set_position_triggers(
+        ctx: Context<SetPositionTriggers>,
+        stop_loss_price: Option<u64>,
+        take_profit_price: Option<u64>,
+    ) -> Result<()> {
+        instructions::handle_set_position_triggers(ctx, stop_loss_price, take_profit_price)
+    }
+
+    /// Anyone can close a position early once a trigger price is hit
+    pub fn execute_trigger(ctx: Context<ExecuteTrigger>) -> Result<()> {
+        instructions::handle_execute_trigger(ctx)
+    }
+
+    // ===== Auto-Roll =====
+
+    /// User opts into auto-roll for an asset with quote constraints
+    pub fn create_roll_preference(
+        ctx: Context<CreateRollPreference>,
+        asset_mint: Pubkey,
+        params: RollPreferenceParams,
+    ) -> Result<()> {
+        instructions::handle_create_roll_preference(ctx, asset_mint, params)
+    }
+
+    /// User toggles or changes their auto-roll constraints
+    pub fn update_roll_preference(
+        ctx: Context<UpdateRollPreference>,
+        enabled: Option<bool>,
+        params: Option<RollPreferenceParams>,
+    ) -> Result<()> {
+        instructions::handle_update_roll_preference(ctx, enabled, params)
+    }
+
+    /// Keeper settles an expired position and re-opens it against a fresh quote
+    pub fn roll_position(ctx: Context<RollPosition>, params: SubmitIntentParams) -> Result<()> {
+        instructions::handle_roll_position(ctx, params)
+    }
+
+    // ===== Insurance Fund =====
+
+    /// Create the insurance fund and its vault for a quote mint
+    pub fn initialize_insurance_fund(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+        instructions::handle_initialize_insurance_fund(ctx)
+    }
+
+    /// Top up the insurance fund (credited to the MM when its registry is passed)
+    pub fn deposit_insurance(ctx: Context<DepositInsurance>, amount: u64) -> Result<()> {
+        instructions::handle_deposit_insurance(ctx, amount)
+    }
+
+    /// Treasury routes the insurance share of `fee_amount` collected fees
+    pub fn accrue_insurance_fees(ctx: Context<AccrueInsuranceFees>, fee_amount: u64) -> Result<()> {
+        instructions::handle_accrue_insurance_fees(ctx, fee_amount)
+    }
+
+    /// Authority pays a user out of the fund after their MM defaulted
+    pub fn pay_insurance_claim(
+        ctx: Context<PayInsuranceClaim>,
+        amount: u64,
+        reason: String,
+    ) -> Result<()> {
+        instructions::handle_pay_insurance_claim(ctx, amount, reason)
+    }
+
+    /// Authority registers what a defaulted MM owed on a position
+    pub fn register_default_claim(ctx: Context<RegisterDefaultClaim>, owed: u64) -> Result<()> {
+        instructions::handle_register_default_claim(ctx, owed)
+    }
+
+    /// Fix the insurance-covered share of an MM's defaults at one expiry
+    pub fn finalize_haircut(ctx: Context<FinalizeHaircut>) -> Result<()> {
+        instructions::handle_finalize_haircut(ctx)
+    }
+
+    /// Pay a registered position its pro-rata share after the haircut
+    pub fn claim_haircut_payout(ctx: Context<ClaimHaircutPayout>) -> Result<()> {
+        instructions::handle_claim_haircut_payout(ctx)
+    }
+
+    // ===== Archival =====
+
+    /// Bind a pre-allocated concurrent Merkle tree as the position archive
+    pub fn initialize_position_archive(
+        ctx: Context<InitializePositionArchive>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        instructions::handle_initialize_position_archive(ctx, max_depth, max_buffer_size)
+    }
+
+    /// Commit a settled position to the archive tree and close its account
+    pub fn archive_position(ctx: Context<ArchivePosition>) -> Result<()> {
+        instructions::handle_archive_position(ctx)
+    }
+
+    /// Post a settled position's outcome through Wormhole for a companion
+    /// contract on another chain (once per position)
+    pub fn attest_settlement(ctx: Context<AttestSettlement>) -> Result<()> {
+        instructions::handle_attest_settlement(ctx)
+    }
+
+    // ===== Address Lookup Table =====
+
+    /// Create the protocol lookup table seeded with its static accounts
+    pub fn create_protocol_lookup_table(
+        ctx: Context<CreateProtocolLookupTable>,
+        recent_slot: u64,
+    ) -> Result<()> {
+        instructions::handle_create_protocol_lookup_table(ctx, recent_slot)
+    }
+
+    /// Add shared accounts (mints, asset configs) to the protocol lookup table
+    pub fn extend_protocol_lookup_table(
+        ctx: Context<ExtendProtocolLookupTable>,
+        addresses: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::handle_extend_protocol_lookup_table(ctx, addresses)
+    }
+
+    // ===== Views =====
+
+    /// Mark-to-market valuation of a position (result via return data)
+    pub fn get_position_value(ctx: Context<GetPositionValue>) -> Result<()> {
+        instructions::handle_get_position_value(ctx)
+    }
+
+    /// Insurance fund balance and flows (result via return data)
+    pub fn get_insurance_fund_report(ctx: Context<GetInsuranceFundReport>) -> Result<()> {
+        instructions::handle_get_insurance_fund_report(ctx)
+    }
+
+    /// Escrow `submit_intent` would take for these terms (result via return data)
+    pub fn quote_escrow_amount(
+        ctx: Context<QuoteEscrowAmount>,
+        strategy: StrategyType,
+        strike_price: u64,
+        contract_size: u64,
+    ) -> Result<()> {
+        instructions::handle_quote_escrow_amount(ctx, strategy, strike_price, contract_size)
+    }
+
+    /// Payout split of a position at `settlement_price` (result via return data)
+    pub fn preview_settlement(
+        ctx: Context<PreviewSettlement>,
+        settlement_price: u64,
+    ) -> Result<()> {
+        instructions::handle_preview_settlement(ctx, settlement_price)
+    }
+
+    /// Market maker fill rate and reputation (result via return data)
+    pub fn get_mm_fill_rate(ctx: Context<GetMMFillRate>) -> Result<()> {
+        instructions::handle_get_mm_fill_rate(ctx)
+    }
+
+    /// MM's locked and free collateral on an asset (result via return data)
+    pub fn get_mm_collateral(ctx: Context<GetMMCollateral>) -> Result<()> {
+        instructions::handle_get_mm_collateral(ctx)
+    }
+
+    /// Open interest in an option series (result via return data)
+    pub fn get_series_open_interest(ctx: Context<GetSeriesOpenInterest>) -> Result<()> {
+        instructions::handle_get_series_open_interest(ctx)
     }
 }
@@ -1,16 +1,223 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{BASIS_POINTS_DIVISOR, MAX_ALT_QUOTE_MINTS, MAX_BASKET_COMPONENTS};
+use crate::errors::ErrorCode;
+use super::StrategyType;
+
+/// Price feed provider for an asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OracleSource {
+    Pyth = 0,
+    Chainlink = 1,
+    Switchboard = 2,
+}
+
+/// How ITM positions on an asset settle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettlementKind {
+    /// Collateral is split pro-rata at the settlement price
+    Cash = 0,
+    /// Collateral goes to the MM against delivery of the other leg
+    Physical = 1,
+}
+
+/// What the underlying is
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AssetClass {
+    /// An SPL or Token-2022 mint
+    Token = 0,
+    /// An off-chain asset (equity, commodity) with no mint: `asset_mint` is
+    /// only a market key, and positions are cash-secured puts settled in the
+    /// quote mint against the asset's feed
+    OffChain = 1,
+}
+
+/// Who may submit intents on an asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AccessGate {
+    /// Permissionless
+    Open = 0,
+    /// User must hold a nonzero balance of the pass mint (`access_gate_key`)
+    PassToken = 1,
+    /// User must hold an unexpired credential from the issuer (`access_gate_key`)
+    Credential = 2,
+}
+
+/// Where a liquid-staking collateral mint's exchange rate is read from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LstRateSource {
+    /// SPL stake pool account (total lamports / pool token supply)
+    StakePool = 0,
+    /// Switchboard pull feed quoting the LST in units of the underlying
+    Switchboard = 1,
+}
+
+/// How the asset's price is derived from its oracle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PriceComposition {
+    /// The oracle quotes the asset itself
+    Direct = 0,
+    /// The oracle quotes a base asset, and the asset (an LST or LP token) is
+    /// priced at fair value: base price x base per token from the rate account
+    ExchangeRate = 1,
+}
+
+/// Underlying per unit of collateral, as the ratio `underlying / collateral`
+#[derive(Clone, Copy, Debug)]
+pub struct CollateralRate {
+    pub underlying: u128,
+    pub collateral: u128,
+}
+
+/// A stablecoin accepted as quote besides the asset's primary quote mint,
+/// priced in USD by its own Pyth feed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AltQuote {
+    pub mint: Pubkey,
+    pub pyth_feed_id: [u8; 32],
+}
+
+impl AltQuote {
+    pub const LEN: usize = 32 + // mint
+        32;  // pyth_feed_id
+
+    pub fn is_empty(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
+}
+
+/// One Pyth feed of an index asset's basket
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BasketComponent {
+    pub pyth_feed_id: [u8; 32],
+    /// Units of the component per unit of the index, scaled by `BASKET_WEIGHT_SCALE`
+    pub weight: u64,
+}
+
+impl BasketComponent {
+    pub const LEN: usize = 32 + // pyth_feed_id
+        8;   // weight
+
+    pub fn is_empty(&self) -> bool {
+        self.weight == 0
+    }
+}
+
+/// Asset settlement parameters applied by `calculate_settlement`
+#[derive(Clone, Copy, Debug)]
+pub struct SettlementTerms {
+    pub kind: SettlementKind,
+    pub min_itm_bps: u16,
+    pub asset_decimals: u8,
+    pub quote_decimals: u8,
+    pub price_exponent: i32,
+    /// Set when a covered call is collateralized in an LST rather than the underlying
+    pub collateral_rate: Option<CollateralRate>,
+}
+
+impl SettlementTerms {
+    /// Collateral base units worth `amount` underlying base units
+    pub fn to_collateral(&self, amount: u64, round_up: bool) -> Option<u64> {
+        let Some(rate) = self.collateral_rate else {
+            return Some(amount);
+        };
+        if rate.underlying == 0 {
+            return None;
+        }
+        let product = (amount as u128).checked_mul(rate.collateral)?;
+        let value = if round_up {
+            product.div_ceil(rate.underlying)
+        } else {
+            product / rate.underlying
+        };
+        u64::try_from(value).ok()
+    }
+
+    /// Quote base units worth `size` underlying base units at `price`
+    /// (in units of 10^price_exponent), with u128 intermediates
+    pub fn quote_value(&self, size: u64, price: u64, round_up: bool) -> Option<u64> {
+        let shift =
+            self.quote_decimals as i32 + self.price_exponent - self.asset_decimals as i32;
+        let product = (size as u128).checked_mul(price as u128)?;
+        let value = if shift >= 0 {
+            product.checked_mul(10u128.checked_pow(shift as u32)?)?
+        } else {
+            let divisor = 10u128.checked_pow(shift.unsigned_abs())?;
+            if round_up {
+                product.div_ceil(divisor)
+            } else {
+                product / divisor
+            }
+        };
+        u64::try_from(value).ok()
+    }
+
+    /// Underlying base units worth `quote_amount` quote base units at `price`,
+    /// rounded down (the inverse of `quote_value`)
+    pub fn underlying_value(&self, quote_amount: u64, price: u64) -> Option<u64> {
+        let shift =
+            self.quote_decimals as i32 + self.price_exponent - self.asset_decimals as i32;
+        let scale = 10u128.checked_pow(shift.unsigned_abs())?;
+        let value = if shift >= 0 {
+            (quote_amount as u128).checked_div((price as u128).checked_mul(scale)?)?
+        } else {
+            (quote_amount as u128).checked_mul(scale)?.checked_div(price as u128)?
+        };
+        u64::try_from(value).ok()
+    }
+}
+
 #[account]
 pub struct AssetConfig {
     pub asset_mint: Pubkey,
     pub quote_mint: Pubkey,           // Always USDC
     pub pyth_feed_id: [u8; 32],      // Pyth price feed ID
+    pub oracle_source: OracleSource,  // Primary price feed provider
+    pub settlement_kind: SettlementKind, // Cash or physical settlement
+    pub min_itm_bps: u16,             // Moneyness below this settles OTM (e.g., 10 = 0.1%)
     pub enabled: bool,
+    pub paused: bool,                 // Per-asset pause (intents, fills, settlement)
+    pub delisted: bool,               // Wind-down: no new intents, existing ones run off
+    pub open_positions: u64,          // Active positions not yet settled
+    pub open_notional: u64,           // Strike notional of open positions, in quote units
+    pub max_open_notional: u64,       // Cap on open_notional (0 = uncapped)
+    pub max_intent_notional: u64,     // Cap on a single intent's strike notional (0 = uncapped)
     pub min_strike_percentage: u16,   // e.g., 80 = 80% of current price
     pub max_strike_percentage: u16,   // e.g., 120 = 120% of current price
     pub min_expiry_seconds: i64,      // e.g., 1 day = 86400
     pub max_expiry_seconds: i64,      // e.g., 90 days = 7776000
     pub decimals: u8,                 // Asset decimals
+    pub quote_decimals: u8,           // Quote mint decimals
+    pub twap_window_seconds: i64,     // Settle at a TWAP over this window before expiry (0 = spot)
+    pub min_twap_observations: u8,    // Observations required for a valid TWAP
+    pub max_confidence_bps: u16,      // Max Pyth conf / price accepted, in bps
+    pub max_price_age_seconds: u64,   // Max Pyth publish age accepted
+    pub switchboard_feed: Pubkey,     // Switchboard pull feed, primary or fallback (default = none)
+    pub chainlink_feed: Pubkey,       // Chainlink store feed (default = none)
+    pub price_exponent: i32,          // Exponent of strikes and oracle prices (Pyth feed exponent)
+    pub access_gate: AccessGate,      // Open or permissioned venue
+    pub access_gate_key: Pubkey,      // KYC pass mint or credential issuer (default = none)
+    pub allow_transfer_fee: bool,     // Listed with fee-bearing Token-2022 mints (amounts grossed up for fees)
+    pub lst_mint: Pubkey,             // LST accepted as covered-call collateral (default = none)
+    pub lst_rate_source: LstRateSource, // Where the LST exchange rate is read from
+    pub lst_rate_account: Pubkey,     // Stake pool or rate feed for `lst_mint`
+    pub max_fill_price_move_bps: u16, // Max spot move between submit and fill, in bps (0 = unchecked)
+    pub series_strike_step: u64,      // Strike grid for OptionSeries (0 = series not listed)
+    pub allowed_strategies: u8,       // Bitmask of StrategyType values new intents may use
+    pub min_contract_size: u64,       // Smallest contract size per intent (0 = no floor)
+    pub max_contract_size: u64,       // Largest contract size per fill (0 = uncapped)
+    pub min_premium: u64,             // Floor on a new intent's total premium, above the global one (0 = none)
+    pub lot_size: u64,                // Notional-sized intents round down to a multiple of this (0 = any size)
+    pub underlying_premium: bool,     // MMs may pay premium in the underlying at oracle spot
+    pub quote_pyth_feed_id: [u8; 32], // Pyth USD feed for the quote currency's peg check (zero = unchecked)
+    pub max_quote_depeg_bps: u16,     // Quote deviation from $1 that halts settlement, in bps
+    pub quote_depegged: bool,         // Settlement halted after a quote depeg, until the authority clears it
+    pub alt_quotes: [AltQuote; MAX_ALT_QUOTE_MINTS], // Alternative stablecoin quotes (empty slots have a default mint)
+    pub price_composition: PriceComposition, // Direct feed, or base feed x exchange rate
+    pub fair_value_rate_source: LstRateSource, // Where the fair-value exchange rate is read from
+    pub fair_value_rate_account: Pubkey, // Stake pool or rate feed quoting the asset in its base
+    pub basket: [BasketComponent; MAX_BASKET_COMPONENTS], // Index components, priced from per-call updates (empty = single feed)
+    pub asset_class: AssetClass,      // Token, or an off-chain asset with no mint
     pub bump: u8,
 }
 
@@ -19,11 +226,306 @@ impl AssetConfig {
         32 + // asset_mint
         32 + // quote_mint
         32 + // pyth_feed_id
+        1 +  // oracle_source
+        1 +  // settlement_kind
+        2 +  // min_itm_bps
         1 +  // enabled
+        1 +  // paused
+        1 +  // delisted
+        8 +  // open_positions
+        8 +  // open_notional
+        8 +  // max_open_notional
+        8 +  // max_intent_notional
         2 +  // min_strike_percentage
         2 +  // max_strike_percentage
         8 +  // min_expiry_seconds
         8 +  // max_expiry_seconds
         1 +  // decimals
+        1 +  // quote_decimals
+        8 +  // twap_window_seconds
+        1 +  // min_twap_observations
+        2 +  // max_confidence_bps
+        8 +  // max_price_age_seconds
+        32 + // switchboard_feed
+        32 + // chainlink_feed
+        4 +  // price_exponent
+        1 +  // access_gate
+        32 + // access_gate_key
+        1 +  // allow_transfer_fee
+        32 + // lst_mint
+        1 +  // lst_rate_source
+        32 + // lst_rate_account
+        2 +  // max_fill_price_move_bps
+        8 +  // series_strike_step
+        1 +  // allowed_strategies
+        8 +  // min_contract_size
+        8 +  // max_contract_size
+        8 +  // min_premium
+        8 +  // lot_size
+        1 +  // underlying_premium
+        32 + // quote_pyth_feed_id
+        2 +  // max_quote_depeg_bps
+        1 +  // quote_depegged
+        AltQuote::LEN * MAX_ALT_QUOTE_MINTS + // alt_quotes
+        1 +  // price_composition
+        1 +  // fair_value_rate_source
+        32 + // fair_value_rate_account
+        BasketComponent::LEN * MAX_BASKET_COMPONENTS + // basket
+        1 +  // asset_class
         1;   // bump
+
+    /// Strike notional of `contract_size` at `strike_price`, in quote units
+    pub fn notional_value(&self, contract_size: u64, strike_price: u64) -> Result<u64> {
+        self.settlement_terms()
+            .quote_value(contract_size, strike_price, false)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    /// Contract size worth `notional` quote units at `spot_price`, rounded
+    /// down to a whole number of lots
+    pub fn contracts_for_notional(&self, notional: u64, spot_price: u64) -> Result<u64> {
+        let size = self
+            .settlement_terms()
+            .underlying_value(notional, spot_price)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(match self.lot_size {
+            0 => size,
+            lot => size - size % lot,
+        })
+    }
+
+    /// Underlying worth `premium` quote units at `spot_price`, rounded down
+    pub fn premium_in_underlying(&self, premium: u64, spot_price: u64) -> Result<u64> {
+        self.settlement_terms()
+            .underlying_value(premium, spot_price)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    /// Whether a strike lies within the listed percentage band around spot
+    pub fn strike_within_bounds(&self, strike_price: u64, spot_price: u64) -> bool {
+        if spot_price == 0 {
+            return false;
+        }
+        let strike = strike_price as u128 * 100;
+        let spot = spot_price as u128;
+        strike >= spot * self.min_strike_percentage as u128
+            && strike <= spot * self.max_strike_percentage as u128
+    }
+
+    /// Whether new intents may use `strategy` on this asset
+    pub fn allows_strategy(&self, strategy: StrategyType) -> bool {
+        self.allowed_strategies & strategy.mask() != 0
+    }
+
+    /// Require a contract size within the asset's min/max bounds
+    pub fn require_contract_size(&self, contract_size: u64) -> Result<()> {
+        require!(
+            contract_size >= self.min_contract_size,
+            ErrorCode::ContractSizeTooSmall
+        );
+        require!(
+            self.max_contract_size == 0 || contract_size <= self.max_contract_size,
+            ErrorCode::ContractSizeTooLarge
+        );
+        Ok(())
+    }
+
+    /// Whether an expiry is within the listed tenor window from `now`
+    pub fn expiry_within_bounds(&self, expiry_timestamp: i64, now: i64) -> bool {
+        let tenor = expiry_timestamp.saturating_sub(now);
+        (self.min_expiry_seconds..=self.max_expiry_seconds).contains(&tenor)
+    }
+
+    /// Whether spot has stayed within the fill-time band around the intent's
+    /// reference price. Always true when the band is off or no reference was
+    /// recorded.
+    pub fn fill_price_within_band(&self, reference_price: u64, spot_price: u64) -> bool {
+        if self.max_fill_price_move_bps == 0 || reference_price == 0 {
+            return true;
+        }
+        let distance = spot_price.abs_diff(reference_price) as u128;
+        distance * BASIS_POINTS_DIVISOR as u128
+            <= reference_price as u128 * self.max_fill_price_move_bps as u128
+    }
+
+    /// Count a new position toward open interest, enforcing the asset cap
+    pub fn record_position_opened(&mut self, notional: u64) -> Result<()> {
+        let open_notional = self
+            .open_notional
+            .checked_add(notional)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            self.max_open_notional == 0 || open_notional <= self.max_open_notional,
+            ErrorCode::OpenInterestCapExceeded
+        );
+        self.open_notional = open_notional;
+        self.open_positions = self.open_positions.saturating_add(1);
+        Ok(())
+    }
+
+    pub fn record_position_closed(&mut self, notional: u64) {
+        self.open_notional = self.open_notional.saturating_sub(notional);
+        self.open_positions = self.open_positions.saturating_sub(1);
+    }
+
+    pub fn uses_twap(&self) -> bool {
+        self.twap_window_seconds > 0
+    }
+
+    pub fn settlement_terms(&self) -> SettlementTerms {
+        SettlementTerms {
+            kind: self.settlement_kind,
+            min_itm_bps: self.min_itm_bps,
+            asset_decimals: self.decimals,
+            quote_decimals: self.quote_decimals,
+            price_exponent: self.price_exponent,
+            collateral_rate: None,
+        }
+    }
+
+    pub fn has_switchboard_feed(&self) -> bool {
+        self.switchboard_feed != Pubkey::default()
+    }
+
+    pub fn has_quote_peg_check(&self) -> bool {
+        self.quote_pyth_feed_id != [0u8; 32]
+    }
+
+    /// Whether intents on this asset may be quoted in `mint`
+    pub fn accepts_quote_mint(&self, mint: Pubkey) -> bool {
+        mint == self.quote_mint || self.alt_quote(mint).is_some()
+    }
+
+    pub fn alt_quote(&self, mint: Pubkey) -> Option<&AltQuote> {
+        self.alt_quotes
+            .iter()
+            .find(|quote| !quote.is_empty() && quote.mint == mint)
+    }
+
+    /// Pyth USD feed pricing `quote_mint`: always set for an alternative
+    /// quote, and for the primary one once its peg is checked
+    pub fn quote_feed_id(&self, quote_mint: Pubkey) -> Option<[u8; 32]> {
+        if quote_mint == self.quote_mint {
+            self.has_quote_peg_check().then_some(self.quote_pyth_feed_id)
+        } else {
+            self.alt_quote(quote_mint).map(|quote| quote.pyth_feed_id)
+        }
+    }
+
+    pub fn has_chainlink_feed(&self) -> bool {
+        self.chainlink_feed != Pubkey::default()
+    }
+
+    /// A permissioned gate needs a pass mint or issuer to check against
+    pub fn has_valid_access_gate(&self) -> bool {
+        self.access_gate == AccessGate::Open || self.access_gate_key != Pubkey::default()
+    }
+
+    /// Whether the feed for `source` has been configured
+    pub fn has_feed_for(&self, source: OracleSource) -> bool {
+        match source {
+            OracleSource::Pyth => self.pyth_feed_id != [0u8; 32],
+            OracleSource::Chainlink => self.has_chainlink_feed(),
+            OracleSource::Switchboard => self.has_switchboard_feed(),
+        }
+    }
+
+    pub fn is_offchain(&self) -> bool {
+        self.asset_class == AssetClass::OffChain
+    }
+
+    /// An off-chain asset has no underlying to escrow, pay or deliver: it
+    /// lists cash-settled puts only
+    pub fn has_valid_asset_class(&self) -> bool {
+        !self.is_offchain()
+            || (self.settlement_kind == SettlementKind::Cash
+                && !self.allows_strategy(StrategyType::CoveredCall)
+                && !self.has_lst_collateral()
+                && !self.underlying_premium
+                && !self.has_fair_value_price())
+    }
+
+    /// Whether the asset is an index over a basket of feeds
+    pub fn has_basket(&self) -> bool {
+        self.basket_len() > 0
+    }
+
+    /// Number of basket components; they fill the array from the front
+    pub fn basket_len(&self) -> usize {
+        self.basket.iter().take_while(|component| !component.is_empty()).count()
+    }
+
+    pub fn has_fair_value_price(&self) -> bool {
+        self.price_composition == PriceComposition::ExchangeRate
+    }
+
+    pub fn has_lst_collateral(&self) -> bool {
+        self.lst_mint != Pubkey::default()
+    }
+
+    /// Mint a user may escrow for `strategy`: the intent's quote mint for
+    /// puts, the underlying (or the configured LST) for covered calls
+    pub fn is_collateral_mint(&self, strategy: StrategyType, quote_mint: Pubkey, mint: Pubkey) -> bool {
+        match strategy {
+            StrategyType::CoveredCall => {
+                mint == self.asset_mint || (self.has_lst_collateral() && mint == self.lst_mint)
+            }
+            StrategyType::CashSecuredPut => mint == quote_mint && self.accepts_quote_mint(mint),
+        }
+    }
+
+    /// Switchboard backs up the primary source when configured separately
+    pub fn has_switchboard_fallback(&self) -> bool {
+        self.oracle_source != OracleSource::Switchboard && self.has_switchboard_feed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(asset_decimals: u8, quote_decimals: u8, price_exponent: i32) -> SettlementTerms {
+        SettlementTerms {
+            kind: SettlementKind::Cash,
+            min_itm_bps: 0,
+            asset_decimals,
+            quote_decimals,
+            price_exponent,
+            collateral_rate: None,
+        }
+    }
+
+    #[test]
+    fn test_quote_value_positive_shift() {
+        // Whole-unit asset priced in cents against USDC: shift of 10^4
+        let terms = terms(0, 6, -2);
+        assert_eq!(terms.quote_value(3, 12_345, false), Some(370_350_000));
+        assert_eq!(terms.quote_value(3, 12_345, true), Some(370_350_000));
+        assert_eq!(terms.underlying_value(370_350_000, 12_345), Some(3));
+        // Short of a whole unit rounds down
+        assert_eq!(terms.underlying_value(370_349_999, 12_345), Some(2));
+    }
+
+    #[test]
+    fn test_quote_value_negative_shift_rounding() {
+        // SOL (9 decimals) at a 1e-8 Pyth price against USDC: shift of 10^-11
+        let terms = terms(9, 6, -8);
+        let (size, price) = (1_500_000_000, 15_012_345_678);
+        assert_eq!(terms.quote_value(size, price, false), Some(225_185_185));
+        assert_eq!(terms.quote_value(size, price, true), Some(225_185_186));
+        // Exact values are the same either way
+        assert_eq!(terms.quote_value(1_000_000_000, 100_000_000, true), Some(1_000_000));
+        assert_eq!(terms.underlying_value(225_185_185, price), Some(1_499_999_998));
+    }
+
+    #[test]
+    fn test_value_overflow_is_none() {
+        assert_eq!(terms(0, 6, -2).quote_value(u64::MAX, u64::MAX, false), None);
+        assert_eq!(terms(9, 6, -8).quote_value(u64::MAX, u64::MAX, true), None);
+        assert_eq!(terms(9, 6, -8).underlying_value(u64::MAX, 1), None);
+        // Scales past 10^38 do not fit in u128
+        assert_eq!(terms(0, 6, 40).quote_value(1, 1, false), None);
+        assert_eq!(terms(0, 6, 40).underlying_value(1, 1), None);
+        assert_eq!(terms(9, 6, -8).underlying_value(1, 0), None);
+    }
 }
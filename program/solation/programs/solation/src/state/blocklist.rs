@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_BLOCKLIST_ENTRIES;
+use super::Position;
+
+/// Sanctioned addresses, managed by the protocol authority.
+/// Blocked keys cannot submit or fill intents, and settlement payouts owed
+/// to them are withheld in the position vault until compliance review clears
+/// them.
+#[account]
+pub struct Blocklist {
+    /// Blocked wallets (users, MMs or payout beneficiaries)
+    pub addresses: Vec<Pubkey>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Blocklist {
+    pub const LEN: usize = 8 +   // discriminator
+        4 + 32 * MAX_BLOCKLIST_ENTRIES +  // addresses
+        1;    // bump
+
+    pub fn is_blocked(&self, key: Pubkey) -> bool {
+        self.addresses.contains(&key)
+    }
+
+    /// Whether the position's holder or their payout beneficiary is blocked
+    pub fn blocks_user_side(&self, position: &Position) -> bool {
+        self.is_blocked(position.holder) || self.is_blocked(position.user_payout_owner())
+    }
+
+    /// Whether the position's MM or its payout beneficiary is blocked
+    pub fn blocks_mm_side(&self, position: &Position) -> bool {
+        self.is_blocked(position.market_maker) || self.is_blocked(position.mm_payout_owner())
+    }
+
+    pub fn blocks_either_side(&self, position: &Position) -> bool {
+        self.blocks_user_side(position) || self.blocks_mm_side(position)
+    }
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_COMPOSERS;
+
+/// Programs allowed to invoke intent submission and fills via CPI, managed
+/// by the protocol authority. Top-level calls are unaffected.
+#[account]
+pub struct ComposerAllowlist {
+    /// Allowlisted composer program IDs (e.g. structured-product vaults)
+    pub programs: Vec<Pubkey>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ComposerAllowlist {
+    pub const LEN: usize = 8 +   // discriminator
+        4 + 32 * MAX_COMPOSERS +  // programs
+        1;    // bump
+
+    pub fn is_allowed(&self, program_id: Pubkey) -> bool {
+        self.programs.contains(&program_id)
+    }
+}
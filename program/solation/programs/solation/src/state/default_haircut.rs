@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::BASIS_POINTS_DIVISOR;
+
+/// Socialized loss for one MM's defaults at one expiry, in one quote mint.
+/// The authority registers each defaulted position's shortfall; finalizing
+/// fixes the share the insurance fund can cover, and every registered
+/// position is then paid the same pro-rata fraction of what it was owed.
+#[account]
+pub struct DefaultHaircut {
+    /// Defaulting market maker
+    pub market_maker: Pubkey,
+    /// Expiry of the defaulted positions
+    pub expiry_timestamp: i64,
+    /// Quote mint the shortfall is owed in
+    pub quote_mint: Pubkey,
+    /// Sum of registered shortfalls
+    pub total_owed: u64,
+    /// Portion of total_owed the insurance fund covers (set on finalize)
+    pub covered_amount: u64,
+    /// Reduction applied to every payout, in bps (set on finalize)
+    pub haircut_bps: u16,
+    /// Positions registered
+    pub claims_registered: u32,
+    /// Positions paid out
+    pub claims_paid: u32,
+    /// Paid out so far
+    pub total_paid: u64,
+    /// Whether the covered share has been fixed
+    pub finalized: bool,
+    /// When the haircut was finalized
+    pub finalized_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl DefaultHaircut {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // market_maker
+        8 +   // expiry_timestamp
+        32 +  // quote_mint
+        8 +   // total_owed
+        8 +   // covered_amount
+        2 +   // haircut_bps
+        4 +   // claims_registered
+        4 +   // claims_paid
+        8 +   // total_paid
+        1 +   // finalized
+        8 +   // finalized_at
+        1;    // bump
+
+    /// Pro-rata payout for a position that was owed `owed`, rounded down so
+    /// the sum of payouts never exceeds `covered_amount`
+    pub fn payout_for(&self, owed: u64) -> Option<u64> {
+        if self.total_owed == 0 {
+            return Some(0);
+        }
+        let payout = (owed as u128)
+            .checked_mul(self.covered_amount as u128)?
+            .checked_div(self.total_owed as u128)?;
+        u64::try_from(payout).ok()
+    }
+
+    /// Haircut implied by covering `covered` of `owed`, in bps
+    pub fn haircut_bps_for(covered: u64, owed: u64) -> u16 {
+        if owed == 0 {
+            return 0;
+        }
+        let paid_bps = (covered as u128) * (BASIS_POINTS_DIVISOR as u128) / (owed as u128);
+        (BASIS_POINTS_DIVISOR as u128).saturating_sub(paid_bps) as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn haircut(total_owed: u64, covered_amount: u64) -> DefaultHaircut {
+        DefaultHaircut {
+            market_maker: Pubkey::default(),
+            expiry_timestamp: 0,
+            quote_mint: Pubkey::default(),
+            total_owed,
+            covered_amount,
+            haircut_bps: DefaultHaircut::haircut_bps_for(covered_amount, total_owed),
+            claims_registered: 0,
+            claims_paid: 0,
+            total_paid: 0,
+            finalized: true,
+            finalized_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_payout_rounds_down_within_cover() {
+        let haircut = haircut(3_000, 1_000);
+        assert_eq!(haircut.payout_for(1_001), Some(333));
+        let paid: u64 = [1_000, 1_000, 1_000]
+            .iter()
+            .map(|owed| haircut.payout_for(*owed).unwrap())
+            .sum();
+        assert_eq!(paid, 999);
+        assert_eq!(haircut.haircut_bps, 6_667);
+    }
+
+    #[test]
+    fn test_payout_with_nothing_owed() {
+        assert_eq!(haircut(0, 0).payout_for(1_000), Some(0));
+        assert_eq!(haircut(1_000, 1_000).payout_for(1_000), Some(1_000));
+    }
+
+    #[test]
+    fn test_payout_overflow_is_none() {
+        // A claim larger than the registered total cannot pay past u64
+        assert_eq!(haircut(1, u64::MAX).payout_for(u64::MAX), None);
+    }
+}
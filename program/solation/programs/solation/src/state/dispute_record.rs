@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_EVIDENCE_ENTRIES, MAX_EVIDENCE_PER_PARTY, MAX_EVIDENCE_URI_LEN};
+
+/// A reference to off-chain evidence: a content hash plus where to fetch it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EvidenceEntry {
+    /// User or MM who submitted the entry
+    pub submitted_by: Pubkey,
+    /// Hash of the evidence document
+    pub content_hash: [u8; 32],
+    /// Where the document can be retrieved (IPFS/Arweave/HTTPS)
+    pub uri: String,
+    /// When the entry was appended
+    pub submitted_at: i64,
+}
+
+impl EvidenceEntry {
+    pub const LEN: usize = 32 +  // submitted_by
+        32 +  // content_hash
+        4 + MAX_EVIDENCE_URI_LEN +  // uri
+        8;    // submitted_at
+}
+
+/// Evidence log for a disputed intent, created when the dispute is flagged.
+/// Both parties append entries (statement and counter-statement) until the
+/// resolver acts.
+#[account]
+pub struct DisputeRecord {
+    /// Disputed intent
+    pub intent: Pubkey,
+    /// Intent's user
+    pub user: Pubkey,
+    /// Intent's market maker
+    pub market_maker: Pubkey,
+    /// Party that flagged the dispute
+    pub flagged_by: Pubkey,
+    /// Lamports the flagger posted, held on this account until reclaimed
+    /// or forfeited
+    pub bond: u64,
+    /// Evidence in submission order
+    pub entries: Vec<EvidenceEntry>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl DisputeRecord {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // intent
+        32 +  // user
+        32 +  // market_maker
+        32 +  // flagged_by
+        8 +   // bond
+        4 + EvidenceEntry::LEN * MAX_EVIDENCE_ENTRIES +  // entries
+        1;    // bump
+
+    pub fn is_party(&self, key: Pubkey) -> bool {
+        key == self.user || key == self.market_maker
+    }
+
+    /// The party on the other side of the flag
+    pub fn counterparty(&self) -> Pubkey {
+        if self.flagged_by == self.user {
+            self.market_maker
+        } else {
+            self.user
+        }
+    }
+
+    /// Whether `party` may append another entry
+    pub fn can_submit(&self, party: Pubkey) -> bool {
+        let submitted = self
+            .entries
+            .iter()
+            .filter(|entry| entry.submitted_by == party)
+            .count();
+        submitted < MAX_EVIDENCE_PER_PARTY && self.entries.len() < MAX_EVIDENCE_ENTRIES
+    }
+}
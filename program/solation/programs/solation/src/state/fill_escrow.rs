@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+/// First leg of a two-phase fill: the MM's premium is held by the program
+/// until the MM also locks its collateral, at which point the position opens.
+/// Unwound (premium back to the MM, intent pending again) if the second leg
+/// is not completed in time.
+#[account]
+pub struct FillEscrow {
+    /// Intent being filled
+    pub intent: Pubkey,
+    /// MM filling the intent
+    pub market_maker: Pubkey,
+    /// Premium owed to the user, held in the premium escrow
+    pub premium_amount: u64,
+    /// Mint of the MM collateral locked in the second leg
+    pub collateral_mint: Pubkey,
+    /// MM collateral required to open the position (0 for cash-settled assets)
+    pub collateral_amount: u64,
+    /// Second leg must land by this time, after which anyone may unwind
+    pub complete_by: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl FillEscrow {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // intent
+        32 +  // market_maker
+        8 +   // premium_amount
+        32 +  // collateral_mint
+        8 +   // collateral_amount
+        8 +   // complete_by
+        1;    // bump
+}
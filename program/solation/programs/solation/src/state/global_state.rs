@@ -1,23 +1,142 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{MAX_ARBITERS, RESUME_STAGES};
+use crate::errors::ErrorCode;
+
 #[account]
 pub struct GlobalState {
     pub authority: Pubkey,        // Program admin
+    pub pauser: Pubkey,           // May pause/unpause individual assets
+    pub dispute_resolver: Pubkey, // Operational key for owner-override dispute resolution
+    pub arbiters: Vec<Pubkey>,     // Arbitration committee (up to MAX_ARBITERS)
+    pub arbitration_threshold: u8, // Votes needed per resolution (0 = committee mode off)
     pub treasury: Pubkey,          // Fee recipient
     pub protocol_fee_bps: u16,     // Protocol fee in basis points (0 for MVP)
     pub paused: bool,              // Emergency pause flag
     pub total_volume: u64,         // Total volume traded
     pub total_positions: u64,      // Total positions created
+    pub settlement_dispute_window: i64, // Seconds after a price snapshot during which it can be disputed (0 = off)
+    pub enabled_operations: u8,    // Bitmask of OPERATION_* families currently allowed
+    pub timelock_delay: i64,       // Seconds a queued parameter change waits before execution
+    pub pending_change_count: u64, // Changes queued so far (next change_id)
+    pub total_open_notional: u64,  // Strike notional of all open positions, in quote units
+    pub max_open_notional: u64,    // Protocol-wide cap on total_open_notional (0 = uncapped)
+    pub insurance_fee_share_bps: u16, // Slice of protocol fees routed to the insurance fund
+    pub keeper_grace_period: i64,  // Seconds a crank is reserved for registered keepers once due (0 = permissionless)
+    pub min_mm_reputation: u32,    // Reputation an MM needs for new intents (0 = no floor)
+    pub min_mm_fill_rate: u8,      // Fill rate (0-100) an MM needs for new intents (0 = no floor)
+    pub max_intent_notional: u64,  // Cap on a single intent's strike notional (0 = uncapped)
+    pub intent_fill_timeout: i64,  // Seconds an MM has to fill a new intent
+    pub min_premium: u64,          // Floor on a new intent's total premium, in quote units (0 = none)
+    pub max_pending_intents: u32,  // Intents one user may have pending at once (0 = uncapped)
+    pub total_pending_intents: u64, // Intents protocol-wide awaiting fill or resolution
+    pub max_total_pending_intents: u64, // Ceiling on total_pending_intents (0 = uncapped)
+    pub dispute_flag_cooldown: i64, // Seconds a party must wait between dispute flags (0 = none)
+    pub dispute_bond: u64,         // Lamports a party posts to flag a dispute (0 = none)
     pub bump: u8,
 }
 
 impl GlobalState {
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
+        32 + // pauser
+        32 + // dispute_resolver
+        4 + 32 * MAX_ARBITERS + // arbiters
+        1 +  // arbitration_threshold
         32 + // treasury
         2 +  // protocol_fee_bps
         1 +  // paused
         8 +  // total_volume
         8 +  // total_positions
+        8 +  // settlement_dispute_window
+        1 +  // enabled_operations
+        8 +  // timelock_delay
+        8 +  // pending_change_count
+        8 +  // total_open_notional
+        8 +  // max_open_notional
+        2 +  // insurance_fee_share_bps
+        8 +  // keeper_grace_period
+        4 +  // min_mm_reputation
+        1 +  // min_mm_fill_rate
+        8 +  // max_intent_notional
+        8 +  // intent_fill_timeout
+        8 +  // min_premium
+        4 +  // max_pending_intents
+        8 +  // total_pending_intents
+        8 +  // max_total_pending_intents
+        8 +  // dispute_flag_cooldown
+        8 +  // dispute_bond
         1;   // bump
+
+    /// Dispute handling is open to the resolver key and the authority
+    pub fn can_resolve_disputes(&self, key: Pubkey) -> bool {
+        key == self.dispute_resolver || key == self.authority
+    }
+
+    /// Whether owner overrides need committee approval
+    pub fn committee_mode(&self) -> bool {
+        self.arbitration_threshold > 0
+    }
+
+    pub fn is_arbiter(&self, key: Pubkey) -> bool {
+        self.arbiters.contains(&key)
+    }
+
+    /// Whether every family in `operations` is currently enabled
+    pub fn operations_enabled(&self, operations: u8) -> bool {
+        self.enabled_operations & operations == operations
+    }
+
+    /// Pause the protocol and disable every instruction family
+    pub fn halt(&mut self) {
+        self.paused = true;
+        self.enabled_operations = 0;
+    }
+
+    /// Count new open interest, enforcing the protocol-wide cap
+    pub fn record_notional_opened(&mut self, notional: u64) -> Result<()> {
+        let total = self
+            .total_open_notional
+            .checked_add(notional)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            self.max_open_notional == 0 || total <= self.max_open_notional,
+            ErrorCode::OpenInterestCapExceeded
+        );
+        self.total_open_notional = total;
+        Ok(())
+    }
+
+    pub fn record_notional_closed(&mut self, notional: u64) {
+        self.total_open_notional = self.total_open_notional.saturating_sub(notional);
+    }
+
+    /// Count a new pending intent, failing fast once the protocol-wide
+    /// ceiling is reached
+    pub fn record_intent_opened(&mut self) -> Result<()> {
+        require!(
+            self.max_total_pending_intents == 0
+                || self.total_pending_intents < self.max_total_pending_intents,
+            ErrorCode::ProtocolSaturated
+        );
+        self.total_pending_intents = self.total_pending_intents.saturating_add(1);
+        Ok(())
+    }
+
+    pub fn record_intent_closed(&mut self) {
+        self.total_pending_intents = self.total_pending_intents.saturating_sub(1);
+    }
+
+    /// Whether a crank that became due at `due_at` is still reserved for
+    /// registered keepers at `now`
+    pub fn keeper_only(&self, due_at: i64, now: i64) -> bool {
+        self.keeper_grace_period > 0 && now < due_at.saturating_add(self.keeper_grace_period)
+    }
+
+    /// Next family to re-enable after a shutdown, in resume order
+    pub fn next_resume_stage(&self) -> Option<u8> {
+        RESUME_STAGES
+            .into_iter()
+            .find(|&stage| !self.operations_enabled(stage))
+    }
 }
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_HEDGE_ADAPTERS;
+
+/// Adapter programs an MM may hedge a fill through, managed by the protocol
+/// authority. Each adapter fronts one perp venue and implements `open_hedge`.
+#[account]
+pub struct HedgeAdapterAllowlist {
+    /// Allowlisted adapter program IDs
+    pub programs: Vec<Pubkey>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl HedgeAdapterAllowlist {
+    pub const LEN: usize = 8 +   // discriminator
+        4 + 32 * MAX_HEDGE_ADAPTERS +  // programs
+        1;    // bump
+
+    pub fn is_allowed(&self, program_id: Pubkey) -> bool {
+        self.programs.contains(&program_id)
+    }
+}
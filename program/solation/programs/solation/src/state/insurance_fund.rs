@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+/// Backstop for one quote mint, used to make users whole when an MM defaults
+/// on its settlement obligations. Funded by a slice of protocol fees and by
+/// MM (or any other) deposits; paid out only by the authority.
+#[account]
+pub struct InsuranceFund {
+    /// Mint the fund holds and pays out in
+    pub quote_mint: Pubkey,
+    /// Token account holding the fund (authority = this PDA)
+    pub vault: Pubkey,
+    /// Protocol fees accrued from the treasury
+    pub total_fee_accruals: u64,
+    /// Deposits made by registered MMs
+    pub total_mm_contributions: u64,
+    /// Deposits from anyone else (protocol top-ups, donations)
+    pub total_other_deposits: u64,
+    /// Paid out on claims
+    pub total_claims_paid: u64,
+    /// Number of claims paid
+    pub claims_count: u64,
+    /// Balance set aside for finalized haircuts, not available to other claims
+    pub reserved_for_haircuts: u64,
+    /// PDA bump
+    pub bump: u8,
+    /// Vault PDA bump
+    pub vault_bump: u8,
+}
+
+impl InsuranceFund {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // quote_mint
+        32 +  // vault
+        8 +   // total_fee_accruals
+        8 +   // total_mm_contributions
+        8 +   // total_other_deposits
+        8 +   // total_claims_paid
+        8 +   // claims_count
+        8 +   // reserved_for_haircuts
+        1 +   // bump
+        1;    // vault_bump
+}
+
+impl InsuranceFund {
+    /// Vault balance not reserved for finalized haircuts
+    pub fn available(&self, vault_balance: u64) -> u64 {
+        vault_balance.saturating_sub(self.reserved_for_haircuts)
+    }
+}
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+use super::PositionTerms;
+
 /// Option strategy types
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum StrategyType {
@@ -9,6 +12,13 @@ pub enum StrategyType {
     CashSecuredPut = 1,
 }
 
+impl StrategyType {
+    /// Bit for this strategy in an AssetConfig strategy whitelist
+    pub const fn mask(self) -> u8 {
+        1 << self as u8
+    }
+}
+
 /// Status of an intent in the system
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum IntentStatus {
@@ -28,6 +38,45 @@ pub enum IntentStatus {
     ResolvedToMM,
     /// Owner split the escrow
     ResolvedSplit,
+    /// MM escrowed the premium, collateral leg outstanding
+    FillInProgress,
+}
+
+/// Owner-override action that resolved an intent
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResolutionKind {
+    MutualUnwind,
+    ForceContinue,
+    ForceSettleNow,
+    EscrowToTreasury,
+    ProportionalSplit,
+}
+
+/// Outcome of an owner-override resolution, kept on the intent so it can be
+/// audited from account state alone
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ResolutionRecord {
+    /// Which override was applied
+    pub kind: ResolutionKind,
+    /// Amount paid to the user
+    pub user_amount: u64,
+    /// Amount paid to the MM
+    pub mm_amount: u64,
+    /// Amount moved to the treasury
+    pub treasury_amount: u64,
+    /// Key that signed the resolution
+    pub resolver: Pubkey,
+    /// When the resolution was applied
+    pub resolved_at: i64,
+}
+
+impl ResolutionRecord {
+    pub const LEN: usize = 1 +   // kind
+        8 +   // user_amount
+        8 +   // mm_amount
+        8 +   // treasury_amount
+        32 +  // resolver
+        8;    // resolved_at
 }
 
 /// Intent account - represents a user's intent to open a position based on an off-chain quote
@@ -61,6 +110,8 @@ pub struct Intent {
     pub quote_signature: [u8; 64],
     /// Nonce to prevent replay attacks
     pub quote_nonce: u64,
+    /// Implied volatility the MM signed the quote at, in basis points
+    pub implied_volatility_bps: u32,
     
     // Escrow state
     /// User's escrow PDA holding locked funds
@@ -73,23 +124,29 @@ pub struct Intent {
     pub created_at: i64,
     /// MM must fill by this time
     pub fill_deadline: i64,
+    /// Oracle spot when the intent was created, for the fill-time price band
+    pub reference_price: u64,
+    /// Shortfall the user accepts, in bps, on premium paid in the underlying
+    /// at oracle spot (None = quote mint only)
+    pub underlying_premium_slippage_bps: Option<u16>,
     
-    // Dispute tracking
-    /// Who flagged the dispute (if any)
-    pub disputed_by: Option<Pubkey>,
-    /// Reason for dispute
-    pub dispute_reason: Option<String>,
+    /// Outcome of the owner-override resolution (if any)
+    pub resolution: Option<ResolutionRecord>,
     
     /// Current status
     pub status: IntentStatus,
     /// PDA bump
     pub bump: u8,
+
+    // Dispute tracking, last so the account only grows when a dispute is flagged
+    /// Who flagged the dispute (if any)
+    pub disputed_by: Option<Pubkey>,
+    /// Hash of the off-chain dispute statement (URI is in the DisputeFlagged event)
+    pub dispute_reason_hash: Option<[u8; 32]>,
 }
 
 impl Intent {
-    /// Maximum length for dispute reason string
-    pub const MAX_DISPUTE_REASON_LEN: usize = 200;
-    
+    /// Size with no dispute recorded
     pub const LEN: usize = 8 +   // discriminator
         8 +   // intent_id
         32 +  // user
@@ -103,14 +160,23 @@ impl Intent {
         8 +   // quote_expiry
         64 +  // quote_signature
         8 +   // quote_nonce
+        4 +   // implied_volatility_bps
         32 +  // user_escrow
         8 +   // escrow_amount
         8 +   // created_at
         8 +   // fill_deadline
-        1 + 32 +  // disputed_by (Option<Pubkey>)
-        4 + Self::MAX_DISPUTE_REASON_LEN +  // dispute_reason (Option<String>)
+        8 +   // reference_price
+        1 + 2 +  // underlying_premium_slippage_bps
+        1 + ResolutionRecord::LEN +  // resolution (Option<ResolutionRecord>)
         1 +   // status
-        1;    // bump
+        1 +   // bump
+        1 +   // disputed_by (None)
+        1;    // dispute_reason_hash (None)
+
+    /// Size once a dispute is flagged (account is reallocated to this)
+    pub const DISPUTED_LEN: usize = Self::LEN +
+        32 +  // disputed_by payload
+        32;   // dispute_reason_hash payload
 
     pub fn is_pending(&self) -> bool {
         self.status == IntentStatus::Pending
@@ -128,7 +194,26 @@ impl Intent {
         matches!(self.status, IntentStatus::Pending | IntentStatus::Disputed)
     }
 
-    pub fn calculate_total_premium(&self) -> u64 {
-        self.premium_per_contract.saturating_mul(self.contract_size)
+    /// Terms of the position a fill of this intent opens
+    pub fn position_terms(&self) -> PositionTerms {
+        PositionTerms {
+            position_id: self.intent_id,
+            user: self.user,
+            market_maker: self.market_maker,
+            strategy: self.strategy,
+            asset_mint: self.asset_mint,
+            quote_mint: self.quote_mint,
+            strike_price: self.strike_price,
+            contract_size: self.contract_size,
+            expiry_timestamp: self.quote_expiry,
+            implied_volatility_bps: self.implied_volatility_bps,
+            user_vault: self.user_escrow,
+        }
+    }
+
+    pub fn calculate_total_premium(&self) -> Result<u64> {
+        self.premium_per_contract
+            .checked_mul(self.contract_size)
+            .ok_or(error!(ErrorCode::MathOverflow))
     }
 }
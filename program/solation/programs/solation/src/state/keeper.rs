@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+/// Registered crank operator. While GlobalState.keeper_grace_period is set,
+/// expirations, settlements and trigger executions are reserved for active
+/// keepers until the grace period after they fall due.
+#[account]
+pub struct Keeper {
+    /// Keeper's signing wallet
+    pub keeper: Pubkey,
+    /// Whether the keeper may run reserved cranks
+    pub active: bool,
+    /// Intents expired by this keeper
+    pub intents_expired: u64,
+    /// Positions settled by this keeper
+    pub positions_settled: u64,
+    /// Triggers executed by this keeper
+    pub triggers_executed: u64,
+    /// Last time this keeper cranked
+    pub last_active: i64,
+    /// When this keeper was registered
+    pub registered_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Keeper {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // keeper
+        1 +   // active
+        8 +   // intents_expired
+        8 +   // positions_settled
+        8 +   // triggers_executed
+        8 +   // last_active
+        8 +   // registered_at
+        1;    // bump
+
+    pub fn record_expiration(&mut self, timestamp: i64) {
+        self.intents_expired = self.intents_expired.saturating_add(1);
+        self.last_active = timestamp;
+    }
+
+    pub fn record_settlements(&mut self, count: u64, timestamp: i64) {
+        self.positions_settled = self.positions_settled.saturating_add(count);
+        self.last_active = timestamp;
+    }
+
+    pub fn record_trigger(&mut self, timestamp: i64) {
+        self.triggers_executed = self.triggers_executed.saturating_add(1);
+        self.last_active = timestamp;
+    }
+}
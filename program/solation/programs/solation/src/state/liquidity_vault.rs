@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+
+use super::VaultFees;
+use crate::constants::BASIS_POINTS_DIVISOR;
+use crate::errors::ErrorCode;
+
+/// Passive liquidity pool for one quote mint. LPs hold share tokens against
+/// the idle balance plus what whitelisted MMs owe it: collateral drawn into
+/// escrowed fills and the premium share charged on each draw. Withdrawals
+/// are queued and paid once the request's epoch has ended.
+#[account]
+pub struct LiquidityVault {
+    pub quote_mint: Pubkey,
+    /// LP share token, minted by the vault PDA
+    pub share_mint: Pubkey,
+    /// Share of a backed fill's premium owed to LPs, in basis points
+    pub premium_share_bps: u16,
+    /// Cap on what MMs may owe, in basis points of the vault's value
+    pub max_utilization_bps: u16,
+    /// Cap on the vault's value in quote units (0 = uncapped)
+    pub deposit_cap: u64,
+    /// Collateral drawn and premium share charged, not yet paid back
+    pub outstanding: u64,
+    /// Premium share charged to MMs across all draws
+    pub total_premium_shared: u64,
+    /// Current withdrawal epoch; requests made in it are paid after it ends
+    pub epoch: u64,
+    pub epoch_started_at: i64,
+    pub epoch_duration: i64,
+    /// Shares escrowed by queued withdrawal requests
+    pub pending_withdrawal_shares: u64,
+    /// Treasury fees, charged as each epoch ends
+    pub fees: VaultFees,
+    /// PDA bump
+    pub bump: u8,
+    pub share_mint_bump: u8,
+}
+
+impl LiquidityVault {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // quote_mint
+        32 +  // share_mint
+        2 +   // premium_share_bps
+        2 +   // max_utilization_bps
+        8 +   // deposit_cap
+        8 +   // outstanding
+        8 +   // total_premium_shared
+        8 +   // epoch
+        8 +   // epoch_started_at
+        8 +   // epoch_duration
+        8 +   // pending_withdrawal_shares
+        VaultFees::LEN + // fees
+        1 +   // bump
+        1;    // share_mint_bump
+
+    /// Vault value in quote units: the idle balance plus what MMs owe
+    pub fn net_asset_value(&self, idle_balance: u64) -> Option<u64> {
+        idle_balance.checked_add(self.outstanding)
+    }
+
+    /// Shares to mint for `deposit` into a vault worth `nav`; the first
+    /// deposit mints one share per quote unit. None once outstanding shares
+    /// are worth nothing.
+    pub fn shares_for_deposit(deposit: u64, nav: u64, share_supply: u64) -> Option<u64> {
+        if share_supply == 0 {
+            return Some(deposit);
+        }
+        if nav == 0 {
+            return None;
+        }
+        u64::try_from(deposit as u128 * share_supply as u128 / nav as u128).ok()
+    }
+
+    /// Quote redeemed by `shares` of a vault worth `nav`
+    pub fn redemption_amount(nav: u64, shares: u64, share_supply: u64) -> Option<u64> {
+        if share_supply == 0 {
+            return None;
+        }
+        u64::try_from(nav as u128 * shares as u128 / share_supply as u128).ok()
+    }
+
+    pub fn epoch_ended(&self, now: i64) -> bool {
+        now >= self.epoch_started_at.saturating_add(self.epoch_duration)
+    }
+
+    /// Record `amount` drawn plus its premium share `fee` out of
+    /// `idle_balance`, keeping what MMs owe under the utilization cap
+    pub fn record_draw(&mut self, amount: u64, fee: u64, idle_balance: u64) -> Result<()> {
+        let nav = self
+            .net_asset_value(idle_balance)
+            .and_then(|nav| nav.checked_add(fee))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let outstanding = self
+            .outstanding
+            .checked_add(amount)
+            .and_then(|owed| owed.checked_add(fee))
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            outstanding as u128 * BASIS_POINTS_DIVISOR as u128
+                <= nav as u128 * self.max_utilization_bps as u128,
+            ErrorCode::LiquidityUtilized
+        );
+        self.outstanding = outstanding;
+        self.total_premium_shared = self.total_premium_shared.saturating_add(fee);
+        Ok(())
+    }
+
+    /// Record `amount` paid back by an MM
+    pub fn record_repayment(&mut self, amount: u64) {
+        self.outstanding = self.outstanding.saturating_sub(amount);
+    }
+}
+
+/// An MM's credit line on a liquidity vault. Its existence is the whitelist;
+/// a zero limit suspends new draws.
+#[account]
+pub struct LpBacking {
+    pub vault: Pubkey,
+    pub market_maker: Pubkey,
+    /// Most the MM may owe the vault at once, in quote units
+    pub credit_limit: u64,
+    /// Collateral drawn and not yet returned
+    pub drawn: u64,
+    /// Premium share charged and not yet paid
+    pub fees_owed: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl LpBacking {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // vault
+        32 +  // market_maker
+        8 +   // credit_limit
+        8 +   // drawn
+        8 +   // fees_owed
+        1;    // bump
+
+    pub fn owed(&self) -> u64 {
+        self.drawn.saturating_add(self.fees_owed)
+    }
+
+    /// Record a draw of `amount` charged `fee`, within the credit limit
+    pub fn record_draw(&mut self, amount: u64, fee: u64) -> Result<()> {
+        let owed = self
+            .owed()
+            .checked_add(amount)
+            .and_then(|owed| owed.checked_add(fee))
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(owed <= self.credit_limit, ErrorCode::CreditLimitExceeded);
+        self.drawn += amount;
+        self.fees_owed += fee;
+        Ok(())
+    }
+
+    /// Apply `amount` of returned collateral against the draw; returns the
+    /// part that reduced what the MM owes
+    pub fn record_return(&mut self, amount: u64) -> u64 {
+        let applied = amount.min(self.drawn);
+        self.drawn -= applied;
+        applied
+    }
+
+    /// Apply an MM repayment of up to `amount`, fees first; returns the part
+    /// applied
+    pub fn record_repayment(&mut self, amount: u64) -> u64 {
+        let to_fees = amount.min(self.fees_owed);
+        self.fees_owed -= to_fees;
+        let to_drawn = (amount - to_fees).min(self.drawn);
+        self.drawn -= to_drawn;
+        to_fees + to_drawn
+    }
+}
+
+/// An LP's queued withdrawal; its shares sit in the vault's share escrow
+#[account]
+pub struct LpWithdrawal {
+    pub vault: Pubkey,
+    pub lp: Pubkey,
+    pub shares: u64,
+    /// Epoch the request was made in; payable once it has ended
+    pub epoch: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl LpWithdrawal {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // vault
+        32 +  // lp
+        8 +   // shares
+        8 +   // epoch
+        1;    // bump
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_deposit_mints_one_share_per_unit() {
+        assert_eq!(LiquidityVault::shares_for_deposit(1_000, 0, 0), Some(1_000));
+    }
+
+    #[test]
+    fn test_shares_round_down_for_existing_holders() {
+        // 100 into a vault worth 300 with 200 shares out is 66.67 shares
+        assert_eq!(LiquidityVault::shares_for_deposit(100, 300, 200), Some(66));
+        assert_eq!(LiquidityVault::shares_for_deposit(150, 300, 200), Some(100));
+    }
+
+    #[test]
+    fn test_shares_without_value_or_overflowing_are_none() {
+        assert_eq!(LiquidityVault::shares_for_deposit(100, 0, 200), None);
+        assert_eq!(LiquidityVault::shares_for_deposit(u64::MAX, 1, u64::MAX), None);
+    }
+}
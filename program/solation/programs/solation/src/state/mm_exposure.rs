@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// MM collateral locked in position MM vaults for one asset. Locked on
+/// complete_escrowed_fill, unlocked as the vault pays physical delivery or
+/// is released back to the MM. Free collateral is the MM vault balance.
+#[account]
+pub struct MMExposure {
+    /// MM registry owner
+    pub market_maker: Pubkey,
+    /// Asset the positions are written on
+    pub asset_mint: Pubkey,
+    /// Quote locked (covered calls' strike proceeds)
+    pub locked_quote: u64,
+    /// Underlying locked (cash-secured puts' delivery)
+    pub locked_underlying: u64,
+    /// Positions with a funded MM vault
+    pub locked_positions: u64,
+    /// A fill on this book locked less than its gross delivery under
+    /// portfolio margin; until the book empties, releases hold back what the
+    /// rest of it needs
+    pub portfolio_margined: bool,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl MMExposure {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // market_maker
+        32 +  // asset_mint
+        8 +   // locked_quote
+        8 +   // locked_underlying
+        8 +   // locked_positions
+        1 +   // portfolio_margined
+        1;    // bump
+
+    fn locked_mut(&mut self, collateral_mint: Pubkey) -> &mut u64 {
+        if collateral_mint == self.asset_mint {
+            &mut self.locked_underlying
+        } else {
+            &mut self.locked_quote
+        }
+    }
+
+    /// Count `amount` of `collateral_mint` newly locked for a position
+    pub fn record_lock(&mut self, collateral_mint: Pubkey, amount: u64) -> Result<()> {
+        let locked = self.locked_mut(collateral_mint);
+        *locked = locked.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        self.locked_positions = self.locked_positions.saturating_add(1);
+        Ok(())
+    }
+
+    /// Count `amount` leaving a position MM vault (delivered or released)
+    pub fn record_unlock(&mut self, collateral_mint: Pubkey, amount: u64) {
+        let locked = self.locked_mut(collateral_mint);
+        *locked = locked.saturating_sub(amount);
+    }
+
+    /// A position's MM vault was emptied and closed
+    pub fn record_release(&mut self, collateral_mint: Pubkey, amount: u64) {
+        self.record_unlock(collateral_mint, amount);
+        self.locked_positions = self.locked_positions.saturating_sub(1);
+        if self.locked_positions == 0 {
+            self.portfolio_margined = false;
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+
 /// Market Maker Registry - on-chain registration of MMs with their signing keys
 #[account]
 pub struct MMRegistry {
@@ -17,6 +19,25 @@ pub struct MMRegistry {
     pub total_volume: u64,
     /// Reputation score (higher is better, updated by owner/backend)
     pub reputation_score: u32,
+    /// Whether the MM accepts trigger-driven early closes
+    pub unwind_enabled: bool,
+    /// Fee charged to the user on early close, in basis points of their payout
+    pub unwind_fee_bps: u16,
+    /// Bot key allowed to fill on the MM's behalf (default = none). It can
+    /// spend from the MM vault only through fills, never withdraw.
+    pub fill_operator: Pubkey,
+    /// Whitelisted by the risk admin for stress-scenario collateral
+    pub portfolio_margin: bool,
+    /// Quote deposited into the insurance fund by this MM
+    pub insurance_contributed: u64,
+    /// Insurance paid out to users this MM defaulted on
+    pub insurance_claims_against: u64,
+    /// Disputes this MM has flagged on intents quoted to it
+    pub disputes_flagged: u64,
+    /// When this MM last flagged a dispute
+    pub last_dispute_flagged_at: i64,
+    /// Flagged disputes the resolver ruled frivolous, forfeiting the bond
+    pub frivolous_disputes: u64,
     /// Last time this MM was active
     pub last_active: i64,
     /// When this MM registered
@@ -34,6 +55,15 @@ impl MMRegistry {
         8 +   // total_intents_expired
         8 +   // total_volume
         4 +   // reputation_score
+        1 +   // unwind_enabled
+        2 +   // unwind_fee_bps
+        32 +  // fill_operator
+        1 +   // portfolio_margin
+        8 +   // insurance_contributed
+        8 +   // insurance_claims_against
+        8 +   // disputes_flagged
+        8 +   // last_dispute_flagged_at
+        8 +   // frivolous_disputes
         8 +   // last_active
         8 +   // registered_at
         1;    // bump
@@ -47,6 +77,40 @@ impl MMRegistry {
         ((self.total_intents_filled as u128 * 100) / total as u128) as u8
     }
 
+    /// Count a dispute the MM flagged, refusing it within `cooldown` seconds
+    /// of its previous flag
+    pub fn record_dispute_flagged(&mut self, cooldown: i64, now: i64) -> Result<()> {
+        require!(
+            self.disputes_flagged == 0
+                || now >= self.last_dispute_flagged_at.saturating_add(cooldown),
+            ErrorCode::DisputeCooldownActive
+        );
+        self.disputes_flagged = self.disputes_flagged.saturating_add(1);
+        self.last_dispute_flagged_at = now;
+        Ok(())
+    }
+
+    /// Count a dispute the MM flagged that the resolver ruled frivolous
+    pub fn record_frivolous_dispute(&mut self) {
+        self.frivolous_disputes = self.frivolous_disputes.saturating_add(1);
+    }
+
+    /// Whether `signer` may fill intents quoted by this MM
+    pub fn can_fill(&self, signer: Pubkey) -> bool {
+        signer == self.owner
+            || (self.fill_operator != Pubkey::default() && signer == self.fill_operator)
+    }
+
+    /// Whether `signer` may withdraw quotes this MM has posted
+    pub fn can_quote(&self, signer: Pubkey) -> bool {
+        signer == self.owner || signer == self.signing_key
+    }
+
+    /// Whether the MM clears both a reputation and a fill-rate bar
+    pub fn meets_quality(&self, min_reputation: u32, min_fill_rate: u8) -> bool {
+        self.reputation_score >= min_reputation && self.fill_rate() >= min_fill_rate
+    }
+
     /// Update reputation based on fill/expire
     pub fn record_fill(&mut self, volume: u64, timestamp: i64) {
         self.total_intents_filled = self.total_intents_filled.saturating_add(1);
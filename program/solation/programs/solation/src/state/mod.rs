@@ -1,13 +1,69 @@
 pub mod asset_config;
+pub mod blocklist;
+pub mod composer_allowlist;
+pub mod default_haircut;
+pub mod dispute_record;
+pub mod fill_escrow;
 pub mod global_state;
+pub mod hedge_adapter_allowlist;
+pub mod insurance_fund;
 pub mod intent;
+pub mod keeper;
+pub mod liquidity_vault;
+pub mod mm_exposure;
 pub mod mm_registry;
 pub mod nonce_tracker;
+pub mod option_series;
+pub mod option_vault;
+pub mod payout_swap;
+pub mod pending_change;
+pub mod resolution_vote;
 pub mod position;
+pub mod posted_quote;
+pub mod premium_schedule;
+pub mod position_archive;
+pub mod protocol_lookup_table;
+pub mod risk_config;
+pub mod roll_preference;
+pub mod settlement_attestation;
+pub mod settlement_observations;
+pub mod standing_quote;
+pub mod tip_vault;
+pub mod user_credential;
+pub mod user_stats;
+pub mod vault_fees;
 
 pub use asset_config::*;
+pub use blocklist::*;
+pub use composer_allowlist::*;
+pub use default_haircut::*;
+pub use dispute_record::*;
+pub use fill_escrow::*;
 pub use global_state::*;
+pub use hedge_adapter_allowlist::*;
+pub use insurance_fund::*;
 pub use intent::*;
+pub use keeper::*;
+pub use liquidity_vault::*;
+pub use mm_exposure::*;
 pub use mm_registry::*;
 pub use nonce_tracker::*;
+pub use option_series::*;
+pub use option_vault::*;
+pub use payout_swap::*;
+pub use pending_change::*;
+pub use resolution_vote::*;
 pub use position::*;
+pub use posted_quote::*;
+pub use premium_schedule::*;
+pub use position_archive::*;
+pub use protocol_lookup_table::*;
+pub use risk_config::*;
+pub use roll_preference::*;
+pub use settlement_attestation::*;
+pub use settlement_observations::*;
+pub use standing_quote::*;
+pub use tip_vault::*;
+pub use user_credential::*;
+pub use user_stats::*;
+pub use vault_fees::*;
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
 
 /// Nonce tracker for preventing replay attacks on quotes
-/// Uses a bitmap to efficiently track used nonces
-#[account]
+/// Uses a bitmap to efficiently track used nonces. Zero-copy so submit_intent
+/// reads and flips bits in place instead of deserializing the account.
+#[account(zero_copy)]
 pub struct NonceTracker {
     /// Market maker this tracker belongs to
     pub market_maker: Pubkey,
@@ -13,6 +14,12 @@ pub struct NonceTracker {
     pub used_bitmap: [u8; 32],
     /// PDA bump
     pub bump: u8,
+    /// Keeps bucket_seconds 4-byte aligned with no implicit padding
+    pub _padding: [u8; 3],
+    /// Width of a time bucket for time-bounded nonces (0 = plain sequential nonces).
+    /// A time-bounded nonce is `(quote_expiry / bucket_seconds) << BUCKET_SEQUENCE_BITS | sequence`,
+    /// so once a bucket is in the past every quote signed in it has expired
+    pub bucket_seconds: u32,
 }
 
 impl NonceTracker {
@@ -22,7 +29,44 @@ impl NonceTracker {
         32 +  // market_maker
         8 +   // base_nonce
         32 +  // used_bitmap
-        1;    // bump
+        1 +   // bump
+        3 +   // _padding
+        4;    // bucket_seconds
+
+    /// Low bits of a time-bounded nonce left for the sequence within its bucket
+    pub const BUCKET_SEQUENCE_BITS: u32 = 8;
+
+    /// Whether quotes carry time-bounded nonces
+    pub fn is_time_bounded(&self) -> bool {
+        self.bucket_seconds > 0
+    }
+
+    /// First nonce of the bucket holding quotes that expire at `timestamp`
+    pub fn bucket_start(&self, timestamp: i64) -> u64 {
+        let bucket = timestamp.max(0) as u64 / self.bucket_seconds.max(1) as u64;
+        bucket << Self::BUCKET_SEQUENCE_BITS
+    }
+
+    /// Whether `nonce` falls in the bucket of a quote expiring at `quote_expiry`;
+    /// any nonce matches when nonces are not time-bounded
+    pub fn matches_expiry(&self, nonce: u64, quote_expiry: i64) -> bool {
+        !self.is_time_bounded()
+            || nonce >> Self::BUCKET_SEQUENCE_BITS
+                == self.bucket_start(quote_expiry) >> Self::BUCKET_SEQUENCE_BITS
+    }
+
+    /// Slide the window past buckets that ended before `now`. Every quote in
+    /// them has expired, so their nonces can no longer be replayed and the
+    /// bitmap space is freed for live buckets
+    pub fn expire_windows(&mut self, now: i64) {
+        if !self.is_time_bounded() {
+            return;
+        }
+        let live_from = self.bucket_start(now);
+        if live_from > self.base_nonce {
+            self.shift_window(live_from - self.base_nonce);
+        }
+    }
 
     /// Check if a nonce has been used
     pub fn is_used(&self, nonce: u64) -> bool {
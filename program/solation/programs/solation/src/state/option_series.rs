@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{SERIES_EXPIRY_INTERVAL, SERIES_EXPIRY_OFFSET};
+use super::{Position, StrategyType};
+
+/// Standardized contract: one asset, style, strike and calendar expiry.
+/// Positions opened on exactly these terms can join the series, making them
+/// fungible for open-interest tracking and batch settlement.
+#[account]
+pub struct OptionSeries {
+    pub asset_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    /// Option style
+    pub strategy: StrategyType,
+    /// Strike, on the asset's series strike grid
+    pub strike_price: u64,
+    /// Calendar expiry (see `is_calendar_expiry`)
+    pub expiry_timestamp: i64,
+    /// Active positions in the series
+    pub open_positions: u64,
+    /// Contracts across those positions
+    pub open_contracts: u64,
+    /// Strike notional across those positions, in quote units
+    pub open_notional: u64,
+    /// When the series was listed
+    pub created_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl OptionSeries {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // asset_mint
+        32 +  // quote_mint
+        1 +   // strategy
+        8 +   // strike_price
+        8 +   // expiry_timestamp
+        8 +   // open_positions
+        8 +   // open_contracts
+        8 +   // open_notional
+        8 +   // created_at
+        1;    // bump
+
+    /// Whether `timestamp` falls on the series expiry calendar (weekly,
+    /// Fridays 08:00 UTC)
+    pub fn is_calendar_expiry(timestamp: i64) -> bool {
+        (timestamp - SERIES_EXPIRY_OFFSET).rem_euclid(SERIES_EXPIRY_INTERVAL) == 0
+    }
+
+    /// Whether `position` was opened on exactly this series' terms
+    pub fn matches(&self, position: &Position) -> bool {
+        position.asset_mint == self.asset_mint
+            && position.strategy == self.strategy
+            && position.strike_price == self.strike_price
+            && position.expiry_timestamp == self.expiry_timestamp
+    }
+
+    pub fn record_opened(&mut self, contracts: u64, notional: u64) {
+        self.open_positions = self.open_positions.saturating_add(1);
+        self.open_contracts = self.open_contracts.saturating_add(contracts);
+        self.open_notional = self.open_notional.saturating_add(notional);
+    }
+
+    pub fn record_closed(&mut self, contracts: u64, notional: u64) {
+        self.open_positions = self.open_positions.saturating_sub(1);
+        self.open_contracts = self.open_contracts.saturating_sub(contracts);
+        self.open_notional = self.open_notional.saturating_sub(notional);
+    }
+
+    /// A member position shrank without closing
+    pub fn record_reduced(&mut self, contracts: u64, notional: u64) {
+        self.open_contracts = self.open_contracts.saturating_sub(contracts);
+        self.open_notional = self.open_notional.saturating_sub(notional);
+    }
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+
+use super::{SettlementTerms, StrategyType, VaultFees};
+
+/// Pooled option-writing vault for one asset and strategy. Depositors hold
+/// share tokens against the vault's balances; each epoch the vault itself
+/// writes one option through the intent flow, using the epoch as its intent
+/// ID. Covered-call vaults take the underlying, cash-secured-put vaults the
+/// quote mint; premium and any asset delivered on assignment stay in the
+/// vault and accrue to the shares. Deposits and withdrawals run in a window
+/// opened as each round's option expires and settles; outside it shares are
+/// queued and paid out when the next round finishes.
+#[account]
+pub struct OptionVault {
+    pub asset_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    /// Option the vault writes each round
+    pub strategy: StrategyType,
+    /// Share token, minted by the vault PDA
+    pub share_mint: Pubkey,
+    /// Picks the quote the vault writes against each epoch
+    pub manager: Pubkey,
+    /// Current round; also the ID of the round's intent and position
+    pub epoch: u64,
+    /// A round's intent or position is outstanding; deposits and
+    /// withdrawals wait for it to finish
+    pub round_active: bool,
+    /// Cap on the vault's value in deposit-mint units (0 = uncapped)
+    pub deposit_cap: u64,
+    /// Length of the deposit/withdraw window after each round
+    pub window_duration: i64,
+    /// End of the current window; the next round starts after it
+    pub window_closes_at: i64,
+    /// Shares queued in the current epoch, payable once its round finishes
+    pub queued_withdrawal_shares: u64,
+    /// Queued shares whose round has finished; paid before the next round
+    pub claimable_withdrawal_shares: u64,
+    /// Premium earned across all rounds, in quote units
+    pub total_premium_earned: u64,
+    /// Treasury fees, charged as each round finishes
+    pub fees: VaultFees,
+    /// PDA bump
+    pub bump: u8,
+    pub share_mint_bump: u8,
+}
+
+impl OptionVault {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // asset_mint
+        32 +  // quote_mint
+        1 +   // strategy
+        32 +  // share_mint
+        32 +  // manager
+        8 +   // epoch
+        1 +   // round_active
+        8 +   // deposit_cap
+        8 +   // window_duration
+        8 +   // window_closes_at
+        8 +   // queued_withdrawal_shares
+        8 +   // claimable_withdrawal_shares
+        8 +   // total_premium_earned
+        VaultFees::LEN + // fees
+        1 +   // bump
+        1;    // share_mint_bump
+
+    /// Deposits and instant withdrawals are open
+    pub fn window_open(&self, now: i64) -> bool {
+        !self.round_active && now < self.window_closes_at
+    }
+
+    /// Close out the round: open the next window and make this epoch's
+    /// queued withdrawals claimable
+    pub fn finish_round(&mut self, now: i64) {
+        self.round_active = false;
+        self.epoch += 1;
+        self.window_closes_at = now.saturating_add(self.window_duration);
+        self.claimable_withdrawal_shares = self
+            .claimable_withdrawal_shares
+            .saturating_add(self.queued_withdrawal_shares);
+        self.queued_withdrawal_shares = 0;
+    }
+
+    /// Mint depositors pay in and rounds escrow: the strategy's collateral
+    pub fn deposit_mint(&self) -> Pubkey {
+        match self.strategy {
+            StrategyType::CoveredCall => self.asset_mint,
+            StrategyType::CashSecuredPut => self.quote_mint,
+        }
+    }
+
+    /// Vault value in deposit-mint units, pricing the other balance at `spot_price`
+    pub fn net_asset_value(
+        &self,
+        asset_balance: u64,
+        quote_balance: u64,
+        terms: &SettlementTerms,
+        spot_price: u64,
+    ) -> Option<u64> {
+        match self.strategy {
+            StrategyType::CoveredCall => {
+                asset_balance.checked_add(terms.underlying_value(quote_balance, spot_price)?)
+            }
+            StrategyType::CashSecuredPut => {
+                quote_balance.checked_add(terms.quote_value(asset_balance, spot_price, false)?)
+            }
+        }
+    }
+
+    /// Shares to mint for `deposit` into a vault worth `nav`, both in
+    /// deposit-mint units; the first deposit mints one share per unit. None
+    /// once outstanding shares are worth nothing: no price dilutes them fairly.
+    pub fn shares_for_deposit(deposit: u64, nav: u64, share_supply: u64) -> Option<u64> {
+        if share_supply == 0 {
+            return Some(deposit);
+        }
+        if nav == 0 {
+            return None;
+        }
+        u64::try_from(deposit as u128 * share_supply as u128 / nav as u128).ok()
+    }
+
+    /// Pro-rata part of `balance` redeemed by `shares`
+    pub fn redemption_amount(balance: u64, shares: u64, share_supply: u64) -> Option<u64> {
+        if share_supply == 0 {
+            return None;
+        }
+        u64::try_from(balance as u128 * shares as u128 / share_supply as u128).ok()
+    }
+}
+
+/// A queued withdrawal from an option vault; its shares sit in the vault's
+/// share escrow until the round of `epoch` finishes
+#[account]
+pub struct VaultWithdrawal {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub shares: u64,
+    /// Epoch the request was made in
+    pub epoch: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl VaultWithdrawal {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // vault
+        32 +  // owner
+        8 +   // shares
+        8 +   // epoch
+        1;    // bump
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_deposit_mints_one_share_per_unit() {
+        assert_eq!(OptionVault::shares_for_deposit(1_000, 0, 0), Some(1_000));
+    }
+
+    #[test]
+    fn test_shares_round_down_for_existing_holders() {
+        // 100 into a vault worth 300 with 200 shares out is 66.67 shares
+        assert_eq!(OptionVault::shares_for_deposit(100, 300, 200), Some(66));
+        assert_eq!(OptionVault::shares_for_deposit(150, 300, 200), Some(100));
+    }
+
+    #[test]
+    fn test_shares_without_value_or_overflowing_are_none() {
+        assert_eq!(OptionVault::shares_for_deposit(100, 0, 200), None);
+        assert_eq!(OptionVault::shares_for_deposit(u64::MAX, 1, u64::MAX), None);
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::BASIS_POINTS_DIVISOR;
+
+/// User's standing request to have their settlement payout swapped into the
+/// position's other leg (e.g. covered-call proceeds back into the underlying)
+/// via a Jupiter route supplied by the settler.
+#[account]
+pub struct PayoutSwap {
+    /// Position whose payout is swapped
+    pub position: Pubkey,
+    /// Position user, who may withdraw the request
+    pub user: Pubkey,
+    /// Mint the payout is swapped into
+    pub output_mint: Pubkey,
+    /// Max shortfall of the swap output against the settlement price (bps)
+    pub max_slippage_bps: u16,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PayoutSwap {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // position
+        32 +  // user
+        32 +  // output_mint
+        2 +   // max_slippage_bps
+        1;    // bump
+
+    /// Least acceptable output for a swap worth `expected_out` at the settlement price
+    pub fn min_output(&self, expected_out: u64) -> u64 {
+        (expected_out as u128
+            * (BASIS_POINTS_DIVISOR - self.max_slippage_bps as u64) as u128
+            / BASIS_POINTS_DIVISOR as u128) as u64
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+/// Strike and expiry bounds of an asset, changed together through the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AssetLimits {
+    pub min_strike_percentage: u16,
+    pub max_strike_percentage: u16,
+    pub min_expiry_seconds: i64,
+    pub max_expiry_seconds: i64,
+}
+
+/// A protocol parameter update that only takes effect after the timelock delay
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParameterChange {
+    /// New protocol fee in basis points
+    ProtocolFee { fee_bps: u16 },
+    /// New fee recipient
+    Treasury { treasury: Pubkey },
+    /// New delay for subsequently queued changes
+    TimelockDelay { delay_seconds: i64 },
+    /// New strike/expiry bounds for an asset
+    AssetLimits { asset_mint: Pubkey, limits: AssetLimits },
+    /// New share of protocol fees accrued to the insurance fund
+    InsuranceFeeShare { fee_share_bps: u16 },
+    /// New window reserving cranks for registered keepers (0 = permissionless)
+    KeeperGracePeriod { grace_seconds: i64 },
+    /// New protocol-wide floor on the quality of MMs taking new intents
+    MmQualityFloor { min_reputation: u32, min_fill_rate: u8 },
+    /// New lamport bond a party posts to flag a dispute (0 = none)
+    DisputeBond { bond: u64 },
+    /// New cap on a single intent's strike notional for an asset (0 = uncapped)
+    AssetMaxIntentNotional { asset_mint: Pubkey, cap: u64 },
+    /// New contract size bounds for an asset (0 = no floor / uncapped)
+    AssetContractSize { asset_mint: Pubkey, min_contract_size: u64, max_contract_size: u64 },
+    /// New premium floor for an asset's intents, on top of the global one
+    AssetMinPremium { asset_mint: Pubkey, floor: u64 },
+    /// New lot size an asset's notional-sized intents round down to (0 = any size)
+    AssetLotSize { asset_mint: Pubkey, lot_size: u64 },
+}
+
+impl ParameterChange {
+    /// Borsh size of the largest variant
+    pub const MAX_SIZE: usize = 1 + // variant tag
+        32 + // asset_mint
+        2 +  // min_strike_percentage
+        2 +  // max_strike_percentage
+        8 +  // min_expiry_seconds
+        8;   // max_expiry_seconds
+}
+
+/// Queued parameter change, executable once `executable_at` has passed.
+/// Gives MMs and users a window to react, and leaves an on-chain record
+/// of every admin update.
+#[account]
+pub struct PendingChange {
+    /// Sequential ID from GlobalState.pending_change_count
+    pub change_id: u64,
+    /// Authority that queued the change (receives the rent back)
+    pub proposer: Pubkey,
+    /// The update to apply
+    pub change: ParameterChange,
+    /// When the change was queued
+    pub queued_at: i64,
+    /// Earliest time the change can be executed
+    pub executable_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PendingChange {
+    pub const LEN: usize = 8 +   // discriminator
+        8 +   // change_id
+        32 +  // proposer
+        ParameterChange::MAX_SIZE + // change
+        8 +   // queued_at
+        8 +   // executable_at
+        1;    // bump
+}
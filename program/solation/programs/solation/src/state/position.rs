@@ -1,12 +1,15 @@
 use anchor_lang::prelude::*;
 use super::StrategyType;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum PositionStatus {
     Active,
     SettledITM,        // In the money, exercised
     SettledOTM,        // Out of money, expired worthless
     SettledATM,        // At the money (edge case)
+    ClosedEarly,       // Closed before expiry by a stop-loss/take-profit trigger
+    AwaitingDistribution, // Settlement price snapshotted, payouts not yet moved
+    SettlementDisputed,   // Snapshotted price challenged, awaiting authority resolution
 }
 
 #[account]
@@ -20,11 +23,49 @@ pub struct Position {
     pub strike_price: u64,            // Strike price in USDC terms
     pub premium_paid: u64,            // Premium user received upfront
     pub contract_size: u64,           // Amount of underlying
+    pub notional: u64,                // Strike notional in quote units, counted toward open interest
     pub created_at: i64,
     pub expiry_timestamp: i64,
     pub settlement_price: Option<u64>, // Pyth price at settlement
+    pub settlement_recorded_at: i64,  // When the settlement price was snapshotted
+    pub distributable_at: i64,        // Payouts allowed from here (end of dispute window)
     pub status: PositionStatus,
 
+    // Early-close triggers set by the user (oracle price units)
+    pub stop_loss_price: Option<u64>,   // Adverse move: above for calls, below for puts
+    pub take_profit_price: Option<u64>, // Favorable move: below for calls, above for puts
+
+    // Alternate payout destinations (cold wallet, treasury); None = original key
+    pub user_beneficiary: Option<Pubkey>,
+    pub mm_beneficiary: Option<Pubkey>,
+
+    // Covered-call user elected to be paid in the underlying
+    pub user_payout_in_underlying: bool,
+
+    // Settlement payouts withheld from blocklisted parties, left in the user vault
+    pub user_payout_withheld: u64,
+    pub mm_payout_withheld: u64,
+
+    // Insurance paid to the user after the MM defaulted on this position
+    pub insurance_paid: u64,
+    pub default_owed: u64,            // Shortfall registered against the MM's expiry haircut
+    pub default_claim_paid: bool,     // Haircut payout taken
+
+    // Standardized OptionSeries this position belongs to (default = none)
+    pub series: Pubkey,
+
+    // Current owner of the user side. `user` seeds the PDA and never changes;
+    // the holder changes when the position is resold.
+    pub holder: Pubkey,
+    pub resale_nonce: u64,            // Nonce the holder's next resale ask must carry
+
+    // LpBacking the MM's escrowed-fill collateral was drawn on (default = own funds)
+    pub collateral_lender: Pubkey,
+
+    // Pricing at trade time: the quote's implied volatility in basis points
+    // (0 for standing-quote trades, whose grids carry no IV)
+    pub implied_volatility_bps: u32,
+
     // Vault accounts holding the locked assets
     pub user_vault: Pubkey,           // User's locked asset PDA
     pub mm_vault_locked: Pubkey,      // MM's locked asset PDA
@@ -45,13 +86,125 @@ impl Position {
         8 +  // strike_price
         8 +  // premium_paid
         8 +  // contract_size
+        8 +  // notional
         8 +  // created_at
         8 +  // expiry_timestamp
         1 + 8 + // settlement_price (Option<u64>)
+        8 +  // settlement_recorded_at
+        8 +  // distributable_at
         1 +  // status
+        1 + 8 + // stop_loss_price (Option<u64>)
+        1 + 8 + // take_profit_price (Option<u64>)
+        1 + 32 + // user_beneficiary (Option<Pubkey>)
+        1 + 32 + // mm_beneficiary (Option<Pubkey>)
+        1 +  // user_payout_in_underlying
+        8 +  // user_payout_withheld
+        8 +  // mm_payout_withheld
+        8 +  // insurance_paid
+        8 +  // default_owed
+        1 +  // default_claim_paid
+        32 + // series
+        32 + // holder
+        8 +  // resale_nonce
+        32 + // collateral_lender
+        4 +  // implied_volatility_bps
         32 + // user_vault
         32 + // mm_vault_locked
         1 +  // bump
         1 +  // user_vault_bump
         1;   // mm_vault_bump
 }
+
+/// Contract terms a Position is opened on, from a filled intent or a taken
+/// standing quote
+#[derive(Clone, Copy, Debug)]
+pub struct PositionTerms {
+    pub position_id: u64,
+    pub user: Pubkey,
+    pub market_maker: Pubkey,
+    pub strategy: StrategyType,
+    pub asset_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub strike_price: u64,
+    pub contract_size: u64,
+    pub expiry_timestamp: i64,
+    /// Implied volatility the trade was quoted at, in basis points
+    pub implied_volatility_bps: u32,
+    /// Token account holding the user's collateral
+    pub user_vault: Pubkey,
+}
+
+impl Position {
+    /// Owner that user-side payouts must be sent to
+    pub fn user_payout_owner(&self) -> Pubkey {
+        self.user_beneficiary.unwrap_or(self.holder)
+    }
+
+    /// Owner that MM-side payouts must be sent to
+    pub fn mm_payout_owner(&self) -> Pubkey {
+        self.mm_beneficiary.unwrap_or(self.market_maker)
+    }
+
+    /// Mint of the MM's leg in a physical settlement: quote for calls,
+    /// underlying for puts
+    pub fn physical_delivery_mint(&self) -> Pubkey {
+        match self.strategy {
+            StrategyType::CoveredCall => self.quote_mint,
+            StrategyType::CashSecuredPut => self.asset_mint,
+        }
+    }
+
+    /// Whether either registered trigger is hit at `price`
+    pub fn trigger_hit(&self, price: u64) -> bool {
+        let (stop_hit, profit_hit) = match self.strategy {
+            StrategyType::CoveredCall => (
+                self.stop_loss_price.is_some_and(|p| price >= p),
+                self.take_profit_price.is_some_and(|p| price <= p),
+            ),
+            StrategyType::CashSecuredPut => (
+                self.stop_loss_price.is_some_and(|p| price <= p),
+                self.take_profit_price.is_some_and(|p| price >= p),
+            ),
+        };
+        stop_hit || profit_hit
+    }
+
+    /// Whether the user side has been sold on since the position opened
+    pub fn is_resold(&self) -> bool {
+        self.holder != self.user
+    }
+
+    pub fn in_series(&self) -> bool {
+        self.series != Pubkey::default()
+    }
+
+    /// Settled or closed early; no further settlement can touch the vaults
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self.status,
+            PositionStatus::SettledITM
+                | PositionStatus::SettledOTM
+                | PositionStatus::SettledATM
+                | PositionStatus::ClosedEarly
+        )
+    }
+
+    /// MM collateral from an escrowed fill still sits in the position MM vault
+    pub fn has_locked_mm_collateral(&self) -> bool {
+        self.mm_vault_bump != 0
+    }
+
+    /// MM collateral was drawn from a liquidity vault and returns to it
+    pub fn is_lp_backed(&self) -> bool {
+        self.collateral_lender != Pubkey::default()
+    }
+
+    /// Settled with nothing left to pay out, so the account can be archived
+    pub fn is_archivable(&self) -> bool {
+        self.is_terminal()
+            && self.user_payout_withheld == 0
+            && self.mm_payout_withheld == 0
+            && (self.default_owed == 0 || self.default_claim_paid)
+            && !self.has_locked_mm_collateral()
+    }
+}
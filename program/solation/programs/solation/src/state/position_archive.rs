@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use solana_keccak_hasher::hash;
+
+use super::{Position, PositionStatus, StrategyType};
+
+/// Concurrent Merkle tree that settled positions are archived into.
+/// The PDA is the tree authority, so only this program can append leaves.
+#[account]
+pub struct PositionArchive {
+    /// SPL account compression tree holding the archived records
+    pub merkle_tree: Pubkey,
+    /// Depth the tree was initialized with
+    pub max_depth: u32,
+    /// Leaves appended so far (index of the next archived record)
+    pub leaf_count: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PositionArchive {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // merkle_tree
+        4 +   // max_depth
+        8 +   // leaf_count
+        1;    // bump
+
+    /// Whether the tree has room for another leaf
+    pub fn has_capacity(&self) -> bool {
+        self.leaf_count < 1u64 << self.max_depth
+    }
+}
+
+/// Final state of a position, logged in full and committed to the archive
+/// tree as its keccak hash
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ArchivedPositionRecord {
+    pub position: Pubkey,
+    pub position_id: u64,
+    pub user: Pubkey,
+    pub market_maker: Pubkey,
+    pub strategy: StrategyType,
+    pub asset_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub strike_price: u64,
+    pub premium_paid: u64,
+    pub contract_size: u64,
+    pub created_at: i64,
+    pub expiry_timestamp: i64,
+    pub settlement_price: Option<u64>,
+    pub status: PositionStatus,
+    pub insurance_paid: u64,
+    pub default_owed: u64,
+    pub archived_at: i64,
+}
+
+impl ArchivedPositionRecord {
+    pub fn new(key: Pubkey, position: &Position, archived_at: i64) -> Self {
+        Self {
+            position: key,
+            position_id: position.position_id,
+            user: position.user,
+            market_maker: position.market_maker,
+            strategy: position.strategy,
+            asset_mint: position.asset_mint,
+            quote_mint: position.quote_mint,
+            strike_price: position.strike_price,
+            premium_paid: position.premium_paid,
+            contract_size: position.contract_size,
+            created_at: position.created_at,
+            expiry_timestamp: position.expiry_timestamp,
+            settlement_price: position.settlement_price,
+            status: position.status,
+            insurance_paid: position.insurance_paid,
+            default_owed: position.default_owed,
+            archived_at,
+        }
+    }
+
+    /// Leaf committed to the archive tree
+    pub fn leaf(&self) -> Result<[u8; 32]> {
+        Ok(hash(&self.try_to_vec()?).to_bytes())
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use super::StrategyType;
+
+/// MM quote whose signature was verified once when it was posted. Users
+/// submit intents against it without an Ed25519 instruction, each taking
+/// part of `contract_size` until it runs out or `valid_until` passes.
+#[account]
+pub struct PostedQuote {
+    /// MM registry owner that signed the quote
+    pub market_maker: Pubkey,
+    /// Who posted the quote (receives the rent back)
+    pub poster: Pubkey,
+    pub asset_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub strategy: StrategyType,
+    pub strike_price: u64,
+    pub premium_per_contract: u64,
+    /// Contracts the MM signed for, shared across every taker
+    pub contract_size: u64,
+    /// Contracts still available to take
+    pub remaining_contracts: u64,
+    /// Option expiry from the signed quote
+    pub quote_expiry: i64,
+    pub quote_nonce: u64,
+    /// Implied volatility the MM signed, in basis points
+    pub implied_volatility_bps: u32,
+    /// MM's signature, carried onto every intent taken from this quote
+    pub mm_signature: [u8; 64],
+    /// When the quote was posted
+    pub posted_at: i64,
+    /// Last moment intents may be submitted against the quote
+    pub valid_until: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PostedQuote {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // market_maker
+        32 +  // poster
+        32 +  // asset_mint
+        32 +  // quote_mint
+        1 +   // strategy
+        8 +   // strike_price
+        8 +   // premium_per_contract
+        8 +   // contract_size
+        8 +   // remaining_contracts
+        8 +   // quote_expiry
+        8 +   // quote_nonce
+        4 +   // implied_volatility_bps
+        64 +  // mm_signature
+        8 +   // posted_at
+        8 +   // valid_until
+        1;    // bump
+
+    pub fn is_live(&self, current_timestamp: i64) -> bool {
+        current_timestamp <= self.valid_until && self.remaining_contracts > 0
+    }
+}
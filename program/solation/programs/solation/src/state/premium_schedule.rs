@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::PREMIUM_INSTALLMENT_GRACE;
+
+/// Installment plan for a position's premium. The user offers it on their
+/// pending intent; a fill that passes it pays only the first installment and
+/// the MM pays the rest on schedule.
+#[account]
+pub struct PremiumSchedule {
+    /// Position the intent opens
+    pub position: Pubkey,
+    /// User who offered the plan and paid its rent
+    pub user: Pubkey,
+    /// MM paying the installments (default until the fill)
+    pub market_maker: Pubkey,
+    /// Number of installments, the first paid at fill
+    pub installments: u8,
+    pub installments_paid: u8,
+    /// Time between installments
+    pub interval_seconds: i64,
+    /// Full premium owed, set at fill
+    pub total_premium: u64,
+    /// Premium paid so far
+    pub premium_paid: u64,
+    /// When the next installment falls due
+    pub next_due: i64,
+    /// Holder cut the unpaid coverage after a missed installment
+    pub cancelled: bool,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PremiumSchedule {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // position
+        32 +  // user
+        32 +  // market_maker
+        1 +   // installments
+        1 +   // installments_paid
+        8 +   // interval_seconds
+        8 +   // total_premium
+        8 +   // premium_paid
+        8 +   // next_due
+        1 +   // cancelled
+        1;    // bump
+
+    /// Whether a fill has started the plan
+    pub fn is_started(&self) -> bool {
+        self.installments_paid > 0
+    }
+
+    /// Whether installments are still owed on a live plan
+    pub fn is_running(&self) -> bool {
+        self.is_started() && !self.cancelled && self.installments_paid < self.installments
+    }
+
+    /// Amount of the next installment; the last one absorbs rounding
+    pub fn next_installment(&self) -> u64 {
+        let base = self.total_premium / self.installments as u64;
+        if self.installments_paid + 1 == self.installments {
+            self.total_premium - self.premium_paid
+        } else {
+            base
+        }
+    }
+
+    /// Start the plan at fill, returning the first installment
+    pub fn start(&mut self, market_maker: Pubkey, total_premium: u64, now: i64) -> u64 {
+        self.market_maker = market_maker;
+        self.total_premium = total_premium;
+        self.next_due = now;
+        self.record_payment()
+    }
+
+    /// Record the next installment as paid, returning its amount
+    pub fn record_payment(&mut self) -> u64 {
+        let amount = self.next_installment();
+        self.premium_paid += amount;
+        self.installments_paid += 1;
+        self.next_due += self.interval_seconds;
+        amount
+    }
+
+    /// Whether the due installment is unpaid past the grace period
+    pub fn is_overdue(&self, now: i64) -> bool {
+        self.is_running() && now > self.next_due + PREMIUM_INSTALLMENT_GRACE
+    }
+}
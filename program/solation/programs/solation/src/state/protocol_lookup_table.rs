@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Address lookup table holding the protocol's static accounts so client
+/// transactions (intent + Ed25519 proof + fill) fit as v0 messages.
+/// The PDA is the table authority.
+#[account]
+pub struct ProtocolLookupTable {
+    /// Lookup table account
+    pub lookup_table: Pubkey,
+    /// Addresses stored in the table so far
+    pub address_count: u16,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ProtocolLookupTable {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // lookup_table
+        2 +   // address_count
+        1;    // bump
+}
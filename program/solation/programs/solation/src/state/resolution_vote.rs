@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_ARBITERS;
+use super::GlobalState;
+
+/// Owner-override resolution an arbitration committee votes on, mirroring the
+/// arguments of the matching instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResolutionProposal {
+    MutualUnwind,
+    ForceContinue { pay_premium: bool, frivolous: bool },
+    ForceSettleNow { settlement_price: u64, user_payout_bps: u16 },
+    EscrowToTreasury,
+    ProportionalSplit { user_bps: u16 },
+}
+
+impl ResolutionProposal {
+    /// Borsh size of the largest variant
+    pub const MAX_SIZE: usize = 1 + // variant tag
+        8 +  // settlement_price
+        2;   // user_payout_bps
+}
+
+/// Committee votes on how to resolve one disputed intent.
+/// Created by the first vote; the matching override instruction executes
+/// only once enough current arbiters have approved the same proposal.
+#[account]
+pub struct ResolutionVote {
+    /// Disputed intent
+    pub intent: Pubkey,
+    /// Resolution being voted on
+    pub proposal: ResolutionProposal,
+    /// Arbiters who approved the proposal
+    pub voters: Vec<Pubkey>,
+    /// Whether the resolution has been carried out
+    pub executed: bool,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ResolutionVote {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // intent
+        ResolutionProposal::MAX_SIZE +  // proposal
+        4 + 32 * MAX_ARBITERS +  // voters
+        1 +   // executed
+        1;    // bump
+
+    /// Votes cast by keys that are still on the committee
+    pub fn approvals(&self, global_state: &GlobalState) -> usize {
+        self.voters
+            .iter()
+            .filter(|voter| global_state.is_arbiter(**voter))
+            .count()
+    }
+}
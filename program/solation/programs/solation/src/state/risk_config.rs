@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::BASIS_POINTS_DIVISOR;
+
+/// Portfolio margin parameters, set by the risk admin. Whitelisted MMs lock
+/// delivery collateral only for what their open book owes under a spot move
+/// of ±`stress_move_bps`, instead of for every position.
+#[account]
+pub struct RiskConfig {
+    /// Key allowed to tune the stress scenario and whitelist MMs
+    pub risk_admin: Pubkey,
+    /// Spot move stressed in each direction, in basis points
+    pub stress_move_bps: u16,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl RiskConfig {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // risk_admin
+        2 +   // stress_move_bps
+        1;    // bump
+
+    /// Lowest and highest spot in the stress scenario
+    pub fn stressed_range(&self, spot_price: u64) -> (u64, u64) {
+        let spot = spot_price as u128;
+        let down = spot * (BASIS_POINTS_DIVISOR - self.stress_move_bps as u64) as u128
+            / BASIS_POINTS_DIVISOR as u128;
+        let up = spot * (BASIS_POINTS_DIVISOR + self.stress_move_bps as u64) as u128
+            / BASIS_POINTS_DIVISOR as u128;
+        (down as u64, u64::try_from(up).unwrap_or(u64::MAX))
+    }
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use super::StrategyType;
+
+/// Standing auto-roll preference for a user on one asset.
+/// When enabled, a keeper may settle the user's expired position and
+/// re-open it against a fresh MM quote that satisfies these constraints.
+#[account]
+pub struct RollPreference {
+    /// User who owns this preference
+    pub user: Pubkey,
+    /// Underlying asset the preference applies to
+    pub asset_mint: Pubkey,
+    /// Strategy new intents must use
+    pub strategy: StrategyType,
+    /// Whether keepers may roll positions for this user/asset
+    pub enabled: bool,
+    /// Max distance of the new strike from the settlement price (bps)
+    pub max_strike_distance_bps: u16,
+    /// Minimum premium per contract the new quote must pay
+    pub min_premium_per_contract: u64,
+    /// Number of positions rolled under this preference
+    pub total_rolls: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl RollPreference {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // user
+        32 +  // asset_mint
+        1 +   // strategy
+        1 +   // enabled
+        2 +   // max_strike_distance_bps
+        8 +   // min_premium_per_contract
+        8 +   // total_rolls
+        1;    // bump
+
+    /// Check whether a new strike is within the allowed distance of the reference price
+    pub fn strike_within_bounds(&self, strike_price: u64, reference_price: u64) -> bool {
+        if reference_price == 0 {
+            return false;
+        }
+        let distance = strike_price.abs_diff(reference_price) as u128;
+        distance * 10000 <= reference_price as u128 * self.max_strike_distance_bps as u128
+    }
+}
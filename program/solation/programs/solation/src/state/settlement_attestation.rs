@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use solana_keccak_hasher::hash;
+
+use super::{Position, PositionStatus, StrategyType};
+
+/// Record that a position's settlement outcome was posted through Wormhole.
+/// One per position, so an outcome can only ever be attested once.
+#[account]
+pub struct SettlementAttestation {
+    /// Attested position
+    pub position: Pubkey,
+    /// Wormhole sequence number the message was emitted with
+    pub sequence: u64,
+    /// Keccak hash of the posted payload
+    pub payload_hash: [u8; 32],
+    /// When the message was posted
+    pub attested_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SettlementAttestation {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // position
+        8 +   // sequence
+        32 +  // payload_hash
+        8 +   // attested_at
+        1;    // bump
+}
+
+/// Settlement outcome carried in the Wormhole message, Borsh-encoded. The
+/// companion contract releases mirrored collateral to the payout owners and
+/// must itself refuse a VAA it has already consumed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SettlementAttestationPayload {
+    /// Layout version, bumped on any change
+    pub version: u8,
+    pub position: Pubkey,
+    pub position_id: u64,
+    pub user_payout_owner: Pubkey,
+    pub mm_payout_owner: Pubkey,
+    pub strategy: StrategyType,
+    pub asset_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub strike_price: u64,
+    pub contract_size: u64,
+    pub expiry_timestamp: i64,
+    pub settlement_price: Option<u64>,
+    pub status: PositionStatus,
+    pub attested_at: i64,
+}
+
+impl SettlementAttestationPayload {
+    pub const VERSION: u8 = 1;
+
+    pub fn new(key: Pubkey, position: &Position, attested_at: i64) -> Self {
+        Self {
+            version: Self::VERSION,
+            position: key,
+            position_id: position.position_id,
+            user_payout_owner: position.user_payout_owner(),
+            mm_payout_owner: position.mm_payout_owner(),
+            strategy: position.strategy,
+            asset_mint: position.asset_mint,
+            quote_mint: position.quote_mint,
+            strike_price: position.strike_price,
+            contract_size: position.contract_size,
+            expiry_timestamp: position.expiry_timestamp,
+            settlement_price: position.settlement_price,
+            status: position.status,
+            attested_at,
+        }
+    }
+
+    /// Encoded payload and its keccak hash
+    pub fn encode(&self) -> Result<(Vec<u8>, [u8; 32])> {
+        let payload = self.try_to_vec()?;
+        let payload_hash = hash(&payload).to_bytes();
+        Ok((payload, payload_hash))
+    }
+}
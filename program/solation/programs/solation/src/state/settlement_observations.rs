@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_SETTLEMENT_OBSERVATIONS;
+
+/// Oracle observations recorded in the window before a position's expiry,
+/// averaged into a TWAP settlement price for assets that opt in.
+/// Zero-copy, with fields ordered so the layout has no implicit padding.
+#[account(zero_copy)]
+pub struct SettlementObservations {
+    /// Position these observations belong to
+    pub position: Pubkey,
+    /// Observed prices
+    pub prices: [u64; MAX_SETTLEMENT_OBSERVATIONS],
+    /// Oracle publish time of each observation
+    pub publish_times: [i64; MAX_SETTLEMENT_OBSERVATIONS],
+    /// Number of observations recorded
+    pub count: u8,
+    /// PDA bump
+    pub bump: u8,
+    /// Keeps the struct 8-byte aligned
+    pub _padding: [u8; 6],
+}
+
+impl SettlementObservations {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // position
+        8 * MAX_SETTLEMENT_OBSERVATIONS +  // prices
+        8 * MAX_SETTLEMENT_OBSERVATIONS +  // publish_times
+        1 +   // count
+        1 +   // bump
+        6;    // _padding
+
+    pub fn last_publish_time(&self) -> Option<i64> {
+        match self.count {
+            0 => None,
+            n => Some(self.publish_times[n as usize - 1]),
+        }
+    }
+
+    pub fn record(&mut self, price: u64, publish_time: i64) {
+        let i = self.count as usize;
+        self.prices[i] = price;
+        self.publish_times[i] = publish_time;
+        self.count += 1;
+    }
+
+    /// Arithmetic mean of recorded prices (observations are evenly spaced)
+    pub fn twap(&self) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+        let n = self.count as usize;
+        let sum: u128 = self.prices[..n].iter().map(|&p| p as u128).sum();
+        Some((sum / n as u128) as u64)
+    }
+}
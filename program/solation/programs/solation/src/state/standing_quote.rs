@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_STANDING_QUOTE_LEVELS;
+use super::StrategyType;
+
+/// One strike on a standing quote's grid
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct QuoteLevel {
+    pub strike_price: u64,
+    pub premium_per_contract: u64,
+}
+
+impl QuoteLevel {
+    pub const LEN: usize = 8 + // strike_price
+        8;  // premium_per_contract
+}
+
+/// Persistent on-chain quote an MM keeps up for one asset, strategy and
+/// expiry. Users take it directly with take_quote; the premium is paid out of
+/// the MM's vault, so the position opens without the MM in the transaction.
+#[account]
+pub struct StandingQuote {
+    /// MM registry owner maintaining the quote
+    pub market_maker: Pubkey,
+    /// MM-chosen ID, so an MM can keep several quotes up
+    pub quote_id: u64,
+    pub asset_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub strategy: StrategyType,
+    /// Option expiry of every position taken from this quote
+    pub expiry_timestamp: i64,
+    /// Strikes on offer and the premium paid for each
+    pub levels: Vec<QuoteLevel>,
+    /// Contracts still available across all strikes
+    pub remaining_contracts: u64,
+    /// Quote stops being takeable after this time
+    pub valid_until: i64,
+    /// Last time the MM updated the quote
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl StandingQuote {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // market_maker
+        8 +   // quote_id
+        32 +  // asset_mint
+        32 +  // quote_mint
+        1 +   // strategy
+        8 +   // expiry_timestamp
+        4 + MAX_STANDING_QUOTE_LEVELS * QuoteLevel::LEN + // levels
+        8 +   // remaining_contracts
+        8 +   // valid_until
+        8 +   // updated_at
+        1;    // bump
+
+    /// Premium per contract quoted at `strike_price`, if it is on the grid
+    pub fn premium_at(&self, strike_price: u64) -> Option<u64> {
+        self.levels
+            .iter()
+            .find(|level| level.strike_price == strike_price)
+            .map(|level| level.premium_per_contract)
+    }
+}
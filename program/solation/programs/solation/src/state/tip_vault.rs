@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Protocol-funded SOL pool paying crank tips. Funded by plain lamport
+/// transfers to the PDA; tips never dip below its rent-exempt minimum.
+#[account]
+pub struct TipVault {
+    /// Total lamports paid out as tips
+    pub total_tipped: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl TipVault {
+    pub const LEN: usize = 8 +   // discriminator
+        8 +   // total_tipped
+        1;    // bump
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+/// Verified credential (e.g. KYC) attested by an issuer for one user.
+/// Permissioned assets name the issuer they accept in `access_gate_key`.
+#[account]
+pub struct UserCredential {
+    /// Key that attested the credential
+    pub issuer: Pubkey,
+    /// Credentialed wallet
+    pub user: Pubkey,
+    /// When the credential was issued
+    pub issued_at: i64,
+    /// Expiry timestamp (0 = never expires)
+    pub expires_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl UserCredential {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // issuer
+        32 +  // user
+        8 +   // issued_at
+        8 +   // expires_at
+        1;    // bump
+
+    pub fn is_valid_at(&self, now: i64) -> bool {
+        self.expires_at == 0 || now < self.expires_at
+    }
+}
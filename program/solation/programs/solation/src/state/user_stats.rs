@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Lifetime activity of one user, for loyalty programs and leaderboards.
+/// Created on the user's first intent; updated on fill and settlement.
+#[account]
+pub struct UserStats {
+    /// User wallet
+    pub user: Pubkey,
+    /// Intents the user has submitted (including auto-rolls)
+    pub intents_submitted: u64,
+    /// Submitted intents that an MM filled
+    pub intents_filled: u64,
+    /// Positions settled or closed early
+    pub positions_settled: u64,
+    /// Premium received from MMs on fills
+    pub premium_earned: u64,
+    /// Unwind fees paid to MMs on early closes
+    pub premium_paid: u64,
+    /// Premium plus payouts minus collateral over closed positions, in quote units
+    pub realized_pnl: i64,
+    /// Intents submitted and not yet filled, cancelled, expired or resolved
+    pub pending_intents: u32,
+    /// Disputes the user has flagged on their intents
+    pub disputes_flagged: u64,
+    /// When the user last flagged a dispute
+    pub last_dispute_flagged_at: i64,
+    /// Flagged disputes the resolver ruled frivolous, forfeiting the bond
+    pub frivolous_disputes: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl UserStats {
+    pub const LEN: usize = 8 +   // discriminator
+        32 +  // user
+        8 +   // intents_submitted
+        8 +   // intents_filled
+        8 +   // positions_settled
+        8 +   // premium_earned
+        8 +   // premium_paid
+        8 +   // realized_pnl
+        4 +   // pending_intents
+        8 +   // disputes_flagged
+        8 +   // last_dispute_flagged_at
+        8 +   // frivolous_disputes
+        1;    // bump
+
+    /// Share of submitted intents that were filled, as percentage (0-100)
+    pub fn fill_rate(&self) -> u8 {
+        if self.intents_submitted == 0 {
+            return 0;
+        }
+        ((self.intents_filled.min(self.intents_submitted) as u128 * 100)
+            / self.intents_submitted as u128) as u8
+    }
+
+    /// Count a newly submitted intent, refusing it when `max_pending`
+    /// intents are already open (0 = uncapped)
+    pub fn record_intent_submitted(&mut self, max_pending: u32) -> Result<()> {
+        require!(
+            max_pending == 0 || self.pending_intents < max_pending,
+            ErrorCode::TooManyPendingIntents
+        );
+        self.pending_intents = self.pending_intents.saturating_add(1);
+        self.intents_submitted = self.intents_submitted.saturating_add(1);
+        Ok(())
+    }
+
+    /// Release a pending intent's slot once it is filled, cancelled,
+    /// expired or resolved
+    pub fn release_pending_intent(&mut self) {
+        self.pending_intents = self.pending_intents.saturating_sub(1);
+    }
+
+    /// Count a dispute the user flagged, refusing it within `cooldown`
+    /// seconds of their previous flag
+    pub fn record_dispute_flagged(&mut self, cooldown: i64, now: i64) -> Result<()> {
+        require!(
+            self.disputes_flagged == 0
+                || now >= self.last_dispute_flagged_at.saturating_add(cooldown),
+            ErrorCode::DisputeCooldownActive
+        );
+        self.disputes_flagged = self.disputes_flagged.saturating_add(1);
+        self.last_dispute_flagged_at = now;
+        Ok(())
+    }
+
+    /// Count a dispute the user flagged that the resolver ruled frivolous
+    pub fn record_frivolous_dispute(&mut self) {
+        self.frivolous_disputes = self.frivolous_disputes.saturating_add(1);
+    }
+
+    pub fn record_fill(&mut self, premium: u64) {
+        self.intents_filled = self.intents_filled.saturating_add(1);
+        self.premium_earned = self.premium_earned.saturating_add(premium);
+    }
+
+    /// Close out a position: the user locked `collateral`, was paid `premium`
+    /// up front and received `payout` back
+    pub fn record_settlement(&mut self, premium: u64, collateral: u64, payout: u64) {
+        let pnl = premium as i128 + payout as i128 - collateral as i128;
+        let pnl = pnl.clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+        self.positions_settled = self.positions_settled.saturating_add(1);
+        self.realized_pnl = self.realized_pnl.saturating_add(pnl);
+    }
+}
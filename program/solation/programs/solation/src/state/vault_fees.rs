@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    BASIS_POINTS_DIVISOR, MAX_VAULT_MANAGEMENT_FEE_BPS, MAX_VAULT_PERFORMANCE_FEE_BPS,
+    SECONDS_PER_YEAR, SHARE_PRICE_SCALE,
+};
+use crate::errors::ErrorCode;
+
+/// Management and performance fees a pooled vault pays the protocol
+/// treasury, charged in newly minted shares at each epoch roll
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct VaultFees {
+    /// Annual fee on the vault's value, in basis points
+    pub management_fee_bps: u16,
+    /// Fee on share-price gains above the high-water mark, in basis points
+    pub performance_fee_bps: u16,
+    /// Highest share price fees have been charged at, scaled by SHARE_PRICE_SCALE
+    pub high_water_mark: u64,
+    /// When fees were last charged; management fees accrue from here
+    pub last_charged_at: i64,
+    /// Management fee taking over at the next charge
+    pub next_management_fee_bps: u16,
+    /// Performance fee taking over at the next charge
+    pub next_performance_fee_bps: u16,
+}
+
+/// Fees charged at one epoch roll, in the vault's value units
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VaultFeeCharge {
+    pub management_fee: u64,
+    pub performance_fee: u64,
+    /// Shares minted to the treasury, worth the two fees after dilution
+    pub fee_shares: u64,
+}
+
+impl VaultFees {
+    pub const LEN: usize = 2 + // management_fee_bps
+        2 +  // performance_fee_bps
+        8 +  // high_water_mark
+        8 +  // last_charged_at
+        2 +  // next_management_fee_bps
+        2;   // next_performance_fee_bps
+
+    pub fn new(now: i64) -> Self {
+        Self {
+            management_fee_bps: 0,
+            performance_fee_bps: 0,
+            high_water_mark: SHARE_PRICE_SCALE,
+            last_charged_at: now,
+            next_management_fee_bps: 0,
+            next_performance_fee_bps: 0,
+        }
+    }
+
+    /// Set new rates, within the protocol caps. Fees accrued so far are still
+    /// charged at the old rates; the new ones apply from the next charge on
+    pub fn set_rates(&mut self, management_fee_bps: u16, performance_fee_bps: u16) -> Result<()> {
+        require!(
+            management_fee_bps <= MAX_VAULT_MANAGEMENT_FEE_BPS
+                && performance_fee_bps <= MAX_VAULT_PERFORMANCE_FEE_BPS,
+            ErrorCode::InvalidVaultFees
+        );
+        self.next_management_fee_bps = management_fee_bps;
+        self.next_performance_fee_bps = performance_fee_bps;
+        Ok(())
+    }
+
+    /// Charge fees on a vault worth `nav` with `share_supply` shares out,
+    /// advancing the high-water mark and the accrual clock, then switch to
+    /// any rates set since the last charge
+    pub fn charge(&mut self, nav: u64, share_supply: u64, now: i64) -> Option<VaultFeeCharge> {
+        let elapsed = now.saturating_sub(self.last_charged_at).max(0) as u128;
+        self.last_charged_at = now;
+        let management_fee_bps =
+            std::mem::replace(&mut self.management_fee_bps, self.next_management_fee_bps);
+        let performance_fee_bps =
+            std::mem::replace(&mut self.performance_fee_bps, self.next_performance_fee_bps);
+        if share_supply == 0 || nav == 0 {
+            return Some(VaultFeeCharge::default());
+        }
+        let (nav, supply) = (nav as u128, share_supply as u128);
+        let bps = BASIS_POINTS_DIVISOR as u128;
+
+        let management_fee = nav * management_fee_bps as u128 * elapsed
+            / (bps * SECONDS_PER_YEAR as u128);
+        let share_price = nav * SHARE_PRICE_SCALE as u128 / supply;
+        let performance_fee = match share_price.checked_sub(self.high_water_mark as u128) {
+            Some(gain) if gain > 0 => {
+                gain * supply / SHARE_PRICE_SCALE as u128 * performance_fee_bps as u128 / bps
+            }
+            _ => 0,
+        };
+
+        // Never take the whole vault; what is left backs the existing shares
+        let total_fee = (management_fee + performance_fee).min(nav - 1);
+        let fee_shares = total_fee * supply / (nav - total_fee);
+
+        let price_after = nav * SHARE_PRICE_SCALE as u128 / (supply + fee_shares);
+        self.high_water_mark = self.high_water_mark.max(u64::try_from(price_after).ok()?);
+
+        Some(VaultFeeCharge {
+            management_fee: u64::try_from(management_fee).ok()?,
+            performance_fee: u64::try_from(performance_fee).ok()?,
+            fee_shares: u64::try_from(fee_shares).ok()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fees(management_fee_bps: u16, performance_fee_bps: u16) -> VaultFees {
+        VaultFees {
+            management_fee_bps,
+            performance_fee_bps,
+            next_management_fee_bps: management_fee_bps,
+            next_performance_fee_bps: performance_fee_bps,
+            ..VaultFees::new(0)
+        }
+    }
+
+    #[test]
+    fn test_management_fee_accrues_over_time() {
+        let mut fees = fees(200, 0);
+        let charge = fees.charge(1_000_000, 1_000_000, SECONDS_PER_YEAR).unwrap();
+        assert_eq!(charge.management_fee, 20_000);
+        assert_eq!(charge.performance_fee, 0);
+        // Fee shares round down, in favor of existing holders
+        assert_eq!(charge.fee_shares, 20_408);
+        assert_eq!(fees.last_charged_at, SECONDS_PER_YEAR);
+    }
+
+    #[test]
+    fn test_performance_fee_above_high_water_mark() {
+        let mut fees = fees(0, 1_000);
+        let charge = fees.charge(1_200_000, 1_000_000, 0).unwrap();
+        assert_eq!(charge.performance_fee, 20_000);
+        assert_eq!(charge.fee_shares, 16_949);
+        assert_eq!(fees.high_water_mark, 1_180_000_177);
+
+        // Nothing more until the share price clears the new mark
+        let charge = fees.charge(1_200_000, 1_016_949, 0).unwrap();
+        assert_eq!(charge, VaultFeeCharge::default());
+    }
+
+    #[test]
+    fn test_new_rates_apply_from_next_charge() {
+        let mut fees = fees(200, 0);
+        fees.set_rates(500, 1_000).unwrap();
+        let charge = fees.charge(1_000_000, 1_000_000, SECONDS_PER_YEAR).unwrap();
+        assert_eq!(charge.management_fee, 20_000);
+        assert_eq!((fees.management_fee_bps, fees.performance_fee_bps), (500, 1_000));
+
+        let charge = fees.charge(1_000_000, 1_000_000, 2 * SECONDS_PER_YEAR).unwrap();
+        assert_eq!(charge.management_fee, 50_000);
+        assert!(fees.set_rates(MAX_VAULT_MANAGEMENT_FEE_BPS + 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_empty_vault_switches_rates_without_fees() {
+        let mut fees = fees(200, 0);
+        fees.set_rates(500, 0).unwrap();
+        assert_eq!(fees.charge(0, 0, SECONDS_PER_YEAR), Some(VaultFeeCharge::default()));
+        assert_eq!(fees.management_fee_bps, 500);
+    }
+
+    #[test]
+    fn test_fee_overflow_is_none() {
+        // A century of management fees on u64::MAX is worth more than u64
+        let mut fees = fees(200, 0);
+        assert_eq!(fees.charge(u64::MAX, 1, 100 * SECONDS_PER_YEAR), None);
+    }
+}
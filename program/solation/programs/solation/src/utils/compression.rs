@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+
+/// SPL account compression program ID
+pub const ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS4qMD4rLA4");
+
+/// SPL noop program ID, used as the compression program's log wrapper
+pub const NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// Discriminators of the compression program's instructions
+const INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+const APPEND_DISCRIMINATOR: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+
+/// Initialize a pre-allocated concurrent Merkle tree with `authority` as its
+/// tree authority
+pub fn init_empty_merkle_tree<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    noop_program: &AccountInfo<'info>,
+    max_depth: u32,
+    max_buffer_size: u32,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&max_depth.to_le_bytes());
+    data.extend_from_slice(&max_buffer_size.to_le_bytes());
+
+    invoke_signed(
+        &tree_instruction(merkle_tree.key(), authority.key(), noop_program.key(), data),
+        &[
+            merkle_tree.clone(),
+            authority.clone(),
+            noop_program.clone(),
+            compression_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Append a leaf to a concurrent Merkle tree owned by `authority`
+pub fn append_leaf<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    noop_program: &AccountInfo<'info>,
+    leaf: [u8; 32],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = APPEND_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&leaf);
+
+    invoke_signed(
+        &tree_instruction(merkle_tree.key(), authority.key(), noop_program.key(), data),
+        &[
+            merkle_tree.clone(),
+            authority.clone(),
+            noop_program.clone(),
+            compression_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Log raw bytes through the noop program so indexers can recover the full
+/// record behind a leaf from transaction data
+pub fn wrap_application_data<'info>(noop_program: &AccountInfo<'info>, data: Vec<u8>) -> Result<()> {
+    invoke(
+        &Instruction {
+            program_id: NOOP_PROGRAM_ID,
+            accounts: vec![],
+            data,
+        },
+        std::slice::from_ref(noop_program),
+    )?;
+    Ok(())
+}
+
+fn tree_instruction(
+    merkle_tree: Pubkey,
+    authority: Pubkey,
+    noop_program: Pubkey,
+    data: Vec<u8>,
+) -> Instruction {
+    Instruction {
+        program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(merkle_tree, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(noop_program, false),
+        ],
+        data,
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+
+use crate::errors::ErrorCode;
+use crate::state::ComposerAllowlist;
+use crate::utils::instructions_sysvar::{current_index, instruction_at};
+
+/// Program that invoked Solation via CPI, or None for a top-level call.
+/// This is the transaction-level program of the current instruction.
+pub fn cpi_caller(instructions_sysvar: &AccountInfo) -> Result<Option<Pubkey>> {
+    if get_stack_height() <= TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(None);
+    }
+    require!(
+        instructions_sysvar.key == &INSTRUCTIONS_SYSVAR_ID,
+        ErrorCode::InvalidSignature
+    );
+    let sysvar_data = instructions_sysvar.try_borrow_data()?;
+    let (program_id, _) = instruction_at(&sysvar_data, current_index(&sysvar_data)?)?;
+    Ok(Some(program_id))
+}
+
+/// Require a CPI caller to be on the composer allowlist
+pub fn require_allowed_caller(
+    instructions_sysvar: &AccountInfo,
+    composer_allowlist: Option<&ComposerAllowlist>,
+) -> Result<()> {
+    if let Some(caller) = cpi_caller(instructions_sysvar)? {
+        require!(
+            composer_allowlist.is_some_and(|allowlist| allowlist.is_allowed(caller)),
+            ErrorCode::ComposerNotAllowed
+        );
+    }
+    Ok(())
+}
@@ -1,11 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::sysvar::instructions::{
-    load_instruction_at_checked, ID as INSTRUCTIONS_SYSVAR_ID,
-};
-use anchor_lang::solana_program::pubkey;
 
 use crate::errors::ErrorCode;
 use crate::state::StrategyType;
+use crate::utils::instructions_sysvar::instruction_at;
 
 /// Ed25519 program ID
 pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
@@ -23,8 +20,13 @@ pub struct Ed25519SignatureOffsets {
     pub message_instruction_index: u16,
 }
 
+/// Length of the signed quote message
+pub const QUOTE_MESSAGE_LEN: usize = 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 4;
+
 /// Construct the quote message that MM should sign
-/// Format: asset_mint || quote_mint || strategy || strike || premium || size || expiry || nonce
+/// Format: asset_mint || quote_mint || strategy || strike || premium || size || expiry || nonce || iv
+/// Built on the stack; submit_intent runs this on every call.
+#[allow(clippy::too_many_arguments)]
 pub fn construct_quote_message(
     asset_mint: &Pubkey,
     quote_mint: &Pubkey,
@@ -34,16 +36,37 @@ pub fn construct_quote_message(
     contract_size: u64,
     quote_expiry: i64,
     quote_nonce: u64,
-) -> Vec<u8> {
-    let mut message = Vec::with_capacity(32 + 32 + 1 + 8 + 8 + 8 + 8 + 8);
-    message.extend_from_slice(&asset_mint.to_bytes());
-    message.extend_from_slice(&quote_mint.to_bytes());
-    message.push(strategy as u8);
-    message.extend_from_slice(&strike_price.to_le_bytes());
-    message.extend_from_slice(&premium_per_contract.to_le_bytes());
-    message.extend_from_slice(&contract_size.to_le_bytes());
-    message.extend_from_slice(&quote_expiry.to_le_bytes());
-    message.extend_from_slice(&quote_nonce.to_le_bytes());
+    implied_volatility_bps: u32,
+) -> [u8; QUOTE_MESSAGE_LEN] {
+    let mut message = [0u8; QUOTE_MESSAGE_LEN];
+    message[0..32].copy_from_slice(asset_mint.as_ref());
+    message[32..64].copy_from_slice(quote_mint.as_ref());
+    message[64] = strategy as u8;
+    message[65..73].copy_from_slice(&strike_price.to_le_bytes());
+    message[73..81].copy_from_slice(&premium_per_contract.to_le_bytes());
+    message[81..89].copy_from_slice(&contract_size.to_le_bytes());
+    message[89..97].copy_from_slice(&quote_expiry.to_le_bytes());
+    message[97..105].copy_from_slice(&quote_nonce.to_le_bytes());
+    message[105..109].copy_from_slice(&implied_volatility_bps.to_le_bytes());
+    message
+}
+
+/// Length of the signed resale ask message
+pub const ASK_MESSAGE_LEN: usize = 32 + 8 + 8 + 8;
+
+/// Construct the resale ask message a position holder signs
+/// Format: position || price || ask_expiry || resale_nonce
+pub fn construct_ask_message(
+    position: &Pubkey,
+    price: u64,
+    ask_expiry: i64,
+    resale_nonce: u64,
+) -> [u8; ASK_MESSAGE_LEN] {
+    let mut message = [0u8; ASK_MESSAGE_LEN];
+    message[0..32].copy_from_slice(position.as_ref());
+    message[32..40].copy_from_slice(&price.to_le_bytes());
+    message[40..48].copy_from_slice(&ask_expiry.to_le_bytes());
+    message[48..56].copy_from_slice(&resale_nonce.to_le_bytes());
     message
 }
 
@@ -56,31 +79,26 @@ pub fn construct_quote_message(
 /// 3. The message in that instruction matches our expected quote message
 /// 
 /// # Arguments
-/// * `instructions_sysvar` - The Instructions sysvar account
+/// * `instructions_sysvar` - The Instructions sysvar account (address-checked by the caller's accounts)
 /// * `expected_signing_key` - The MM's registered signing key
 /// * `expected_message` - The constructed quote message to verify
 /// * `ed25519_instruction_index` - Index of the Ed25519Program instruction in the transaction
+///
+/// When Solation is invoked via CPI the Ed25519Program instruction is still a
+/// top-level instruction of the transaction, so composers pass its absolute index.
 pub fn verify_ed25519_signature(
     instructions_sysvar: &AccountInfo,
     expected_signing_key: &Pubkey,
     expected_message: &[u8],
     ed25519_instruction_index: u8,
 ) -> Result<()> {
-    // Verify we have the correct sysvar
-    require!(
-        instructions_sysvar.key == &INSTRUCTIONS_SYSVAR_ID,
-        ErrorCode::InvalidSignature
-    );
-
-    // Load the Ed25519Program instruction
-    let ed25519_ix = load_instruction_at_checked(
-        ed25519_instruction_index as usize,
-        instructions_sysvar,
-    ).map_err(|_| ErrorCode::InvalidSignature)?;
+    // Read the Ed25519Program instruction in place
+    let sysvar_data = instructions_sysvar.try_borrow_data()?;
+    let (program_id, data) = instruction_at(&sysvar_data, ed25519_instruction_index as usize)?;
 
     // Verify it's the Ed25519 program
     require!(
-        ed25519_ix.program_id == ED25519_PROGRAM_ID,
+        program_id == ED25519_PROGRAM_ID,
         ErrorCode::InvalidSignature
     );
 
@@ -89,9 +107,7 @@ pub fn verify_ed25519_signature(
     // [1]: padding (u8) 
     // [2..]: Ed25519SignatureOffsets for each signature
     // Then: signature data, pubkey data, message data
-    
-    let data = &ed25519_ix.data;
-    
+
     // Need at least 2 bytes for header
     require!(data.len() >= 2, ErrorCode::InvalidSignature);
     
@@ -111,6 +127,16 @@ pub fn verify_ed25519_signature(
         message_instruction_index: u16::from_le_bytes([data[14], data[15]]),
     };
 
+    // The signature, key and message must all live in the Ed25519 instruction
+    // itself; data read from another instruction was not what we check below
+    let own_index = |index: u16| index == u16::MAX || index == ed25519_instruction_index as u16;
+    require!(
+        own_index(offsets.signature_instruction_index)
+            && own_index(offsets.public_key_instruction_index)
+            && own_index(offsets.message_instruction_index),
+        ErrorCode::InvalidSignature
+    );
+
     // Extract the public key from the instruction data
     let pubkey_start = offsets.public_key_offset as usize;
     let pubkey_end = pubkey_start + 32;
@@ -142,7 +168,6 @@ pub fn verify_ed25519_signature(
 
     // If we get here, the Ed25519 program verified the signature
     // and we've confirmed the pubkey and message match our expectations
-    msg!("Ed25519 signature verified successfully");
     Ok(())
 }
 
@@ -160,6 +185,7 @@ mod tests {
         let size = 1_000000u64; // 1 contract
         let expiry = 1700000000i64;
         let nonce = 12345u64;
+        let iv = 6500u32; // 65%
 
         let msg = construct_quote_message(
             &asset_mint,
@@ -170,10 +196,11 @@ mod tests {
             size,
             expiry,
             nonce,
+            iv,
         );
 
-        // 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 = 105 bytes
-        assert_eq!(msg.len(), 105);
+        // 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 4 = 109 bytes
+        assert_eq!(msg.len(), 109);
         
         // Verify asset_mint is first
         assert_eq!(&msg[0..32], &asset_mint.to_bytes());
@@ -181,5 +208,7 @@ mod tests {
         assert_eq!(&msg[32..64], &quote_mint.to_bytes());
         // Verify strategy
         assert_eq!(msg[64], 0); // CoveredCall = 0
+        // Verify IV is last
+        assert_eq!(&msg[105..109], &iv.to_le_bytes());
     }
 }
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+
+/// Anchor discriminator of the adapter's `open_hedge` instruction
+/// (sha256("global:open_hedge")[..8])
+pub const OPEN_HEDGE_DISCRIMINATOR: [u8; 8] = [76, 209, 98, 107, 64, 37, 197, 168];
+
+/// Direction of the perp position an adapter opens
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HedgeSide {
+    Long,
+    Short,
+}
+
+/// Adapter interface: `open_hedge(request: HedgeRequest)` with the MM
+/// signer first, then the venue accounts in the adapter's own order. The
+/// adapter opens a `side` perp of `size` underlying base units for the MM
+/// and must fail rather than fill partially.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct HedgeRequest {
+    /// Signer the perp is opened for, and who pays for it
+    pub market_maker: Pubkey,
+    /// Position being hedged
+    pub position: Pubkey,
+    pub asset_mint: Pubkey,
+    pub side: HedgeSide,
+    /// Underlying base units
+    pub size: u64,
+    /// Venue-specific parameters (market index, price limit, ...)
+    pub adapter_data: Vec<u8>,
+}
+
+/// Call an allowlisted adapter's `open_hedge`. Only `market_maker` signs:
+/// no PDA of this program lends its authority to the adapter.
+pub fn invoke_hedge_adapter<'info>(
+    adapter_program: &AccountInfo<'info>,
+    market_maker: &AccountInfo<'info>,
+    adapter_accounts: &[AccountInfo<'info>],
+    request: &HedgeRequest,
+) -> Result<()> {
+    let mut data = OPEN_HEDGE_DISCRIMINATOR.to_vec();
+    request.serialize(&mut data)?;
+
+    let mut accounts = Vec::with_capacity(adapter_accounts.len() + 1);
+    accounts.push(AccountMeta::new(market_maker.key(), true));
+    accounts.extend(adapter_accounts.iter().map(|account| AccountMeta {
+        pubkey: account.key(),
+        is_signer: account.is_signer,
+        is_writable: account.is_writable,
+    }));
+    let ix = Instruction {
+        program_id: adapter_program.key(),
+        accounts,
+        data,
+    };
+
+    let mut infos = Vec::with_capacity(adapter_accounts.len() + 2);
+    infos.push(market_maker.clone());
+    infos.extend_from_slice(adapter_accounts);
+    infos.push(adapter_program.clone());
+    invoke(&ix, &infos)?;
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Program ID and data of the top-level instruction at `index`, read in place
+/// from the Instructions sysvar's data. Unlike `load_instruction_at_checked`
+/// this allocates nothing and skips the account metas, which is most of the
+/// cost of introspection.
+///
+/// Sysvar layout: num_instructions (u16) || offsets (u16 each) || instructions,
+/// each laid out as num_accounts (u16) || metas (1 + 32 each) || program_id ||
+/// data_len (u16) || data.
+pub fn instruction_at(sysvar_data: &[u8], index: usize) -> Result<(Pubkey, &[u8])> {
+    let num_instructions = read_u16(sysvar_data, 0)? as usize;
+    require!(index < num_instructions, ErrorCode::InvalidSignature);
+
+    let start = read_u16(sysvar_data, 2 + index * 2)? as usize;
+    let num_accounts = read_u16(sysvar_data, start)? as usize;
+
+    let program_id_start = start + 2 + num_accounts * 33;
+    let program_id: [u8; 32] = sysvar_data
+        .get(program_id_start..program_id_start + 32)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ErrorCode::InvalidSignature)?;
+
+    let data_len = read_u16(sysvar_data, program_id_start + 32)? as usize;
+    let data_start = program_id_start + 34;
+    let data = sysvar_data
+        .get(data_start..data_start + data_len)
+        .ok_or(ErrorCode::InvalidSignature)?;
+
+    Ok((Pubkey::new_from_array(program_id), data))
+}
+
+/// Index of the currently executing top-level instruction (trailing u16)
+pub fn current_index(sysvar_data: &[u8]) -> Result<usize> {
+    let offset = sysvar_data
+        .len()
+        .checked_sub(2)
+        .ok_or(ErrorCode::InvalidSignature)?;
+    Ok(read_u16(sysvar_data, offset)? as usize)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| error!(ErrorCode::InvalidSignature))
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+/// Jupiter aggregator v6 program ID
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+/// Execute a Jupiter route built off-chain (`route_data` and its accounts, in
+/// order), with `authority` signing as the route's token transfer authority.
+/// Callers must check balances before and after: the route itself is untrusted.
+pub fn invoke_jupiter_route<'info>(
+    jupiter_program: &AccountInfo<'info>,
+    route_accounts: &[AccountInfo<'info>],
+    route_data: Vec<u8>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let accounts = route_accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key() == authority.key(),
+            is_writable: account.is_writable,
+        })
+        .collect();
+    let ix = Instruction {
+        program_id: JUPITER_PROGRAM_ID,
+        accounts,
+        data: route_data,
+    };
+
+    let mut infos = Vec::with_capacity(route_accounts.len() + 2);
+    infos.extend_from_slice(route_accounts);
+    infos.push(authority.clone());
+    infos.push(jupiter_program.clone());
+    invoke_signed(&ix, &infos, signer_seeds)?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+/// Address lookup table program ID
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM_ID: Pubkey =
+    pubkey!("AddressLookupTab1e1111111111111111111111111");
+
+/// Bincode tags of the lookup table program's instructions
+const CREATE_LOOKUP_TABLE_TAG: u32 = 0;
+const EXTEND_LOOKUP_TABLE_TAG: u32 = 2;
+
+/// Lookup table address derived from its authority and creation slot
+pub fn derive_lookup_table_address(authority: &Pubkey, recent_slot: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[authority.as_ref(), &recent_slot.to_le_bytes()],
+        &ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+    )
+}
+
+/// Create a lookup table owned by a PDA `authority`
+#[allow(clippy::too_many_arguments)]
+pub fn create_lookup_table<'info>(
+    lookup_table_program: &AccountInfo<'info>,
+    lookup_table: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    recent_slot: u64,
+    bump_seed: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = CREATE_LOOKUP_TABLE_TAG.to_le_bytes().to_vec();
+    data.extend_from_slice(&recent_slot.to_le_bytes());
+    data.push(bump_seed);
+
+    invoke_signed(
+        &table_instruction(lookup_table.key(), authority.key(), payer.key(), data),
+        &[
+            lookup_table.clone(),
+            authority.clone(),
+            payer.clone(),
+            system_program.clone(),
+            lookup_table_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Append `new_addresses` to a lookup table owned by a PDA `authority`
+pub fn extend_lookup_table<'info>(
+    lookup_table_program: &AccountInfo<'info>,
+    lookup_table: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_addresses: &[Pubkey],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = EXTEND_LOOKUP_TABLE_TAG.to_le_bytes().to_vec();
+    data.extend_from_slice(&(new_addresses.len() as u64).to_le_bytes());
+    for address in new_addresses {
+        data.extend_from_slice(address.as_ref());
+    }
+
+    invoke_signed(
+        &table_instruction(lookup_table.key(), authority.key(), payer.key(), data),
+        &[
+            lookup_table.clone(),
+            authority.clone(),
+            payer.clone(),
+            system_program.clone(),
+            lookup_table_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+fn table_instruction(
+    lookup_table: Pubkey,
+    authority: Pubkey,
+    payer: Pubkey,
+    data: Vec<u8>,
+) -> Instruction {
+    Instruction {
+        program_id: ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(lookup_table, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(anchor_lang::system_program::ID, false),
+        ],
+        data,
+    }
+}
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::PullFeedAccountData;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::{AssetConfig, CollateralRate, LstRateSource, SettlementTerms};
+
+/// SPL stake pool program ID
+pub const STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// Byte offsets into an SPL `StakePool` account
+const STAKE_POOL_ACCOUNT_TYPE: u8 = 1;
+const STAKE_POOL_POOL_MINT_OFFSET: usize = 162;
+const STAKE_POOL_TOTAL_LAMPORTS_OFFSET: usize = 258;
+const STAKE_POOL_TOKEN_SUPPLY_OFFSET: usize = 266;
+const STAKE_POOL_LAST_UPDATE_EPOCH_OFFSET: usize = 274;
+
+/// Exchange rate for a position vault holding `vault_mint`: None unless the
+/// vault holds the asset's LST collateral, in which case the rate account is required
+pub fn collateral_rate_for(
+    asset_config: &AssetConfig,
+    vault_mint: Pubkey,
+    rate_account: Option<&AccountInfo>,
+    clock: &Clock,
+) -> Result<Option<CollateralRate>> {
+    if !asset_config.has_lst_collateral() || vault_mint != asset_config.lst_mint {
+        return Ok(None);
+    }
+    let rate_account = rate_account.ok_or(ErrorCode::MissingOracleAccount)?;
+    require_keys_eq!(
+        rate_account.key(),
+        asset_config.lst_rate_account,
+        ErrorCode::InvalidOracleAccount
+    );
+
+    read_rate(
+        asset_config.lst_rate_source,
+        rate_account,
+        asset_config.lst_mint,
+        asset_config,
+        clock,
+    )
+    .map(Some)
+}
+
+/// Base asset per unit of a fair-value priced asset (e.g. SOL per LST),
+/// from its configured rate account
+pub fn fair_value_rate(
+    asset_config: &AssetConfig,
+    rate_account: Option<&AccountInfo>,
+    clock: &Clock,
+) -> Result<CollateralRate> {
+    let rate_account = rate_account.ok_or(ErrorCode::MissingOracleAccount)?;
+    require_keys_eq!(
+        rate_account.key(),
+        asset_config.fair_value_rate_account,
+        ErrorCode::InvalidOracleAccount
+    );
+    read_rate(
+        asset_config.fair_value_rate_source,
+        rate_account,
+        asset_config.asset_mint,
+        asset_config,
+        clock,
+    )
+}
+
+/// Read a rate for `mint` from a stake pool or Switchboard rate feed
+fn read_rate(
+    source: LstRateSource,
+    rate_account: &AccountInfo,
+    mint: Pubkey,
+    asset_config: &AssetConfig,
+    clock: &Clock,
+) -> Result<CollateralRate> {
+    let rate = match source {
+        LstRateSource::StakePool => read_stake_pool_rate(rate_account, mint, clock)?,
+        LstRateSource::Switchboard => read_switchboard_rate(rate_account, asset_config, clock)?,
+    };
+    require!(
+        rate.underlying > 0 && rate.collateral > 0,
+        ErrorCode::InvalidOraclePrice
+    );
+    Ok(rate)
+}
+
+/// Stake pool rate for the pool minting `pool_mint`; only trusted once the
+/// pool has been updated this epoch
+fn read_stake_pool_rate(
+    pool: &AccountInfo,
+    pool_mint: Pubkey,
+    clock: &Clock,
+) -> Result<CollateralRate> {
+    require_keys_eq!(*pool.owner, STAKE_POOL_PROGRAM_ID, ErrorCode::InvalidOracleAccount);
+    let data = pool.try_borrow_data()?;
+    require!(
+        data.len() >= STAKE_POOL_LAST_UPDATE_EPOCH_OFFSET + 8
+            && data[0] == STAKE_POOL_ACCOUNT_TYPE,
+        ErrorCode::InvalidOracleAccount
+    );
+
+    let mint = Pubkey::try_from(
+        &data[STAKE_POOL_POOL_MINT_OFFSET..STAKE_POOL_POOL_MINT_OFFSET + 32],
+    )
+    .map_err(|_| ErrorCode::InvalidOracleAccount)?;
+    require_keys_eq!(mint, pool_mint, ErrorCode::InvalidOracleAccount);
+
+    let read_u64 = |offset: usize| {
+        u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap_or_default())
+    };
+    require!(
+        read_u64(STAKE_POOL_LAST_UPDATE_EPOCH_OFFSET) == clock.epoch,
+        ErrorCode::PriceTooStale
+    );
+
+    Ok(CollateralRate {
+        underlying: read_u64(STAKE_POOL_TOTAL_LAMPORTS_OFFSET) as u128,
+        collateral: read_u64(STAKE_POOL_TOKEN_SUPPLY_OFFSET) as u128,
+    })
+}
+
+/// Switchboard rate feed, held to the asset's max price age
+fn read_switchboard_rate(
+    feed_info: &AccountInfo,
+    asset_config: &AssetConfig,
+    clock: &Clock,
+) -> Result<CollateralRate> {
+    let data = feed_info
+        .try_borrow_data()
+        .map_err(|_| ErrorCode::InvalidOracleAccount)?;
+    let feed = PullFeedAccountData::parse(data).map_err(|_| ErrorCode::InvalidOracleAccount)?;
+
+    let age = clock.unix_timestamp.saturating_sub(feed.last_update_timestamp);
+    require!(
+        feed.result.slot != 0 && (0..=asset_config.max_price_age_seconds as i64).contains(&age),
+        ErrorCode::PriceTooStale
+    );
+    require!(feed.result.value > 0, ErrorCode::InvalidOraclePrice);
+
+    Ok(CollateralRate {
+        underlying: feed.result.value as u128,
+        collateral: 10u128.pow(SWITCHBOARD_PRECISION),
+    })
+}
+
+/// The asset's settlement terms for a vault holding `vault_mint`
+pub fn settlement_terms_for_vault(
+    asset_config: &AssetConfig,
+    vault_mint: Pubkey,
+    rate_account: Option<&AccountInfo>,
+    clock: &Clock,
+) -> Result<SettlementTerms> {
+    Ok(SettlementTerms {
+        collateral_rate: collateral_rate_for(asset_config, vault_mint, rate_account, clock)?,
+        ..asset_config.settlement_terms()
+    })
+}
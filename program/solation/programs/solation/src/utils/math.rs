@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::BASIS_POINTS_DIVISOR;
+use crate::errors::ErrorCode;
+
+/// `amount * bps / 10_000`, rounded down, with a u128 intermediate
+pub fn apply_bps(amount: u64, bps: u64) -> Result<u64> {
+    let value = (amount as u128)
+        .checked_mul(bps as u128)
+        .and_then(|product| product.checked_div(BASIS_POINTS_DIVISOR as u128))
+        .ok_or(ErrorCode::MathOverflow)?;
+    u64::try_from(value).map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+/// Split `amount` into `(share, rest)` where `share` is `bps` of it
+pub fn split_bps(amount: u64, bps: u64) -> Result<(u64, u64)> {
+    require!(bps <= BASIS_POINTS_DIVISOR, ErrorCode::InvalidPercentage);
+    let share = apply_bps(amount, bps)?;
+    let rest = amount.checked_sub(share).ok_or(ErrorCode::MathOverflow)?;
+    Ok((share, rest))
+}
+
+/// Split collateral into `(poster, counterparty)` where the collateral poster
+/// gets `bps` of it rounded up. Rounding always favors the poster: the
+/// counterparty's share is the one rounded down, and nothing is left over.
+pub fn split_bps_to_poster(amount: u64, bps: u64) -> Result<(u64, u64)> {
+    require!(bps <= BASIS_POINTS_DIVISOR, ErrorCode::InvalidPercentage);
+    let (counterparty, poster) = split_bps(amount, BASIS_POINTS_DIVISOR - bps)?;
+    Ok((poster, counterparty))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_bps_extremes() {
+        assert_eq!(apply_bps(u64::MAX, BASIS_POINTS_DIVISOR).unwrap(), u64::MAX);
+        assert_eq!(apply_bps(u64::MAX, 5_000).unwrap(), u64::MAX / 2);
+        assert_eq!(apply_bps(u64::MAX, 0).unwrap(), 0);
+        // More than 100% of u64::MAX does not fit
+        assert!(apply_bps(u64::MAX, BASIS_POINTS_DIVISOR + 1).is_err());
+    }
+
+    #[test]
+    fn test_split_bps_conserves_amount() {
+        let (share, rest) = split_bps(u64::MAX, 3_333).unwrap();
+        assert_eq!(share as u128 + rest as u128, u64::MAX as u128);
+        assert!(split_bps(1_000, BASIS_POINTS_DIVISOR + 1).is_err());
+    }
+
+    #[test]
+    fn test_split_bps_to_poster_rounds_up_for_poster() {
+        // 33.33% of 10 is 3.333: the poster gets 4, the counterparty 6
+        assert_eq!(split_bps_to_poster(10, 3_333).unwrap(), (4, 6));
+        assert_eq!(split_bps_to_poster(10, 0).unwrap(), (0, 10));
+        assert_eq!(split_bps_to_poster(10, BASIS_POINTS_DIVISOR).unwrap(), (10, 0));
+        assert!(split_bps_to_poster(10, BASIS_POINTS_DIVISOR + 1).is_err());
+    }
+}
@@ -1,3 +1,27 @@
+pub mod compression;
+pub mod cpi_guard;
 pub mod ed25519_verify;
+pub mod hedge_adapter;
+pub mod instructions_sysvar;
+pub mod jupiter;
+pub mod lookup_table;
+pub mod lst;
+pub mod math;
+pub mod native_sol;
+pub mod oracle;
+pub mod token_extensions;
+pub mod wormhole;
 
+pub use compression::*;
+pub use cpi_guard::*;
 pub use ed25519_verify::*;
+pub use hedge_adapter::*;
+pub use instructions_sysvar::*;
+pub use jupiter::*;
+pub use lookup_table::*;
+pub use lst::*;
+pub use math::*;
+pub use native_sol::*;
+pub use oracle::*;
+pub use token_extensions::*;
+pub use wormhole::*;
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{self, CloseAccount, SyncNative};
+
+/// Whether `mint` is wrapped SOL
+pub fn is_native_mint(mint: Pubkey) -> bool {
+    mint == native_mint::ID
+}
+
+/// Move `amount` lamports from `payer` into a wSOL token account and sync
+/// its token balance
+pub fn wrap_native<'info>(
+    payer: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            system_program::Transfer {
+                from: payer.clone(),
+                to: token_account.clone(),
+            },
+        ),
+        amount,
+    )?;
+    sync_native(token_account, token_program)
+}
+
+/// Bring a wSOL token account's token balance up to its lamports above rent
+pub fn sync_native<'info>(
+    token_account: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    token_interface::sync_native(CpiContext::new(
+        token_program.clone(),
+        SyncNative {
+            account: token_account.clone(),
+        },
+    ))
+}
+
+/// Close a wSOL token account, paying its wrapped balance and rent to
+/// `destination` as native SOL
+pub fn unwrap_native<'info>(
+    token_account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        CloseAccount {
+            account: token_account.clone(),
+            destination: destination.clone(),
+            authority: authority.clone(),
+        },
+        signer_seeds,
+    ))
+}
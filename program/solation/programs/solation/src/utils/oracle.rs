@@ -0,0 +1,543 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{get_return_data, invoke};
+use pyth_solana_receiver_sdk::error::GetPriceError;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use switchboard_on_demand::PullFeedAccountData;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::{AssetConfig, OracleSource};
+use crate::utils::lst::fair_value_rate;
+
+/// Chainlink OCR2 store program ID
+pub const CHAINLINK_STORE_PROGRAM_ID: Pubkey =
+    pubkey!("HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny");
+
+/// Discriminator of the store program's `query` instruction
+const CHAINLINK_QUERY_DISCRIMINATOR: [u8; 8] = [0x27, 0xfb, 0x82, 0x9f, 0x2e, 0x88, 0xa4, 0xa9];
+
+/// Borsh tags of the store's `Query` enum
+const CHAINLINK_QUERY_DECIMALS: u8 = 1;
+const CHAINLINK_QUERY_LATEST_ROUND_DATA: u8 = 4;
+
+/// Oracle accounts passed to any instruction that reads a price.
+/// Only the accounts for the asset's source (and fallback) are required.
+#[derive(Accounts)]
+pub struct OracleAccounts<'info> {
+    /// Pyth price update
+    pub price_update: Option<Account<'info, PriceUpdateV2>>,
+
+    /// Switchboard pull feed
+    /// CHECK: Matched against the asset's configured feed and parsed by the Switchboard SDK
+    pub switchboard_feed: Option<AccountInfo<'info>>,
+
+    /// Chainlink store feed
+    /// CHECK: Matched against the asset's configured feed and read via the store program
+    pub chainlink_feed: Option<AccountInfo<'info>>,
+
+    /// CHECK: Chainlink store program
+    #[account(address = CHAINLINK_STORE_PROGRAM_ID)]
+    pub chainlink_program: Option<AccountInfo<'info>>,
+
+    /// Stake pool or rate feed, for positions collateralized in the asset's LST
+    /// CHECK: Matched against the asset's configured LST rate account
+    pub lst_rate: Option<AccountInfo<'info>>,
+
+    /// Stake pool or rate feed, for assets priced at fair value
+    /// CHECK: Matched against the asset's configured fair-value rate account
+    pub fair_value_rate: Option<AccountInfo<'info>>,
+}
+
+/// A validated price, in units of the asset's `price_exponent`
+#[derive(Clone, Copy, Debug)]
+pub struct OraclePrice {
+    pub price: u64,
+    pub conf: u64,
+    pub publish_time: i64,
+    pub source: OracleSource,
+}
+
+/// Chainlink round as returned by the store program
+#[derive(AnchorDeserialize)]
+struct ChainlinkRound {
+    _round_id: u32,
+    _slot: u64,
+    timestamp: u32,
+    answer: i128,
+}
+
+/// Read the asset's primary oracle
+pub fn read_oracle_price(
+    oracle: &OracleAccounts,
+    asset_config: &AssetConfig,
+    clock: &Clock,
+) -> Result<OraclePrice> {
+    require!(!asset_config.has_basket(), ErrorCode::BasketPriceUpdatesRequired);
+    let price = read_oracle_source(asset_config.oracle_source, oracle, asset_config, clock)?;
+    fair_value_price(price, oracle, asset_config, clock)
+}
+
+/// Read the asset's price: the index level from `basket_updates` for a
+/// basket asset, its oracle otherwise
+pub fn read_asset_price(
+    oracle: &OracleAccounts,
+    basket_updates: &[AccountInfo],
+    asset_config: &AssetConfig,
+    clock: &Clock,
+) -> Result<OraclePrice> {
+    if asset_config.has_basket() {
+        read_basket_price(basket_updates, asset_config, clock)
+    } else {
+        read_oracle_price(oracle, asset_config, clock)
+    }
+}
+
+/// Index level of a basket asset: the weighted sum of its components' Pyth
+/// prices, each rescaled to the asset's `price_exponent`. `updates` holds one
+/// price update per component, in basket order. Every component is held to
+/// the asset's staleness and confidence bounds; the level is as old as its
+/// oldest component.
+pub fn read_basket_price(
+    updates: &[AccountInfo],
+    asset_config: &AssetConfig,
+    clock: &Clock,
+) -> Result<OraclePrice> {
+    let components = &asset_config.basket[..asset_config.basket_len()];
+    require!(
+        updates.len() >= components.len(),
+        ErrorCode::BasketPriceUpdatesRequired
+    );
+
+    let mut level = 0u128;
+    let mut conf = 0u128;
+    let mut publish_time = i64::MAX;
+    for (component, update) in components.iter().zip(updates) {
+        require_keys_eq!(*update.owner, PriceUpdateV2::owner(), ErrorCode::InvalidOracleAccount);
+        let price_update = {
+            let data = update.try_borrow_data()?;
+            PriceUpdateV2::try_deserialize(&mut &data[..])?
+        };
+        let price = price_update
+            .get_price_no_older_than(clock, asset_config.max_price_age_seconds, &component.pyth_feed_id)
+            .map_err(pyth_error)?;
+        require!(
+            price.price > 0 && price.exponent <= 0,
+            ErrorCode::InvalidOraclePrice
+        );
+        require!(
+            price.conf as u128 * BASIS_POINTS_DIVISOR as u128
+                <= price.price as u128 * asset_config.max_confidence_bps as u128,
+            ErrorCode::PriceConfidenceTooWide
+        );
+
+        let decimals = price.exponent.unsigned_abs();
+        let component_price = rescale(price.price as i128, decimals, asset_config.price_exponent)?;
+        let component_conf = rescale(price.conf as i128, decimals, asset_config.price_exponent)?;
+        level += component_price as u128 * component.weight as u128;
+        conf += component_conf as u128 * component.weight as u128;
+        publish_time = publish_time.min(price.publish_time);
+    }
+
+    let scale = BASKET_WEIGHT_SCALE as u128;
+    let price = u64::try_from(level / scale).map_err(|_| ErrorCode::MathOverflow)?;
+    require!(price > 0, ErrorCode::InvalidOraclePrice);
+    Ok(OraclePrice {
+        price,
+        conf: u64::try_from(conf / scale).map_err(|_| ErrorCode::MathOverflow)?,
+        publish_time,
+        source: OracleSource::Pyth,
+    })
+}
+
+/// Restate a validated feed price as the asset's price: unchanged for a
+/// direct feed, base price x exchange rate for a fair-value priced asset.
+/// The confidence band scales with the price.
+pub fn fair_value_price(
+    price: OraclePrice,
+    oracle: &OracleAccounts,
+    asset_config: &AssetConfig,
+    clock: &Clock,
+) -> Result<OraclePrice> {
+    if !asset_config.has_fair_value_price() {
+        return Ok(price);
+    }
+    let rate = fair_value_rate(asset_config, oracle.fair_value_rate.as_ref(), clock)?;
+    let scale = |value: u64| {
+        u64::try_from(value as u128 * rate.underlying / rate.collateral)
+            .map_err(|_| error!(ErrorCode::MathOverflow))
+    };
+    let fair_price = scale(price.price)?;
+    require!(fair_price > 0, ErrorCode::InvalidOraclePrice);
+    Ok(OraclePrice {
+        price: fair_price,
+        conf: scale(price.conf)?,
+        ..price
+    })
+}
+
+/// Read and validate a specific oracle source.
+///
+/// Every adapter is held to the asset's `max_price_age_seconds` and
+/// `max_confidence_bps`, so sources are interchangeable at the call site.
+pub fn read_oracle_source(
+    source: OracleSource,
+    oracle: &OracleAccounts,
+    asset_config: &AssetConfig,
+    clock: &Clock,
+) -> std::result::Result<OraclePrice, ErrorCode> {
+    let price = match source {
+        OracleSource::Pyth => read_pyth(oracle, asset_config, clock)?,
+        OracleSource::Switchboard => read_switchboard(oracle, asset_config)?,
+        OracleSource::Chainlink => read_chainlink(oracle, asset_config)?,
+    };
+
+    let age = clock.unix_timestamp.saturating_sub(price.publish_time);
+    if !(0..=asset_config.max_price_age_seconds as i64).contains(&age) {
+        return Err(ErrorCode::PriceTooStale);
+    }
+    if price.price == 0 {
+        return Err(ErrorCode::InvalidOraclePrice);
+    }
+
+    // Reject when conf / price exceeds the per-asset maximum
+    if price.conf as u128 * BASIS_POINTS_DIVISOR as u128
+        > price.price as u128 * asset_config.max_confidence_bps as u128
+    {
+        return Err(ErrorCode::PriceConfidenceTooWide);
+    }
+
+    Ok(price)
+}
+
+/// Whether a failed read may be retried against a fallback source
+pub fn is_recoverable_oracle_error(error: ErrorCode) -> bool {
+    matches!(
+        error,
+        ErrorCode::PriceTooStale
+            | ErrorCode::InsufficientVerificationLevel
+            | ErrorCode::PriceConfidenceTooWide
+    )
+}
+
+/// Pyth adapter: requires a fully verified update for the asset's feed ID
+fn read_pyth(
+    oracle: &OracleAccounts,
+    asset_config: &AssetConfig,
+    clock: &Clock,
+) -> std::result::Result<OraclePrice, ErrorCode> {
+    let price_update = oracle
+        .price_update
+        .as_ref()
+        .ok_or(ErrorCode::MissingOracleAccount)?;
+
+    let price = price_update
+        .get_price_no_older_than(
+            clock,
+            asset_config.max_price_age_seconds,
+            &asset_config.pyth_feed_id,
+        )
+        .map_err(pyth_error)?;
+
+    if price.price <= 0 {
+        return Err(ErrorCode::InvalidOraclePrice);
+    }
+
+    // Restate the feed's own exponent in the asset's strike exponent
+    let decimals = price.exponent.unsigned_abs();
+    Ok(OraclePrice {
+        price: rescale(price.price as i128, decimals, asset_config.price_exponent)?,
+        conf: rescale(price.conf as i128, decimals, asset_config.price_exponent)?,
+        publish_time: price.publish_time,
+        source: OracleSource::Pyth,
+    })
+}
+
+fn pyth_error(error: GetPriceError) -> ErrorCode {
+    match error {
+        GetPriceError::PriceTooOld => ErrorCode::PriceTooStale,
+        GetPriceError::InsufficientVerificationLevel => ErrorCode::InsufficientVerificationLevel,
+        _ => ErrorCode::PythFeedIdMismatch,
+    }
+}
+
+/// USD price of a stablecoin quote currency, `price * 10^exponent`
+#[derive(Clone, Copy, Debug)]
+pub struct QuotePrice {
+    pub price: u64,
+    pub exponent: i32,
+}
+
+impl QuotePrice {
+    fn peg(&self) -> u128 {
+        10u128.pow(self.exponent.unsigned_abs())
+    }
+
+    /// Deviation from $1, in bps
+    pub fn depeg_bps(&self) -> u64 {
+        let deviation =
+            (self.price as u128).abs_diff(self.peg()) * BASIS_POINTS_DIVISOR as u128 / self.peg();
+        u64::try_from(deviation).unwrap_or(u64::MAX)
+    }
+
+    /// Restate a USD price in units of this quote currency
+    pub fn restate(&self, usd_price: u64) -> Option<u64> {
+        let value = (usd_price as u128)
+            .checked_mul(self.peg())?
+            .checked_div(self.price as u128)?;
+        u64::try_from(value).ok()
+    }
+}
+
+/// Read a quote currency's USD price from its Pyth feed, held to the asset's
+/// staleness bound
+pub fn read_quote_price(
+    price_update: Option<&Account<PriceUpdateV2>>,
+    feed_id: &[u8; 32],
+    asset_config: &AssetConfig,
+    clock: &Clock,
+) -> Result<QuotePrice> {
+    let price_update = price_update.ok_or(ErrorCode::MissingOracleAccount)?;
+    let price = price_update
+        .get_price_no_older_than(clock, asset_config.max_price_age_seconds, feed_id)
+        .map_err(pyth_error)?;
+    // Stablecoin feeds carry a negative exponent; anything else is not a USD quote
+    require!(
+        price.price > 0 && (-18..=0).contains(&price.exponent),
+        ErrorCode::InvalidOraclePrice
+    );
+
+    Ok(QuotePrice {
+        price: price.price as u64,
+        exponent: price.exponent,
+    })
+}
+
+/// Switchboard adapter: median result, with the feed's std dev as confidence
+fn read_switchboard(
+    oracle: &OracleAccounts,
+    asset_config: &AssetConfig,
+) -> std::result::Result<OraclePrice, ErrorCode> {
+    let feed_info = oracle
+        .switchboard_feed
+        .as_ref()
+        .ok_or(ErrorCode::MissingOracleAccount)?;
+    if !asset_config.has_switchboard_feed() || feed_info.key() != asset_config.switchboard_feed {
+        return Err(ErrorCode::InvalidOracleAccount);
+    }
+
+    let data = feed_info
+        .try_borrow_data()
+        .map_err(|_| ErrorCode::InvalidOracleAccount)?;
+    let feed = PullFeedAccountData::parse(data).map_err(|_| ErrorCode::InvalidOracleAccount)?;
+    if feed.result.slot == 0 {
+        return Err(ErrorCode::PriceTooStale);
+    }
+    if feed.result.value <= 0 {
+        return Err(ErrorCode::InvalidOraclePrice);
+    }
+
+    Ok(OraclePrice {
+        price: rescale(feed.result.value, SWITCHBOARD_PRECISION, asset_config.price_exponent)?,
+        conf: rescale(
+            feed.result.std_dev.abs(),
+            SWITCHBOARD_PRECISION,
+            asset_config.price_exponent,
+        )?,
+        publish_time: feed.last_update_timestamp,
+        source: OracleSource::Switchboard,
+    })
+}
+
+/// Chainlink adapter: latest round via the store program (no confidence band)
+fn read_chainlink(
+    oracle: &OracleAccounts,
+    asset_config: &AssetConfig,
+) -> std::result::Result<OraclePrice, ErrorCode> {
+    let (Some(feed), Some(program)) = (&oracle.chainlink_feed, &oracle.chainlink_program) else {
+        return Err(ErrorCode::MissingOracleAccount);
+    };
+    if !asset_config.has_chainlink_feed() || feed.key() != asset_config.chainlink_feed {
+        return Err(ErrorCode::InvalidOracleAccount);
+    }
+
+    let decimals: u8 = chainlink_query(program, feed, CHAINLINK_QUERY_DECIMALS)?;
+    let round: ChainlinkRound = chainlink_query(program, feed, CHAINLINK_QUERY_LATEST_ROUND_DATA)?;
+    if round.answer <= 0 {
+        return Err(ErrorCode::InvalidOraclePrice);
+    }
+
+    Ok(OraclePrice {
+        price: rescale(round.answer, decimals as u32, asset_config.price_exponent)?,
+        conf: 0,
+        publish_time: round.timestamp as i64,
+        source: OracleSource::Chainlink,
+    })
+}
+
+/// CPI into the Chainlink store's read-only `query` instruction
+fn chainlink_query<'info, T: AnchorDeserialize>(
+    program: &AccountInfo<'info>,
+    feed: &AccountInfo<'info>,
+    query: u8,
+) -> std::result::Result<T, ErrorCode> {
+    let mut data = CHAINLINK_QUERY_DISCRIMINATOR.to_vec();
+    data.push(query);
+
+    let ix = Instruction {
+        program_id: CHAINLINK_STORE_PROGRAM_ID,
+        accounts: vec![AccountMeta::new_readonly(feed.key(), false)],
+        data,
+    };
+    invoke(&ix, &[feed.clone(), program.clone()])
+        .map_err(|_| ErrorCode::InvalidOracleAccount)?;
+
+    let (program_id, data) = get_return_data().ok_or(ErrorCode::InvalidOracleAccount)?;
+    if program_id != CHAINLINK_STORE_PROGRAM_ID {
+        return Err(ErrorCode::InvalidOracleAccount);
+    }
+    T::try_from_slice(&data).map_err(|_| ErrorCode::InvalidOracleAccount)
+}
+
+/// Convert a value with `decimals` places into units of 10^`exponent`
+fn rescale(value: i128, decimals: u32, exponent: i32) -> std::result::Result<u64, ErrorCode> {
+    let shift = decimals as i32 + exponent;
+    let scaled = if shift >= 0 {
+        10i128
+            .checked_pow(shift as u32)
+            .map(|divisor| value / divisor)
+    } else {
+        10i128
+            .checked_pow(shift.unsigned_abs())
+            .and_then(|multiplier| value.checked_mul(multiplier))
+    };
+    scaled
+        .and_then(|v| u64::try_from(v).ok())
+        .ok_or(ErrorCode::InvalidOraclePrice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::BasketComponent;
+    use pyth_solana_receiver_sdk::price_update::{PriceFeedMessage, VerificationLevel};
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn clock() -> Clock {
+        Clock {
+            unix_timestamp: NOW,
+            ..Clock::default()
+        }
+    }
+
+    fn price_update(
+        feed_id: [u8; 32],
+        price: i64,
+        conf: u64,
+        exponent: i32,
+    ) -> AccountInfo<'static> {
+        let update = PriceUpdateV2 {
+            write_authority: Pubkey::default(),
+            verification_level: VerificationLevel::Full,
+            price_message: PriceFeedMessage {
+                feed_id,
+                price,
+                conf,
+                exponent,
+                publish_time: NOW,
+                prev_publish_time: NOW - 1,
+                ema_price: price,
+                ema_conf: conf,
+            },
+            posted_slot: 0,
+        };
+        let mut data = Vec::new();
+        update.try_serialize(&mut data).unwrap();
+        AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            false,
+            Box::leak(Box::new(0)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(PriceUpdateV2::owner())),
+            false,
+            0,
+        )
+    }
+
+    // Index priced in 1e-6 units over the given (feed, weight) components
+    fn basket_asset(components: &[([u8; 32], u64)]) -> AssetConfig {
+        let mut asset_config =
+            AssetConfig::try_deserialize_unchecked(&mut &[0u8; AssetConfig::LEN][..]).unwrap();
+        asset_config.price_exponent = -6;
+        asset_config.max_price_age_seconds = 60;
+        asset_config.max_confidence_bps = 100;
+        for (slot, (pyth_feed_id, weight)) in asset_config.basket.iter_mut().zip(components) {
+            *slot = BasketComponent {
+                pyth_feed_id: *pyth_feed_id,
+                weight: *weight,
+            };
+        }
+        asset_config
+    }
+
+    #[test]
+    fn test_rescale_shifts_both_ways() {
+        // 8 decimals down to 1e-6 truncates
+        assert_eq!(rescale(15_012_345_678, 8, -6).ok(), Some(150_123_456));
+        // 5 decimals up to 1e-8
+        assert_eq!(rescale(1_234_567, 5, -8).ok(), Some(1_234_567_000));
+        assert_eq!(rescale(42, 6, -6).ok(), Some(42));
+    }
+
+    #[test]
+    fn test_rescale_rejects_negative_and_overflow() {
+        assert!(rescale(-1, 6, -6).is_err());
+        assert!(rescale(1, 0, -39).is_err());
+        assert!(rescale(i128::MAX, 0, -1).is_err());
+        assert!(rescale(u64::MAX as i128 + 1, 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_basket_price_weights_components() {
+        let (btc, eth) = ([1u8; 32], [2u8; 32]);
+        // Half a BTC at $60,000 (1e-8) plus two ETH at $3,000 (1e-5)
+        let asset_config = basket_asset(&[
+            (btc, BASKET_WEIGHT_SCALE / 2),
+            (eth, 2 * BASKET_WEIGHT_SCALE),
+        ]);
+        let updates = [
+            price_update(btc, 6_000_000_000_000, 3_000_000_000, -8),
+            price_update(eth, 300_000_000, 100_000, -5),
+        ];
+        let price = read_basket_price(&updates, &asset_config, &clock()).unwrap();
+        assert_eq!(price.price, 36_000_000_000);
+        assert_eq!(price.conf, 15_000_000 + 2_000_000);
+        assert_eq!(price.publish_time, NOW);
+    }
+
+    #[test]
+    fn test_basket_price_rounds_weighted_level_down() {
+        let feed = [1u8; 32];
+        // A third of $1.000001 is $0.333333 and change
+        let asset_config = basket_asset(&[(feed, BASKET_WEIGHT_SCALE / 3)]);
+        let updates = [price_update(feed, 1_000_001, 0, -6)];
+        let price = read_basket_price(&updates, &asset_config, &clock()).unwrap();
+        assert_eq!(price.price, 333_333);
+    }
+
+    #[test]
+    fn test_basket_price_requires_every_component() {
+        let (btc, eth) = ([1u8; 32], [2u8; 32]);
+        let asset_config = basket_asset(&[(btc, BASKET_WEIGHT_SCALE), (eth, BASKET_WEIGHT_SCALE)]);
+        let updates = [price_update(btc, 6_000_000_000_000, 0, -8)];
+        assert!(read_basket_price(&updates, &asset_config, &clock()).is_err());
+        // Updates out of basket order carry the wrong feeds
+        let updates = [
+            price_update(eth, 300_000_000, 0, -5),
+            price_update(btc, 6_000_000_000_000, 0, -8),
+        ];
+        assert!(read_basket_price(&updates, &asset_config, &clock()).is_err());
+    }
+}
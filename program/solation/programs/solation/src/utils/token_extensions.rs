@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::Mint;
+
+use crate::errors::ErrorCode;
+
+/// Token-2022 mint extensions that leave transfers and balances untouched
+const ALLOWED_MINT_EXTENSIONS: &[ExtensionType] = &[
+    ExtensionType::MintCloseAuthority,
+    ExtensionType::InterestBearingConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+    ExtensionType::GroupPointer,
+    ExtensionType::TokenGroup,
+    ExtensionType::GroupMemberPointer,
+    ExtensionType::TokenGroupMember,
+];
+
+/// Reject Token-2022 mints carrying any extension outside the allow-list
+/// (transfer hooks, permanent delegates, frozen defaults, ...). Transfer fees
+/// are accepted only when the asset opted into fee-aware accounting.
+/// Legacy SPL Token mints are always supported.
+pub fn require_supported_mint(
+    mint: &InterfaceAccount<Mint>,
+    allow_transfer_fee: bool,
+) -> Result<()> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner == anchor_spl::token::ID {
+        return Ok(());
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    for extension in state.get_extension_types()? {
+        if extension == ExtensionType::TransferFeeConfig {
+            require!(allow_transfer_fee, ErrorCode::TransferFeeMintRejected);
+        } else {
+            require!(
+                ALLOWED_MINT_EXTENSIONS.contains(&extension),
+                ErrorCode::UnsupportedMintExtension
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Fee the mint withholds when `amount` is sent this epoch (0 without a
+/// transfer-fee extension)
+pub fn transfer_fee_for(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    match transfer_fee_config(mint)? {
+        Some(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(error!(ErrorCode::MathOverflow)),
+        None => Ok(0),
+    }
+}
+
+/// Amount to send so that `net` arrives after the mint's transfer fee
+pub fn gross_up_for_fee(mint: &InterfaceAccount<Mint>, net: u64) -> Result<u64> {
+    let fee = match transfer_fee_config(mint)? {
+        Some(config) => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, net)
+            .ok_or(error!(ErrorCode::MathOverflow))?,
+        None => 0,
+    };
+    net.checked_add(fee).ok_or(error!(ErrorCode::MathOverflow))
+}
+
+fn transfer_fee_config(mint: &InterfaceAccount<Mint>) -> Result<Option<TransferFeeConfig>> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner == anchor_spl::token::ID {
+        return Ok(None);
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    Ok(state.get_extension::<TransferFeeConfig>().ok().copied())
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::sysvar;
+
+use crate::errors::ErrorCode;
+
+/// Wormhole core bridge program ID
+pub const WORMHOLE_CORE_BRIDGE_ID: Pubkey =
+    pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+
+/// Seeds of the core bridge's own PDAs
+pub const WORMHOLE_BRIDGE_SEED: &[u8] = b"Bridge";
+pub const WORMHOLE_FEE_COLLECTOR_SEED: &[u8] = b"fee_collector";
+pub const WORMHOLE_SEQUENCE_SEED: &[u8] = b"Sequence";
+
+/// Tag of the core bridge's `PostMessage` instruction
+const POST_MESSAGE_INSTRUCTION: u8 = 1;
+
+/// Guardians sign only once the posting slot is finalized
+const CONSISTENCY_FINALIZED: u8 = 1;
+
+/// Offset of the message fee in the bridge config account: guardian set
+/// index (u32), last lamports (u64), guardian set expiration (u32)
+const BRIDGE_FEE_OFFSET: usize = 16;
+
+/// Lamports the core bridge charges per message
+pub fn wormhole_message_fee(bridge_config: &AccountInfo) -> Result<u64> {
+    let data = bridge_config.try_borrow_data()?;
+    let fee = data
+        .get(BRIDGE_FEE_OFFSET..BRIDGE_FEE_OFFSET + 8)
+        .ok_or(ErrorCode::InvalidWormholeAccount)?;
+    Ok(u64::from_le_bytes(fee.try_into().unwrap()))
+}
+
+/// Sequence number the emitter's next message will carry (0 before its first)
+pub fn wormhole_next_sequence(sequence: &AccountInfo) -> Result<u64> {
+    let data = sequence.try_borrow_data()?;
+    Ok(match data.get(..8) {
+        Some(bytes) => u64::from_le_bytes(bytes.try_into().unwrap()),
+        None => 0,
+    })
+}
+
+/// Post `payload` through the core bridge. The emitter and message accounts
+/// are PDAs of this program and sign with `signer_seeds`; the message fee
+/// must already be in the fee collector.
+#[allow(clippy::too_many_arguments)]
+pub fn post_wormhole_message<'info>(
+    wormhole_program: &AccountInfo<'info>,
+    bridge_config: &AccountInfo<'info>,
+    message: &AccountInfo<'info>,
+    emitter: &AccountInfo<'info>,
+    sequence: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    fee_collector: &AccountInfo<'info>,
+    clock: &AccountInfo<'info>,
+    rent: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    payload: Vec<u8>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = vec![POST_MESSAGE_INSTRUCTION];
+    data.extend_from_slice(&0u32.to_le_bytes()); // nonce (no batching)
+    data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    data.extend_from_slice(&payload);
+    data.push(CONSISTENCY_FINALIZED);
+
+    let ix = Instruction {
+        program_id: WORMHOLE_CORE_BRIDGE_ID,
+        accounts: vec![
+            AccountMeta::new(bridge_config.key(), false),
+            AccountMeta::new(message.key(), true),
+            AccountMeta::new_readonly(emitter.key(), true),
+            AccountMeta::new(sequence.key(), false),
+            AccountMeta::new(payer.key(), true),
+            AccountMeta::new(fee_collector.key(), false),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+            AccountMeta::new_readonly(system_program.key(), false),
+        ],
+        data,
+    };
+    invoke_signed(
+        &ix,
+        &[
+            bridge_config.clone(),
+            message.clone(),
+            emitter.clone(),
+            sequence.clone(),
+            payer.clone(),
+            fee_collector.clone(),
+            clock.clone(),
+            rent.clone(),
+            system_program.clone(),
+            wormhole_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
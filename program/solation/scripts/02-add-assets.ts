@@ -59,10 +59,15 @@ async function main() {
           STRIKE_PARAMS.MAX_STRIKE_PERCENTAGE,
           new anchor.BN(EXPIRY_PARAMS.MIN_EXPIRY_SECONDS),
           new anchor.BN(EXPIRY_PARAMS.MAX_EXPIRY_SECONDS),
-          DECIMALS.SOL
+          DECIMALS.SOL,
+          false, // allow_transfer_fee
+          { token: {} } // asset_class
         )
         .accounts({
+          assetMintAccount: SOL_MINT,
+          quoteMintAccount: usdcMint,
           authority,
+          payer: authority,
         })
         .rpc();
 
@@ -97,10 +102,15 @@ async function main() {
           STRIKE_PARAMS.MAX_STRIKE_PERCENTAGE,
           new anchor.BN(EXPIRY_PARAMS.MIN_EXPIRY_SECONDS),
           new anchor.BN(EXPIRY_PARAMS.MAX_EXPIRY_SECONDS),
-          DECIMALS.USDC
+          DECIMALS.USDC,
+          false, // allow_transfer_fee
+          { token: {} } // asset_class
         )
         .accounts({
+          assetMintAccount: usdcMint,
+          quoteMintAccount: usdcMint,
           authority,
+          payer: authority,
         })
         .rpc();
 
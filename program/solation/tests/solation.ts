@@ -9,14 +9,24 @@ import {
   SYSVAR_INSTRUCTIONS_PUBKEY,
   Transaction,
   Ed25519Program,
+  ComputeBudgetProgram,
 } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  ACCOUNT_SIZE,
+  MINT_SIZE,
+  AccountLayout,
+  MintLayout,
   createMint,
   createAccount,
   mintTo,
   getAccount,
+  getAssociatedTokenAddressSync,
 } from "@solana/spl-token";
+import { BankrunProvider, startAnchor } from "anchor-bankrun";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import { createHash } from "crypto";
 import { expect } from "chai";
 import * as nacl from "tweetnacl";
 
@@ -279,8 +289,9 @@ describe("Solation Off-Chain RFQ System", () => {
       contractSize: BN;
       quoteExpiry: BN;
       quoteNonce: BN;
+      impliedVolatilityBps: number;
     }): Buffer {
-      const buf = Buffer.alloc(105); // 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8
+      const buf = Buffer.alloc(109); // 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 4
       let offset = 0;
       
       params.assetMint.toBuffer().copy(buf, offset); offset += 32;
@@ -290,7 +301,8 @@ describe("Solation Off-Chain RFQ System", () => {
       params.premiumPerContract.toArrayLike(Buffer, "le", 8).copy(buf, offset); offset += 8;
       params.contractSize.toArrayLike(Buffer, "le", 8).copy(buf, offset); offset += 8;
       params.quoteExpiry.toArrayLike(Buffer, "le", 8).copy(buf, offset); offset += 8;
-      params.quoteNonce.toArrayLike(Buffer, "le", 8).copy(buf, offset);
+      params.quoteNonce.toArrayLike(Buffer, "le", 8).copy(buf, offset); offset += 8;
+      buf.writeUInt32LE(params.impliedVolatilityBps, offset);
       
       return buf;
     }
@@ -309,6 +321,7 @@ describe("Solation Off-Chain RFQ System", () => {
         contractSize,
         quoteExpiry,
         quoteNonce,
+        impliedVolatilityBps: 6_500, // 65%
       });
 
       // Sign with MM's signing key
@@ -360,18 +373,1094 @@ describe("Solation Off-Chain RFQ System", () => {
       const state = await program.account.globalState.fetch(globalState);
       expect(state.paused).to.equal(true);
 
-      // Unpause for other tests
+      expect(state.enabledOperations).to.equal(0);
+
+      // Resume in stages for other tests: settlement, fills, new intents
+      for (const enabled of [0b001, 0b011, 0b111]) {
+        await program.methods
+          .resumeProtocol()
+          .accounts({
+            authority: authority.publicKey,
+            globalState,
+          })
+          .signers([authority])
+          .rpc();
+
+        const stagedState = await program.account.globalState.fetch(globalState);
+        expect(stagedState.enabledOperations).to.equal(enabled);
+      }
+
+      const updatedState = await program.account.globalState.fetch(globalState);
+      expect(updatedState.paused).to.equal(false);
+    });
+  });
+});
+
+describe("Solation Fund Flows", () => {
+  // Runs against an in-process bank so the clock can be moved past
+  // timelocks, expiries, vault windows and LP epochs
+  const idl = require("../target/idl/solation.json");
+  const programId = new PublicKey(idl.address);
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<Solation>;
+  let authority: Keypair;
+  let now: number;
+  let txNonce = 0;
+
+  const treasury = Keypair.generate();
+  const marketMaker = Keypair.generate();
+  const mmSigningKey = Keypair.generate();
+  const manager = Keypair.generate();
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+  const carol = Keypair.generate();
+  const dave = Keypair.generate();
+
+  const assetMint = Keypair.generate().publicKey;
+  const quoteMint = Keypair.generate().publicKey;
+  const priceUpdate = Keypair.generate().publicKey;
+
+  // Both mints use 6 decimals and prices carry 8, so 10 contracts
+  // (10 * UNIT) at a $95 strike escrow 950 * UNIT of quote
+  const DECIMALS = 6;
+  const UNIT = 1_000_000;
+  const PRICE_SCALE = 100_000_000;
+  const SPOT = 100 * PRICE_SCALE;
+  const PUT_STRIKE = 95 * PRICE_SCALE;
+  const CALL_STRIKE = 110 * PRICE_SCALE;
+  const HOUR = 3_600;
+  const DAY = 86_400;
+
+  const PYTH_RECEIVER_ID = new PublicKey("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+  const PYTH_FEED_ID = Buffer.alloc(32, 1);
+  const PRICE_UPDATE_DISCRIMINATOR = createHash("sha256")
+    .update("account:PriceUpdateV2")
+    .digest()
+    .subarray(0, 8);
+
+  const u64 = (value: number) => new BN(value).toArrayLike(Buffer, "le", 8);
+  const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, programId)[0];
+  const ata = (mint: PublicKey, owner: PublicKey) =>
+    getAssociatedTokenAddressSync(mint, owner, true);
+
+  const globalState = pda(Buffer.from("global_state"));
+  const blocklist = pda(Buffer.from("blocklist"));
+  const assetConfig = pda(Buffer.from("asset_config"), assetMint.toBuffer());
+  const mmRegistry = pda(Buffer.from("mm_registry"), marketMaker.publicKey.toBuffer());
+  const nonceTracker = pda(Buffer.from("nonce_tracker"), marketMaker.publicKey.toBuffer());
+  const mmVault = pda(Buffer.from("mm_vault"), mmRegistry.toBuffer(), quoteMint.toBuffer());
+
+  const positionPda = (user: PublicKey, positionId: number) =>
+    pda(Buffer.from("position"), user.toBuffer(), u64(positionId));
+  const positionVaultPda = (position: PublicKey) =>
+    pda(Buffer.from("position_user_vault"), position.toBuffer());
+  const userStatsPda = (user: PublicKey) => pda(Buffer.from("user_stats"), user.toBuffer());
+  const standingQuotePda = (quoteId: number) =>
+    pda(Buffer.from("standing_quote"), marketMaker.publicKey.toBuffer(), u64(quoteId));
+
+  // Option expiry shared by every standing-quote position
+  let optionExpiry: number;
+
+  async function setTokenAccount(address: PublicKey, mint: PublicKey, owner: PublicKey, amount: number) {
+    const data = Buffer.alloc(ACCOUNT_SIZE);
+    AccountLayout.encode(
+      {
+        mint,
+        owner,
+        amount: BigInt(amount),
+        delegateOption: 0,
+        delegate: PublicKey.default,
+        state: 1,
+        isNativeOption: 0,
+        isNative: BigInt(0),
+        delegatedAmount: BigInt(0),
+        closeAuthorityOption: 0,
+        closeAuthority: PublicKey.default,
+      },
+      data
+    );
+    context.setAccount(address, { lamports: 2_039_280, data, owner: TOKEN_PROGRAM_ID, executable: false });
+  }
+
+  async function setMint(address: PublicKey, decimals: number) {
+    const data = Buffer.alloc(MINT_SIZE);
+    MintLayout.encode(
+      {
+        mintAuthorityOption: 1,
+        mintAuthority: authority.publicKey,
+        supply: BigInt(1_000_000_000 * UNIT),
+        decimals,
+        isInitialized: true,
+        freezeAuthorityOption: 0,
+        freezeAuthority: PublicKey.default,
+      },
+      data
+    );
+    context.setAccount(address, { lamports: 1_461_600, data, owner: TOKEN_PROGRAM_ID, executable: false });
+  }
+
+  async function setPrice(price: number) {
+    const data = Buffer.alloc(134);
+    let offset = 0;
+    PRICE_UPDATE_DISCRIMINATOR.copy(data, offset); offset += 8;
+    authority.publicKey.toBuffer().copy(data, offset); offset += 32;
+    data.writeUInt8(1, offset); offset += 1; // VerificationLevel::Full
+    PYTH_FEED_ID.copy(data, offset); offset += 32;
+    data.writeBigInt64LE(BigInt(price), offset); offset += 8;
+    data.writeBigUInt64LE(BigInt(price / 1000), offset); offset += 8;
+    data.writeInt32LE(-8, offset); offset += 4;
+    data.writeBigInt64LE(BigInt(now), offset); offset += 8;
+    data.writeBigInt64LE(BigInt(now - 1), offset); offset += 8;
+    data.writeBigInt64LE(BigInt(price), offset); offset += 8;
+    data.writeBigUInt64LE(BigInt(price / 1000), offset); offset += 8;
+    data.writeBigUInt64LE(BigInt(1), offset);
+    context.setAccount(priceUpdate, { lamports: 1_844_400, data, owner: PYTH_RECEIVER_ID, executable: false });
+  }
+
+  // Move the clock forward and republish the price so it stays fresh
+  async function warp(seconds: number) {
+    const clock = await context.banksClient.getClock();
+    now += seconds;
+    context.setClock(
+      new Clock(
+        clock.slot + BigInt(1),
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        BigInt(now)
+      )
+    );
+    await setPrice(SPOT);
+  }
+
+  async function tokenBalance(address: PublicKey): Promise<number> {
+    const account = await context.banksClient.getAccount(address);
+    if (!account) return 0;
+    return Number(AccountLayout.decode(Buffer.from(account.data)).amount);
+  }
+
+  async function mintSupply(address: PublicKey): Promise<number> {
+    const account = await context.banksClient.getAccount(address);
+    return Number(MintLayout.decode(Buffer.from(account.data)).supply);
+  }
+
+  // Retried transactions must differ from the rejected one, or the bank
+  // drops them as already processed
+  const unique = () => ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 - ++txNonce });
+  const computeBudget = () => ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 });
+
+  async function expectError(promise: Promise<unknown>, name: string) {
+    const entry = idl.errors.find((e: { name: string }) => e.name.toLowerCase() === name.toLowerCase());
+    expect(entry, `unknown error ${name}`).to.exist;
+    try {
+      await promise;
+    } catch (e) {
+      const code = e.code ?? e.error?.errorCode?.number;
+      const text = `${e.message ?? e} ${(e.logs ?? []).join(" ")}`;
+      if (code === entry.code) return;
+      expect(text).to.satisfy(
+        (t: string) => t.includes(entry.name) || t.toLowerCase().includes(`0x${entry.code.toString(16)}`),
+        `expected ${name}, got: ${text}`
+      );
+      return;
+    }
+    expect.fail(`expected ${name}`);
+  }
+
+  // Signed quote for the intent flow: the Ed25519 instruction goes first,
+  // and the params point back at it
+  function signedQuote(params: {
+    intentId: number;
+    strategy: number;
+    strikePrice: number;
+    premiumPerContract: number;
+    contractSize: number;
+    quoteExpiry: number;
+    quoteNonce: number;
+  }) {
+    const message = Buffer.alloc(109); // 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 4
+    let offset = 0;
+    assetMint.toBuffer().copy(message, offset); offset += 32;
+    quoteMint.toBuffer().copy(message, offset); offset += 32;
+    message.writeUInt8(params.strategy, offset); offset += 1;
+    u64(params.strikePrice).copy(message, offset); offset += 8;
+    u64(params.premiumPerContract).copy(message, offset); offset += 8;
+    u64(params.contractSize).copy(message, offset); offset += 8;
+    u64(params.quoteExpiry).copy(message, offset); offset += 8;
+    u64(params.quoteNonce).copy(message, offset); offset += 8;
+    message.writeUInt32LE(5000, offset);
+
+    return {
+      ed25519Ix: Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: mmSigningKey.secretKey,
+        message,
+      }),
+      params: {
+        intentId: new BN(params.intentId),
+        assetMint,
+        quoteMint,
+        strategy: params.strategy === 0 ? { coveredCall: {} } : { cashSecuredPut: {} },
+        strikePrice: new BN(params.strikePrice),
+        premiumPerContract: new BN(params.premiumPerContract),
+        contractSize: new BN(params.contractSize),
+        quoteExpiry: new BN(params.quoteExpiry),
+        quoteNonce: new BN(params.quoteNonce),
+        impliedVolatilityBps: 5000,
+        mmSignature: Array.from(nacl.sign.detached(message, mmSigningKey.secretKey)),
+        ed25519InstructionIndex: 0,
+        minMmReputation: null,
+        minMmFillRate: null,
+      },
+    };
+  }
+
+  function takeQuote(user: Keypair, quoteId: number, positionId: number, strikePrice: number, contractSize: number, escrowMint: PublicKey) {
+    const position = positionPda(user.publicKey, positionId);
+    return program.methods
+      .takeQuote({
+        positionId: new BN(positionId),
+        strikePrice: new BN(strikePrice),
+        contractSize: new BN(contractSize),
+        minPremiumPerContract: new BN(1),
+        minMmReputation: null,
+        minMmFillRate: null,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        globalState,
+        standingQuote: standingQuotePda(quoteId),
+        mmRegistry,
+        blocklist,
+        assetConfig,
+        position,
+        positionUserVault: positionVaultPda(position),
+        userStats: userStatsPda(user.publicKey),
+        userTokenAccount: ata(escrowMint, user.publicKey),
+        escrowMint,
+        mmVault,
+        userPremiumAccount: ata(quoteMint, user.publicKey),
+        quoteMint,
+        oracle: { priceUpdate },
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([computeBudget()])
+      .signers([user])
+      .rpc();
+  }
+
+  before(async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    program = new Program<Solation>(idl, provider);
+    authority = context.payer;
+    now = Number((await context.banksClient.getClock()).unixTimestamp);
+
+    for (const keypair of [treasury, marketMaker, manager, alice, bob, carol, dave]) {
+      context.setAccount(keypair.publicKey, {
+        lamports: 100 * anchor.web3.LAMPORTS_PER_SOL,
+        data: Buffer.alloc(0),
+        owner: SystemProgram.programId,
+        executable: false,
+      });
+    }
+
+    await setMint(assetMint, DECIMALS);
+    await setMint(quoteMint, DECIMALS);
+    await warp(0);
+
+    for (const user of [alice, bob, carol, dave]) {
+      await setTokenAccount(ata(quoteMint, user.publicKey), quoteMint, user.publicKey, 10_000 * UNIT);
+    }
+    for (const user of [alice, bob, dave]) {
+      await setTokenAccount(ata(assetMint, user.publicKey), assetMint, user.publicKey, 100 * UNIT);
+    }
+    await setTokenAccount(ata(quoteMint, authority.publicKey), quoteMint, authority.publicKey, 1_000 * UNIT);
+    await setTokenAccount(ata(quoteMint, marketMaker.publicKey), quoteMint, marketMaker.publicKey, 0);
+    await setTokenAccount(ata(assetMint, marketMaker.publicKey), assetMint, marketMaker.publicKey, 0);
+
+    await program.methods
+      .initializeGlobalState(100)
+      .accountsPartial({
+        globalState,
+        authority: authority.publicKey,
+        treasury: treasury.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .initializeBlocklist()
+      .accountsPartial({
+        authority: authority.publicKey,
+        globalState,
+        blocklist,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .addAsset(assetMint, quoteMint, Array.from(PYTH_FEED_ID), 50, 200, new BN(HOUR), new BN(30 * DAY), DECIMALS, false, { token: {} })
+      .accountsPartial({
+        globalState,
+        assetConfig,
+        assetMintAccount: assetMint,
+        quoteMintAccount: quoteMint,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .registerMm(mmSigningKey.publicKey)
+      .accountsPartial({
+        owner: marketMaker.publicKey,
+        mmRegistry,
+        nonceTracker,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([marketMaker])
+      .rpc();
+
+    await program.methods
+      .openMmVault()
+      .accountsPartial({
+        owner: marketMaker.publicKey,
+        mmRegistry,
+        mmVault,
+        mint: quoteMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([marketMaker])
+      .rpc();
+
+    // Premium float the MM's standing quotes pay out of
+    await setTokenAccount(mmVault, quoteMint, mmRegistry, 1_000 * UNIT);
+  });
+
+  describe("Timelock", () => {
+    it("Holds a queued fee change until the delay has passed", async () => {
+      const pendingChange = pda(Buffer.from("pending_change"), u64(0));
+
       await program.methods
-        .updateGlobalState(null, null, null, false)
-        .accounts({
+        .queueParameterChange({ protocolFee: { feeBps: 250 } })
+        .accountsPartial({
           authority: authority.publicKey,
+          payer: authority.publicKey,
           globalState,
+          pendingChange,
+          systemProgram: SystemProgram.programId,
         })
-        .signers([authority])
         .rpc();
 
-      const updatedState = await program.account.globalState.fetch(globalState);
-      expect(updatedState.paused).to.equal(false);
+      const pending = await program.account.pendingChange.fetch(pendingChange);
+      expect(pending.executableAt.toNumber()).to.equal(now + 48 * HOUR);
+
+      const execute = () =>
+        program.methods.executeParameterChange().accountsPartial({
+          executor: authority.publicKey,
+          globalState,
+          pendingChange,
+          proposer: authority.publicKey,
+        });
+
+      await expectError(execute().rpc(), "TimelockNotElapsed");
+
+      await warp(48 * HOUR);
+      await execute().preInstructions([unique()]).rpc();
+
+      const state = await program.account.globalState.fetch(globalState);
+      expect(state.protocolFeeBps).to.equal(250);
+      expect(await program.account.pendingChange.fetchNullable(pendingChange)).to.be.null;
+    });
+
+    it("Drops a cancelled change without applying it", async () => {
+      const pendingChange = pda(Buffer.from("pending_change"), u64(1));
+
+      await program.methods
+        .queueParameterChange({ keeperGracePeriod: { graceSeconds: new BN(HOUR) } })
+        .accountsPartial({
+          authority: authority.publicKey,
+          payer: authority.publicKey,
+          globalState,
+          pendingChange,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .cancelParameterChange()
+        .accountsPartial({
+          authority: authority.publicKey,
+          globalState,
+          pendingChange,
+          proposer: authority.publicKey,
+        })
+        .rpc();
+
+      expect(await program.account.pendingChange.fetchNullable(pendingChange)).to.be.null;
+      const state = await program.account.globalState.fetch(globalState);
+      expect(state.keeperGracePeriod.toNumber()).to.equal(0);
+    });
+  });
+
+  describe("Standing Quotes", () => {
+    it("Opens positions against standing quotes, paying premium from the MM vault", async () => {
+      optionExpiry = now + 7 * DAY;
+
+      const quotes = [
+        { quoteId: 1, strategy: { cashSecuredPut: {} }, strikePrice: PUT_STRIKE, premiumPerContract: 2 },
+        { quoteId: 2, strategy: { coveredCall: {} }, strikePrice: CALL_STRIKE, premiumPerContract: 1 },
+      ];
+      for (const quote of quotes) {
+        await program.methods
+          .setStandingQuote({
+            quoteId: new BN(quote.quoteId),
+            assetMint,
+            strategy: quote.strategy,
+            expiryTimestamp: new BN(optionExpiry),
+            levels: [{ strikePrice: new BN(quote.strikePrice), premiumPerContract: new BN(quote.premiumPerContract) }],
+            maxContracts: new BN(100 * UNIT),
+            validUntil: new BN(now + HOUR),
+          })
+          .accountsPartial({
+            owner: marketMaker.publicKey,
+            mmRegistry,
+            assetConfig,
+            standingQuote: standingQuotePda(quote.quoteId),
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([marketMaker])
+          .rpc();
+      }
+
+      await takeQuote(alice, 1, 1, PUT_STRIKE, 10 * UNIT, quoteMint);
+      await takeQuote(bob, 1, 1, PUT_STRIKE, 5 * UNIT, quoteMint);
+      await takeQuote(carol, 1, 1, PUT_STRIKE, 10 * UNIT, quoteMint);
+      await takeQuote(dave, 2, 1, CALL_STRIKE, 10 * UNIT, assetMint);
+
+      expect(await tokenBalance(positionVaultPda(positionPda(alice.publicKey, 1)))).to.equal(950 * UNIT);
+      expect(await tokenBalance(positionVaultPda(positionPda(bob.publicKey, 1)))).to.equal(475 * UNIT);
+      expect(await tokenBalance(positionVaultPda(positionPda(dave.publicKey, 1)))).to.equal(10 * UNIT);
+
+      // Collateral out, premium in
+      expect(await tokenBalance(ata(quoteMint, alice.publicKey))).to.equal((10_000 - 950 + 20) * UNIT);
+      expect(await tokenBalance(ata(assetMint, dave.publicKey))).to.equal(90 * UNIT);
+      expect(await tokenBalance(ata(quoteMint, dave.publicKey))).to.equal(10_010 * UNIT);
+      expect(await tokenBalance(mmVault)).to.equal((1_000 - 20 - 10 - 20 - 10) * UNIT);
+
+      const putQuote = await program.account.standingQuote.fetch(standingQuotePda(1));
+      expect(putQuote.remainingContracts.toNumber()).to.equal(75 * UNIT);
+    });
+  });
+
+  describe("Blocklist", () => {
+    const carolPosition = () => positionPda(carol.publicKey, 1);
+
+    it("Rejects quote takes from a blocked address", async () => {
+      await program.methods
+        .updateBlocklist(carol.publicKey, true)
+        .accountsPartial({ authority: authority.publicKey, globalState, blocklist })
+        .rpc();
+
+      await expectError(takeQuote(carol, 1, 2, PUT_STRIKE, UNIT, quoteMint), "AddressBlocked");
+    });
+
+    it("Withholds a blocked user's payout at settlement", async () => {
+      await warp(optionExpiry - now + 60);
+
+      const position = carolPosition();
+      const carolBefore = await tokenBalance(ata(quoteMint, carol.publicKey));
+
+      // Spot settles above the put strike, so the collateral goes back to the user
+      await program.methods
+        .settlePosition(null)
+        .accountsPartial({
+          settler: authority.publicKey,
+          globalState,
+          position,
+          assetConfig,
+          mmRegistry,
+          userStats: userStatsPda(carol.publicKey),
+          positionUserVault: positionVaultPda(position),
+          vaultMint: quoteMint,
+          positionMmVault: mmVault,
+          positionAuthority: position,
+          userPayoutOwner: carol.publicKey,
+          userDestination: ata(quoteMint, carol.publicKey),
+          mmPayoutOwner: marketMaker.publicKey,
+          mmDestination: ata(quoteMint, marketMaker.publicKey),
+          oracle: { priceUpdate },
+          blocklist,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .preInstructions([computeBudget()])
+        .rpc();
+
+      const settled = await program.account.position.fetch(position);
+      expect(settled.status).to.not.have.property("active");
+      expect(settled.userPayoutWithheld.toNumber()).to.equal(950 * UNIT);
+      expect(await tokenBalance(positionVaultPda(position))).to.equal(950 * UNIT);
+      expect(await tokenBalance(ata(quoteMint, carol.publicKey))).to.equal(carolBefore);
+    });
+
+    it("Releases a withheld payout once the address is cleared", async () => {
+      const position = carolPosition();
+      const claim = () =>
+        program.methods
+          .claimWithheldPayout()
+          .accountsPartial({
+            claimant: carol.publicKey,
+            globalState,
+            blocklist,
+            position,
+            positionUserVault: positionVaultPda(position),
+            vaultMint: quoteMint,
+            positionAuthority: position,
+            destination: ata(quoteMint, carol.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([carol]);
+
+      await expectError(claim().rpc(), "AddressBlocked");
+
+      await program.methods
+        .updateBlocklist(carol.publicKey, false)
+        .accountsPartial({ authority: authority.publicKey, globalState, blocklist })
+        .rpc();
+
+      const carolBefore = await tokenBalance(ata(quoteMint, carol.publicKey));
+      await claim().preInstructions([unique()]).rpc();
+
+      expect(await tokenBalance(ata(quoteMint, carol.publicKey))).to.equal(carolBefore + 950 * UNIT);
+      expect(await tokenBalance(positionVaultPda(position))).to.equal(0);
+      const claimed = await program.account.position.fetch(position);
+      expect(claimed.userPayoutWithheld.toNumber()).to.equal(0);
+    });
+  });
+
+  describe("Insurance Haircuts", () => {
+    const insuranceFund = pda(Buffer.from("insurance_fund"), quoteMint.toBuffer());
+    const insuranceVault = pda(Buffer.from("insurance_vault"), quoteMint.toBuffer());
+    const defaultHaircut = () =>
+      pda(
+        Buffer.from("default_haircut"),
+        marketMaker.publicKey.toBuffer(),
+        new BN(optionExpiry).toArrayLike(Buffer, "le", 8),
+        quoteMint.toBuffer()
+      );
+
+    const claimPayout = (user: Keypair) =>
+      program.methods.claimHaircutPayout().accountsPartial({
+        claimer: authority.publicKey,
+        position: positionPda(user.publicKey, 1),
+        defaultHaircut: defaultHaircut(),
+        insuranceFund,
+        insuranceVault,
+        mmRegistry,
+        blocklist,
+        userDestination: ata(quoteMint, user.publicKey),
+        quoteMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      });
+
+    it("Funds the insurance fund", async () => {
+      await program.methods
+        .initializeInsuranceFund()
+        .accountsPartial({
+          authority: authority.publicKey,
+          globalState,
+          quoteMint,
+          insuranceFund,
+          insuranceVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+
+      await program.methods
+        .depositInsurance(new BN(300 * UNIT))
+        .accountsPartial({
+          depositor: authority.publicKey,
+          insuranceFund,
+          insuranceVault,
+          depositorTokenAccount: ata(quoteMint, authority.publicKey),
+          quoteMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      expect(await tokenBalance(insuranceVault)).to.equal(300 * UNIT);
+    });
+
+    it("Registers default claims against one MM and expiry", async () => {
+      const claims: [Keypair, number][] = [
+        [alice, 400 * UNIT],
+        [bob, 200 * UNIT],
+      ];
+      for (const [user, owed] of claims) {
+        await program.methods
+          .registerDefaultClaim(new BN(owed))
+          .accountsPartial({
+            authority: authority.publicKey,
+            payer: authority.publicKey,
+            globalState,
+            position: positionPda(user.publicKey, 1),
+            defaultHaircut: defaultHaircut(),
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+
+      const haircut = await program.account.defaultHaircut.fetch(defaultHaircut());
+      expect(haircut.totalOwed.toNumber()).to.equal(600 * UNIT);
+      expect(haircut.claimsRegistered).to.equal(2);
+
+      await expectError(claimPayout(alice).rpc(), "HaircutNotFinalized");
+    });
+
+    it("Finalizes the haircut against the fund balance", async () => {
+      await program.methods
+        .finalizeHaircut()
+        .accountsPartial({
+          authority: authority.publicKey,
+          globalState,
+          defaultHaircut: defaultHaircut(),
+          insuranceFund,
+          insuranceVault,
+        })
+        .rpc();
+
+      const haircut = await program.account.defaultHaircut.fetch(defaultHaircut());
+      expect(haircut.coveredAmount.toNumber()).to.equal(300 * UNIT);
+      expect(haircut.haircutBps).to.equal(5000);
+
+      const fund = await program.account.insuranceFund.fetch(insuranceFund);
+      expect(fund.reservedForHaircuts.toNumber()).to.equal(300 * UNIT);
+    });
+
+    it("Pays each claim its pro-rata share of the cover", async () => {
+      const aliceBefore = await tokenBalance(ata(quoteMint, alice.publicKey));
+      const bobBefore = await tokenBalance(ata(quoteMint, bob.publicKey));
+
+      await claimPayout(alice).rpc();
+      await claimPayout(bob).rpc();
+
+      expect(await tokenBalance(ata(quoteMint, alice.publicKey))).to.equal(aliceBefore + 200 * UNIT);
+      expect(await tokenBalance(ata(quoteMint, bob.publicKey))).to.equal(bobBefore + 100 * UNIT);
+      expect(await tokenBalance(insuranceVault)).to.equal(0);
+
+      const haircut = await program.account.defaultHaircut.fetch(defaultHaircut());
+      expect(haircut.claimsPaid).to.equal(2);
+      const fund = await program.account.insuranceFund.fetch(insuranceFund);
+      expect(fund.reservedForHaircuts.toNumber()).to.equal(0);
+
+      await expectError(claimPayout(alice).preInstructions([unique()]).rpc(), "DefaultClaimAlreadyRegistered");
+    });
+  });
+
+  describe("Auto-Roll", () => {
+    const rollPreference = pda(Buffer.from("roll_preference"), dave.publicKey.toBuffer(), assetMint.toBuffer());
+    const davePosition = positionPda(dave.publicKey, 1);
+
+    function roll(strikePrice: number) {
+      const { ed25519Ix, params } = signedQuote({
+        intentId: 1,
+        strategy: 0,
+        strikePrice,
+        premiumPerContract: 1,
+        contractSize: 10 * UNIT,
+        quoteExpiry: now + 7 * DAY,
+        quoteNonce: 1,
+      });
+      const intent = pda(Buffer.from("intent"), dave.publicKey.toBuffer(), u64(1));
+      const call = program.methods
+        .rollPosition(params)
+        .accountsPartial({
+          keeper: authority.publicKey,
+          globalState,
+          position: davePosition,
+          assetConfig,
+          rollPreference,
+          userStats: userStatsPda(dave.publicKey),
+          positionMmRegistry: mmRegistry,
+          positionUserVault: positionVaultPda(davePosition),
+          vaultMint: assetMint,
+          positionAuthority: davePosition,
+          userDestination: ata(assetMint, dave.publicKey),
+          mmDestination: ata(assetMint, marketMaker.publicKey),
+          oracle: { priceUpdate },
+          mmRegistry,
+          blocklist,
+          nonceTracker,
+          intent,
+          userEscrow: pda(Buffer.from("user_escrow"), intent.toBuffer()),
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .preInstructions([ed25519Ix, computeBudget()]);
+      return { call, intent };
+    }
+
+    it("Stores the user's roll preference", async () => {
+      await program.methods
+        .createRollPreference(assetMint, {
+          strategy: { coveredCall: {} },
+          maxStrikeDistanceBps: 2000,
+          minPremiumPerContract: new BN(1),
+        })
+        .accountsPartial({
+          user: dave.publicKey,
+          rollPreference,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([dave])
+        .rpc();
+
+      const preference = await program.account.rollPreference.fetch(rollPreference);
+      expect(preference.enabled).to.equal(true);
+      expect(preference.totalRolls.toNumber()).to.equal(0);
+    });
+
+    it("Rejects a rolled strike outside the user's bounds", async () => {
+      await expectError(roll(150 * PRICE_SCALE).call.rpc(), "RollConstraintViolated");
+    });
+
+    it("Rolls an expired covered call into a new intent escrowing its collateral", async () => {
+      const { call, intent } = roll(CALL_STRIKE);
+      await call.rpc();
+
+      const rolled = await program.account.position.fetch(davePosition);
+      expect(rolled.status).to.not.have.property("active");
+      expect(await tokenBalance(positionVaultPda(davePosition))).to.equal(0);
+
+      const newIntent = await program.account.intent.fetch(intent);
+      expect(newIntent.escrowAmount.toNumber()).to.equal(10 * UNIT);
+      expect(await tokenBalance(pda(Buffer.from("user_escrow"), intent.toBuffer()))).to.equal(10 * UNIT);
+
+      const preference = await program.account.rollPreference.fetch(rollPreference);
+      expect(preference.totalRolls.toNumber()).to.equal(1);
+    });
+  });
+
+  describe("Option Vault", () => {
+    const vault = pda(Buffer.from("option_vault"), assetMint.toBuffer(), Buffer.from([0]));
+    const shareMint = pda(Buffer.from("option_vault_shares"), vault.toBuffer());
+    const vaultAssetAccount = ata(assetMint, vault);
+    const vaultQuoteAccount = ata(quoteMint, vault);
+    const shareEscrow = ata(shareMint, vault);
+
+    const deposit = (depositor: Keypair, amount: number) =>
+      program.methods
+        .depositToVault(new BN(amount))
+        .accountsPartial({
+          depositor: depositor.publicKey,
+          vault,
+          assetConfig,
+          blocklist,
+          oracle: { priceUpdate },
+          depositMint: assetMint,
+          shareMint,
+          vaultAssetAccount,
+          vaultQuoteAccount,
+          depositorTokenAccount: ata(assetMint, depositor.publicKey),
+          depositorShareAccount: ata(shareMint, depositor.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([depositor]);
+
+    it("Creates a covered-call vault", async () => {
+      await program.methods
+        .createOptionVault({ coveredCall: {} }, manager.publicKey)
+        .accountsPartial({
+          authority: authority.publicKey,
+          globalState,
+          assetConfig,
+          assetMint,
+          quoteMint,
+          vault,
+          shareMint,
+          vaultAssetAccount,
+          vaultQuoteAccount,
+          shareEscrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const created = await program.account.optionVault.fetch(vault);
+      expect(created.manager.toBase58()).to.equal(manager.publicKey.toBase58());
+      expect(created.windowClosesAt.toNumber()).to.equal(now + DAY);
+    });
+
+    it("Mints shares pro-rata to the vault's value", async () => {
+      await deposit(alice, 40 * UNIT).rpc();
+      await deposit(bob, 10 * UNIT).rpc();
+
+      expect(await tokenBalance(ata(shareMint, alice.publicKey))).to.equal(40 * UNIT);
+      expect(await tokenBalance(ata(shareMint, bob.publicKey))).to.equal(10 * UNIT);
+      expect(await tokenBalance(vaultAssetAccount)).to.equal(50 * UNIT);
+    });
+
+    it("Redeems shares while the window is open", async () => {
+      const bobBefore = await tokenBalance(ata(assetMint, bob.publicKey));
+
+      await program.methods
+        .withdrawFromVault(new BN(5 * UNIT))
+        .accountsPartial({
+          withdrawer: bob.publicKey,
+          vault,
+          assetMint,
+          quoteMint,
+          shareMint,
+          vaultAssetAccount,
+          vaultQuoteAccount,
+          withdrawerShareAccount: ata(shareMint, bob.publicKey),
+          withdrawerAssetAccount: ata(assetMint, bob.publicKey),
+          withdrawerQuoteAccount: ata(quoteMint, bob.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([bob])
+        .rpc();
+
+      expect(await tokenBalance(ata(assetMint, bob.publicKey))).to.equal(bobBefore + 5 * UNIT);
+      expect(await tokenBalance(vaultAssetAccount)).to.equal(45 * UNIT);
+      expect(await mintSupply(shareMint)).to.equal(45 * UNIT);
+    });
+
+    it("Rejects deposits once outstanding shares are worthless", async () => {
+      await setTokenAccount(vaultAssetAccount, assetMint, vault, 0);
+      await expectError(deposit(bob, UNIT).rpc(), "VaultNavDepleted");
+      await setTokenAccount(vaultAssetAccount, assetMint, vault, 45 * UNIT);
+    });
+
+    it("Queues withdrawals once the window has closed", async () => {
+      await warp(DAY + 1);
+
+      await expectError(deposit(alice, UNIT).rpc(), "VaultWindowClosed");
+
+      await program.methods
+        .queueVaultWithdrawal(new BN(10 * UNIT))
+        .accountsPartial({
+          owner: alice.publicKey,
+          vault,
+          shareMint,
+          ownerShareAccount: ata(shareMint, alice.publicKey),
+          shareEscrow,
+          withdrawal: pda(Buffer.from("vault_withdrawal"), vault.toBuffer(), alice.publicKey.toBuffer()),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+      expect(await tokenBalance(shareEscrow)).to.equal(10 * UNIT);
+      expect(await tokenBalance(ata(shareMint, alice.publicKey))).to.equal(30 * UNIT);
+      const queued = await program.account.optionVault.fetch(vault);
+      expect(queued.queuedWithdrawalShares.toNumber()).to.equal(10 * UNIT);
+    });
+
+    it("Escrows vault collateral for the round's intent", async () => {
+      const epoch = (await program.account.optionVault.fetch(vault)).epoch.toNumber();
+      const { ed25519Ix, params } = signedQuote({
+        intentId: epoch,
+        strategy: 0,
+        strikePrice: CALL_STRIKE,
+        premiumPerContract: 1,
+        contractSize: 20 * UNIT,
+        quoteExpiry: now + 7 * DAY,
+        quoteNonce: 2,
+      });
+      const intent = pda(Buffer.from("intent"), vault.toBuffer(), u64(epoch));
+      const userEscrow = pda(Buffer.from("user_escrow"), intent.toBuffer());
+
+      await program.methods
+        .vaultSubmitIntent(params)
+        .accountsPartial({
+          manager: manager.publicKey,
+          vault,
+          globalState,
+          mmRegistry,
+          blocklist,
+          nonceTracker,
+          assetConfig,
+          intent,
+          userEscrow,
+          userStats: userStatsPda(vault),
+          vaultDepositAccount: vaultAssetAccount,
+          depositMint: assetMint,
+          oracle: { priceUpdate },
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .preInstructions([ed25519Ix, computeBudget()])
+        .signers([manager])
+        .rpc();
+
+      expect(await tokenBalance(userEscrow)).to.equal(20 * UNIT);
+      expect(await tokenBalance(vaultAssetAccount)).to.equal(25 * UNIT);
+      const active = await program.account.optionVault.fetch(vault);
+      expect(active.roundActive).to.equal(true);
+
+      // Queued shares wait for the round to finish
+      await expectError(
+        program.methods
+          .claimVaultWithdrawal()
+          .accountsPartial({
+            vault,
+            withdrawal: pda(Buffer.from("vault_withdrawal"), vault.toBuffer(), alice.publicKey.toBuffer()),
+            owner: alice.publicKey,
+            assetMint,
+            quoteMint,
+            shareMint,
+            vaultAssetAccount,
+            vaultQuoteAccount,
+            shareEscrow,
+            ownerAssetAccount: ata(assetMint, alice.publicKey),
+            ownerQuoteAccount: ata(quoteMint, alice.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([alice])
+          .rpc(),
+        "VaultRoundActive"
+      );
+    });
+  });
+
+  describe("Liquidity Vault", () => {
+    const vault = pda(Buffer.from("liquidity_vault"), quoteMint.toBuffer());
+    const shareMint = pda(Buffer.from("lp_shares"), vault.toBuffer());
+    const vaultQuoteAccount = ata(quoteMint, vault);
+    const shareEscrow = ata(shareMint, vault);
+    const withdrawal = pda(Buffer.from("lp_withdrawal"), vault.toBuffer(), bob.publicKey.toBuffer());
+
+    const depositLiquidity = (lp: Keypair, amount: number) =>
+      program.methods
+        .depositLiquidity(new BN(amount))
+        .accountsPartial({
+          lp: lp.publicKey,
+          vault,
+          blocklist,
+          quoteMint,
+          shareMint,
+          vaultQuoteAccount,
+          lpQuoteAccount: ata(quoteMint, lp.publicKey),
+          lpShareAccount: ata(shareMint, lp.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([lp]);
+
+    const advanceEpoch = () =>
+      program.methods.advanceLiquidityEpoch().accountsPartial({
+        vault,
+        globalState,
+        shareMint,
+        vaultQuoteAccount,
+        treasuryShareAccount: ata(shareMint, treasury.publicKey),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      });
+
+    const claimWithdrawal = () =>
+      program.methods
+        .claimLpWithdrawal()
+        .accountsPartial({
+          lp: bob.publicKey,
+          vault,
+          withdrawal,
+          quoteMint,
+          shareMint,
+          vaultQuoteAccount,
+          shareEscrow,
+          lpQuoteAccount: ata(quoteMint, bob.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([bob]);
+
+    it("Creates the quote liquidity vault", async () => {
+      await program.methods
+        .createLiquidityVault(2000, 8000, new BN(DAY))
+        .accountsPartial({
+          authority: authority.publicKey,
+          globalState,
+          quoteMint,
+          vault,
+          shareMint,
+          vaultQuoteAccount,
+          shareEscrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const created = await program.account.liquidityVault.fetch(vault);
+      expect(created.premiumShareBps).to.equal(2000);
+      expect(created.epochDuration.toNumber()).to.equal(DAY);
+    });
+
+    it("Mints LP shares against deposits", async () => {
+      await depositLiquidity(alice, 1_000 * UNIT).rpc();
+      await depositLiquidity(bob, 500 * UNIT).rpc();
+
+      expect(await tokenBalance(ata(shareMint, alice.publicKey))).to.equal(1_000 * UNIT);
+      expect(await tokenBalance(ata(shareMint, bob.publicKey))).to.equal(500 * UNIT);
+      expect(await tokenBalance(vaultQuoteAccount)).to.equal(1_500 * UNIT);
+    });
+
+    it("Rejects deposits once outstanding shares are worthless", async () => {
+      await setTokenAccount(vaultQuoteAccount, quoteMint, vault, 0);
+      await expectError(depositLiquidity(bob, UNIT).rpc(), "VaultNavDepleted");
+      await setTokenAccount(vaultQuoteAccount, quoteMint, vault, 1_500 * UNIT);
+    });
+
+    it("Pays a queued withdrawal only after the epoch advances", async () => {
+      await program.methods
+        .requestLpWithdrawal(new BN(200 * UNIT))
+        .accountsPartial({
+          lp: bob.publicKey,
+          vault,
+          shareMint,
+          lpShareAccount: ata(shareMint, bob.publicKey),
+          shareEscrow,
+          withdrawal,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bob])
+        .rpc();
+
+      expect(await tokenBalance(shareEscrow)).to.equal(200 * UNIT);
+
+      await expectError(claimWithdrawal().rpc(), "LpEpochNotEnded");
+      await expectError(advanceEpoch().rpc(), "LpEpochNotEnded");
+
+      await warp(DAY);
+      await setTokenAccount(ata(shareMint, treasury.publicKey), shareMint, treasury.publicKey, 0);
+      await advanceEpoch().preInstructions([unique()]).rpc();
+
+      const bobBefore = await tokenBalance(ata(quoteMint, bob.publicKey));
+      await claimWithdrawal().preInstructions([unique()]).rpc();
+
+      expect(await tokenBalance(ata(quoteMint, bob.publicKey))).to.equal(bobBefore + 200 * UNIT);
+      expect(await tokenBalance(vaultQuoteAccount)).to.equal(1_300 * UNIT);
+      expect(await tokenBalance(shareEscrow)).to.equal(0);
+      expect(await mintSupply(shareMint)).to.equal(1_300 * UNIT);
     });
   });
 });